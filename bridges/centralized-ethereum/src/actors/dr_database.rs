@@ -1,9 +1,13 @@
 use actix::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::{cmp, collections::hash_map::Entry, collections::HashMap, fmt, future::Future};
-use web3::{ethabi::Bytes, types::U256};
+use web3::{
+    ethabi::Bytes,
+    types::{H256, U256},
+};
 use witnet_data_structures::chain::Hash;
 use witnet_node::{storage_mngr, utils::stop_system_if_panicking};
+use witnet_util::timestamp::get_timestamp;
 
 /// Database key that stores the Data Request information
 const BRIDGE_DB_KEY: &[u8] = b"bridge_db_key";
@@ -13,6 +17,10 @@ const BRIDGE_DB_KEY: &[u8] = b"bridge_db_key";
 pub struct DrDatabase {
     dr: HashMap<DrId, DrInfoBridge>,
     max_dr_id: DrId,
+    /// Transactions through which data request results were reported, for those reports that may
+    /// still be reverted by a re-org.
+    #[serde(default)]
+    reports: HashMap<DrId, H256>,
 }
 
 impl Drop for DrDatabase {
@@ -34,6 +42,52 @@ impl DrDatabase {
             }
         }
     }
+
+    /// List the data requests in "pending" state.
+    fn pending_drs(&self) -> Vec<(DrId, Bytes, Hash, i64)> {
+        self.dr
+            .iter()
+            .filter_map(|(dr_id, dr_info)| {
+                if let DrState::Pending = dr_info.dr_state {
+                    Some((
+                        *dr_id,
+                        dr_info.dr_bytes.clone(),
+                        dr_info.dr_tx_hash.unwrap(),
+                        dr_info.dr_tx_creation_timestamp.unwrap(),
+                    ))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Mark a data request as finished, keeping track of the transaction that reported it.
+    fn set_reported(&mut self, dr_id: DrId, report_tx_hash: H256) {
+        if let Some(dr_info) = self.dr.get_mut(&dr_id) {
+            dr_info.dr_state = DrState::Finished;
+        }
+        self.reports.insert(dr_id, report_tx_hash);
+    }
+
+    /// Put a finished data request back into "pending" state because the transaction that
+    /// reported it is no longer in the canonical chain. The creation timestamp of the data request
+    /// transaction is reset to `timestamp`, so that it is not mistaken for a timed out one.
+    ///
+    /// Returns `false` if the data request cannot be reported again.
+    fn revert_report(&mut self, dr_id: DrId, timestamp: i64) -> bool {
+        self.reports.remove(&dr_id);
+
+        match self.dr.get_mut(&dr_id) {
+            Some(dr_info) if dr_info.dr_tx_hash.is_some() => {
+                dr_info.dr_state = DrState::Pending;
+                dr_info.dr_tx_creation_timestamp = Some(timestamp);
+
+                true
+            }
+            _ => false,
+        }
+    }
 }
 
 /// Data request ID, as set in the ethereum contract
@@ -116,24 +170,43 @@ impl Actor for DrDatabase {
     fn started(&mut self, ctx: &mut Self::Context) {
         log::debug!("DrDatabase actor has been started!");
 
-        let fut = storage_mngr::get::<_, DrDatabase>(&BRIDGE_DB_KEY)
-            .into_actor(self)
-            .map(
-                |dr_database_from_storage, act, _| match dr_database_from_storage {
-                    Ok(dr_database_from_storage) => {
-                        if let Some(mut dr_database_from_storage) = dr_database_from_storage {
-                            log::info!("Database loaded from storage");
-                            act.dr = std::mem::take(&mut dr_database_from_storage.dr);
-                            act.max_dr_id = dr_database_from_storage.max_dr_id;
-                        } else {
-                            log::info!("No database in storage");
-                        }
-                    }
-                    Err(e) => {
-                        panic!("Error while getting bridge database from storage: {}", e);
+        let fut = async {
+            match storage_mngr::get::<_, DrDatabase>(&BRIDGE_DB_KEY).await {
+                // Databases stored before report transactions were persisted lack the `reports`
+                // field, which bincode cannot default, so they are read using their old layout
+                Err(e) => {
+                    storage_mngr::get::<_, (HashMap<DrId, DrInfoBridge>, DrId)>(&BRIDGE_DB_KEY)
+                        .await
+                        .map(|dr_database| {
+                            dr_database.map(|(dr, max_dr_id)| DrDatabase {
+                                dr,
+                                max_dr_id,
+                                reports: HashMap::new(),
+                            })
+                        })
+                        .map_err(|_| e)
+                }
+                dr_database => dr_database,
+            }
+        }
+        .into_actor(self)
+        .map(
+            |dr_database_from_storage, act, _| match dr_database_from_storage {
+                Ok(dr_database_from_storage) => {
+                    if let Some(mut dr_database_from_storage) = dr_database_from_storage {
+                        log::info!("Database loaded from storage");
+                        act.dr = std::mem::take(&mut dr_database_from_storage.dr);
+                        act.max_dr_id = dr_database_from_storage.max_dr_id;
+                        act.reports = std::mem::take(&mut dr_database_from_storage.reports);
+                    } else {
+                        log::info!("No database in storage");
                     }
-                },
-            );
+                }
+                Err(e) => {
+                    panic!("Error while getting bridge database from storage: {}", e);
+                }
+            },
+        );
 
         ctx.wait(fut);
     }
@@ -179,6 +252,45 @@ impl Message for SetDrState {
     type Result = Result<(), ()>;
 }
 
+/// Mark a data request as reported through the given Ethereum transaction
+pub struct SetDrReported {
+    /// Data Request id
+    pub dr_id: DrId,
+    /// Hash of the `reportResultBatch` transaction
+    pub report_tx_hash: H256,
+}
+
+impl Message for SetDrReported {
+    type Result = ();
+}
+
+/// Get a list of all the reports that may still be reverted by a re-org
+pub struct GetAllReportedDrs;
+
+impl Message for GetAllReportedDrs {
+    type Result = Result<Vec<(DrId, H256)>, ()>;
+}
+
+/// Report a data request again, because the transaction that reported it was dropped
+pub struct RevertDrReport {
+    /// Data Request id
+    pub dr_id: DrId,
+}
+
+impl Message for RevertDrReport {
+    type Result = Result<bool, ()>;
+}
+
+/// Stop tracking the report of a data request, because it cannot be reverted anymore
+pub struct ForgetDrReport {
+    /// Data Request id
+    pub dr_id: DrId,
+}
+
+impl Message for ForgetDrReport {
+    type Result = ();
+}
+
 /// Count number of data requests in given state
 pub struct CountDrsPerState;
 
@@ -224,22 +336,7 @@ impl Handler<GetAllPendingDrs> for DrDatabase {
     type Result = Result<Vec<(DrId, Bytes, Hash, i64)>, ()>;
 
     fn handle(&mut self, _msg: GetAllPendingDrs, _ctx: &mut Self::Context) -> Self::Result {
-        Ok(self
-            .dr
-            .iter()
-            .filter_map(|(dr_id, dr_info)| {
-                if let DrState::Pending = dr_info.dr_state {
-                    Some((
-                        *dr_id,
-                        dr_info.dr_bytes.clone(),
-                        dr_info.dr_tx_hash.unwrap(),
-                        dr_info.dr_tx_creation_timestamp.unwrap(),
-                    ))
-                } else {
-                    None
-                }
-            })
-            .collect())
+        Ok(self.pending_drs())
     }
 }
 
@@ -258,7 +355,7 @@ impl Handler<SetDrState> for DrDatabase {
         let SetDrState { dr_id, dr_state } = msg;
         match self.dr.entry(dr_id) {
             Entry::Occupied(entry) => {
-                entry.into_mut().dr_state = DrState::Finished;
+                entry.into_mut().dr_state = dr_state;
                 log::debug!("Data request #{} updated to state {}", dr_id, dr_state,);
             }
             Entry::Vacant(entry) => {
@@ -281,6 +378,69 @@ impl Handler<SetDrState> for DrDatabase {
     }
 }
 
+impl Handler<SetDrReported> for DrDatabase {
+    type Result = ();
+
+    fn handle(&mut self, msg: SetDrReported, ctx: &mut Self::Context) -> Self::Result {
+        let SetDrReported {
+            dr_id,
+            report_tx_hash,
+        } = msg;
+        self.set_reported(dr_id, report_tx_hash);
+        log::debug!(
+            "Data request #{} reported in transaction {:?}",
+            dr_id,
+            report_tx_hash
+        );
+
+        // Persist Data Request Database
+        ctx.spawn(self.persist().into_actor(self));
+    }
+}
+
+impl Handler<GetAllReportedDrs> for DrDatabase {
+    type Result = Result<Vec<(DrId, H256)>, ()>;
+
+    fn handle(&mut self, _msg: GetAllReportedDrs, _ctx: &mut Self::Context) -> Self::Result {
+        Ok(self
+            .reports
+            .iter()
+            .map(|(dr_id, report_tx_hash)| (*dr_id, *report_tx_hash))
+            .collect())
+    }
+}
+
+impl Handler<RevertDrReport> for DrDatabase {
+    type Result = Result<bool, ()>;
+
+    fn handle(&mut self, msg: RevertDrReport, ctx: &mut Self::Context) -> Self::Result {
+        let reverted = self.revert_report(msg.dr_id, get_timestamp());
+        if reverted {
+            log::debug!(
+                "Data request #{} updated to state {}",
+                msg.dr_id,
+                DrState::Pending
+            );
+
+            // Persist Data Request Database
+            ctx.spawn(self.persist().into_actor(self));
+        }
+
+        Ok(reverted)
+    }
+}
+
+impl Handler<ForgetDrReport> for DrDatabase {
+    type Result = ();
+
+    fn handle(&mut self, msg: ForgetDrReport, ctx: &mut Self::Context) -> Self::Result {
+        if self.reports.remove(&msg.dr_id).is_some() {
+            // Persist Data Request Database
+            ctx.spawn(self.persist().into_actor(self));
+        }
+    }
+}
+
 impl Handler<CountDrsPerState> for DrDatabase {
     type Result = Result<(u32, u32, u32, u32), ()>;
 
@@ -306,3 +466,142 @@ impl actix::Supervised for DrDatabase {}
 
 /// Required trait for being able to retrieve DrDatabase address from system registry
 impl SystemService for DrDatabase {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actors::eth_poller::{report_tx_status, ReportTxStatus};
+    use web3::types::TransactionReceipt;
+
+    #[test]
+    fn dropped_report_is_reported_again() {
+        let dr_id = DrId::from(7u64);
+        let dr_tx_hash = Hash::SHA256([1; 32]);
+        let report_tx_hash = H256::from([2; 32]);
+        let mut dr_database = DrDatabase::default();
+        dr_database.dr.insert(
+            dr_id,
+            DrInfoBridge {
+                dr_bytes: vec![1, 2, 3],
+                dr_state: DrState::Pending,
+                dr_tx_hash: Some(dr_tx_hash),
+                dr_tx_creation_timestamp: Some(1_000),
+            },
+        );
+
+        // The report transaction gets mined
+        dr_database.set_reported(dr_id, report_tx_hash);
+        assert!(dr_database.pending_drs().is_empty());
+        let receipt = TransactionReceipt {
+            transaction_hash: report_tx_hash,
+            block_number: Some(100u64.into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            report_tx_status(Some(&receipt), true, 110, 64),
+            ReportTxStatus::Unsettled
+        );
+
+        // After a re-org, the transaction goes back to the mempool and has no receipt
+        assert_eq!(
+            report_tx_status(None, true, 111, 64),
+            ReportTxStatus::Unsettled
+        );
+
+        // Eventually the client no longer knows about the transaction
+        assert_eq!(
+            report_tx_status(None, false, 112, 64),
+            ReportTxStatus::Dropped
+        );
+        assert!(dr_database.revert_report(dr_id, 2_000));
+
+        // The data request is pending again, so its result will be reported again
+        assert_eq!(
+            dr_database.pending_drs(),
+            vec![(dr_id, vec![1, 2, 3], dr_tx_hash, 2_000)]
+        );
+        assert!(dr_database.reports.is_empty());
+    }
+
+    #[test]
+    fn report_is_forgotten_once_final() {
+        let receipt = TransactionReceipt {
+            block_number: Some(100u64.into()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            report_tx_status(Some(&receipt), true, 163, 64),
+            ReportTxStatus::Unsettled
+        );
+        assert_eq!(
+            report_tx_status(Some(&receipt), true, 164, 64),
+            ReportTxStatus::Final
+        );
+    }
+
+    #[test]
+    fn set_dr_state_stores_the_given_state() {
+        actix::System::new().block_on(async {
+            let dr_id = DrId::from(9u64);
+            let dr_database = DrDatabase::default().start();
+            dr_database
+                .send(SetDrInfoBridge(
+                    dr_id,
+                    DrInfoBridge {
+                        dr_bytes: vec![1, 2, 3],
+                        dr_state: DrState::New,
+                        ..Default::default()
+                    },
+                ))
+                .await
+                .unwrap();
+
+            // Existing data requests used to be marked as finished regardless of the given state
+            dr_database
+                .send(SetDrState {
+                    dr_id,
+                    dr_state: DrState::Dismissed,
+                })
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(
+                dr_database.send(CountDrsPerState).await.unwrap(),
+                Ok((0, 0, 0, 1))
+            );
+            assert_eq!(dr_database.send(GetAllNewDrs).await.unwrap(), Ok(vec![]));
+
+            // Unknown data requests are inserted with the given state
+            dr_database
+                .send(SetDrState {
+                    dr_id: DrId::from(10u64),
+                    dr_state: DrState::Dismissed,
+                })
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(
+                dr_database.send(CountDrsPerState).await.unwrap(),
+                Ok((0, 0, 0, 2))
+            );
+        });
+    }
+
+    #[test]
+    fn report_without_dr_tx_cannot_be_reverted() {
+        let dr_id = DrId::from(8u64);
+        let mut dr_database = DrDatabase::default();
+        dr_database.dr.insert(
+            dr_id,
+            DrInfoBridge {
+                dr_state: DrState::Finished,
+                ..Default::default()
+            },
+        );
+        dr_database.reports.insert(dr_id, H256::from([2; 32]));
+
+        assert!(!dr_database.revert_report(dr_id, 2_000));
+        assert!(dr_database.reports.is_empty());
+    }
+}
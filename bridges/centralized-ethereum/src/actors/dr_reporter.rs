@@ -1,15 +1,19 @@
 use crate::{
-    actors::dr_database::{DrDatabase, DrId, DrState, SetDrState},
-    config::Config,
+    actors::{
+        dr_database::{DrDatabase, DrId, DrState, SetDrReported, SetDrState},
+        metrics::{CountReporterError, Metrics},
+    },
+    config::{Config, GasEscalation},
     handle_receipt,
 };
 use actix::prelude::*;
-use std::{collections::HashSet, sync::Arc, time::Duration};
+use std::{cmp, collections::HashSet, sync::Arc, time::Duration};
 use web3::{
-    contract::{self, tokens::Tokenize, Contract},
+    api::Eth,
+    contract::{self, Contract},
     ethabi::{ethereum_types::H256, Token},
     transports::Http,
-    types::{H160, U256},
+    types::{BlockNumber, TransactionReceipt, H160, U256},
     Web3,
 };
 use web3_unit_converter::Unit;
@@ -31,12 +35,18 @@ pub struct DrReporter {
     pub eth_from_balance_alert: bool,
     /// report_result_limit
     pub eth_max_gas: Option<u64>,
+    /// Safety margin (in percent) added to the estimated gas of report transactions
+    pub eth_gas_margin_percent: u64,
+    /// Gas price escalation for report transactions that take too long to be mined
+    pub eth_gas_escalation: Option<GasEscalation>,
     /// Price of $nanoWit in Wei, used to improve estimation of report profits
     pub eth_nanowit_wei_price: Option<u64>,
     /// Max time to wait for an ethereum transaction to be confirmed before returning an error
     pub eth_txs_timeout_ms: u64,
     /// Number of block confirmations needed to assume finality when sending transactions to ethereum
     pub eth_txs_confirmations: usize,
+    /// Max number of results reported together in a single reportResultBatch transaction
+    pub eth_max_report_batch_size: usize,
     /// maximum result size (in bytes)
     pub witnet_dr_max_result_size: usize,
     /// Pending reportResult transactions. The actor should not attempt to report these requests
@@ -82,9 +92,12 @@ impl DrReporter {
             eth_from_balance_threshold: config.eth_from_balance_threshold,
             eth_from_balance_alert: false,
             eth_max_gas: config.eth_gas_limits.report_result,
+            eth_gas_margin_percent: config.eth_gas_margin_percent,
+            eth_gas_escalation: config.eth_gas_escalation,
             eth_nanowit_wei_price: config.eth_nanowit_wei_price,
             eth_txs_timeout_ms: config.eth_txs_timeout_ms,
             eth_txs_confirmations: config.eth_txs_confirmations,
+            eth_max_report_batch_size: usize::from(config.eth_max_report_batch_size),
             witnet_dr_max_result_size: config.witnet_dr_max_result_size,
             pending_dr_reports: Default::default(),
         }
@@ -146,7 +159,10 @@ impl Handler<DrReporterMsg> for DrReporter {
         let eth_from_balance_threshold = self.eth_from_balance_threshold;
         let mut eth_from_balance_alert = self.eth_from_balance_alert;
         let eth_max_gas = self.eth_max_gas;
+        let eth_gas_margin_percent = self.eth_gas_margin_percent;
+        let eth_gas_escalation = self.eth_gas_escalation;
         let eth_txs_confirmations = self.eth_txs_confirmations;
+        let eth_max_report_batch_size = self.eth_max_report_batch_size;
         let eth_tx_timeout = Duration::from_millis(self.eth_txs_timeout_ms);
         let eth_nanowit_wei_price = U256::from(self.eth_nanowit_wei_price.unwrap_or_default());
 
@@ -221,42 +237,80 @@ impl Handler<DrReporterMsg> for DrReporter {
                 })
                 .collect();
 
-            let batched_reports = split_by_gas_limit(
-                batched_report,
-                &wrb_contract,
-                eth_from,
-                eth_gas_price,
-                eth_nanowit_wei_price,
-                eth_max_gas,
-            )
-            .await;
+            let report_calls = if supports_report_result_batch(wrb_contract.abi()) {
+                let mut report_calls = vec![];
+                for batch in group_reports(batched_report, eth_max_report_batch_size) {
+                    let batches = split_by_gas_limit(
+                        batch,
+                        &wrb_contract,
+                        eth_from,
+                        eth_gas_price,
+                        eth_nanowit_wei_price,
+                        eth_max_gas,
+                    )
+                    .await;
+                    report_calls.extend(
+                        batches.into_iter().map(|(batch, eth_gas_limit)| {
+                            (ReportCall::Batch(batch), eth_gas_limit)
+                        }),
+                    );
+                }
+
+                report_calls
+            } else {
+                log::warn!("No reportResultBatch in WRB contract, reporting results one by one");
+                estimate_single_reports(
+                    batched_report,
+                    &wrb_contract,
+                    eth_from,
+                    eth_gas_price,
+                    eth_nanowit_wei_price,
+                    eth_max_gas,
+                )
+                .await
+            };
 
             log::info!(
                 "{:?} will be reported in {} transactions",
                 dr_ids,
-                batched_reports.len(),
+                report_calls.len(),
             );
 
-            for (batched_report, eth_gas_limit) in batched_reports {
-                // log::debug!("Executing reportResultBatch {:?}", batched_report);
+            for (report_call, estimated_gas) in report_calls {
+                let function_name = report_call.function_name();
+                let call_params = report_call.params();
+                let call_dr_ids = report_call.dr_ids();
+                let (eth_gas_limit, capped) =
+                    apply_gas_margin(estimated_gas, eth_gas_margin_percent, eth_max_gas);
+                if capped {
+                    log::warn!(
+                        "{}(..) estimated gas {} plus {}% margin exceeds the gas limit, using {}",
+                        function_name,
+                        estimated_gas,
+                        eth_gas_margin_percent,
+                        eth_gas_limit
+                    );
+                }
 
-                let receipt_fut = wrb_contract.call_with_confirmations(
-                    "reportResultBatch",
-                    batched_report.clone(),
+                let receipt_fut = send_report_call(
+                    &eth,
+                    &wrb_contract,
+                    function_name,
+                    &call_params,
                     eth_from,
-                    contract::Options::with(|opt| {
-                        opt.gas = Some(eth_gas_limit);
-                        opt.gas_price = Some(eth_gas_price);
-                    }),
+                    eth_gas_limit,
+                    eth_gas_price,
                     eth_txs_confirmations,
+                    eth_gas_escalation,
                 );
 
                 let receipt = tokio::time::timeout(eth_tx_timeout, receipt_fut).await;
                 match receipt {
                     Ok(Ok(receipt)) => {
-                        log::debug!("{:?} <> {:?}", dr_ids, receipt);
+                        log::debug!("{:?} <> {:?}", call_dr_ids, receipt);
                         match handle_receipt(&receipt).await {
                             Ok(()) => {
+                                let report_tx_hash = receipt.transaction_hash;
                                 let mut dismissed_dr_reports: HashSet<DrId> = Default::default();
                                 for log in receipt.logs {
                                     if let Some((dismissed_dr_id, reason)) =
@@ -272,7 +326,11 @@ impl Handler<DrReporterMsg> for DrReporter {
                                     }
                                 }
                                 let dr_database_addr = DrDatabase::from_registry();
-                                for report in &msg.reports {
+                                for report in msg
+                                    .reports
+                                    .iter()
+                                    .filter(|report| call_dr_ids.contains(&report.dr_id))
+                                {
                                     if dismissed_dr_reports.contains(&report.dr_id) {
                                         // Dismiss data requests that could not (or need not) get reported
                                         dr_database_addr
@@ -283,16 +341,17 @@ impl Handler<DrReporterMsg> for DrReporter {
                                             .await
                                             .ok();
                                     } else {
-                                        // Finalize data requests that got successfully reported
+                                        // Finalize data requests that got successfully reported,
+                                        // keeping track of the transaction in case of a re-org
                                         log::info!(
                                             "[{}] <= dr_tally_tx = {}",
                                             report.dr_id,
                                             report.dr_tally_tx_hash
                                         );
                                         dr_database_addr
-                                            .send(SetDrState {
+                                            .send(SetDrReported {
                                                 dr_id: report.dr_id,
-                                                dr_state: DrState::Finished,
+                                                report_tx_hash,
                                             })
                                             .await
                                             .ok();
@@ -301,9 +360,11 @@ impl Handler<DrReporterMsg> for DrReporter {
                             }
                             Err(()) => {
                                 log::error!(
-                                    "reportResultBatch(..) tx reverted: {}",
+                                    "{}(..) tx reverted: {}",
+                                    function_name,
                                     receipt.transaction_hash
                                 );
+                                Metrics::from_registry().do_send(CountReporterError);
                             }
                         }
                     }
@@ -311,17 +372,20 @@ impl Handler<DrReporterMsg> for DrReporter {
                         // Error in call_with_confirmations
                         log::error!(
                             "{}: {:?}",
-                            format!("Cannot call reportResultBatch{:?}", &batched_report),
+                            format!("Cannot call {}{:?}", function_name, &call_params),
                             e
                         );
+                        Metrics::from_registry().do_send(CountReporterError);
                     }
                     Err(elapsed) => {
                         // Timeout is over
                         log::warn!(
-                            "Timeout ({} secs) when calling reportResultBatch{:?}",
+                            "Timeout ({} secs) when calling {}{:?}",
                             elapsed,
-                            &batched_report
+                            function_name,
+                            &call_params
                         );
+                        Metrics::from_registry().do_send(CountReporterError);
                     }
                 }
             }
@@ -365,6 +429,168 @@ impl Handler<DrReporterMsg> for DrReporter {
     }
 }
 
+/// Add a safety margin of `margin_percent` to the estimated gas of a transaction, so that it does
+/// not run out of gas if the state of the contract changes before it is mined.
+///
+/// The result never exceeds `max_gas`, if any. Returns the gas limit to use, and whether it was
+/// capped.
+fn apply_gas_margin(
+    estimated_gas: U256,
+    margin_percent: u64,
+    max_gas: Option<u64>,
+) -> (U256, bool) {
+    let margin = estimated_gas.saturating_mul(U256::from(margin_percent)) / 100;
+    let gas = estimated_gas.saturating_add(margin);
+
+    match max_gas.map(U256::from) {
+        Some(max_gas) if gas > max_gas => (max_gas, true),
+        _ => (gas, false),
+    }
+}
+
+/// Gas price of a report transaction that is waiting to be mined, which is escalated every time
+/// that the transaction is not mined within the configured number of blocks.
+#[derive(Debug)]
+struct GasPriceEscalator {
+    escalation: GasEscalation,
+    gas_price: U256,
+    last_sent_block: u64,
+}
+
+impl GasPriceEscalator {
+    /// Start tracking a transaction that was sent in `block` with `gas_price`.
+    fn new(escalation: GasEscalation, gas_price: U256, block: u64) -> Self {
+        Self {
+            escalation,
+            gas_price,
+            last_sent_block: block,
+        }
+    }
+
+    /// Tell that the transaction has not been mined yet as of `block`. Returns the gas price of
+    /// the replacement transaction if it must be sent again, which is 12.5% higher than the
+    /// previous one, capped at the maximum gas price.
+    fn not_mined(&mut self, block: u64) -> Option<U256> {
+        let max_gas_price = U256::from(self.escalation.max_gas_price);
+        if block < self.last_sent_block + self.escalation.resubmit_blocks
+            || self.gas_price >= max_gas_price
+        {
+            return None;
+        }
+
+        let bump = cmp::max(self.gas_price / 8, U256::one());
+        self.gas_price = cmp::min(self.gas_price.saturating_add(bump), max_gas_price);
+        self.last_sent_block = block;
+
+        Some(self.gas_price)
+    }
+}
+
+/// Send a report transaction and wait for it to be mined and confirmed.
+///
+/// If `gas_escalation` is set and the transaction is not mined in time, it is sent again with the
+/// same nonce and a higher gas price, so that it replaces the previous one in the mempool.
+#[allow(clippy::too_many_arguments)]
+async fn send_report_call(
+    eth: &Eth<Http>,
+    wrb_contract: &Contract<Http>,
+    function_name: &str,
+    params: &[Token],
+    eth_from: H160,
+    eth_gas_limit: U256,
+    eth_gas_price: U256,
+    eth_txs_confirmations: usize,
+    gas_escalation: Option<GasEscalation>,
+) -> Result<TransactionReceipt, contract::Error> {
+    let gas_escalation = match gas_escalation {
+        Some(gas_escalation) => gas_escalation,
+        None => {
+            return wrb_contract
+                .call_with_confirmations(
+                    function_name,
+                    params,
+                    eth_from,
+                    contract::Options::with(|opt| {
+                        opt.gas = Some(eth_gas_limit);
+                        opt.gas_price = Some(eth_gas_price);
+                    }),
+                    eth_txs_confirmations,
+                )
+                .await
+                .map_err(contract::Error::Api);
+        }
+    };
+
+    let nonce = eth
+        .transaction_count(eth_from, Some(BlockNumber::Pending))
+        .await
+        .map_err(contract::Error::Api)?;
+    let send = |gas_price: U256| {
+        wrb_contract.call(
+            function_name,
+            params,
+            eth_from,
+            contract::Options::with(|opt| {
+                opt.gas = Some(eth_gas_limit);
+                opt.gas_price = Some(gas_price);
+                opt.nonce = Some(nonce);
+            }),
+        )
+    };
+
+    let mut tx_hashes = vec![send(eth_gas_price).await?];
+    let block = eth.block_number().await.map_err(contract::Error::Api)?;
+    let mut escalator = GasPriceEscalator::new(gas_escalation, eth_gas_price, block.as_u64());
+
+    loop {
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        let block = eth
+            .block_number()
+            .await
+            .map_err(contract::Error::Api)?
+            .as_u64();
+
+        // Any of the transactions sent with this nonce may have been mined
+        let mut mined_receipt = None;
+        for tx_hash in &tx_hashes {
+            if let Some(receipt) = eth
+                .transaction_receipt(*tx_hash)
+                .await
+                .map_err(contract::Error::Api)?
+            {
+                mined_receipt = Some(receipt);
+                break;
+            }
+        }
+
+        match mined_receipt {
+            Some(receipt) => {
+                let mined_block = receipt.block_number.map(|x| x.as_u64()).unwrap_or(block);
+                let confirmations = eth_txs_confirmations as u64;
+                if block + 1 >= mined_block.saturating_add(confirmations) {
+                    return Ok(receipt);
+                }
+            }
+            None => {
+                if let Some(gas_price) = escalator.not_mined(block) {
+                    log::warn!(
+                        "{}(..) tx not mined after {} blocks, resubmitting with gas price {}",
+                        function_name,
+                        gas_escalation.resubmit_blocks,
+                        gas_price
+                    );
+                    match send(gas_price).await {
+                        Ok(tx_hash) => tx_hashes.push(tx_hash),
+                        // The previous transaction may have been mined in the meantime, so keep
+                        // waiting for it
+                        Err(e) => log::warn!("Cannot resubmit {}(..) tx: {:?}", function_name, e),
+                    }
+                }
+            }
+        }
+    }
+}
+
 /// Get the queryId of a PostedResult event, or return None if this is a different kind of event
 fn parse_batch_report_error_log(
     wrb_contract_abi: &web3::ethabi::Contract,
@@ -398,6 +624,179 @@ fn parse_batch_report_error_log(
     }
 }
 
+/// A transaction that reports some data request results to the WRB contract.
+enum ReportCall {
+    /// Call `reportResultBatch` with a list of report tuples.
+    Batch(Vec<Token>),
+    /// Call `reportResult` with the fields of a single report tuple, for WRB contracts that do not
+    /// support `reportResultBatch`.
+    Single(Token),
+}
+
+impl ReportCall {
+    /// Name of the WRB contract function to call.
+    fn function_name(&self) -> &'static str {
+        match self {
+            ReportCall::Batch(_) => "reportResultBatch",
+            ReportCall::Single(_) => "reportResult",
+        }
+    }
+
+    /// Arguments of the WRB contract function.
+    fn params(&self) -> Vec<Token> {
+        match self {
+            ReportCall::Batch(reports) => vec![Token::Array(reports.clone())],
+            ReportCall::Single(report) => report_fields(report).to_vec(),
+        }
+    }
+
+    /// Ids of the data requests reported in this call.
+    fn dr_ids(&self) -> Vec<DrId> {
+        let reports = match self {
+            ReportCall::Batch(reports) => reports.as_slice(),
+            ReportCall::Single(report) => std::slice::from_ref(report),
+        };
+
+        reports
+            .iter()
+            .map(|report| match &report_fields(report)[0] {
+                Token::Uint(dr_id) => *dr_id,
+                _ => panic!("Cannot extract query id from batch tuple"),
+            })
+            .collect()
+    }
+}
+
+/// Fields of a report tuple: query id, result timestamp, tally hash and CBOR-encoded result.
+fn report_fields(report: &Token) -> &[Token] {
+    match report {
+        Token::Tuple(fields) if fields.len() == 4 => fields,
+        _ => panic!("Cannot extract report fields from batch tuple"),
+    }
+}
+
+/// Whether the WRB contract can report several results in a single `reportResultBatch` call.
+fn supports_report_result_batch(wrb_contract_abi: &web3::ethabi::Contract) -> bool {
+    wrb_contract_abi.function("reportResultBatch").is_ok()
+}
+
+/// Group report tuples into batches of at most `max_batch_size` reports, keeping their order.
+fn group_reports(reports: Vec<Token>, max_batch_size: usize) -> Vec<Vec<Token>> {
+    reports
+        .chunks(cmp::max(max_batch_size, 1))
+        .map(<[Token]>::to_vec)
+        .collect()
+}
+
+/// Estimate the gas needed to report each result with its own reportResult transaction, as well as
+/// the profit of doing so.
+///
+/// Reports whose gas or profit cannot be estimated are skipped, just like when batching them.
+async fn estimate_single_reports(
+    reports: Vec<Token>,
+    wrb_contract: &Contract<Http>,
+    eth_from: H160,
+    eth_gas_price: U256,
+    eth_nanowit_wei_price: U256,
+    eth_max_gas: Option<u64>,
+) -> Vec<(ReportCall, U256)> {
+    let mut v = vec![];
+
+    for report in reports {
+        let report_call = ReportCall::Single(report);
+        let estimated_gas = wrb_contract
+            .estimate_gas(
+                report_call.function_name(),
+                report_call.params().as_slice(),
+                eth_from,
+                contract::Options::with(|opt| {
+                    opt.gas = eth_max_gas.map(Into::into);
+                    opt.gas_price = Some(eth_gas_price);
+                }),
+            )
+            .await;
+
+        let estimated_gas = match estimated_gas {
+            Ok(estimated_gas) => estimated_gas,
+            Err(e) => {
+                log::error!("Cannot estimate gas limit: {:?}", e);
+                log::warn!("Skipping report: {:?}", report_call.params());
+                continue;
+            }
+        };
+        log::debug!("reportResult estimated gas: {:?}", estimated_gas);
+
+        let estimated_profit = estimate_report_earnings(
+            &report_call,
+            wrb_contract,
+            eth_from,
+            eth_gas_price,
+            eth_nanowit_wei_price,
+            eth_max_gas,
+        )
+        .await;
+
+        match estimated_profit {
+            Ok((revenues, expenses)) => {
+                log::debug!(
+                    "reportResult estimated profit: {} - {} ETH",
+                    Unit::Wei(&revenues.to_string())
+                        .to_eth_str()
+                        .unwrap_or_default(),
+                    Unit::Wei(&expenses.to_string())
+                        .to_eth_str()
+                        .unwrap_or_default(),
+                );
+                v.push((report_call, estimated_gas));
+            }
+            Err(e) => {
+                log::error!("Cannot estimate report profit: {:?}", e);
+                log::warn!("Skipping report: {:?}", report_call.params());
+            }
+        }
+    }
+
+    v
+}
+
+/// Ask the WRB contract for the revenues and expenses of sending a report transaction, taking into
+/// account the size of its input data, which may affect the actual profit on some layer-2 EVM
+/// chains.
+async fn estimate_report_earnings(
+    report_call: &ReportCall,
+    wrb_contract: &Contract<Http>,
+    eth_from: H160,
+    eth_gas_price: U256,
+    eth_nanowit_wei_price: U256,
+    eth_max_gas: Option<u64>,
+) -> Result<(U256, U256), web3::contract::Error> {
+    let query_ids = report_call.dr_ids().into_iter().map(Token::Uint).collect();
+    let report_msg_data = wrb_contract
+        .abi()
+        .function(report_call.function_name())
+        .and_then(|f| f.encode_input(&report_call.params()));
+
+    let params = (
+        Token::Array(query_ids),
+        Token::Bytes(report_msg_data.unwrap_or_default()),
+        Token::Uint(eth_gas_price),
+        Token::Uint(eth_nanowit_wei_price),
+    );
+
+    wrb_contract
+        .query(
+            "estimateReportEarnings",
+            params,
+            eth_from,
+            contract::Options::with(|opt| {
+                opt.gas = eth_max_gas.map(Into::into);
+                opt.gas_price = Some(eth_gas_price);
+            }),
+            None,
+        )
+        .await
+}
+
 /// Split a batched report (argument of reportResultBatch) into multiple smaller
 /// batched reports in order to fit into some gas limit.
 ///
@@ -415,15 +814,13 @@ async fn split_by_gas_limit(
     let mut stack = vec![batched_report];
 
     while let Some(batch_params) = stack.pop() {
-        let eth_report_result_batch_params = batch_params.clone();
-
         // --------------------------------------------------------------------------
         // First: try to estimate gas required for reporting this batch of tuples ...
 
         let estimated_gas = wrb_contract
             .estimate_gas(
                 "reportResultBatch",
-                eth_report_result_batch_params.clone(),
+                batch_params.clone(),
                 eth_from,
                 contract::Options::with(|opt| {
                     opt.gas = eth_max_gas.map(Into::into);
@@ -458,45 +855,15 @@ async fn split_by_gas_limit(
         // ------------------------------------------------
         // Second: try to estimate actual profit, if any...
 
-        let query_ids: Vec<Token> = batch_params
-            .iter()
-            .map(|report_params| {
-                if let Token::Tuple(report_params) = report_params {
-                    assert_eq!(report_params.len(), 4);
-
-                    report_params[0].clone()
-                } else {
-                    panic!("Cannot extract query id from batch tuple");
-                }
-            })
-            .collect();
-
-        // the size of the report result tx data may affect the actual profit
-        // on some layer-2 EVM chains:
-        let eth_report_result_batch_msg_data = wrb_contract
-            .abi()
-            .function("reportResultBatch")
-            .and_then(|f| f.encode_input(&eth_report_result_batch_params.into_tokens()));
-
-        let params = (
-            Token::Array(query_ids),
-            Token::Bytes(eth_report_result_batch_msg_data.unwrap_or_default()),
-            Token::Uint(eth_gas_price),
-            Token::Uint(eth_nanowit_wei_price),
-        );
-
-        let estimated_profit: Result<(U256, U256), web3::contract::Error> = wrb_contract
-            .query(
-                "estimateReportEarnings",
-                params,
-                eth_from,
-                contract::Options::with(|opt| {
-                    opt.gas = eth_max_gas.map(Into::into);
-                    opt.gas_price = Some(eth_gas_price);
-                }),
-                None,
-            )
-            .await;
+        let estimated_profit = estimate_report_earnings(
+            &ReportCall::Batch(batch_params.clone()),
+            wrb_contract,
+            eth_from,
+            eth_gas_price,
+            eth_nanowit_wei_price,
+            eth_max_gas,
+        )
+        .await;
 
         match estimated_profit {
             Ok((revenues, expenses)) => {
@@ -701,6 +1068,122 @@ mod tests {
         );
     }
 
+    fn report_tuple(dr_id: u64) -> Token {
+        Token::Tuple(vec![
+            Token::Uint(U256::from(dr_id)),
+            Token::Uint(U256::zero()),
+            Token::FixedBytes(vec![0; 32]),
+            Token::Bytes(vec![0x18, 0x2a]),
+        ])
+    }
+
+    #[test]
+    fn group_reports_below_batch_limit() {
+        let reports: Vec<_> = (0..5).map(report_tuple).collect();
+
+        let groups = group_reports(reports.clone(), 32);
+        assert_eq!(groups, vec![reports.clone()]);
+
+        let report_call = ReportCall::Batch(groups[0].clone());
+        assert_eq!(report_call.function_name(), "reportResultBatch");
+        assert_eq!(report_call.params(), vec![Token::Array(reports)]);
+        assert_eq!(
+            report_call.dr_ids(),
+            (0..5).map(U256::from).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn group_reports_above_batch_limit() {
+        let reports: Vec<_> = (0..7).map(report_tuple).collect();
+
+        let groups = group_reports(reports.clone(), 3);
+        assert_eq!(
+            groups,
+            vec![
+                reports[0..3].to_vec(),
+                reports[3..6].to_vec(),
+                reports[6..].to_vec()
+            ]
+        );
+
+        // A batch size of 0 is treated as 1
+        assert_eq!(group_reports(reports, 0).len(), 7);
+    }
+
+    #[test]
+    fn report_result_fallback() {
+        let wrb_contract_abi_json: &[u8] = include_bytes!("../../../wrb_abi.json");
+        let mut wrb_contract_abi = web3::ethabi::Contract::load(wrb_contract_abi_json)
+            .map_err(|e| format!("Unable to load WRB contract from ABI: {:?}", e))
+            .unwrap();
+        hack_fix_functions_with_multiple_definitions(&mut wrb_contract_abi);
+        assert!(supports_report_result_batch(&wrb_contract_abi));
+
+        // Single reports are encoded as the arguments of reportResult
+        let report_call = ReportCall::Single(report_tuple(7));
+        assert_eq!(report_call.function_name(), "reportResult");
+        assert_eq!(report_call.dr_ids(), vec![U256::from(7)]);
+        wrb_contract_abi
+            .function(report_call.function_name())
+            .and_then(|function| function.encode_input(&report_call.params()))
+            .expect("encode args failed");
+
+        wrb_contract_abi.functions.remove("reportResultBatch");
+        assert!(!supports_report_result_batch(&wrb_contract_abi));
+    }
+
+    #[test]
+    fn gas_margin() {
+        let gas = |estimated_gas: u64, margin_percent, max_gas| {
+            let (gas, capped) =
+                apply_gas_margin(U256::from(estimated_gas), margin_percent, max_gas);
+
+            (gas.as_u64(), capped)
+        };
+
+        assert_eq!(gas(100_000, 0, None), (100_000, false));
+        assert_eq!(gas(100_000, 10, None), (110_000, false));
+        assert_eq!(gas(100_000, 25, Some(200_000)), (125_000, false));
+        // Rounds down
+        assert_eq!(gas(99_999, 10, None), (109_998, false));
+        // The margin never exceeds the gas limit
+        assert_eq!(gas(100_000, 25, Some(120_000)), (120_000, true));
+        assert_eq!(gas(100_000, 20, Some(120_000)), (120_000, false));
+        // Saturates instead of overflowing
+        assert_eq!(apply_gas_margin(U256::MAX, 10, None), (U256::MAX, false));
+    }
+
+    #[test]
+    fn gas_price_escalation_schedule() {
+        let escalation = GasEscalation {
+            resubmit_blocks: 2,
+            max_gas_price: 150,
+        };
+        let mut escalator = GasPriceEscalator::new(escalation, U256::from(100), 10);
+
+        let schedule: Vec<_> = (10..=20)
+            .map(|block| escalator.not_mined(block).map(|x| x.as_u64()))
+            .collect();
+        assert_eq!(
+            schedule,
+            vec![
+                None,
+                None,
+                Some(112),
+                None,
+                Some(126),
+                None,
+                Some(141),
+                None,
+                // Capped at the maximum gas price
+                Some(150),
+                None,
+                None
+            ]
+        );
+    }
+
     #[test]
     fn test_u256_mul_f64() {
         let x = u256_saturating_mul_f64(U256::from(1_000_000), 0.0);
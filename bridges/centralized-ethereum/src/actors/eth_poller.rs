@@ -1,7 +1,7 @@
 use crate::{
     actors::dr_database::{
-        DrDatabase, DrInfoBridge, DrState, GetLastDrId, SetDrInfoBridge, SetDrState,
-        WitnetQueryStatus,
+        DrDatabase, DrInfoBridge, DrState, ForgetDrReport, GetAllReportedDrs, GetLastDrId,
+        RevertDrReport, SetDrInfoBridge, SetDrState, WitnetQueryStatus,
     },
     config::Config,
 };
@@ -11,7 +11,7 @@ use web3::{
     contract::{self, Contract},
     ethabi::{Bytes, Token},
     transports::Http,
-    types::U256,
+    types::{TransactionId, TransactionReceipt, U256},
     Web3,
 };
 use witnet_node::utils::stop_system_if_panicking;
@@ -30,6 +30,8 @@ pub struct EthPoller {
     pub skip_first: u64,
     /// Max number of queries to be batched together
     pub max_batch_size: u16,
+    /// Number of blocks after which a report transaction is assumed to be safe from re-orgs
+    pub reorg_depth: u64,
 }
 
 impl Drop for EthPoller {
@@ -71,6 +73,7 @@ impl EthPoller {
             polling_rate_ms: config.eth_new_drs_polling_rate_ms,
             skip_first: config.storage_skip_first.unwrap_or(0),
             max_batch_size: config.eth_max_batch_size,
+            reorg_depth: config.eth_reorg_depth,
         }
     }
 
@@ -78,6 +81,8 @@ impl EthPoller {
         let wrb_contract = self.wrb_contract.clone().unwrap();
         let skip_first = U256::from(self.skip_first);
         let max_batch_size = self.max_batch_size;
+        let eth = self.web3.as_ref().unwrap().eth();
+        let reorg_depth = self.reorg_depth;
 
         log::debug!("Polling WitnetOracle at {:?}", wrb_contract.address());
 
@@ -198,6 +203,8 @@ impl EthPoller {
                     }
                 }
             }
+
+            check_reorged_reports(&eth, &dr_database_addr, reorg_depth).await;
         };
 
         ctx.spawn(fut.into_actor(self).then(move |(), _act, ctx| {
@@ -212,3 +219,99 @@ impl EthPoller {
         }));
     }
 }
+
+/// Outcome of checking whether a report transaction is still part of the canonical chain
+#[derive(Debug, PartialEq, Eq)]
+pub enum ReportTxStatus {
+    /// The transaction is no longer known by the Ethereum client, so it needs to be reported again
+    Dropped,
+    /// The transaction is pending, or in the canonical chain but a re-org could still revert it
+    Unsettled,
+    /// The transaction is buried deep enough to be safe from re-orgs
+    Final,
+}
+
+/// Tell the status of a report transaction given its receipt, as returned by the Ethereum client.
+/// Clients only return receipts for transactions that are included in the canonical chain, so a
+/// transaction without receipt is only considered dropped if the client does not know about it
+/// (`known_tx`), as otherwise it is still waiting in the mempool to be mined again.
+pub fn report_tx_status(
+    receipt: Option<&TransactionReceipt>,
+    known_tx: bool,
+    current_block: u64,
+    reorg_depth: u64,
+) -> ReportTxStatus {
+    match receipt.and_then(|receipt| receipt.block_number) {
+        None if known_tx => ReportTxStatus::Unsettled,
+        None => ReportTxStatus::Dropped,
+        Some(block_number) if current_block >= block_number.as_u64() + reorg_depth => {
+            ReportTxStatus::Final
+        }
+        Some(_) => ReportTxStatus::Unsettled,
+    }
+}
+
+/// Look for report transactions that were dropped from the canonical chain because of a re-org, and
+/// put their data requests back into "pending" state so that they get reported again.
+async fn check_reorged_reports(
+    eth: &web3::api::Eth<Http>,
+    dr_database_addr: &Addr<DrDatabase>,
+    reorg_depth: u64,
+) {
+    let reports = match dr_database_addr.send(GetAllReportedDrs).await {
+        Ok(Ok(reports)) if !reports.is_empty() => reports,
+        _ => return,
+    };
+
+    let current_block = match eth.block_number().await {
+        Ok(block_number) => block_number.as_u64(),
+        Err(e) => {
+            log::error!("Fail to get current block number: {:?}", e);
+            return;
+        }
+    };
+
+    for (dr_id, report_tx_hash) in reports {
+        let receipt = match eth.transaction_receipt(report_tx_hash).await {
+            Ok(receipt) => receipt,
+            Err(e) => {
+                log::error!(
+                    "[{}] => fail to get receipt of report tx {:?}: {:?}",
+                    dr_id,
+                    report_tx_hash,
+                    e
+                );
+                continue;
+            }
+        };
+        let known_tx = if receipt.is_some() {
+            true
+        } else {
+            match eth.transaction(TransactionId::Hash(report_tx_hash)).await {
+                Ok(transaction) => transaction.is_some(),
+                Err(e) => {
+                    log::error!(
+                        "[{}] => fail to get report tx {:?}: {:?}",
+                        dr_id,
+                        report_tx_hash,
+                        e
+                    );
+                    continue;
+                }
+            }
+        };
+
+        match report_tx_status(receipt.as_ref(), known_tx, current_block, reorg_depth) {
+            ReportTxStatus::Dropped => {
+                log::warn!(
+                    "[{}] => report tx {:?} was dropped by a re-org, will report again",
+                    dr_id,
+                    report_tx_hash
+                );
+                dr_database_addr.do_send(RevertDrReport { dr_id });
+            }
+            ReportTxStatus::Final => dr_database_addr.do_send(ForgetDrReport { dr_id }),
+            ReportTxStatus::Unsettled => {}
+        }
+    }
+}
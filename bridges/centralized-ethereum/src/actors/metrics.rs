@@ -0,0 +1,381 @@
+use crate::{
+    actors::dr_database::{CountDrsPerState, DrDatabase},
+    config::Config,
+};
+use actix::prelude::*;
+use std::{fmt::Write as _, time::Duration};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+};
+use web3::{transports::Http, Web3};
+use witnet_net::client::tcp::{methods, JsonRpcClient};
+use witnet_node::utils::stop_system_if_panicking;
+
+/// Maximum time that the metrics server waits for a client to send its request or to accept the
+/// response, so that idle connections do not pile up
+const CONNECTION_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Snapshot of the status of the bridge, as exposed by the metrics server
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BridgeMetrics {
+    /// Whether the Witnet node answered the last status request
+    pub witnet_up: bool,
+    /// Last epoch to which the Witnet node is synced
+    pub witnet_last_synced_epoch: Option<u32>,
+    /// Whether the Ethereum client answered the last status request
+    pub eth_up: bool,
+    /// Last Ethereum block seen
+    pub eth_last_block: Option<u64>,
+    /// Data requests that have not been resolved in Witnet yet
+    pub drs_pending: u32,
+    /// Report transactions that failed, reverted or timed out since the bridge started
+    pub reporter_errors: u64,
+}
+
+impl BridgeMetrics {
+    /// Whether the bridge can reach both the Witnet node and the Ethereum client
+    pub fn is_healthy(&self) -> bool {
+        self.witnet_up && self.eth_up
+    }
+
+    /// Render the metrics in the Prometheus text exposition format. Metrics whose value is not
+    /// known yet are left out.
+    pub fn to_prometheus(&self) -> String {
+        let metrics: [(&str, &str, &str, Option<u64>); 6] = [
+            (
+                "witnet_bridge_witnet_up",
+                "gauge",
+                "Whether the Witnet node answered the last status request.",
+                Some(u64::from(self.witnet_up)),
+            ),
+            (
+                "witnet_bridge_witnet_last_synced_epoch",
+                "gauge",
+                "Last epoch to which the Witnet node is synced.",
+                self.witnet_last_synced_epoch.map(u64::from),
+            ),
+            (
+                "witnet_bridge_eth_up",
+                "gauge",
+                "Whether the Ethereum client answered the last status request.",
+                Some(u64::from(self.eth_up)),
+            ),
+            (
+                "witnet_bridge_eth_last_block",
+                "gauge",
+                "Last Ethereum block seen.",
+                self.eth_last_block,
+            ),
+            (
+                "witnet_bridge_drs_pending",
+                "gauge",
+                "Data requests that have not been resolved in Witnet yet.",
+                Some(u64::from(self.drs_pending)),
+            ),
+            (
+                "witnet_bridge_reporter_errors_total",
+                "counter",
+                "Report transactions that failed, reverted or timed out.",
+                Some(self.reporter_errors),
+            ),
+        ];
+
+        let mut text = String::new();
+        for (name, kind, help, value) in metrics {
+            if let Some(value) = value {
+                // Writing into a String cannot fail
+                let _ = write!(
+                    text,
+                    "# HELP {name} {help}\n# TYPE {name} {kind}\n{name} {value}\n",
+                    name = name,
+                    help = help,
+                    kind = kind,
+                    value = value
+                );
+            }
+        }
+
+        text
+    }
+}
+
+/// Metrics actor keeps track of the status of the bridge, so that it can be served over HTTP
+#[derive(Default)]
+pub struct Metrics {
+    /// Web3 object
+    pub web3: Option<Web3<Http>>,
+    /// JSON WIT/RPC client connection to Wit/node
+    pub wit_client: Option<Addr<JsonRpcClient>>,
+    /// Period to refresh the metrics
+    pub polling_rate_ms: u64,
+    /// Latest metrics
+    pub metrics: BridgeMetrics,
+}
+
+impl Drop for Metrics {
+    fn drop(&mut self) {
+        log::trace!("Dropping Metrics");
+        stop_system_if_panicking("Metrics");
+    }
+}
+
+/// Make actor from Metrics
+impl Actor for Metrics {
+    /// Every actor has to provide execution Context in which it can run.
+    type Context = Context<Self>;
+
+    /// Method to be executed when the actor is started
+    fn started(&mut self, ctx: &mut Self::Context) {
+        log::debug!("Metrics actor has been started!");
+
+        if self.web3.is_some() && self.wit_client.is_some() {
+            self.refresh_metrics(ctx, Duration::from_millis(self.polling_rate_ms));
+        }
+    }
+}
+
+/// Required trait for being able to retrieve Metrics address from system registry
+impl actix::Supervised for Metrics {}
+
+/// Required trait for being able to retrieve Metrics address from system registry
+impl SystemService for Metrics {}
+
+impl Metrics {
+    /// Initialize `Metrics` taking the configuration from a `Config` structure
+    pub fn from_config(config: &Config, web3: Web3<Http>, wit_client: Addr<JsonRpcClient>) -> Self {
+        Self {
+            web3: Some(web3),
+            wit_client: Some(wit_client),
+            polling_rate_ms: config.metrics_polling_rate_ms,
+            metrics: BridgeMetrics::default(),
+        }
+    }
+
+    fn refresh_metrics(&self, ctx: &mut Context<Self>, period: Duration) {
+        let eth = self.web3.as_ref().unwrap().eth();
+        let wit_client = self.wit_client.clone().unwrap();
+
+        let fut = async move {
+            let witnet_last_synced_epoch = match methods::sync_status(&wit_client).await {
+                Ok(sync_status) => Some(sync_status.chain_beacon.checkpoint),
+                Err(e) => {
+                    log::debug!("Cannot get Witnet node sync status: {}", e);
+                    None
+                }
+            };
+            let eth_last_block = match eth.block_number().await {
+                Ok(block_number) => Some(block_number.as_u64()),
+                Err(e) => {
+                    log::debug!("Cannot get last Ethereum block number: {}", e);
+                    None
+                }
+            };
+            let drs_pending = DrDatabase::from_registry()
+                .send(CountDrsPerState)
+                .await
+                .ok()
+                .and_then(Result::ok)
+                .map(|(drs_new, drs_pending, _, _)| drs_new + drs_pending);
+
+            (witnet_last_synced_epoch, eth_last_block, drs_pending)
+        };
+
+        ctx.spawn(fut.into_actor(self).then(
+            move |(witnet_last_synced_epoch, eth_last_block, drs_pending), act, ctx| {
+                act.metrics.witnet_up = witnet_last_synced_epoch.is_some();
+                act.metrics.witnet_last_synced_epoch =
+                    witnet_last_synced_epoch.or(act.metrics.witnet_last_synced_epoch);
+                act.metrics.eth_up = eth_last_block.is_some();
+                act.metrics.eth_last_block = eth_last_block.or(act.metrics.eth_last_block);
+                if let Some(drs_pending) = drs_pending {
+                    act.metrics.drs_pending = drs_pending;
+                }
+
+                // Wait until the function finished to schedule next call.
+                // This avoids tasks running in parallel.
+                ctx.run_later(period, move |act, ctx| {
+                    act.refresh_metrics(ctx, period);
+                });
+
+                actix::fut::ready(())
+            },
+        ));
+    }
+}
+
+/// Count a report transaction that failed, reverted or timed out
+pub struct CountReporterError;
+
+impl Message for CountReporterError {
+    type Result = ();
+}
+
+impl Handler<CountReporterError> for Metrics {
+    type Result = ();
+
+    fn handle(&mut self, _msg: CountReporterError, _ctx: &mut Self::Context) -> Self::Result {
+        self.metrics.reporter_errors += 1;
+    }
+}
+
+/// Get the latest metrics
+pub struct GetMetrics;
+
+impl Message for GetMetrics {
+    type Result = BridgeMetrics;
+}
+
+impl Handler<GetMetrics> for Metrics {
+    type Result = MessageResult<GetMetrics>;
+
+    fn handle(&mut self, _msg: GetMetrics, _ctx: &mut Self::Context) -> Self::Result {
+        MessageResult(self.metrics.clone())
+    }
+}
+
+/// Serve the metrics of the bridge over HTTP:
+///
+/// - `GET /metrics`: all the metrics in the Prometheus text exposition format.
+/// - `GET /health`: status 200 if the bridge can reach both the Witnet node and the Ethereum
+///   client, or 503 otherwise.
+pub async fn serve(listener: TcpListener, metrics: Addr<Metrics>) {
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                actix::spawn(handle_connection(stream, metrics.clone()));
+            }
+            Err(e) => {
+                log::warn!("Metrics server failed to accept connection: {}", e);
+            }
+        }
+    }
+}
+
+async fn handle_connection(mut stream: TcpStream, metrics: Addr<Metrics>) {
+    // Only the request line matters, which always fits in the first read
+    let mut buffer = [0; 1024];
+    let request = match tokio::time::timeout(CONNECTION_TIMEOUT, stream.read(&mut buffer)).await {
+        Ok(Ok(read)) => String::from_utf8_lossy(&buffer[..read]).into_owned(),
+        Ok(Err(e)) => {
+            log::debug!("Metrics server failed to read request: {}", e);
+            return;
+        }
+        Err(_) => {
+            log::debug!("Metrics server timed out waiting for a request");
+            return;
+        }
+    };
+    let mut request_line = request
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .split_whitespace();
+    let (method, path) = (request_line.next(), request_line.next());
+
+    let (status, content_type, body) = match (method, path) {
+        (Some("GET"), Some("/metrics")) => {
+            let metrics = metrics.send(GetMetrics).await.unwrap_or_default();
+            (
+                "200 OK",
+                "text/plain; version=0.0.4",
+                metrics.to_prometheus(),
+            )
+        }
+        (Some("GET"), Some("/health")) => {
+            let metrics = metrics.send(GetMetrics).await.unwrap_or_default();
+            let status = if metrics.is_healthy() {
+                "200 OK"
+            } else {
+                "503 Service Unavailable"
+            };
+            let body = serde_json::json!({
+                "healthy": metrics.is_healthy(),
+                "witnet_up": metrics.witnet_up,
+                "eth_up": metrics.eth_up,
+            });
+            (status, "application/json", body.to_string())
+        }
+        (Some("GET"), _) => ("404 Not Found", "text/plain", String::from("Not Found\n")),
+        _ => (
+            "405 Method Not Allowed",
+            "text/plain",
+            String::from("Method Not Allowed\n"),
+        ),
+    };
+
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    );
+    match tokio::time::timeout(CONNECTION_TIMEOUT, stream.write_all(response.as_bytes())).await {
+        Ok(Ok(())) => {}
+        Ok(Err(e)) => log::debug!("Metrics server failed to write response: {}", e),
+        Err(_) => log::debug!("Metrics server timed out writing the response"),
+    }
+    stream.shutdown().await.ok();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn get(address: std::net::SocketAddr, path: &str) -> String {
+        let mut stream = TcpStream::connect(address).await.unwrap();
+        stream
+            .write_all(format!("GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", path).as_bytes())
+            .await
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+
+        response
+    }
+
+    #[test]
+    fn metrics_server() {
+        actix::System::new().block_on(async {
+            // Without a Witnet node nor an Ethereum client, the metrics are never refreshed
+            let mut metrics = Metrics::default();
+            metrics.metrics = BridgeMetrics {
+                witnet_up: true,
+                witnet_last_synced_epoch: Some(1234),
+                eth_up: false,
+                eth_last_block: None,
+                drs_pending: 7,
+                reporter_errors: 2,
+            };
+            let metrics = metrics.start();
+            metrics.send(CountReporterError).await.unwrap();
+
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let address = listener.local_addr().unwrap();
+            actix::spawn(serve(listener, metrics));
+
+            let response = get(address, "/metrics").await;
+            assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+            for gauge in [
+                "witnet_bridge_witnet_up 1\n",
+                "witnet_bridge_witnet_last_synced_epoch 1234\n",
+                "witnet_bridge_eth_up 0\n",
+                "witnet_bridge_drs_pending 7\n",
+                "witnet_bridge_reporter_errors_total 3\n",
+                "# TYPE witnet_bridge_reporter_errors_total counter\n",
+            ] {
+                assert!(response.contains(gauge), "missing {:?}", gauge);
+            }
+            // Unknown values are not exposed
+            assert!(!response.contains("witnet_bridge_eth_last_block"));
+
+            let response = get(address, "/health").await;
+            assert!(response.starts_with("HTTP/1.1 503 Service Unavailable\r\n"));
+            assert!(response.ends_with(r#"{"eth_up":false,"healthy":false,"witnet_up":true}"#));
+
+            let response = get(address, "/unknown").await;
+            assert!(response.starts_with("HTTP/1.1 404 Not Found\r\n"));
+        });
+    }
+}
@@ -10,6 +10,9 @@ pub mod dr_sender;
 /// eth_poller actor module
 pub mod eth_poller;
 
+/// metrics actor module
+pub mod metrics;
+
 /// wit_poller actor module
 pub mod wit_poller;
 
@@ -13,22 +13,39 @@ use witnet_data_structures::chain::Environment;
 #[derive(Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct Config {
+    /// Chain id that the ethereum client is expected to be connected to. If missing, any chain is
+    /// accepted
+    pub eth_chain_id: Option<u64>,
     /// Ethereum account used to report data request results
     pub eth_from: H160,
     /// Ethereum account balance under which alerts will be logged
     pub eth_from_balance_threshold: u64,
+    /// Gas price escalation for report transactions that take too long to be mined. If missing,
+    /// report transactions are never sent again
+    #[serde(default, deserialize_with = "nested_toml_if_using_envy")]
+    pub eth_gas_escalation: Option<GasEscalation>,
     /// Gas limits for some methods. If missing, let the client estimate
     #[serde(deserialize_with = "nested_toml_if_using_envy")]
     pub eth_gas_limits: Gas,
+    /// Safety margin (in percent) added to the estimated gas of report transactions, capped at
+    /// the `report_result` gas limit if there is one
+    #[serde(default = "default_gas_margin_percent")]
+    pub eth_gas_margin_percent: u64,
     /// Url of the ethereum client
     pub eth_jsonrpc_url: String,
     /// Max number of queries to be batched together
     #[serde(default = "default_max_batch_size")]
     pub eth_max_batch_size: u16,
+    /// Max number of results to be reported together in a single reportResultBatch transaction
+    #[serde(default = "default_max_report_batch_size")]
+    pub eth_max_report_batch_size: u16,
     /// Price of $nanoWit in Wei, used to improve estimation of report profits
     pub eth_nanowit_wei_price: Option<u64>,
     /// Polling period for checking new queries in the WitnetOracle contract
     pub eth_new_drs_polling_rate_ms: u64,
+    /// Number of blocks after which report transactions are no longer checked for re-orgs
+    #[serde(default = "default_reorg_depth")]
+    pub eth_reorg_depth: u64,
     /// Number of block confirmations needed to assume finality when sending transactions to ethereum
     #[serde(default = "one")]
     pub eth_txs_confirmations: usize,
@@ -43,6 +60,13 @@ pub struct Config {
     #[serde(default = "default_watch_dog_polling_rate_minutes")]
     pub watch_dog_polling_rate_minutes: u32,
 
+    /// Address of the HTTP server exposing the /metrics and /health endpoints. If missing, the
+    /// server is not started
+    pub metrics_server_address: Option<SocketAddr>,
+    /// Polling period for refreshing the metrics
+    #[serde(default = "default_metrics_polling_rate_ms")]
+    pub metrics_polling_rate_ms: u64,
+
     /// Minimum collateral required on data requests read from the WitnetOracle contract
     pub witnet_dr_min_collateral_nanowits: u64,
     /// Maximium data request transaction fee assumed by the bridge
@@ -73,14 +97,30 @@ fn one() -> usize {
     1
 }
 
+fn default_gas_margin_percent() -> u64 {
+    10
+}
+
 fn default_max_batch_size() -> u16 {
     256
 }
 
+fn default_max_report_batch_size() -> u16 {
+    32
+}
+
+fn default_reorg_depth() -> u64 {
+    64
+}
+
 fn default_watch_dog_polling_rate_minutes() -> u32 {
     15
 }
 
+fn default_metrics_polling_rate_ms() -> u64 {
+    15_000
+}
+
 /// Gas limits for some methods. If missing, let the client estimate
 #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -91,6 +131,17 @@ pub struct Gas {
     pub report_result: Option<u64>,
 }
 
+/// Gas price escalation for report transactions that take too long to be mined
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GasEscalation {
+    /// Number of blocks to wait for a report transaction to be mined before sending it again, with
+    /// the same nonce and a 12.5% higher gas price
+    pub resubmit_blocks: u64,
+    /// Maximum gas price (in Wei) that resubmitted report transactions can use
+    pub max_gas_price: u64,
+}
+
 /// Storage
 #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
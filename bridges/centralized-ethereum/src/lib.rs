@@ -107,13 +107,52 @@ pub async fn check_witnet_node_running(witnet_addr: &str) -> Result<(), String>
     }
 }
 
-/// Check if the ethereum node is running
-pub async fn check_ethereum_node_running(eth_jsonrpc_url: &str) -> Result<(), String> {
+/// Check if the ethereum node is running and, if `expected_chain_id` is set, that it is connected
+/// to that chain
+pub async fn check_ethereum_node_running(
+    eth_jsonrpc_url: &str,
+    expected_chain_id: Option<u64>,
+) -> Result<(), String> {
     let web3_http = web3::transports::Http::new(eth_jsonrpc_url)
         .map_err(|e| format!("Failed to connect to Ethereum client.\nError: {:?}", e))
         .unwrap();
     let web3 = web3::Web3::new(web3_http);
 
+    check_ethereum_node_syncing(&web3, eth_jsonrpc_url).await?;
+
+    match expected_chain_id {
+        Some(expected_chain_id) => check_ethereum_chain_id(&web3, expected_chain_id).await,
+        None => Ok(()),
+    }
+}
+
+/// Check that the ethereum node is connected to the expected chain
+async fn check_ethereum_chain_id(web3: &Web3<Http>, expected_chain_id: u64) -> Result<(), String> {
+    let chain_id = web3.eth().chain_id().await.map_err(|e| {
+        log::error!("Failed to get chain id from ethereum node: {}", e);
+
+        e.to_string()
+    })?;
+
+    if chain_id == expected_chain_id.into() {
+        log::debug!("Ethereum node is connected to chain id {}", chain_id);
+
+        Ok(())
+    } else {
+        let e = format!(
+            "Ethereum node is connected to chain id {}, but chain id {} was expected",
+            chain_id, expected_chain_id
+        );
+        log::error!("{}", e);
+
+        Err(e)
+    }
+}
+
+async fn check_ethereum_node_syncing(
+    web3: &Web3<Http>,
+    eth_jsonrpc_url: &str,
+) -> Result<(), String> {
     // Use a sample web3 call to check http connection
     let res = web3.eth().syncing().await;
     match res {
@@ -174,6 +213,81 @@ pub async fn handle_receipt(receipt: &TransactionReceipt) -> Result<(), ()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+    };
+
+    /// Mock of an ethereum client that is synced to the chain with the given id. Returns its URL.
+    fn mock_ethereum_node(chain_id: u64) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                std::thread::spawn(move || {
+                    let mut reader = BufReader::new(stream.try_clone().unwrap());
+                    // Keep-alive connections may carry several requests
+                    loop {
+                        let mut content_length = 0;
+                        loop {
+                            let mut line = String::new();
+                            if reader.read_line(&mut line).unwrap_or(0) == 0 {
+                                return;
+                            }
+                            let line = line.trim_end().to_ascii_lowercase();
+                            if line.is_empty() {
+                                break;
+                            }
+                            if let Some(length) = line.strip_prefix("content-length:") {
+                                content_length = length.trim().parse().unwrap();
+                            }
+                        }
+                        let mut body = vec![0; content_length];
+                        reader.read_exact(&mut body).unwrap();
+
+                        let request: serde_json::Value = serde_json::from_slice(&body).unwrap();
+                        let result = match request["method"].as_str() {
+                            Some("eth_chainId") => json!(format!("{:#x}", chain_id)),
+                            _ => json!(false),
+                        };
+                        let response = json!({
+                            "jsonrpc": "2.0",
+                            "result": result,
+                            "id": request["id"],
+                        })
+                        .to_string();
+                        write!(
+                            stream,
+                            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                            response.len(),
+                            response
+                        )
+                        .unwrap();
+                    }
+                });
+            }
+        });
+
+        url
+    }
+
+    #[test]
+    fn check_ethereum_node_chain_id() {
+        let url = mock_ethereum_node(5);
+
+        actix::System::new().block_on(async move {
+            assert_eq!(check_ethereum_node_running(&url, None).await, Ok(()));
+            assert_eq!(check_ethereum_node_running(&url, Some(5)).await, Ok(()));
+
+            let e = check_ethereum_node_running(&url, Some(1))
+                .await
+                .unwrap_err();
+            assert!(e.contains("chain id 5"), "{}", e);
+            assert!(e.contains("chain id 1 was expected"), "{}", e);
+        });
+    }
 
     #[test]
     fn test_hack_fix_functions_with_multiple_definitions() {
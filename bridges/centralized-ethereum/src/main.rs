@@ -6,8 +6,13 @@ use structopt::StructOpt;
 
 use witnet_centralized_ethereum_bridge::{
     actors::{
-        dr_database::DrDatabase, dr_reporter::DrReporter, dr_sender::DrSender,
-        eth_poller::EthPoller, watch_dog::WatchDog, wit_poller::WitPoller,
+        dr_database::DrDatabase,
+        dr_reporter::DrReporter,
+        dr_sender::DrSender,
+        eth_poller::EthPoller,
+        metrics::{self, Metrics},
+        watch_dog::WatchDog,
+        wit_poller::WitPoller,
     },
     check_ethereum_node_running, check_witnet_node_running, config, create_wrb_contract,
 };
@@ -80,9 +85,9 @@ fn run(callback: fn()) -> Result<(), String> {
         callback();
 
         // Check if Ethereum and Witnet nodes are running before starting actors
-        check_ethereum_node_running(&config.eth_jsonrpc_url)
+        check_ethereum_node_running(&config.eth_jsonrpc_url, config.eth_chain_id)
             .await
-            .expect("ethereum node not running");
+            .expect("ethereum node not running or connected to the wrong chain");
 
         check_witnet_node_running(&config.witnet_jsonrpc_socket.to_string())
             .await
@@ -120,6 +125,19 @@ fn run(callback: fn()) -> Result<(), String> {
         let dr_sender_addr = DrSender::from_config(&config, node_client.clone()).start();
         SystemRegistry::set(dr_sender_addr);
 
+        // Start Metrics actor and its HTTP server
+        if let Some(metrics_server_address) = config.metrics_server_address {
+            let metrics_addr =
+                Metrics::from_config(&config, web3.clone(), node_client.clone()).start();
+            SystemRegistry::set(metrics_addr.clone());
+
+            let listener = tokio::net::TcpListener::bind(metrics_server_address)
+                .await
+                .expect("metrics server failed to bind");
+            log::info!("Metrics server listening on {}", metrics_server_address);
+            actix::spawn(metrics::serve(listener, metrics_addr));
+        }
+
         // Initialize Storage Manager
         let mut node_config = NodeConfig::default();
         node_config
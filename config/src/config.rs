@@ -50,7 +50,7 @@ use witnet_crypto::hash::HashFunction;
 use witnet_data_structures::{
     chain::{ConsensusConstants, Environment, Epoch, PartialConsensusConstants},
     proto::versioning::ProtocolVersion,
-    witnessing::WitnessingConfig,
+    witnessing::{IpVersion, ResolveOverride, RetryPolicy, TlsVersion, WitnessingConfig},
 };
 use witnet_protected::ProtectedString;
 
@@ -289,6 +289,72 @@ pub struct Witnessing {
     /// and we are taking as small of a risk as possible when committing to specially crafted data
     /// requests that may be potentially ill-intended.
     pub proxies: Vec<String>,
+
+    /// Caps the total download rate of all the concurrent retrievals, in bytes per second. This
+    /// is useful for nodes running on metered connections. A value of 0 means no limit.
+    pub max_download_rate: u64,
+
+    /// Maximum number of attempts for retrievals that fail because of transient errors, that is,
+    /// 5xx status codes or connection errors. A value of 1 disables retries.
+    pub retry_max_attempts: u32,
+
+    /// Delay before retrying a failed retrieval for the first time. It doubles on every subsequent
+    /// retry, with some random jitter.
+    #[partial_struct(serde(
+        default,
+        deserialize_with = "from_millis",
+        serialize_with = "to_millis",
+        rename = "retry_base_delay_milliseconds"
+    ))]
+    pub retry_base_delay: Duration,
+
+    /// Only allow retrieving data sources over TLS, i.e. reject plain `http://` URLs.
+    pub require_tls: bool,
+
+    /// Pin the user agent sent in HTTP retrievals. If not set, a common user agent is picked at
+    /// random on every request.
+    #[partial_struct(skip)]
+    #[partial_struct(serde(default))]
+    pub user_agent: Option<String>,
+
+    /// Names of additional headers, such as API keys, whose values must be redacted in logs.
+    /// `Authorization`, `Cookie` and `Set-Cookie` are always redacted. This never changes the
+    /// results of retrievals, so HTTP-HEAD retrievals still see the actual values.
+    pub redacted_headers: Vec<String>,
+
+    /// Maximum time to wait for the connection to a data source to be established, so that
+    /// unreachable hosts fail fast. A value of 0 means no timeout.
+    #[partial_struct(serde(
+        default,
+        deserialize_with = "from_millis",
+        serialize_with = "to_millis",
+        rename = "connect_timeout_milliseconds"
+    ))]
+    pub connect_timeout: Duration,
+
+    /// Maximum time to wait without receiving any data from a data source once connected. Slow
+    /// responses are fine as long as they keep streaming data. A value of 0 means no timeout.
+    #[partial_struct(serde(
+        default,
+        deserialize_with = "from_millis",
+        serialize_with = "to_millis",
+        rename = "read_timeout_milliseconds"
+    ))]
+    pub read_timeout: Duration,
+
+    /// Which IP version to use when connecting to data sources: `any`, `v4` or `v6`. Nodes on
+    /// IPv6-only networks can use `v6` so as not to try IPv4 addresses that cannot be reached.
+    pub ip_version: IpVersion,
+
+    /// Fixed addresses for some data source hosts, written as `host:port:address`, which are then
+    /// never resolved through DNS.
+    pub resolve: Vec<ResolveOverride>,
+
+    /// Refuse data sources that do not support this TLS version or a later one. Only `"1.2"` is
+    /// supported for now. If not set, the defaults of the HTTP client apply.
+    #[partial_struct(skip)]
+    #[partial_struct(serde(default))]
+    pub min_tls_version: Option<TlsVersion>,
 }
 
 /// Available storage backends
@@ -301,6 +367,17 @@ pub enum StorageBackend {
     RocksDB,
 }
 
+/// Compression algorithms for the data written by the RocksDB storage backend
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+pub enum StorageCompression {
+    #[serde(rename = "none")]
+    None,
+    #[serde(rename = "lz4")]
+    Lz4,
+    #[serde(rename = "zstd")]
+    Zstd,
+}
+
 /// Storage-specific configuration
 #[derive(PartialStruct, Debug, Clone, PartialEq, Eq)]
 #[partial_struct(derive(Deserialize, Serialize, Default, Debug, Clone, PartialEq, Eq))]
@@ -320,6 +397,11 @@ pub struct Storage {
     pub utxos_in_memory: bool,
     /// RocksDB option max_open_files. -1 means unlimited.
     pub max_open_files: i32,
+    /// Compression of the data written to the RocksDB database. If not set, the RocksDB default is
+    /// used. Existing data is only recompressed as it gets compacted.
+    #[partial_struct(skip)]
+    #[partial_struct(serde(default))]
+    pub compression: Option<StorageCompression>,
 }
 
 /// JsonRPC API configuration
@@ -782,6 +864,7 @@ impl Storage {
             max_open_files: config
                 .max_open_files
                 .unwrap_or_else(|| defaults.storage_max_open_files()),
+            compression: config.compression,
         }
     }
 
@@ -792,6 +875,7 @@ impl Storage {
             master_key_import_path: self.master_key_import_path.clone(),
             utxos_in_memory: Some(self.utxos_in_memory),
             max_open_files: Some(self.max_open_files),
+            compression: self.compression,
         }
     }
 }
@@ -949,6 +1033,12 @@ pub struct Wallet {
     pub node_url: Vec<String>,
     /// How many blocks to ask a Witnet node for when synchronizing.
     pub node_sync_batch_size: u32,
+    /// Maximum duration in milliseconds to wait between attempts to reconnect to a Witnet node.
+    /// The wait starts at 250 milliseconds and grows with every failed attempt up to this value.
+    pub node_reconnect_max_backoff: u64,
+    /// Maximum duration in milliseconds of the random delay added to every wait between attempts
+    /// to reconnect to a Witnet node.
+    pub node_reconnect_jitter: u64,
     /// How many worker threads the wallet uses.
     #[partial_struct(skip)]
     #[partial_struct(serde(default))]
@@ -996,6 +1086,12 @@ impl Wallet {
                 .unwrap_or_else(|| defaults.wallet_server_addr()),
             node_url: config.node_url.clone(),
             node_sync_batch_size: config.node_sync_batch_size.unwrap_or(50),
+            node_reconnect_max_backoff: config
+                .node_reconnect_max_backoff
+                .unwrap_or_else(|| defaults.wallet_node_reconnect_max_backoff()),
+            node_reconnect_jitter: config
+                .node_reconnect_jitter
+                .unwrap_or_else(|| defaults.wallet_node_reconnect_jitter()),
             concurrency: config.concurrency,
             db_path: config.db_path.clone().unwrap_or_else(dirs::data_dir),
             db_file_name: config
@@ -1044,6 +1140,8 @@ impl Wallet {
             server_addr: Some(self.server_addr),
             node_url: self.node_url.clone(),
             node_sync_batch_size: Some(self.node_sync_batch_size),
+            node_reconnect_max_backoff: Some(self.node_reconnect_max_backoff),
+            node_reconnect_jitter: Some(self.node_reconnect_jitter),
             concurrency: self.concurrency,
             db_path: Some(self.db_path.clone()),
             db_file_name: Some(self.db_file_name.clone()),
@@ -1147,6 +1245,37 @@ impl Witnessing {
                 .proxies
                 .clone()
                 .unwrap_or_else(|| defaults.witnessing_proxies()),
+            max_download_rate: config
+                .max_download_rate
+                .unwrap_or_else(|| defaults.witnessing_max_download_rate()),
+            retry_max_attempts: config
+                .retry_max_attempts
+                .unwrap_or_else(|| defaults.witnessing_retry_max_attempts()),
+            retry_base_delay: config
+                .retry_base_delay
+                .unwrap_or_else(|| defaults.witnessing_retry_base_delay()),
+            require_tls: config
+                .require_tls
+                .unwrap_or_else(|| defaults.witnessing_require_tls()),
+            user_agent: config.user_agent.clone(),
+            redacted_headers: config
+                .redacted_headers
+                .clone()
+                .unwrap_or_else(|| defaults.witnessing_redacted_headers()),
+            connect_timeout: config
+                .connect_timeout
+                .unwrap_or_else(|| defaults.witnessing_connect_timeout()),
+            read_timeout: config
+                .read_timeout
+                .unwrap_or_else(|| defaults.witnessing_read_timeout()),
+            ip_version: config
+                .ip_version
+                .unwrap_or_else(|| defaults.witnessing_ip_version()),
+            resolve: config
+                .resolve
+                .clone()
+                .unwrap_or_else(|| defaults.witnessing_resolve()),
+            min_tls_version: config.min_tls_version,
         }
     }
 
@@ -1155,6 +1284,17 @@ impl Witnessing {
             allow_unproxied: Some(self.allow_unproxied),
             paranoid_percentage: Some(self.paranoid_percentage),
             proxies: Some(self.proxies.clone()),
+            max_download_rate: Some(self.max_download_rate),
+            retry_max_attempts: Some(self.retry_max_attempts),
+            retry_base_delay: Some(self.retry_base_delay),
+            require_tls: Some(self.require_tls),
+            user_agent: self.user_agent.clone(),
+            redacted_headers: Some(self.redacted_headers.clone()),
+            connect_timeout: Some(self.connect_timeout),
+            read_timeout: Some(self.read_timeout),
+            ip_version: Some(self.ip_version),
+            resolve: Some(self.resolve.clone()),
+            min_tls_version: self.min_tls_version,
         }
     }
 
@@ -1191,9 +1331,70 @@ impl Witnessing {
 
         let paranoid = f32::from(self.paranoid_percentage) / 100.0;
 
+        let max_download_rate = if self.max_download_rate > 0 {
+            log::info!(
+                "Retrieval download rate is capped to {} bytes per second",
+                self.max_download_rate
+            );
+            Some(self.max_download_rate)
+        } else {
+            None
+        };
+
+        let retry = if self.retry_max_attempts > 1 {
+            log::info!(
+                "Retrievals failing with transient errors will be attempted up to {} times",
+                self.retry_max_attempts
+            );
+            Some(RetryPolicy {
+                max_attempts: self.retry_max_attempts,
+                base_delay: self.retry_base_delay,
+            })
+        } else {
+            None
+        };
+
+        if self.require_tls {
+            log::info!("Retrieval of data sources over plain HTTP is disabled");
+        }
+
+        if let Some(user_agent) = &self.user_agent {
+            log::info!("Retrievals will use the {:?} user agent", user_agent);
+        }
+
+        let connect_timeout = Some(self.connect_timeout).filter(|timeout| !timeout.is_zero());
+        let read_timeout = Some(self.read_timeout).filter(|timeout| !timeout.is_zero());
+        log::info!(
+            "Retrieval connect timeout is {:?} and read timeout is {:?}",
+            connect_timeout,
+            read_timeout
+        );
+
+        if self.ip_version != IpVersion::Any {
+            log::info!("Retrievals will only use {:?} addresses", self.ip_version);
+        }
+        for resolve in &self.resolve {
+            log::info!("Retrievals will bypass DNS with the {} override", resolve);
+        }
+        if let Some(min_tls_version) = self.min_tls_version {
+            log::info!("Retrievals will require {:?} or later", min_tls_version);
+        }
+
         WitnessingConfig {
             paranoid_threshold: paranoid,
+            paranoid: true,
+            rotation: Default::default(),
             transports,
+            max_download_rate,
+            retry,
+            require_tls: self.require_tls,
+            user_agent: self.user_agent,
+            redacted_headers: self.redacted_headers,
+            connect_timeout,
+            read_timeout,
+            ip_version: self.ip_version,
+            resolve: self.resolve,
+            min_tls_version: self.min_tls_version,
         }
     }
 }
@@ -1349,6 +1550,7 @@ mod tests {
         let config = Storage::from_partial(&partial_config, &Testnet);
 
         assert_eq!(config.db_path.to_str(), Testnet.storage_db_path().to_str());
+        assert_eq!(config.compression, None);
     }
 
     #[test]
@@ -1359,10 +1561,13 @@ mod tests {
             master_key_import_path: None,
             utxos_in_memory: None,
             max_open_files: None,
+            compression: Some(StorageCompression::Zstd),
         };
         let config = Storage::from_partial(&partial_config, &Testnet);
 
         assert_eq!(config.db_path.to_str(), Some("other"));
+        assert_eq!(config.compression, Some(StorageCompression::Zstd));
+        assert_eq!(config.to_partial(), partial_config);
     }
 
     #[test]
@@ -1447,12 +1652,53 @@ mod tests {
             allow_unproxied: Some(true),
             paranoid_percentage: Some(51),
             proxies: Some(Vec::<String>::new()),
+            max_download_rate: Some(1_000_000),
+            retry_max_attempts: Some(3),
+            retry_base_delay: Some(Duration::from_millis(250)),
+            require_tls: Some(true),
+            user_agent: Some(String::from("witnet-test/1.0")),
+            redacted_headers: Some(vec![String::from("X-Api-Key")]),
+            connect_timeout: Some(Duration::from_millis(2_000)),
+            read_timeout: Some(Duration::from_secs(0)),
+            ip_version: Some(IpVersion::V6),
+            resolve: Some(vec!["example.com:443:[::1]".parse().unwrap()]),
+            min_tls_version: Some(TlsVersion::V1_2),
         };
         let config = Witnessing::from_partial(&partial, &Testnet);
 
         assert!(config.allow_unproxied);
         assert_eq!(config.paranoid_percentage, 51);
         assert_eq!(config.proxies, Vec::<String>::new());
+        assert_eq!(config.max_download_rate, 1_000_000);
+        assert_eq!(config.retry_max_attempts, 3);
+        assert_eq!(config.retry_base_delay, Duration::from_millis(250));
+        assert!(config.require_tls);
+        assert_eq!(config.user_agent, Some(String::from("witnet-test/1.0")));
+        assert_eq!(config.redacted_headers, vec![String::from("X-Api-Key")]);
+        assert_eq!(config.connect_timeout, Duration::from_millis(2_000));
+        assert_eq!(config.read_timeout, Duration::from_secs(0));
+        assert_eq!(config.ip_version, IpVersion::V6);
+        assert_eq!(
+            config.resolve,
+            vec!["example.com:443:[::1]".parse().unwrap()]
+        );
+
+        let config = config.into_config();
+        assert_eq!(config.max_download_rate, Some(1_000_000));
+        assert_eq!(
+            config.retry,
+            Some(RetryPolicy {
+                max_attempts: 3,
+                base_delay: Duration::from_millis(250),
+            })
+        );
+        assert!(config.require_tls);
+        assert_eq!(config.user_agent, Some(String::from("witnet-test/1.0")));
+        assert_eq!(config.connect_timeout, Some(Duration::from_millis(2_000)));
+        assert_eq!(config.read_timeout, None);
+        assert_eq!(config.ip_version, IpVersion::V6);
+        assert_eq!(config.resolve.len(), 1);
+        assert_eq!(config.min_tls_version, Some(TlsVersion::V1_2));
     }
 
     #[test]
@@ -13,6 +13,7 @@ use witnet_crypto::hash::HashFunction;
 use witnet_data_structures::{
     chain::{Epoch, Hash},
     proto::versioning::ProtocolVersion,
+    witnessing::{IpVersion, ResolveOverride},
 };
 use witnet_protected::ProtectedString;
 
@@ -146,6 +147,51 @@ pub trait Defaults {
         vec![]
     }
 
+    /// Do not cap the download rate of retrievals by default.
+    fn witnessing_max_download_rate(&self) -> u64 {
+        0
+    }
+
+    /// Do not retry failed retrievals by default.
+    fn witnessing_retry_max_attempts(&self) -> u32 {
+        1
+    }
+
+    /// Wait half a second before retrying a failed retrieval for the first time.
+    fn witnessing_retry_base_delay(&self) -> Duration {
+        Duration::from_millis(500)
+    }
+
+    /// Allow retrieving data sources over plain HTTP by default.
+    fn witnessing_require_tls(&self) -> bool {
+        false
+    }
+
+    /// Only redact the headers that are always redacted by default.
+    fn witnessing_redacted_headers(&self) -> Vec<String> {
+        vec![]
+    }
+
+    /// Do not time out while connecting to data sources by default.
+    fn witnessing_connect_timeout(&self) -> Duration {
+        Duration::from_secs(0)
+    }
+
+    /// Do not time out while reading from data sources by default.
+    fn witnessing_read_timeout(&self) -> Duration {
+        Duration::from_secs(0)
+    }
+
+    /// Connect to data sources over both IPv4 and IPv6 by default.
+    fn witnessing_ip_version(&self) -> IpVersion {
+        IpVersion::Any
+    }
+
+    /// Resolve the hosts of all data sources through DNS by default.
+    fn witnessing_resolve(&self) -> Vec<ResolveOverride> {
+        vec![]
+    }
+
     /// Timestamp at the start of epoch 0
     fn consensus_constants_checkpoint_zero_timestamp(&self) -> i64;
 
@@ -394,6 +440,16 @@ pub trait Defaults {
         SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 11212)
     }
 
+    /// Wallet maximum backoff time in milliseconds between attempts to reconnect to a node
+    fn wallet_node_reconnect_max_backoff(&self) -> u64 {
+        15_000
+    }
+
+    /// Wallet maximum jitter in milliseconds added to the backoff time between reconnections
+    fn wallet_node_reconnect_jitter(&self) -> u64 {
+        250
+    }
+
     /// Wallet db file name
     fn wallet_db_file_name(&self) -> String {
         "witnet_wallet.db".to_string()
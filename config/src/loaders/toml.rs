@@ -136,12 +136,17 @@ known_peers = ['192.168.1.12:1234']
             r"
 [storage]
 db_path = 'dbfiles'
+compression = 'zstd'
     ",
         )
         .unwrap();
 
         assert_eq!(empty_config.storage, PartialStorage::default());
         assert_eq!(config.storage.db_path, Some(PathBuf::from("dbfiles")));
+        assert_eq!(
+            config.storage.compression,
+            Some(crate::config::StorageCompression::Zstd)
+        );
     }
 
     #[test]
@@ -3,7 +3,7 @@
 use digest::Digest;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
-use std::fmt::Formatter;
+use std::{fmt::Formatter, io};
 
 /// Enumeration of hash-function names
 #[derive(Clone, PartialEq, Eq, Debug)]
@@ -43,3 +43,42 @@ pub fn calculate_sha256(bytes: &[u8]) -> Sha256 {
     hash.copy_from_slice(&hasher.result());
     Sha256(hash)
 }
+
+/// Incremental SHA256 hasher, used to hash large inputs chunk by chunk without having to buffer
+/// them.
+///
+/// Feeding the same bytes to `update`, in any number of chunks, results in the same hash as
+/// `calculate_sha256`.
+#[derive(Clone, Default)]
+pub struct Sha256Hasher(sha2::Sha256);
+
+impl Sha256Hasher {
+    /// Create a new hasher
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append some bytes to the data being hashed
+    pub fn update(&mut self, bytes: &[u8]) {
+        self.0.input(bytes);
+    }
+
+    /// Consume the hasher and return the SHA256 hash of all the bytes passed to `update`
+    pub fn finalize(self) -> Sha256 {
+        let mut hash = [0; 32];
+        hash.copy_from_slice(&self.0.result());
+        Sha256(hash)
+    }
+}
+
+/// Allows hashing readers with `std::io::copy`
+impl io::Write for Sha256Hasher {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
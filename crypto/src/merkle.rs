@@ -274,6 +274,43 @@ impl<T: Copy + PartialEq> FullMerkleTree<T> {
     }
 }
 
+/// Verify that `leaf` is included in the SHA256 merkle tree with the given `root`, given the
+/// merkle path of the leaf and its proof index, as returned by `FullMerkleTree::inclusion_proof`.
+///
+/// Note that `proof_index` is not the index of the leaf: both only match when the tree is
+/// balanced. This allows verifying a proof without knowing the number of leaves in the tree.
+/// Use `proof_index` to derive it from the index of the leaf and the number of leaves.
+pub fn verify_inclusion_proof(
+    root: Sha256,
+    leaf: Sha256,
+    proof: &[Sha256],
+    proof_index: usize,
+) -> bool {
+    InclusionProof::sha256(proof_index, proof.to_vec()).verify(leaf, root)
+}
+
+/// Get the proof index of the leaf at `leaf_index` in a merkle tree with `leaves` leaves, which is
+/// the index expected by `verify_inclusion_proof`.
+pub fn proof_index(leaf_index: usize, leaves: usize) -> usize {
+    let mut i = leaf_index;
+    let mut layer_len = leaves;
+    let mut proof_len = 0;
+    let mut proof_index = 0;
+
+    // Same walk as `FullMerkleTree::inclusion_proof`, but only keeping track of the length of
+    // each layer
+    while layer_len > 1 {
+        if i ^ 1 < layer_len {
+            proof_index |= (i & 1) << proof_len;
+            proof_len += 1;
+        }
+        i >>= 1;
+        layer_len = (layer_len + 1) / 2;
+    }
+
+    proof_index
+}
+
 /// Inclusion proof of an element in a merkle tree
 #[derive(Debug)]
 pub struct InclusionProof<T> {
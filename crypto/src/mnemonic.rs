@@ -15,12 +15,86 @@
 //! let seed = mnemonic.seed(&passphrase);
 //! ```
 
-use failure::Error;
+use failure::Fail;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use witnet_protected::ProtectedString;
 
+/// Error type for mnemonic phrases that cannot be parsed
+#[derive(Debug, Fail)]
+pub enum MnemonicError {
+    /// The phrase does not have 12, 15, 18, 21 or 24 words
+    #[fail(
+        display = "Invalid number of mnemonic words: {}, must be 12, 15, 18, 21 or 24",
+        count
+    )]
+    InvalidWordCount {
+        /// Number of words in the phrase
+        count: usize,
+    },
+    /// The phrase contains a word that is not in the word list
+    #[fail(display = "Unknown mnemonic word: {}", word)]
+    UnknownWord {
+        /// The first unknown word of the phrase
+        word: String,
+    },
+    /// All the words are valid but the checksum does not match, usually because some words
+    /// were mistyped or swapped
+    #[fail(display = "Invalid mnemonic checksum")]
+    InvalidChecksum,
+    /// Any other error returned by the BIP39 implementation
+    #[fail(display = "Invalid mnemonic: {}", _0)]
+    Other(failure::Error),
+}
+
+/// Check that a phrase in English is a valid BIP39 mnemonic, so that it can be safely used to
+/// generate a seed.
+pub fn validate(phrase: &str) -> Result<(), MnemonicError> {
+    validate_lang(phrase, Lang::English)
+}
+
+/// Check that a phrase in another language is a valid BIP39 mnemonic.
+pub fn validate_lang(phrase: &str, language: Lang) -> Result<(), MnemonicError> {
+    parse_phrase(phrase, language.into()).map(|_| ())
+}
+
+fn parse_phrase(phrase: &str, language: bip39::Language) -> Result<bip39::Mnemonic, MnemonicError> {
+    let count = phrase.split_whitespace().count();
+    if ![12, 15, 18, 21, 24].contains(&count) {
+        return Err(MnemonicError::InvalidWordCount { count });
+    }
+
+    bip39::Mnemonic::from_phrase(phrase, language).map_err(|err| {
+        match err.downcast::<bip39::ErrorKind>() {
+            Ok(bip39::ErrorKind::InvalidChecksum) => MnemonicError::InvalidChecksum,
+            Ok(bip39::ErrorKind::InvalidWord) => phrase
+                .split_whitespace()
+                .find(|word| !is_known_word(word, language))
+                .map(|word| MnemonicError::UnknownWord {
+                    word: word.to_string(),
+                })
+                .unwrap_or_else(|| MnemonicError::Other(bip39::ErrorKind::InvalidWord.into())),
+            Ok(kind) => MnemonicError::Other(kind.into()),
+            Err(err) => MnemonicError::Other(err),
+        }
+    })
+}
+
+// The word list of tiny-bip39 is not public, so instead of looking the word up we parse a phrase
+// made only of that word: unknown words fail before the checksum is checked.
+fn is_known_word(word: &str, language: bip39::Language) -> bool {
+    let phrase = vec![word; 12].join(" ");
+
+    match bip39::Mnemonic::validate(&phrase, language) {
+        Err(err) => !matches!(
+            err.downcast_ref::<bip39::ErrorKind>(),
+            Some(bip39::ErrorKind::InvalidWord)
+        ),
+        Ok(()) => true,
+    }
+}
+
 /// BIP39 Mnemonic
 pub struct Mnemonic(bip39::Mnemonic);
 
@@ -46,23 +120,26 @@ impl Mnemonic {
     }
 
     /// Get a mnemonic from a existing phrase in English.
-    pub fn from_phrase(phrase: ProtectedString) -> Result<Mnemonic, Error> {
+    pub fn from_phrase(phrase: ProtectedString) -> Result<Mnemonic, MnemonicError> {
         Self::from_phrase_lang(phrase, Lang::English)
     }
 
     /// Get a mnemonic from a existing phrase in English.
-    pub fn from_phrase_ref(phrase: &str) -> Result<Mnemonic, Error> {
+    pub fn from_phrase_ref(phrase: &str) -> Result<Mnemonic, MnemonicError> {
         Self::from_phrase_lang_ref(phrase, Lang::English)
     }
 
     /// Get a mnemonic from a existing phrase in another language.
-    pub fn from_phrase_lang(phrase: ProtectedString, language: Lang) -> Result<Mnemonic, Error> {
-        bip39::Mnemonic::from_phrase(AsRef::<str>::as_ref(&phrase), language.into()).map(Mnemonic)
+    pub fn from_phrase_lang(
+        phrase: ProtectedString,
+        language: Lang,
+    ) -> Result<Mnemonic, MnemonicError> {
+        parse_phrase(AsRef::<str>::as_ref(&phrase), language.into()).map(Mnemonic)
     }
 
     /// Get a mnemonic from a existing phrase in another language.
-    pub fn from_phrase_lang_ref(phrase: &str, language: Lang) -> Result<Mnemonic, Error> {
-        bip39::Mnemonic::from_phrase(phrase, language.into()).map(Mnemonic)
+    pub fn from_phrase_lang_ref(phrase: &str, language: Lang) -> Result<Mnemonic, MnemonicError> {
+        parse_phrase(phrase, language.into()).map(Mnemonic)
     }
 }
 
@@ -218,4 +295,53 @@ mod tests {
             assert_eq!((*expected_seed).to_string(), seed);
         }
     }
+
+    #[test]
+    fn test_validate() {
+        let (phrase, _) = crate::test_vectors::TREZOR_MNEMONICS[0];
+
+        assert!(validate(phrase).is_ok());
+        assert!(Mnemonic::from_phrase_ref(phrase).is_ok());
+    }
+
+    #[test]
+    fn test_validate_swapped_words() {
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        assert!(validate(phrase).is_ok());
+
+        // Swapping two words keeps all the words valid but breaks the checksum
+        let swapped = "about abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon";
+        assert!(matches!(
+            validate(swapped),
+            Err(MnemonicError::InvalidChecksum)
+        ));
+        assert!(matches!(
+            Mnemonic::from_phrase(swapped.into()),
+            Err(MnemonicError::InvalidChecksum)
+        ));
+    }
+
+    #[test]
+    fn test_validate_word_count() {
+        let phrase = "legal winner thank year wave sausage worth useful legal winner thank";
+
+        assert!(matches!(
+            validate(phrase),
+            Err(MnemonicError::InvalidWordCount { count: 11 })
+        ));
+        assert!(matches!(
+            validate(""),
+            Err(MnemonicError::InvalidWordCount { count: 0 })
+        ));
+    }
+
+    #[test]
+    fn test_validate_unknown_word() {
+        let phrase = "legal winner thank year wave sausage worth useful legal winnr thank yellow";
+
+        match validate(phrase) {
+            Err(MnemonicError::UnknownWord { word }) => assert_eq!(word, "winnr"),
+            other => panic!("Expected an unknown word error, got {:?}", other),
+        }
+    }
 }
@@ -16,6 +16,21 @@ pub fn sign(secret_key: SecretKey, data: &[u8]) -> Result<Signature, Error> {
 
     Ok(secret_key.sign_ecdsa(msg))
 }
+
+/// Sign `data` with provided secret key, deriving the nonce from the key and the data as described
+/// in RFC 6979, so that signing the same data with the same key always results in the same
+/// signature. Use this when the signatures must match the ones produced by other implementations,
+/// e.g. in test vectors.
+///
+/// `sign` currently uses the same nonce function, but only this function guarantees it.
+/// - Returns an Error if data is not a 32-byte array
+pub fn sign_deterministic(secret_key: SecretKey, data: &[u8]) -> Result<Signature, Error> {
+    let msg = Message::from_digest_slice(data)?;
+
+    // libsecp256k1 uses RFC 6979 unless extra entropy is supplied with `sign_ecdsa_with_noncedata`
+    Ok(secp256k1::SECP256K1.sign_ecdsa(&msg, &secret_key))
+}
+
 /// Verify signature with a provided public key.
 /// - Returns an Error if data is not a 32-byte array
 pub fn verify(public_key: &PublicKey, data: &[u8], sig: &Signature) -> Result<(), Error> {
@@ -28,7 +43,7 @@ pub fn verify(public_key: &PublicKey, data: &[u8], sig: &Signature) -> Result<()
 mod tests {
     use crate::{
         hash::{calculate_sha256, Sha256},
-        signature::{sign, verify},
+        signature::{sign, sign_deterministic, verify},
     };
     use secp256k1::{ecdsa::Signature, PublicKey, SecretKey};
 
@@ -49,6 +64,27 @@ mod tests {
         assert!(verify(&public_key, &data, &signature).is_ok());
     }
 
+    #[test]
+    fn test_sign_deterministic() {
+        let data = [0xab; 32];
+        let secret_key = SecretKey::from_slice(&[0xcd; 32]).expect("32 bytes, within curve order");
+        let public_key = PublicKey::from_secret_key_global(&secret_key);
+
+        let signature1 = sign_deterministic(secret_key, &data).unwrap();
+        let signature2 = sign_deterministic(secret_key, &data).unwrap();
+        assert_eq!(signature1, signature2);
+        assert!(verify(&public_key, &data, &signature1).is_ok());
+
+        // Same signature as in test_sign_and_verify
+        assert_eq!(signature1, sign(secret_key, &data).unwrap());
+
+        // A different message results in a different signature
+        let signature3 = sign_deterministic(secret_key, &[0xac; 32]).unwrap();
+        assert_ne!(signature1, signature3);
+
+        assert!(sign_deterministic(secret_key, &data[..31]).is_err());
+    }
+
     #[test]
     fn test_der_and_compact() {
         let der1 = "3044\
@@ -1,4 +1,5 @@
-use witnet_crypto::hash::{calculate_sha256, Sha256, EMPTY_SHA256};
+use rand::{Rng, RngCore};
+use witnet_crypto::hash::{calculate_sha256, Sha256, Sha256Hasher, EMPTY_SHA256};
 
 #[test]
 fn sha256() {
@@ -15,3 +16,27 @@ fn sha256() {
 fn empty_sha256() {
     assert_eq!(EMPTY_SHA256, calculate_sha256(b""));
 }
+
+#[test]
+fn incremental_sha256() {
+    let mut rng = rand::thread_rng();
+    let mut data = vec![0; 1024 * 1024];
+    rng.fill_bytes(&mut data);
+
+    let mut hasher = Sha256Hasher::new();
+    let mut rest = data.as_slice();
+    while !rest.is_empty() {
+        let (chunk, tail) = rest.split_at(rng.gen_range(0, rest.len().min(64 * 1024) + 1));
+        hasher.update(chunk);
+        rest = tail;
+    }
+    assert_eq!(hasher.finalize(), calculate_sha256(&data));
+
+    // Hashing a reader
+    let mut hasher = Sha256Hasher::new();
+    std::io::copy(&mut data.as_slice(), &mut hasher).unwrap();
+    assert_eq!(hasher.finalize(), calculate_sha256(&data));
+
+    // Nothing hashed
+    assert_eq!(Sha256Hasher::new().finalize(), EMPTY_SHA256);
+}
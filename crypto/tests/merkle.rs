@@ -1,6 +1,7 @@
 use witnet_crypto::hash::{calculate_sha256, Sha256};
 use witnet_crypto::merkle::{
-    merkle_tree_root, FullMerkleTree, InclusionProof, ProgressiveMerkleTree,
+    merkle_tree_root, proof_index, verify_inclusion_proof, FullMerkleTree, InclusionProof,
+    ProgressiveMerkleTree,
 };
 
 #[test]
@@ -245,3 +246,119 @@ fn manual_inclusion_proof() {
     let proof = InclusionProof::sha256(proof_index, lemma);
     assert!(proof.verify(Sha256([9; 32]), mt_root));
 }
+
+// Check that every leaf of a tree with `n` leaves can be proven using only the root, the lemma and
+// the proof index, and that the proof of one leaf does not prove any other leaf
+fn check_verify_inclusion_proof(n: u8) {
+    let leaves: Vec<Sha256> = (0..n).map(|i| calculate_sha256(&[i])).collect();
+    let mt = FullMerkleTree::sha256(leaves.clone());
+    let root = mt.root();
+
+    for (idx, leaf) in leaves.iter().enumerate() {
+        let proof = mt.inclusion_proof(idx).unwrap();
+        assert!(verify_inclusion_proof(
+            root,
+            *leaf,
+            proof.lemma(),
+            proof.proof_index()
+        ));
+
+        for (other_idx, other_leaf) in leaves.iter().enumerate() {
+            if other_idx != idx {
+                assert!(!verify_inclusion_proof(
+                    root,
+                    *other_leaf,
+                    proof.lemma(),
+                    proof.proof_index()
+                ));
+            }
+        }
+    }
+    assert!(mt.inclusion_proof(leaves.len()).is_none());
+}
+
+#[test]
+fn verify_inclusion_proof_single_leaf() {
+    check_verify_inclusion_proof(1);
+
+    // The root of a one-leaf tree is the leaf itself, so the proof is empty
+    let leaf = calculate_sha256(b"a");
+    let proof = FullMerkleTree::sha256(vec![leaf])
+        .inclusion_proof(0)
+        .unwrap();
+    assert!(proof.lemma().is_empty());
+    assert!(verify_inclusion_proof(leaf, leaf, &[], 0));
+    assert!(!verify_inclusion_proof(
+        leaf,
+        calculate_sha256(b"b"),
+        &[],
+        0
+    ));
+}
+
+#[test]
+fn verify_inclusion_proof_power_of_two() {
+    check_verify_inclusion_proof(2);
+    check_verify_inclusion_proof(8);
+
+    // In a balanced tree the proof index is the index of the leaf
+    let leaves: Vec<Sha256> = (0..8).map(|i| Sha256([i; 32])).collect();
+    let mt = FullMerkleTree::sha256(leaves);
+    for idx in 0..8 {
+        let proof = mt.inclusion_proof(idx).unwrap();
+        assert_eq!(proof.proof_index(), idx);
+        assert_eq!(proof.lemma().len(), 3);
+    }
+}
+
+#[test]
+fn verify_inclusion_proof_non_power_of_two() {
+    check_verify_inclusion_proof(3);
+    check_verify_inclusion_proof(7);
+    check_verify_inclusion_proof(10);
+
+    // The last leaf of a 5-leaf tree is promoted twice, so its proof only contains the root of
+    // the first 4 leaves, which must be concatenated from the left
+    let leaves: Vec<Sha256> = (0..5).map(|i| Sha256([i; 32])).collect();
+    let mt = FullMerkleTree::sha256(leaves.clone());
+    let proof = mt.inclusion_proof(4).unwrap();
+    assert_eq!(proof.lemma(), &[mt.nodes()[2][0]]);
+    assert_eq!(proof.proof_index(), 1);
+    assert!(verify_inclusion_proof(
+        mt.root(),
+        leaves[4],
+        proof.lemma(),
+        1
+    ));
+    // Using the index of the leaf instead of the proof index does not work
+    assert!(!verify_inclusion_proof(
+        mt.root(),
+        leaves[4],
+        proof.lemma(),
+        4
+    ));
+}
+
+#[test]
+fn verify_inclusion_proof_with_leaf_index() {
+    for n in 1..=10u8 {
+        let leaves: Vec<Sha256> = (0..n).map(|i| calculate_sha256(&[i])).collect();
+        let mt = FullMerkleTree::sha256(leaves.clone());
+
+        for (leaf_index, leaf) in leaves.iter().enumerate() {
+            let proof = mt.inclusion_proof(leaf_index).unwrap();
+            let index = proof_index(leaf_index, leaves.len());
+            assert_eq!(index, proof.proof_index());
+            assert!(verify_inclusion_proof(
+                mt.root(),
+                *leaf,
+                proof.lemma(),
+                index
+            ));
+        }
+    }
+
+    // The last leaf of a 5-leaf tree has leaf index 4 but proof index 1
+    assert_eq!(proof_index(4, 5), 1);
+    assert_eq!(proof_index(6, 7), 3);
+}
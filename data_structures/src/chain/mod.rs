@@ -2074,13 +2074,16 @@ pub enum RADType {
     /// HTTP HEAD request
     #[serde(rename = "HTTP-HEAD")]
     HttpHead,
+    /// GraphQL query, sent as an HTTP POST request
+    #[serde(rename = "GRAPHQL")]
+    GraphQl,
 }
 
 impl RADType {
     pub fn is_http(&self) -> bool {
         matches!(
             self,
-            RADType::HttpGet | RADType::HttpPost | RADType::HttpHead
+            RADType::HttpGet | RADType::HttpPost | RADType::HttpHead | RADType::GraphQl
         )
     }
 }
@@ -2248,6 +2251,11 @@ impl RADRetrieve {
             RADType::HttpHead => {
                 check(&[Field::Kind, Field::Url, Field::Script], &[Field::Headers])
             }
+            // The body holds the query, which is mandatory
+            RADType::GraphQl => check(
+                &[Field::Kind, Field::Url, Field::Script, Field::Body],
+                &[Field::Headers],
+            ),
         }
     }
 
@@ -2274,6 +2282,14 @@ impl RADRetrieve {
         }
     }
 
+    /// Hash identifying this retrieval, meant to be used as a key when caching its responses.
+    ///
+    /// This covers every field, and is computed over the Protocol Buffers encoding, so it does not
+    /// change across nodes or versions. Note that the order of the headers matters.
+    pub fn fingerprint(&self) -> Hash {
+        calculate_sha256(&self.to_pb_bytes().unwrap()).into()
+    }
+
     /// Return the weight, used to enforce the block size limit.
     pub fn weight(&self) -> u32 {
         let kind_weight = 1;
@@ -7718,4 +7734,27 @@ mod tests {
         state.update_stage(extra_rounds, false);
         assert_eq!(state.stage, DataRequestStage::TALLY);
     }
+
+    #[test]
+    fn rad_retrieve_fingerprint() {
+        let retrieve = RADRetrieve {
+            kind: RADType::HttpPost,
+            url: "https://example.com/price".to_string(),
+            script: vec![0x80],
+            body: b"{}".to_vec(),
+            headers: vec![("Accept".to_string(), "application/json".to_string())],
+        };
+
+        assert_eq!(retrieve.fingerprint(), retrieve.clone().fingerprint());
+        let decoded = RADRetrieve::from_pb_bytes(&retrieve.to_pb_bytes().unwrap()).unwrap();
+        assert_eq!(decoded.fingerprint(), retrieve.fingerprint());
+
+        let mut other_header = retrieve.clone();
+        other_header.headers[0].1 = "text/plain".to_string();
+        assert_ne!(other_header.fingerprint(), retrieve.fingerprint());
+
+        let mut other_kind = retrieve.clone();
+        other_kind.kind = RADType::HttpGet;
+        assert_ne!(other_kind.fingerprint(), retrieve.fingerprint());
+    }
 }
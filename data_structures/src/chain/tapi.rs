@@ -91,6 +91,7 @@ pub fn wip_info() -> HashMap<String, Epoch> {
     active_wips.insert("WIP0027".to_string(), 1708901);
     // TODO: Add epoch when WIP0028 was activated
     // active_wips.insert("WIP0028".to_string(), 2949141);
+    // TODO: Add epoch when WIP0029 gets activated
 
     active_wips
 }
@@ -111,6 +112,7 @@ fn test_wip_info() -> HashMap<String, Epoch> {
     active_wips.insert("WIP0026".to_string(), 0);
     active_wips.insert("WIP0027".to_string(), 0);
     // active_wips.insert("WIP0028".to_string(), 0);
+    // active_wips.insert("WIP0029".to_string(), 0);
 
     active_wips
 }
@@ -129,6 +131,7 @@ pub fn current_active_wips() -> ActiveWips {
 pub fn all_wips_active() -> ActiveWips {
     let mut active_wips = current_active_wips();
     active_wips.active_wips.insert("WIP0028".to_string(), 0);
+    active_wips.active_wips.insert("WIP0029".to_string(), 0);
 
     active_wips
 }
@@ -581,6 +584,11 @@ impl ActiveWips {
         self.wip_active("WIP0028")
     }
 
+    // WIP 0029 extends RADON with new operators, filters and reducers
+    pub fn wip0029(&self) -> bool {
+        self.wip_active("WIP0029")
+    }
+
     /// Convenience method for inserting WIPs.
     pub fn insert_wip(&mut self, wip: &str, activation_epoch: Epoch) {
         self.active_wips.insert(String::from(wip), activation_epoch);
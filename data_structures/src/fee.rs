@@ -3,7 +3,10 @@ use std::{fmt, ops, str};
 pub use num_traits::Zero;
 use serde::{Deserialize, Serialize};
 
-use crate::{chain::priority::Priority, wit::Wit};
+use crate::{
+    chain::priority::{PrioritiesEstimate, Priority},
+    wit::Wit,
+};
 
 #[derive(Copy, Clone, Debug, Deserialize, Hash, PartialEq, Eq, PartialOrd, Serialize)]
 pub struct AbsoluteFee(Wit);
@@ -159,6 +162,106 @@ impl From<RelativeFee> for Fee {
     }
 }
 
+/// Priority tiers to choose from when estimating the fee of a transaction, from the slowest and
+/// cheapest to the fastest and most expensive. These are the same tiers as in `PrioritiesEstimate`.
+#[derive(Copy, Clone, Debug, Deserialize, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FeePriority {
+    Stinky,
+    Low,
+    Medium,
+    High,
+    Opulent,
+}
+
+impl FeePriority {
+    /// All the tiers, in increasing order.
+    pub const ALL: [FeePriority; 5] = [
+        FeePriority::Stinky,
+        FeePriority::Low,
+        FeePriority::Medium,
+        FeePriority::High,
+        FeePriority::Opulent,
+    ];
+}
+
+/// Recent fee statistics for one kind of transaction, that is, the priority that each tier needs
+/// for a transaction to be included in a block in time.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct FeeStats {
+    pub stinky: Priority,
+    pub low: Priority,
+    pub medium: Priority,
+    pub high: Priority,
+    pub opulent: Priority,
+}
+
+impl FeeStats {
+    /// Statistics for value transfer transactions, out of the estimate returned by
+    /// `PriorityEngine::estimate_priority`.
+    pub fn vtt(estimate: &PrioritiesEstimate) -> Self {
+        Self {
+            stinky: estimate.vtt_stinky.priority,
+            low: estimate.vtt_low.priority,
+            medium: estimate.vtt_medium.priority,
+            high: estimate.vtt_high.priority,
+            opulent: estimate.vtt_opulent.priority,
+        }
+    }
+
+    /// Statistics for data request transactions, out of the estimate returned by
+    /// `PriorityEngine::estimate_priority`.
+    pub fn drt(estimate: &PrioritiesEstimate) -> Self {
+        Self {
+            stinky: estimate.drt_stinky.priority,
+            low: estimate.drt_low.priority,
+            medium: estimate.drt_medium.priority,
+            high: estimate.drt_high.priority,
+            opulent: estimate.drt_opulent.priority,
+        }
+    }
+
+    /// Priority of a tier.
+    pub fn tier(&self, priority: FeePriority) -> Priority {
+        match priority {
+            FeePriority::Stinky => self.stinky,
+            FeePriority::Low => self.low,
+            FeePriority::Medium => self.medium,
+            FeePriority::High => self.high,
+            FeePriority::Opulent => self.opulent,
+        }
+    }
+}
+
+/// The default priorities, to be used when there are no statistics available yet.
+impl Default for FeeStats {
+    fn default() -> Self {
+        Self {
+            stinky: Priority::default_stinky(),
+            low: Priority::default_low(),
+            medium: Priority::default_medium(),
+            high: Priority::default_high(),
+            opulent: Priority::default_opulent(),
+        }
+    }
+}
+
+/// Suggest an absolute fee, in nanowits, for a transaction of the given weight and priority.
+///
+/// This does no network calls: the recent fee statistics must be provided by the caller, e.g. out
+/// of the response of the `priority` JSON-RPC method.
+pub fn estimate_fee(weight: u32, priority: FeePriority, stats: &FeeStats) -> u64 {
+    // Noisy statistics must never make a higher tier suggest a lower fee than the tiers below it
+    FeePriority::ALL
+        .into_iter()
+        .filter(|tier| *tier <= priority)
+        .map(|tier| stats.tier(tier))
+        .max()
+        .unwrap_or_else(Priority::zero)
+        .derive_fee_wit(weight)
+        .nanowits()
+}
+
 /// Allow backwards compatibility with old Wallet API clients that may provide fee values without
 /// tagging whether they are absolute or relative.
 ///
@@ -252,4 +355,71 @@ mod tests {
         let fee = deserialize_fee_backwards_compatible(&mut deserializer).unwrap();
         assert_eq!(fee, Fee::relative_from_float(123.456));
     }
+
+    #[test]
+    fn test_estimate_fee() {
+        let stats = FeeStats {
+            stinky: Priority::from(0.5),
+            low: Priority::from(1.0),
+            medium: Priority::from(2.0),
+            high: Priority::from(4.5),
+            opulent: Priority::from(10.0),
+        };
+
+        assert_eq!(estimate_fee(1_000, FeePriority::Stinky, &stats), 500);
+        assert_eq!(estimate_fee(1_000, FeePriority::Low, &stats), 1_000);
+        assert_eq!(estimate_fee(1_000, FeePriority::Medium, &stats), 2_000);
+        assert_eq!(estimate_fee(1_000, FeePriority::High, &stats), 4_500);
+        assert_eq!(estimate_fee(1_000, FeePriority::Opulent, &stats), 10_000);
+        assert_eq!(estimate_fee(0, FeePriority::Opulent, &stats), 0);
+    }
+
+    #[test]
+    fn test_estimate_fee_increases_with_priority() {
+        for stats in [
+            FeeStats::default(),
+            // Noisy statistics in which a higher tier has a lower priority
+            FeeStats {
+                stinky: Priority::from(1.0),
+                low: Priority::from(3.0),
+                medium: Priority::from(2.0),
+                high: Priority::from(5.0),
+                opulent: Priority::from(5.0),
+            },
+        ] {
+            let low = estimate_fee(10_000, FeePriority::Low, &stats);
+            let medium = estimate_fee(10_000, FeePriority::Medium, &stats);
+            let high = estimate_fee(10_000, FeePriority::High, &stats);
+
+            assert!(low <= medium, "{:?}", stats);
+            assert!(medium <= high, "{:?}", stats);
+            assert!(low < high, "{:?}", stats);
+        }
+
+        let default = FeeStats::default();
+        assert!(
+            estimate_fee(10_000, FeePriority::Low, &default)
+                < estimate_fee(10_000, FeePriority::Medium, &default)
+        );
+        assert!(
+            estimate_fee(10_000, FeePriority::Medium, &default)
+                < estimate_fee(10_000, FeePriority::High, &default)
+        );
+    }
+
+    #[test]
+    fn test_fee_stats_from_estimate() {
+        let mut estimate = PrioritiesEstimate::default();
+        estimate.vtt_medium.priority = Priority::from(2.0);
+        estimate.drt_medium.priority = Priority::from(3.0);
+
+        assert_eq!(
+            FeeStats::vtt(&estimate).tier(FeePriority::Medium),
+            Priority::from(2.0)
+        );
+        assert_eq!(
+            FeeStats::drt(&estimate).tier(FeePriority::Medium),
+            Priority::from(3.0)
+        );
+    }
 }
@@ -53,6 +53,7 @@ impl ProtobufConvert for chain::RADType {
             chain::RADType::Rng => witnet::DataRequestOutput_RADRequest_RADType::Rng,
             chain::RADType::HttpPost => witnet::DataRequestOutput_RADRequest_RADType::HttpPost,
             chain::RADType::HttpHead => witnet::DataRequestOutput_RADRequest_RADType::HttpHead,
+            chain::RADType::GraphQl => witnet::DataRequestOutput_RADRequest_RADType::GraphQl,
         }
     }
 
@@ -63,6 +64,7 @@ impl ProtobufConvert for chain::RADType {
             witnet::DataRequestOutput_RADRequest_RADType::Rng => chain::RADType::Rng,
             witnet::DataRequestOutput_RADRequest_RADType::HttpPost => chain::RADType::HttpPost,
             witnet::DataRequestOutput_RADRequest_RADType::HttpHead => chain::RADType::HttpHead,
+            witnet::DataRequestOutput_RADRequest_RADType::GraphQl => chain::RADType::GraphQl,
         })
     }
 }
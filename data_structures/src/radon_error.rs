@@ -6,6 +6,8 @@ use num_enum::{IntoPrimitive, TryFromPrimitive};
 use serde::Serialize;
 use serde_cbor::Value as SerdeCborValue;
 
+use crate::chain::tapi::ActiveWips;
+
 #[derive(Clone, Copy, Debug, Eq, IntoPrimitive, PartialEq, Serialize, TryFromPrimitive)]
 #[repr(u8)]
 /// List of RADON-level errors.
@@ -26,6 +28,8 @@ pub enum RadonErrors {
     RequestTooManySources = 0x10,
     /// The script contains too many calls.
     ScriptTooManyCalls = 0x11,
+    /// The script nests too many subscripts or executes too many calls.
+    ScriptTooComplex = 0x12,
     // Operator errors
     /// The operator does not exist.
     UnsupportedOperator = 0x20,
@@ -76,6 +80,44 @@ pub enum RadonErrors {
     UnhandledIntercept = 0xFF,
 }
 
+impl RadonErrors {
+    /// Human-readable description of the error, meant for displaying results to users.
+    pub fn description(&self) -> &'static str {
+        match self {
+            RadonErrors::Unknown => "Unknown error",
+            RadonErrors::SourceScriptNotCBOR => "A source script is not valid CBOR",
+            RadonErrors::SourceScriptNotArray => "A source script is not a CBOR array",
+            RadonErrors::SourceScriptNotRADON => "A source script is not a valid RADON script",
+            RadonErrors::RequestTooManySources => "The request contains too many sources",
+            RadonErrors::ScriptTooManyCalls => "A script contains too many calls",
+            RadonErrors::ScriptTooComplex => "A script nests too many subscripts or runs too long",
+            RadonErrors::UnsupportedOperator => "A script uses an operator that does not exist",
+            RadonErrors::HTTPError => "A source could not be retrieved due to an HTTP error",
+            RadonErrors::RetrieveTimeout => "A source could not be retrieved in time",
+            RadonErrors::Underflow => "A math operation caused an underflow",
+            RadonErrors::Overflow => "A math operation caused an overflow",
+            RadonErrors::DivisionByZero => "A math operation tried to divide by zero",
+            RadonErrors::NoReveals => "No witness revealed a result",
+            RadonErrors::InsufficientConsensus => "The witnesses did not reach enough consensus",
+            RadonErrors::InsufficientCommits => "Not enough witnesses committed a result",
+            RadonErrors::TallyExecution => "The tally stage failed to execute",
+            RadonErrors::TooManyWitnesses => "There were not enough stakers to resolve the request",
+            RadonErrors::MalformedReveal => "A reveal could not be decoded",
+            RadonErrors::EncodeReveal => "A reveal could not be encoded",
+            RadonErrors::ArrayIndexOutOfBounds => "A script accessed an array out of its bounds",
+            RadonErrors::MapKeyNotFound => "A script accessed a map key that does not exist",
+            RadonErrors::BridgeMalformedRequest => "The bridge could not parse the request",
+            RadonErrors::BridgePoorIncentives => {
+                "The bridge rejected the request because its reward is too low"
+            }
+            RadonErrors::BridgeOversizedResult => {
+                "The result is larger than what the bridge can report"
+            }
+            RadonErrors::UnhandledIntercept => "An error was not intercepted as it should",
+        }
+    }
+}
+
 /// Use `RadonErrors::Unknown` as the default value of `RadonErrors`.
 impl Default for RadonErrors {
     fn default() -> Self {
@@ -116,12 +158,41 @@ where
 
     /// Encode `RadonError` as tagged CBOR value with tag 39.
     /// Returns the result as `CborValue`.
+    ///
+    /// Arrays and maps always have definite lengths, and map entries are sorted by key. NaN
+    /// arguments are encoded as they are, see `encode_tagged_value_with_active_wips`.
     pub fn encode_tagged_value(&self) -> Result<CborValue, failure::Error> {
+        self.encode_tagged_value_canonicalizing_nans(false)
+    }
+
+    /// Encode `RadonError` as tagged CBOR value with tag 39, following the encoding rules that
+    /// apply under the given set of active WIPs.
+    /// Returns the result as `CborValue`.
+    ///
+    /// Once WIP0029 is active, NaN arguments are replaced by a single canonical NaN, so that all
+    /// the nodes agree on the bytes of errors included in reveal and tally transactions.
+    pub fn encode_tagged_value_with_active_wips(
+        &self,
+        active_wips: &ActiveWips,
+    ) -> Result<CborValue, failure::Error> {
+        self.encode_tagged_value_canonicalizing_nans(active_wips.wip0029())
+    }
+
+    fn encode_tagged_value_canonicalizing_nans(
+        &self,
+        canonicalize_nans: bool,
+    ) -> Result<CborValue, failure::Error> {
         let values: Vec<CborValue> = self
             .inner
             .encode_cbor_array()?
             .into_iter()
             .map(|scv| {
+                let scv = if canonicalize_nans {
+                    canonicalize_serde_cbor_value(scv)
+                } else {
+                    scv
+                };
+
                 // FIXME(#953): remove this conversion
                 try_from_serde_cbor_value_for_cbor_value(scv)
             })
@@ -136,10 +207,17 @@ where
     /// Encode `RadonErorr` as tagged CBOR value with tag 39.
     /// Returns the result as bytes.
     pub fn encode_tagged_bytes(&self) -> Result<Vec<u8>, failure::Error> {
-        let mut encoder = GenericEncoder::new(Cursor::new(Vec::new()));
-        encoder.value(&self.encode_tagged_value()?)?;
+        encode_cbor_value(&self.encode_tagged_value()?)
+    }
 
-        Ok(encoder.into_inner().into_writer().into_inner())
+    /// Encode `RadonErorr` as tagged CBOR value with tag 39, following the encoding rules that
+    /// apply under the given set of active WIPs.
+    /// Returns the result as bytes.
+    pub fn encode_tagged_bytes_with_active_wips(
+        &self,
+        active_wips: &ActiveWips,
+    ) -> Result<Vec<u8>, failure::Error> {
+        encode_cbor_value(&self.encode_tagged_value_with_active_wips(active_wips)?)
     }
 
     /// Get a reference to the inner error type
@@ -162,6 +240,50 @@ where
     }
 }
 
+fn encode_cbor_value(value: &CborValue) -> Result<Vec<u8>, failure::Error> {
+    let mut encoder = GenericEncoder::new(Cursor::new(Vec::new()));
+    encoder.value(value)?;
+
+    Ok(encoder.into_inner().into_writer().into_inner())
+}
+
+/// Bit pattern of the NaN that replaces any other NaN found in the arguments of a `RadonError`.
+/// This is the positive quiet NaN with no payload.
+const CANONICAL_NAN_BITS: u64 = 0x7ff8_0000_0000_0000;
+
+/// Replace every NaN found in a `SerdeCborValue` by the canonical NaN. This is only part of the
+/// encoding of `RadonError` once WIP0029 is active.
+///
+/// The sign and payload of a NaN depend on the platform that produced it, and would otherwise leak
+/// into the encoding.
+pub fn canonicalize_serde_cbor_value(serde_cbor_value: SerdeCborValue) -> SerdeCborValue {
+    match serde_cbor_value {
+        SerdeCborValue::Float(float) if float.is_nan() => {
+            SerdeCborValue::Float(f64::from_bits(CANONICAL_NAN_BITS))
+        }
+        SerdeCborValue::Array(values) => SerdeCborValue::Array(
+            values
+                .into_iter()
+                .map(canonicalize_serde_cbor_value)
+                .collect(),
+        ),
+        SerdeCborValue::Map(map) => SerdeCborValue::Map(
+            map.into_iter()
+                .map(|(key, value)| {
+                    (
+                        canonicalize_serde_cbor_value(key),
+                        canonicalize_serde_cbor_value(value),
+                    )
+                })
+                .collect(),
+        ),
+        SerdeCborValue::Tag(tag, value) => {
+            SerdeCborValue::Tag(tag, Box::new(canonicalize_serde_cbor_value(*value)))
+        }
+        value => value,
+    }
+}
+
 /// Convert SerdeCborValue into CborValue
 pub fn try_from_serde_cbor_value_for_cbor_value(serde_cbor_value: SerdeCborValue) -> CborValue {
     // FIXME(#953): impl TryFrom<SerdeCborValue> for <CborValue>
@@ -181,3 +303,24 @@ pub fn try_from_cbor_value_for_serde_cbor_value(cbor_value: CborValue) -> SerdeC
 
     serde_cbor::from_slice(&buffer).unwrap()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_radon_errors_descriptions() {
+        let variants = (0..=u8::MAX)
+            .filter_map(|code| RadonErrors::try_from(code).ok())
+            .collect::<Vec<_>>();
+        assert!(variants.contains(&RadonErrors::UnhandledIntercept));
+
+        for variant in variants {
+            assert!(
+                !variant.description().is_empty(),
+                "{:?} has no description",
+                variant
+            );
+        }
+    }
+}
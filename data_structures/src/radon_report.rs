@@ -1,9 +1,14 @@
+use std::collections::HashMap;
 use std::convert::TryFrom;
-use std::time::{Duration, SystemTime};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use serde_cbor::Value as SerdeCborValue;
 
-use crate::{chain::tapi::ActiveWips, radon_error::ErrorLike};
+use crate::{
+    chain::{tapi::ActiveWips, Epoch},
+    radon_error::ErrorLike,
+};
 
 /// A high level data structure aimed to be used as the return type of RAD executor methods:
 ///
@@ -69,6 +74,360 @@ where
     pub fn into_inner(self) -> RT {
         self.result
     }
+
+    /// Proportion of reveals (or sources, for aggregation stage reports) that were found to be in
+    /// consensus, if this is a tally or aggregation stage report.
+    ///
+    /// This is `None` for any other stage.
+    pub fn consensus(&self) -> Option<f32> {
+        match &self.context.stage {
+            Stage::Aggregation(metadata) => Some(metadata.consensus),
+            Stage::Tally(metadata) => Some(metadata.consensus),
+            _ => None,
+        }
+    }
+
+    /// Positional vector of the values that were filtered out, if this is a tally or aggregation
+    /// stage report.
+    ///
+    /// This is `None` for any other stage.
+    pub fn liars(&self) -> Option<&[bool]> {
+        match &self.context.stage {
+            Stage::Aggregation(metadata) => Some(&metadata.liars),
+            Stage::Tally(metadata) => Some(&metadata.liars),
+            _ => None,
+        }
+    }
+
+    /// URL that the HTTP request of a retrieval stage report ultimately landed on, after following
+    /// any redirects.
+    ///
+    /// This is `None` for any other stage, and for retrievals that did not send an HTTP request.
+    pub fn final_url(&self) -> Option<&str> {
+        match &self.context.stage {
+            Stage::Retrieval(metadata) => metadata.final_url.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Serialize the full report into a compact CBOR representation that is suitable for storage.
+    ///
+    /// Structure members are identified by one-letter keys, and every `RT` value is embedded as a
+    /// byte string containing its own CBOR encoding, as produced by `TypeLike::encode`.
+    pub fn to_compact_bytes(&self) -> Result<Vec<u8>, failure::Error> {
+        let compact = CompactRadonReport::try_from(self)?;
+
+        Ok(serde_cbor::to_vec(&compact)?)
+    }
+
+    /// Recover a `RadonReport` from the output of `to_compact_bytes`.
+    pub fn from_compact_bytes(bytes: &[u8]) -> Result<Self, failure::Error>
+    where
+        for<'a> RT: TryFrom<&'a [u8], Error = <RT as TypeLike>::Error>,
+    {
+        let compact: CompactRadonReport = serde_cbor::from_slice(bytes)?;
+
+        compact.into_report()
+    }
+
+    /// Compare this report with another one, telling where their executions diverged.
+    ///
+    /// If both reports kept their partial results, the first pair of partial results that differ
+    /// points at the call that made them diverge. Otherwise, only the final results are compared.
+    pub fn diff(&self, other: &Self) -> ReportDiff<RT>
+    where
+        RT: PartialEq,
+    {
+        if let (Some(left), Some(right)) = (&self.partial_results, &other.partial_results) {
+            // The first partial result is the input of the script, and the following ones are the
+            // outputs of each of its calls
+            if let Some((index, (left, right))) = left
+                .iter()
+                .zip(right)
+                .enumerate()
+                .find(|(_, (left, right))| left != right)
+            {
+                return match index.checked_sub(1) {
+                    None => ReportDiff::Input {
+                        left: left.clone(),
+                        right: right.clone(),
+                    },
+                    Some(call_index) => ReportDiff::Call {
+                        call_index,
+                        left: left.clone(),
+                        right: right.clone(),
+                    },
+                };
+            }
+
+            if left.len() != right.len() {
+                return ReportDiff::Length {
+                    left: left.len(),
+                    right: right.len(),
+                };
+            }
+        }
+
+        if self.result != other.result {
+            ReportDiff::Result {
+                left: self.result.clone(),
+                right: other.result.clone(),
+            }
+        } else {
+            ReportDiff::Equal
+        }
+    }
+}
+
+/// Outcome of comparing two `RadonReport`s with `RadonReport::diff`. `left` values come from the
+/// report on which `diff` was called, and `right` values from the one it was compared with.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub enum ReportDiff<RT> {
+    /// Both reports have the same result, and the same partial results if they kept them.
+    Equal,
+    /// The scripts were run on different inputs.
+    Input { left: RT, right: RT },
+    /// The scripts were run on the same input, and the outputs of all the calls before
+    /// `call_index` were the same, but the outputs of the call at `call_index` differ.
+    Call {
+        call_index: usize,
+        left: RT,
+        right: RT,
+    },
+    /// Every partial result that can be compared is the same, but one of the scripts executed
+    /// more calls than the other.
+    Length { left: usize, right: usize },
+    /// The final results differ, and there are no partial results telling where they diverged.
+    Result { left: RT, right: RT },
+}
+
+/// Mirror of `RadonReport` used by `RadonReport::to_compact_bytes`.
+#[derive(Deserialize, Serialize)]
+struct CompactRadonReport {
+    #[serde(rename = "c")]
+    context: CompactReportContext,
+    #[serde(rename = "p", default, skip_serializing_if = "Option::is_none")]
+    partial_results: Option<Vec<SerdeCborValue>>,
+    #[serde(rename = "r")]
+    result: SerdeCborValue,
+    #[serde(rename = "t")]
+    running_time: (u64, u32),
+}
+
+/// Mirror of `ReportContext` used by `RadonReport::to_compact_bytes`.
+#[derive(Deserialize, Serialize)]
+struct CompactReportContext {
+    #[serde(rename = "a", default, skip_serializing_if = "Option::is_none")]
+    call_arguments: Option<Vec<SerdeCborValue>>,
+    #[serde(rename = "ai", default, skip_serializing_if = "Option::is_none")]
+    call_argument_index: Option<usize>,
+    #[serde(rename = "ci", default, skip_serializing_if = "Option::is_none")]
+    call_index: Option<usize>,
+    #[serde(rename = "co", default, skip_serializing_if = "Option::is_none")]
+    call_operator: Option<usize>,
+    #[serde(rename = "e", default, skip_serializing_if = "Option::is_none")]
+    completion_time: Option<(u64, u32)>,
+    #[serde(rename = "s")]
+    stage: CompactStage,
+    #[serde(rename = "b", default, skip_serializing_if = "Option::is_none")]
+    start_time: Option<(u64, u32)>,
+    #[serde(rename = "si", default, skip_serializing_if = "Option::is_none")]
+    script_index: Option<usize>,
+    #[serde(rename = "w", default, skip_serializing_if = "Option::is_none")]
+    active_wips: Option<(HashMap<String, Epoch>, Epoch)>,
+}
+
+/// Mirror of `Stage` used by `RadonReport::to_compact_bytes`.
+#[derive(Deserialize, Serialize)]
+enum CompactStage {
+    #[serde(rename = "a")]
+    Aggregation(f32, Vec<bool>),
+    #[serde(rename = "c")]
+    Contextless,
+    #[serde(rename = "r")]
+    Retrieval(
+        Vec<Vec<Vec<SerdeCborValue>>>,
+        Vec<SerdeCborValue>,
+        Option<CapturedHttpRequest>,
+        Option<String>,
+        Vec<String>,
+    ),
+    #[serde(rename = "t")]
+    Tally(f32, Vec<bool>, Vec<bool>, Vec<SerdeCborValue>),
+}
+
+fn compact_value<RT: TypeLike>(value: &RT) -> Result<SerdeCborValue, failure::Error> {
+    Ok(SerdeCborValue::Bytes(value.encode()?))
+}
+
+fn compact_values<RT: TypeLike>(values: &[RT]) -> Result<Vec<SerdeCborValue>, failure::Error> {
+    values.iter().map(compact_value).collect()
+}
+
+fn expand_value<RT>(value: SerdeCborValue) -> Result<RT, failure::Error>
+where
+    RT: TypeLike,
+    for<'a> RT: TryFrom<&'a [u8], Error = <RT as TypeLike>::Error>,
+{
+    match value {
+        SerdeCborValue::Bytes(bytes) => Ok(RT::try_from(bytes.as_slice())?),
+        other => Err(failure::format_err!(
+            "Expected a byte string in compact RadonReport, found {:?}",
+            other
+        )),
+    }
+}
+
+fn expand_values<RT>(values: Vec<SerdeCborValue>) -> Result<Vec<RT>, failure::Error>
+where
+    RT: TypeLike,
+    for<'a> RT: TryFrom<&'a [u8], Error = <RT as TypeLike>::Error>,
+{
+    values.into_iter().map(expand_value).collect()
+}
+
+fn compact_time(time: SystemTime) -> Result<(u64, u32), failure::Error> {
+    let since_epoch = time.duration_since(UNIX_EPOCH)?;
+
+    Ok((since_epoch.as_secs(), since_epoch.subsec_nanos()))
+}
+
+fn expand_time((secs, nanos): (u64, u32)) -> SystemTime {
+    UNIX_EPOCH + Duration::new(secs, nanos)
+}
+
+impl<RT> TryFrom<&RadonReport<RT>> for CompactRadonReport
+where
+    RT: TypeLike,
+{
+    type Error = failure::Error;
+
+    fn try_from(report: &RadonReport<RT>) -> Result<Self, Self::Error> {
+        let context = &report.context;
+        let stage = match &context.stage {
+            Stage::Aggregation(metadata) => {
+                CompactStage::Aggregation(metadata.consensus, metadata.liars.clone())
+            }
+            Stage::Contextless => CompactStage::Contextless,
+            Stage::Retrieval(metadata) => CompactStage::Retrieval(
+                metadata
+                    .subscript_partial_results
+                    .iter()
+                    .map(|subscript| {
+                        subscript
+                            .iter()
+                            .map(|values| compact_values(values))
+                            .collect::<Result<Vec<_>, _>>()
+                    })
+                    .collect::<Result<_, _>>()?,
+                compact_values(&metadata.recovered_errors)?,
+                metadata.http_request.clone(),
+                metadata.final_url.clone(),
+                metadata.divergent_final_hosts.clone(),
+            ),
+            Stage::Tally(metadata) => CompactStage::Tally(
+                metadata.consensus,
+                metadata.errors.clone(),
+                metadata.liars.clone(),
+                compact_values(&metadata.subscript_partial_results)?,
+            ),
+        };
+
+        Ok(CompactRadonReport {
+            context: CompactReportContext {
+                call_arguments: context.call_arguments.clone(),
+                call_argument_index: context.call_argument_index,
+                call_index: context.call_index,
+                call_operator: context.call_operator,
+                completion_time: context.completion_time.map(compact_time).transpose()?,
+                stage,
+                start_time: context.start_time.map(compact_time).transpose()?,
+                script_index: context.script_index,
+                active_wips: context
+                    .active_wips
+                    .as_ref()
+                    .map(|wips| (wips.active_wips.clone(), wips.block_epoch)),
+            },
+            partial_results: report
+                .partial_results
+                .as_ref()
+                .map(|partial_results| compact_values(partial_results))
+                .transpose()?,
+            result: compact_value(&report.result)?,
+            running_time: (
+                report.running_time.as_secs(),
+                report.running_time.subsec_nanos(),
+            ),
+        })
+    }
+}
+
+impl CompactRadonReport {
+    fn into_report<RT>(self) -> Result<RadonReport<RT>, failure::Error>
+    where
+        RT: TypeLike,
+        for<'a> RT: TryFrom<&'a [u8], Error = <RT as TypeLike>::Error>,
+    {
+        let context = self.context;
+        let stage = match context.stage {
+            CompactStage::Aggregation(consensus, liars) => {
+                Stage::Aggregation(AggregationMetaData { consensus, liars })
+            }
+            CompactStage::Contextless => Stage::Contextless,
+            CompactStage::Retrieval(
+                subscript_partial_results,
+                recovered_errors,
+                http_request,
+                final_url,
+                divergent_final_hosts,
+            ) => Stage::Retrieval(RetrievalMetadata {
+                subscript_partial_results: subscript_partial_results
+                    .into_iter()
+                    .map(|subscript| {
+                        subscript
+                            .into_iter()
+                            .map(expand_values)
+                            .collect::<Result<Vec<_>, _>>()
+                    })
+                    .collect::<Result<_, _>>()?,
+                recovered_errors: expand_values(recovered_errors)?,
+                http_request,
+                final_url,
+                divergent_final_hosts,
+            }),
+            CompactStage::Tally(consensus, errors, liars, subscript_partial_results) => {
+                Stage::Tally(TallyMetaData {
+                    consensus,
+                    errors,
+                    liars,
+                    subscript_partial_results: expand_values(subscript_partial_results)?,
+                })
+            }
+        };
+
+        Ok(RadonReport {
+            context: ReportContext {
+                call_arguments: context.call_arguments,
+                call_argument_index: context.call_argument_index,
+                call_index: context.call_index,
+                call_operator: context.call_operator,
+                completion_time: context.completion_time.map(expand_time),
+                stage,
+                start_time: context.start_time.map(expand_time),
+                script_index: context.script_index,
+                active_wips: context
+                    .active_wips
+                    .map(|(active_wips, block_epoch)| ActiveWips {
+                        active_wips,
+                        block_epoch,
+                    }),
+                ..Default::default()
+            },
+            partial_results: self.partial_results.map(expand_values).transpose()?,
+            result: expand_value(self.result)?,
+            running_time: Duration::new(self.running_time.0, self.running_time.1),
+        })
+    }
 }
 
 /// This is the main serializer for turning `RadonReport` into a CBOR-encoded byte stream that can be
@@ -80,7 +439,10 @@ where
     type Error = RT::Error;
 
     fn try_from(report: &RadonReport<RT>) -> Result<Self, Self::Error> {
-        report.result.encode()
+        match &report.context.active_wips {
+            Some(active_wips) => report.result.encode_with_active_wips(active_wips),
+            None => report.result.encode(),
+        }
     }
 }
 
@@ -93,6 +455,12 @@ pub trait TypeLike: Clone + Sized {
     /// Serialize the `TypeLike` as a `Vec<u8>`.
     fn encode(&self) -> Result<Vec<u8>, Self::Error>;
 
+    /// Serialize the `TypeLike` as a `Vec<u8>`, following the encoding rules that apply under the
+    /// given set of active WIPs.
+    fn encode_with_active_wips(&self, _active_wips: &ActiveWips) -> Result<Vec<u8>, Self::Error> {
+        self.encode()
+    }
+
     /// Eases interception of RADON errors (errors that we want to commit, reveal and tally) so
     /// they can be handled as valid `RadonTypes::RadonError` values, which are subject to
     /// commitment, revealing, tallying, etc.
@@ -133,6 +501,21 @@ where
     pub script_index: Option<usize>,
     /// Active WIPs
     pub active_wips: Option<ActiveWips>,
+    /// Limits on the complexity of the scripts executed in this context.
+    #[serde(skip)]
+    pub script_limits: ScriptLimits,
+    /// The number of calls executed so far by the script being processed, including the calls in
+    /// its subscripts.
+    #[serde(skip)]
+    pub executed_calls: usize,
+    /// The number of scripts whose execution is in progress: 1 while running a script, 2 while
+    /// running one of its subscripts, and so on.
+    #[serde(skip)]
+    pub script_depth: usize,
+    /// Whether operators that fall back to a default value keep the errors they recover from in
+    /// the retrieval metadata. Only enabled when the script is executed with partial results.
+    #[serde(skip)]
+    pub record_recovered_errors: bool,
 }
 
 impl<RT> Default for ReportContext<RT>
@@ -150,6 +533,32 @@ where
             start_time: None,
             script_index: None,
             active_wips: None,
+            script_limits: ScriptLimits::default(),
+            executed_calls: 0,
+            script_depth: 0,
+            record_recovered_errors: false,
+        }
+    }
+}
+
+/// Limits on the complexity of RADON scripts, which protect witnesses from requests crafted to
+/// make them execute an exponential number of calls through nested subscripts.
+///
+/// These limits are only enforced once WIP0029 is active.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ScriptLimits {
+    /// Maximum number of calls executed by a script, counting every execution of the calls in its
+    /// subscripts.
+    pub max_calls: usize,
+    /// Maximum nesting depth of subscripts. A script without subscripts has a depth of 0.
+    pub max_subscript_depth: usize,
+}
+
+impl Default for ScriptLimits {
+    fn default() -> Self {
+        Self {
+            max_calls: 100_000,
+            max_subscript_depth: 8,
         }
     }
 }
@@ -169,16 +578,23 @@ where
         self.completion_time = Some(SystemTime::now())
     }
 
-    /// Compute difference between start and completion time.
-    fn duration(&self) -> Duration {
+    /// Compute difference between start and completion time, if both are known.
+    pub fn running_time(&self) -> Option<Duration> {
         match (self.start_time, self.completion_time) {
-            (Some(start_time), Some(completion_time)) => completion_time
-                .duration_since(start_time)
-                .expect("Completion time should always be equal or greater than start time"),
-            _ => Duration::default(),
+            (Some(start_time), Some(completion_time)) => Some(
+                completion_time
+                    .duration_since(start_time)
+                    .expect("Completion time should always be equal or greater than start time"),
+            ),
+            _ => None,
         }
     }
 
+    /// Compute difference between start and completion time.
+    fn duration(&self) -> Duration {
+        self.running_time().unwrap_or_default()
+    }
+
     /// Create a context that is initialized for a particular stage
     pub fn from_stage(stage: Stage<RT>) -> Self {
         Self {
@@ -208,7 +624,7 @@ where
     RT: TypeLike,
 {
     /// Metadata for Aggregation stage.
-    Aggregation,
+    Aggregation(AggregationMetaData),
     /// Metadata for contextless execution of RADON scripts.
     Contextless,
     /// Metadata for Retrieval stage.
@@ -227,6 +643,20 @@ where
     }
 }
 
+impl<RT> Stage<RT>
+where
+    RT: TypeLike,
+{
+    /// Record the values that were filtered out by a filter, if this stage keeps track of them.
+    pub fn update_liars(&mut self, new_liars: Vec<bool>) {
+        match self {
+            Stage::Aggregation(metadata) => metadata.update_liars(new_liars),
+            Stage::Tally(metadata) => metadata.update_liars(new_liars),
+            Stage::Contextless | Stage::Retrieval(_) => {}
+        }
+    }
+}
+
 /// Retrieval and aggregation specific metadata structure.
 #[derive(Clone, Debug, Serialize)]
 pub struct RetrievalMetadata<RT>
@@ -244,6 +674,19 @@ where
     /// * `element_index` is the index of the element inside the array that serves as the input of
     ///     the subscript.
     pub subscript_partial_results: Vec<Vec<Vec<RT>>>,
+    /// Errors that did not make the script fail because the operator raising them was given a
+    /// default value to fall back to (e.g. `StringParseJSONMap` with a default argument). Only
+    /// recorded if partial results are enabled.
+    pub recovered_errors: Vec<RT>,
+    /// The HTTP request that would have been sent to the data source, only present when the
+    /// retrieval ran in capture mode and therefore no request was actually sent.
+    pub http_request: Option<CapturedHttpRequest>,
+    /// The URL that the HTTP request ultimately landed on, which differs from the one in the
+    /// retrieval if any redirects were followed.
+    pub final_url: Option<String>,
+    /// The schemes and hosts that the HTTP requests of a paranoid retrieval landed on, only when
+    /// they were not the same for all the transports.
+    pub divergent_final_hosts: Vec<String>,
 }
 
 impl<RT> Default for RetrievalMetadata<RT>
@@ -253,13 +696,53 @@ where
     fn default() -> Self {
         Self {
             subscript_partial_results: vec![],
+            recovered_errors: vec![],
+            http_request: None,
+            final_url: None,
+            divergent_final_hosts: vec![],
         }
     }
 }
 
-// This structure is not needed yet but it is here just in case we need it in the future.
-///// Retrieval-specific metadata structure.
-//pub struct AggregationMetaData {}
+/// An HTTP request exactly as it would go on the wire.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct CapturedHttpRequest {
+    /// HTTP method, e.g. `GET`.
+    pub method: String,
+    /// Full URL of the request.
+    pub url: String,
+    /// Headers of the request, in the order in which they would be sent.
+    pub headers: Vec<(String, String)>,
+    /// Body of the request.
+    pub body: Vec<u8>,
+}
+
+/// Aggregation-specific metadata structure.
+#[derive(Clone, Debug, Serialize)]
+pub struct AggregationMetaData {
+    /// Proportion between the number of sources and those that passed all the filters, same as
+    /// `TallyMetaData::consensus`.
+    pub consensus: f32,
+    /// A positional vector of the source values that were filtered out (`true`) or not (`false`).
+    pub liars: Vec<bool>,
+}
+
+impl Default for AggregationMetaData {
+    fn default() -> Self {
+        Self {
+            // Consensus is initialized to 100% because it is only updated when there are some lies
+            consensus: 1.0,
+            liars: vec![],
+        }
+    }
+}
+
+impl AggregationMetaData {
+    /// Update liars vector, see `TallyMetaData::update_liars`.
+    pub fn update_liars(&mut self, new_liars: Vec<bool>) {
+        self.consensus = merge_liars(&mut self.liars, new_liars);
+    }
+}
 
 /// Tally-specific metadata structure.
 #[derive(Clone, Debug, Serialize)]
@@ -303,29 +786,35 @@ where
 {
     /// Update liars vector
     /// new_liars length has to be less than false elements in liars
-    // FIXME: Allow for now, since there is no safe cast function from a usize to float yet
-    #[allow(clippy::cast_precision_loss)]
     pub fn update_liars(&mut self, new_liars: Vec<bool>) {
-        if self.liars.is_empty() {
-            self.liars = new_liars;
-        } else if !new_liars.is_empty() {
-            let mut new_iter = new_liars.iter();
-
-            for liar in &mut self.liars {
-                if !*liar {
-                    *liar = *new_iter.next().unwrap();
-                }
-            }
+        self.consensus = merge_liars(&mut self.liars, new_liars);
+    }
+}
 
-            assert!(new_iter.next().is_none());
+/// Merge the liars found by a filter into those found by the previous filters, which must have
+/// as many truthers as there are elements in `new_liars`, and return the resulting consensus.
+// FIXME: Allow for now, since there is no safe cast function from a usize to float yet
+#[allow(clippy::cast_precision_loss)]
+fn merge_liars(liars: &mut Vec<bool>, new_liars: Vec<bool>) -> f32 {
+    if liars.is_empty() {
+        *liars = new_liars;
+    } else if !new_liars.is_empty() {
+        let mut new_iter = new_liars.iter();
+
+        for liar in liars.iter_mut() {
+            if !*liar {
+                *liar = *new_iter.next().unwrap();
+            }
         }
 
-        // TODO: consensus will be NaN when self.liars.len() == 0
-        self.consensus = self.liars.iter().fold(0., |count, liar| match liar {
-            true => count,
-            false => count + 1.,
-        }) / self.liars.len() as f32;
+        assert!(new_iter.next().is_none());
     }
+
+    // TODO: consensus will be NaN when liars.len() == 0
+    liars.iter().fold(0., |count, liar| match liar {
+        true => count,
+        false => count + 1.,
+    }) / liars.len() as f32
 }
 
 #[cfg(test)]
@@ -430,4 +919,28 @@ mod tests {
         metadata.update_liars(vec![]);
         assert_eq!(metadata.liars, expected);
     }
+
+    #[test]
+    fn test_consensus() {
+        let report = |stage| RadonReport::<DummyType> {
+            context: ReportContext::from_stage(stage),
+            partial_results: None,
+            result: DummyType,
+            running_time: Duration::default(),
+        };
+        let tally = TallyMetaData {
+            consensus: 0.75,
+            ..Default::default()
+        };
+
+        assert_eq!(report(Stage::Tally(tally)).consensus(), Some(0.75));
+        assert_eq!(
+            report(Stage::Aggregation(AggregationMetaData::default())).consensus(),
+            Some(1.0)
+        );
+        assert_eq!(
+            report(Stage::Retrieval(RetrievalMetadata::default())).consensus(),
+            None
+        );
+    }
 }
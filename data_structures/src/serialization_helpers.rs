@@ -217,6 +217,8 @@ enum UtxoSelectionStrategyName {
     BigFirst,
     #[serde(rename = "small_first", alias = "SmallFirst")]
     SmallFirst,
+    #[serde(rename = "branch_and_bound", alias = "BranchAndBound")]
+    BranchAndBound,
 }
 
 impl From<UtxoSelectionStrategyName> for UtxoSelectionStrategy {
@@ -227,6 +229,9 @@ impl From<UtxoSelectionStrategyName> for UtxoSelectionStrategy {
             UtxoSelectionStrategyName::SmallFirst => {
                 UtxoSelectionStrategy::SmallFirst { from: None }
             }
+            UtxoSelectionStrategyName::BranchAndBound => {
+                UtxoSelectionStrategy::BranchAndBound { from: None }
+            }
         }
     }
 }
@@ -237,6 +242,9 @@ impl<'a> From<&'a UtxoSelectionStrategy> for UtxoSelectionStrategyName {
             UtxoSelectionStrategy::Random { .. } => UtxoSelectionStrategyName::Random,
             UtxoSelectionStrategy::BigFirst { .. } => UtxoSelectionStrategyName::BigFirst,
             UtxoSelectionStrategy::SmallFirst { .. } => UtxoSelectionStrategyName::SmallFirst,
+            UtxoSelectionStrategy::BranchAndBound { .. } => {
+                UtxoSelectionStrategyName::BranchAndBound
+            }
         }
     }
 }
@@ -31,6 +31,22 @@ pub struct CollectedOutputs {
     pub total_value: u64,
 }
 
+impl CollectedOutputs {
+    /// Value left over after paying `amount` with these outputs, which goes to the change output.
+    pub fn change(&self, amount: u64) -> u64 {
+        self.total_value.saturating_sub(amount)
+    }
+}
+
+/// Maximum number of steps of the search done by `UtxoSelectionStrategy::BranchAndBound`. Once
+/// reached, the best selection found so far is used.
+const BRANCH_AND_BOUND_MAX_TRIES: usize = 100_000;
+
+/// Maximum number of inputs that `UtxoSelectionStrategy::BranchAndBound` may choose, so that the
+/// weight and the fee of the transaction stay close to those of a transaction built with
+/// `UtxoSelectionStrategy::BigFirst`. If no such selection is found, `BigFirst` is used instead.
+const BRANCH_AND_BOUND_MAX_INPUTS: usize = 10;
+
 /// Structure that resumes the information needed to create a Transaction
 #[derive(Clone, Debug)]
 pub struct TransactionInfo {
@@ -130,6 +146,8 @@ pub trait OutputsCollection {
         let mut pointers = vec![];
 
         let utxo_iter = self.sort_by(utxo_strategy);
+        // Branch and bound needs to know all the available outputs before choosing
+        let take_all = matches!(utxo_strategy, UtxoSelectionStrategy::BranchAndBound { .. });
 
         for pointer in utxo_iter.iter() {
             let output: ValueTransferOutput = self.get(pointer).unwrap();
@@ -160,11 +178,19 @@ pub trait OutputsCollection {
             pointers.push(*pointer);
             outputs.push(output);
 
-            if total_value >= amount {
+            if total_value >= amount && !take_all {
                 break;
             }
         }
 
+        if take_all && total_value >= amount {
+            let values: Vec<u64> = outputs.iter().map(|output| output.value).collect();
+            let selected = branch_and_bound(&values, amount, BRANCH_AND_BOUND_MAX_INPUTS);
+            pointers = selected.iter().map(|i| pointers[*i]).collect();
+            outputs = selected.iter().map(|i| outputs[*i].clone()).collect();
+            total_value = outputs.iter().map(|output| output.value).sum();
+        }
+
         if total_value >= amount {
             Ok(CollectedOutputs {
                 resolved: outputs,
@@ -412,6 +438,74 @@ pub fn calculate_weight(
     Ok(weight)
 }
 
+/// Choose the combination of at most `max_values` of `values` that adds up to at least `amount`
+/// with the lowest excess, using as few values as possible in case of a tie. This is a depth-first
+/// search which tries including each value before excluding it, and discards the branches that
+/// cannot reach `amount`, would need more than `max_values` values, or cannot improve on an exact
+/// match.
+///
+/// The search starts from the first values that are enough to reach `amount`, which are used if
+/// there is no better combination. As `values` are expected to be sorted from biggest to smallest,
+/// like `UtxoSelectionStrategy::BigFirst` does, this is the combination with the fewest values, so
+/// it is used even if it has more than `max_values` values.
+///
+/// `values` must add up to at least `amount`. Returns the indexes of the chosen values, in
+/// increasing order.
+fn branch_and_bound(values: &[u64], amount: u64, max_values: usize) -> Vec<usize> {
+    // remaining[i] is the sum of values[i..]
+    let mut remaining = vec![0; values.len() + 1];
+    for i in (0..values.len()).rev() {
+        remaining[i] = remaining[i + 1] + values[i];
+    }
+
+    // Start with as many of the first values as needed, like BigFirst does with sorted values, so
+    // that there is always a solution
+    let greedy_len = remaining
+        .iter()
+        .position(|rest| remaining[0] - rest >= amount)
+        .unwrap_or(values.len());
+    let mut best: Vec<usize> = (0..greedy_len).collect();
+    let mut best_excess = remaining[0] - remaining[greedy_len] - amount;
+
+    let mut current: Vec<usize> = vec![];
+    let mut current_sum = 0;
+    let mut next = 0;
+    for _ in 0..BRANCH_AND_BOUND_MAX_TRIES {
+        let backtrack = if current_sum + remaining[next] < amount {
+            // Not enough value left
+            true
+        } else if current_sum >= amount {
+            let excess = current_sum - amount;
+            if excess < best_excess || (excess == best_excess && current.len() < best.len()) {
+                best = current.clone();
+                best_excess = excess;
+            }
+            // Adding more values would only increase the excess
+            true
+        } else {
+            // Too many values, or an exact match that cannot be improved with more values
+            current.len() >= max_values || (best_excess == 0 && current.len() + 1 >= best.len())
+        };
+
+        if backtrack {
+            // Exclude the last included value, and continue with the ones after it
+            match current.pop() {
+                Some(last) => {
+                    current_sum -= values[last];
+                    next = last + 1;
+                }
+                None => break,
+            }
+        } else {
+            current_sum += values[next];
+            current.push(next);
+            next += 1;
+        }
+    }
+
+    best
+}
+
 /// Get total balance
 pub fn get_total_balance(
     all_utxos: &UnspentOutputsPool,
@@ -2200,4 +2294,107 @@ mod tests {
         assert_eq!(inputs.len(), 1);
         assert_eq!(outputs.len(), 0);
     }
+
+    #[test]
+    fn utxo_selection_strategies() {
+        let outputs = vec![
+            pay_me(1),
+            pay_me(5),
+            pay_me(10),
+            pay_me(50),
+            pay_me(100),
+            pay_me(500),
+            pay_me(334),
+        ];
+        let (mut own_utxos, all_utxos) = build_utxo_set(outputs, None, vec![]);
+        let mut utxos = NodeUtxos {
+            all_utxos: &all_utxos,
+            own_utxos: &mut own_utxos,
+            pkh: my_pkh(),
+        };
+
+        let mut take = |amount, utxo_strategy| {
+            utxos
+                .take_enough_utxos(amount, 0, None, &utxo_strategy)
+                .unwrap()
+        };
+
+        let big_first = take(600, UtxoSelectionStrategy::BigFirst { from: None });
+        let small_first = take(600, UtxoSelectionStrategy::SmallFirst { from: None });
+        let branch_and_bound = take(600, UtxoSelectionStrategy::BranchAndBound { from: None });
+
+        // 500 + 334
+        assert_eq!(big_first.pointers.len(), 2);
+        assert_eq!(big_first.change(600), 234);
+        // 1 + 5 + 10 + 50 + 100 + 334 + 500
+        assert_eq!(small_first.pointers.len(), 7);
+        assert_eq!(small_first.change(600), 400);
+        // 500 + 100
+        assert_eq!(branch_and_bound.pointers.len(), 2);
+        assert_eq!(branch_and_bound.change(600), 0);
+        let mut values: Vec<u64> = branch_and_bound
+            .resolved
+            .iter()
+            .map(|output| output.value)
+            .collect();
+        values.sort_unstable();
+        assert_eq!(values, vec![100, 500]);
+
+        // When there is no exact match, the change is as small as possible: 334 + 10
+        let branch_and_bound = take(343, UtxoSelectionStrategy::BranchAndBound { from: None });
+        assert_eq!(branch_and_bound.total_value, 344);
+        assert_eq!(branch_and_bound.pointers.len(), 2);
+        assert_eq!(
+            take(343, UtxoSelectionStrategy::BigFirst { from: None }).change(343),
+            157
+        );
+
+        // Not enough money
+        assert_eq!(
+            utxos
+                .take_enough_utxos(
+                    1001,
+                    0,
+                    None,
+                    &UtxoSelectionStrategy::BranchAndBound { from: None }
+                )
+                .unwrap_err(),
+            TransactionError::NoMoney {
+                total_balance: 1000,
+                available_balance: 1000,
+                transaction_value: 1001,
+            }
+        );
+    }
+
+    #[test]
+    fn branch_and_bound_selection() {
+        // Exact match
+        assert_eq!(branch_and_bound(&[8, 5, 4, 3], 7, 10), vec![2, 3]);
+        // Among exact matches, the one with fewer values
+        assert_eq!(branch_and_bound(&[6, 4, 3, 2, 1], 6, 10), vec![0]);
+        // No exact match: smallest excess
+        assert_eq!(branch_and_bound(&[10, 6, 6], 11, 10), vec![1, 2]);
+        // Everything is needed
+        assert_eq!(branch_and_bound(&[3, 2, 1], 6, 10), vec![0, 1, 2]);
+        // Unsorted values still work
+        assert_eq!(branch_and_bound(&[1, 9, 5, 2], 7, 10), vec![2, 3]);
+    }
+
+    #[test]
+    fn branch_and_bound_max_values() {
+        // Lots of dust that would be an exact match is not used if it takes too many values
+        let mut values = vec![100];
+        values.extend(vec![1; 50]);
+        assert_eq!(branch_and_bound(&values, 50, 10), vec![0]);
+        assert_eq!(
+            branch_and_bound(&values, 50, 50),
+            (1..=50).collect::<Vec<_>>()
+        );
+        // Combinations with few values are still preferred if they have a lower excess
+        assert_eq!(branch_and_bound(&[10, 6, 6], 11, 2), vec![1, 2]);
+        // If the first values that are enough already exceed the limit, they are used anyway
+        assert_eq!(branch_and_bound(&[10, 6, 6], 11, 1), vec![0, 1]);
+        assert_eq!(branch_and_bound(&[3, 2, 1], 6, 2), vec![0, 1, 2]);
+    }
 }
@@ -407,7 +407,8 @@ impl OutputsCollection for NodeUtxosRef<'_> {
         }
 
         match strategy {
-            UtxoSelectionStrategy::BigFirst { from: _ } => {
+            UtxoSelectionStrategy::BigFirst { from: _ }
+            | UtxoSelectionStrategy::BranchAndBound { from: _ } => {
                 self.own_utxos.sort(self.all_utxos, true)
             }
             UtxoSelectionStrategy::SmallFirst { from: _ } => {
@@ -500,11 +501,16 @@ impl OutputsCollection for NodeUtxos<'_> {
 }
 
 /// Strategy to sort our own unspent outputs pool
+///
+/// `BranchAndBound` looks for the combination of UTXOs that results in the smallest change, to
+/// reduce UTXO fragmentation. It only considers combinations of a few UTXOs, so as not to inflate
+/// the weight and the fee of the transaction, and falls back to `BigFirst` otherwise.
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub enum UtxoSelectionStrategy {
     Random { from: Option<PublicKeyHash> },
     BigFirst { from: Option<PublicKeyHash> },
     SmallFirst { from: Option<PublicKeyHash> },
+    BranchAndBound { from: Option<PublicKeyHash> },
 }
 
 impl Default for UtxoSelectionStrategy {
@@ -522,6 +528,7 @@ impl UtxoSelectionStrategy {
             UtxoSelectionStrategy::Random { from } => from,
             UtxoSelectionStrategy::BigFirst { from } => from,
             UtxoSelectionStrategy::SmallFirst { from } => from,
+            UtxoSelectionStrategy::BranchAndBound { from } => from,
         }
     }
 
@@ -532,6 +539,7 @@ impl UtxoSelectionStrategy {
             UtxoSelectionStrategy::Random { from } => from,
             UtxoSelectionStrategy::BigFirst { from } => from,
             UtxoSelectionStrategy::SmallFirst { from } => from,
+            UtxoSelectionStrategy::BranchAndBound { from } => from,
         }
     }
 
@@ -1,5 +1,6 @@
-use std::{fmt, iter::Sum, ops::*};
+use std::{fmt, iter::Sum, ops::*, str::FromStr};
 
+use failure::Fail;
 use serde::{Deserialize, Serialize};
 
 use crate::{chain::Epoch, staking::helpers::Power};
@@ -10,6 +11,8 @@ pub const NANOWITS_PER_WIT: u64 = 1_000_000_000;
 // 10 ^ WIT_DECIMAL_PLACES
 /// Number of decimal places used in the string representation of wit value.
 pub const WIT_DECIMAL_PLACES: u8 = 9;
+/// Symbol of the wit unit, used when displaying and parsing wit values.
+pub const WIT_SYMBOL: &str = "WIT";
 
 /// Unit of value
 #[derive(
@@ -44,6 +47,11 @@ impl Wit {
 
         (amount_wits, amount_nanowits)
     }
+
+    /// Format the value in wits followed by the unit, e.g. `1.234567890 WIT`
+    pub fn to_string_with_unit(self) -> String {
+        format!("{} {}", self, WIT_SYMBOL)
+    }
 }
 
 impl fmt::Display for Wit {
@@ -61,6 +69,68 @@ impl fmt::Display for Wit {
     }
 }
 
+/// Error when parsing a `Wit` value from a string
+#[derive(Debug, PartialEq, Eq, Fail)]
+pub enum WitParseError {
+    /// The amount is not a positive decimal number
+    #[fail(display = "Invalid wit amount: {:?}", _0)]
+    InvalidAmount(String),
+    /// The amount has more decimal places than a nanowit can represent
+    #[fail(
+        display = "Too many decimal places: {}, wit amounts have at most {}",
+        _0, WIT_DECIMAL_PLACES
+    )]
+    TooManyDecimals(usize),
+    /// The amount is followed by something other than the wit symbol
+    #[fail(display = "Invalid unit: {:?}, expected {}", _0, WIT_SYMBOL)]
+    InvalidUnit(String),
+    /// The amount does not fit in a `u64` of nanowits
+    #[fail(display = "The wit amount is too large")]
+    Overflow,
+}
+
+/// Parse an amount in wits, with up to 9 decimal places and optionally followed by the wit
+/// symbol, e.g. `1.5`, `1.5 WIT` or `0.000000001 wit`.
+impl FromStr for Wit {
+    type Err = WitParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let amount_len = s
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(s.len());
+        let (amount, unit) = s.split_at(amount_len);
+        let unit = unit.trim_start();
+        if !unit.is_empty() && !unit.eq_ignore_ascii_case(WIT_SYMBOL) {
+            return Err(WitParseError::InvalidUnit(unit.to_string()));
+        }
+
+        let invalid_amount = || WitParseError::InvalidAmount(amount.to_string());
+        let (wits, decimals) = match amount.split_once('.') {
+            Some((wits, decimals)) => (wits, decimals),
+            None => (amount, "0"),
+        };
+        if wits.is_empty() || decimals.is_empty() || decimals.contains('.') {
+            return Err(invalid_amount());
+        }
+        let decimal_places = usize::from(WIT_DECIMAL_PLACES);
+        if decimals.len() > decimal_places {
+            return Err(WitParseError::TooManyDecimals(decimals.len()));
+        }
+
+        // Only digits are left, so parsing can only fail because of an overflow
+        let wits = wits.parse::<u64>().map_err(|_| WitParseError::Overflow)?;
+        let nanowits = format!("{:0<width$}", decimals, width = decimal_places)
+            .parse::<u64>()
+            .map_err(|_| invalid_amount())?;
+
+        wits.checked_mul(NANOWITS_PER_WIT)
+            .and_then(|wits| wits.checked_add(nanowits))
+            .map(Self::from_nanowits)
+            .ok_or(WitParseError::Overflow)
+    }
+}
+
 impl Add for Wit {
     type Output = Self;
 
@@ -212,4 +282,71 @@ mod tests {
             "321.234567890"
         );
     }
+
+    #[test]
+    fn wit_to_string_with_unit() {
+        assert_eq!(
+            Wit::from_nanowits(0).to_string_with_unit(),
+            "0.000000000 WIT"
+        );
+        assert_eq!(
+            Wit::from_nanowits(1_234_567_890).to_string_with_unit(),
+            "1.234567890 WIT"
+        );
+    }
+
+    #[test]
+    fn wit_from_str() {
+        assert_eq!(
+            Wit::from_str("0.000000001 WIT").unwrap(),
+            Wit::from_nanowits(1)
+        );
+        assert_eq!(
+            Wit::from_str("1.5 WIT").unwrap(),
+            Wit::from_nanowits(1_500_000_000)
+        );
+        assert_eq!(
+            Wit::from_str("1.5").unwrap(),
+            Wit::from_nanowits(1_500_000_000)
+        );
+        assert_eq!(Wit::from_str(" 2wit ").unwrap(), Wit::from_wits(2));
+        assert_eq!(
+            Wit::from_str("18446744073.709551615 WIT").unwrap(),
+            Wit::from_nanowits(u64::MAX)
+        );
+
+        // Round trip
+        for nanowits in [0, 1, 90, 1_234_567_890, u64::MAX] {
+            let wit = Wit::from_nanowits(nanowits);
+            assert_eq!(Wit::from_str(&wit.to_string_with_unit()).unwrap(), wit);
+            assert_eq!(Wit::from_str(&wit.to_string()).unwrap(), wit);
+        }
+    }
+
+    #[test]
+    fn wit_from_str_errors() {
+        assert_eq!(
+            Wit::from_str("1.0000000001 WIT"),
+            Err(WitParseError::TooManyDecimals(10))
+        );
+        assert_eq!(
+            Wit::from_str("18446744073.709551616 WIT"),
+            Err(WitParseError::Overflow)
+        );
+        assert_eq!(
+            Wit::from_str("1.5 nanowit"),
+            Err(WitParseError::InvalidUnit("nanowit".to_string()))
+        );
+        assert_eq!(
+            Wit::from_str("-1 WIT"),
+            Err(WitParseError::InvalidUnit("-1 WIT".to_string()))
+        );
+        for amount in ["", "WIT", ".5", "1.", "1.2.3"] {
+            assert!(
+                matches!(Wit::from_str(amount), Err(WitParseError::InvalidAmount(_))),
+                "{:?} should be rejected",
+                amount
+            );
+        }
+    }
 }
@@ -1,3 +1,17 @@
+use std::{
+    fmt,
+    net::IpAddr,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use rand::{thread_rng, Rng};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
 /// Holds witnessing configuration after it has been validated.
 ///
 /// This is ready to use with `witnet_node::actors::RadManager::from_config` or in
@@ -9,6 +23,37 @@ where
 {
     pub transports: Vec<Option<T>>,
     pub paranoid_threshold: f32,
+    /// Whether to retrieve every source through all the transports and check that they agree. If
+    /// disabled, only one transport is used, which saves bandwidth at the cost of trusting that
+    /// single transport.
+    pub paranoid: bool,
+    /// Which transport to use for each retrieval when paranoid mode is disabled.
+    pub rotation: TransportRotation,
+    /// Maximum total download rate for retrievals, in bytes per second. `None` means unlimited.
+    pub max_download_rate: Option<u64>,
+    /// How to retry retrievals that fail because of transient errors. `None` means no retries.
+    pub retry: Option<RetryPolicy>,
+    /// Whether to reject retrievals from sources that do not use TLS, i.e. plain `http://` URLs.
+    pub require_tls: bool,
+    /// User agent to send in every HTTP retrieval. `None` means picking one at random from a list
+    /// of common user agents on every request.
+    pub user_agent: Option<String>,
+    /// Names of the headers whose values are redacted in logs, on top of `Authorization`, `Cookie`
+    /// and `Set-Cookie`, which are always redacted.
+    pub redacted_headers: Vec<String>,
+    /// Maximum time to wait for the connection to a data source to be established. `None` means
+    /// no timeout.
+    pub connect_timeout: Option<Duration>,
+    /// Maximum time to wait without receiving any data from a data source once connected. `None`
+    /// means no timeout.
+    pub read_timeout: Option<Duration>,
+    /// Which IP version to use when connecting to data sources.
+    pub ip_version: IpVersion,
+    /// Fixed addresses for some hosts, which are then never resolved through DNS.
+    pub resolve: Vec<ResolveOverride>,
+    /// Minimum TLS version that data sources must support. `None` means the default of the HTTP
+    /// client.
+    pub min_tls_version: Option<TlsVersion>,
 }
 
 impl<T> Default for WitnessingConfig<T>
@@ -19,6 +64,18 @@ where
         Self {
             transports: vec![None],
             paranoid_threshold: 0.51,
+            paranoid: true,
+            rotation: TransportRotation::default(),
+            max_download_rate: None,
+            retry: None,
+            require_tls: false,
+            user_agent: None,
+            redacted_headers: vec![],
+            connect_timeout: None,
+            read_timeout: None,
+            ip_version: IpVersion::default(),
+            resolve: vec![],
+            min_tls_version: None,
         }
     }
 }
@@ -43,3 +100,170 @@ where
         Ok(transports)
     }
 }
+
+/// Policy for choosing a single transport for every retrieval, used when paranoid mode is
+/// disabled.
+#[derive(Clone, Debug, Default)]
+pub enum TransportRotation {
+    /// Always use the first transport.
+    #[default]
+    First,
+    /// Use every transport in turn. The counter is shared by all the clones of this policy, so
+    /// that the rotation goes on across retrievals.
+    RoundRobin(Arc<AtomicUsize>),
+    /// Use a transport chosen at random on every retrieval.
+    Random,
+}
+
+impl TransportRotation {
+    /// Create a round-robin policy that starts with the first transport.
+    pub fn round_robin() -> Self {
+        TransportRotation::RoundRobin(Arc::new(AtomicUsize::new(0)))
+    }
+
+    /// Index of the transport to use for the next retrieval, out of `transports` transports.
+    ///
+    /// Returns 0 if there are no transports at all.
+    pub fn next_transport(&self, transports: usize) -> usize {
+        if transports == 0 {
+            return 0;
+        }
+
+        match self {
+            TransportRotation::First => 0,
+            TransportRotation::RoundRobin(counter) => {
+                counter.fetch_add(1, Ordering::Relaxed) % transports
+            }
+            TransportRotation::Random => thread_rng().gen_range(0..transports),
+        }
+    }
+}
+
+/// Retry policy for retrievals that fail with transient errors, such as 5xx HTTP status codes or
+/// connection errors.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RetryPolicy {
+    /// Maximum number of attempts, including the first one.
+    pub max_attempts: u32,
+    /// Delay before the first retry. The delay doubles on every subsequent retry, and a random
+    /// jitter of up to half of it is subtracted so that witnesses do not retry all at once.
+    pub base_delay: Duration,
+}
+
+/// IP version to use when connecting to data sources.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IpVersion {
+    /// Use whatever addresses the system resolver returns, be them IPv4 or IPv6.
+    #[default]
+    Any,
+    /// Only connect to IPv4 addresses.
+    V4,
+    /// Only connect to IPv6 addresses, e.g. on IPv6-only networks.
+    V6,
+}
+
+/// Minimum TLS version that can be required from data sources.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum TlsVersion {
+    /// TLS 1.2 or later, which refuses sources that only offer TLS 1.1 or lower.
+    #[serde(rename = "1.2")]
+    V1_2,
+}
+
+/// Fixed address for a host and port, which bypasses DNS resolution in the same fashion as the
+/// `--resolve` option of curl. Written as `host:port:address`, e.g. `example.com:443:127.0.0.1` or
+/// `example.com:443:[::1]`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ResolveOverride {
+    pub host: String,
+    pub port: u16,
+    pub address: IpAddr,
+}
+
+impl fmt::Display for ResolveOverride {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.address {
+            IpAddr::V4(address) => write!(f, "{}:{}:{}", self.host, self.port, address),
+            IpAddr::V6(address) => write!(f, "{}:{}:[{}]", self.host, self.port, address),
+        }
+    }
+}
+
+impl FromStr for ResolveOverride {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || format!("{:?} is not a valid `host:port:address` override", s);
+
+        let mut parts = s.splitn(3, ':');
+        let (host, port, address) = match (parts.next(), parts.next(), parts.next()) {
+            (Some(host), Some(port), Some(address)) if !host.is_empty() => (host, port, address),
+            _ => return Err(error()),
+        };
+        let port = port.parse().map_err(|_| error())?;
+        let address = address
+            .strip_prefix('[')
+            .and_then(|address| address.strip_suffix(']'))
+            .unwrap_or(address)
+            .parse()
+            .map_err(|_| error())?;
+
+        Ok(ResolveOverride {
+            host: host.to_string(),
+            port,
+            address,
+        })
+    }
+}
+
+impl Serialize for ResolveOverride {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for ResolveOverride {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    use super::*;
+
+    #[test]
+    fn test_resolve_override_from_str() {
+        let ipv4 = ResolveOverride {
+            host: String::from("example.com"),
+            port: 443,
+            address: IpAddr::V4(Ipv4Addr::LOCALHOST),
+        };
+        let ipv6 = ResolveOverride {
+            address: IpAddr::V6(Ipv6Addr::LOCALHOST),
+            ..ipv4.clone()
+        };
+
+        assert_eq!("example.com:443:127.0.0.1".parse(), Ok(ipv4.clone()));
+        assert_eq!("example.com:443:[::1]".parse(), Ok(ipv6.clone()));
+        assert_eq!("example.com:443:::1".parse(), Ok(ipv6.clone()));
+        assert_eq!(ipv4.to_string().parse(), Ok(ipv4));
+        assert_eq!(ipv6.to_string().parse(), Ok(ipv6));
+
+        for invalid in [
+            "",
+            "example.com",
+            "example.com:443",
+            ":443:127.0.0.1",
+            "example.com:https:127.0.0.1",
+            "example.com:443:localhost",
+        ] {
+            assert!(invalid.parse::<ResolveOverride>().is_err(), "{:?}", invalid);
+        }
+    }
+}
@@ -1,17 +1,44 @@
 use isahc::prelude::*;
 
+use std::{net::IpAddr, time::Duration};
+
 use failure::Fail;
-use isahc::config::RedirectPolicy;
+use futures::AsyncReadExt;
+use isahc::config::{RedirectPolicy, ResolveMap};
 use isahc::http;
 use isahc::http::request::Builder;
 
+pub use isahc::config::IpVersion;
+
 /// Maximum number of HTTP redirects to follow
 const MAX_REDIRECTS: u32 = 4;
 
+/// Cipher suites offered when requiring TLS 1.2 or later: every TLS 1.2 suite with forward secrecy
+/// and a SHA-2 (or AEAD) MAC, including the CBC ones, so that no suite usable with TLS 1.1 and
+/// lower is offered and older servers fail the handshake. TLS 1.3 suites are configured separately
+/// by libcurl, so they are still offered.
+const TLS_1_2_CIPHERS: [&str; 14] = [
+    "ECDHE-ECDSA-AES128-GCM-SHA256",
+    "ECDHE-RSA-AES128-GCM-SHA256",
+    "ECDHE-ECDSA-AES256-GCM-SHA384",
+    "ECDHE-RSA-AES256-GCM-SHA384",
+    "ECDHE-ECDSA-CHACHA20-POLY1305",
+    "ECDHE-RSA-CHACHA20-POLY1305",
+    "DHE-RSA-AES128-GCM-SHA256",
+    "DHE-RSA-AES256-GCM-SHA384",
+    "ECDHE-ECDSA-AES128-SHA256",
+    "ECDHE-RSA-AES128-SHA256",
+    "ECDHE-ECDSA-AES256-SHA384",
+    "ECDHE-RSA-AES256-SHA384",
+    "DHE-RSA-AES128-SHA256",
+    "DHE-RSA-AES256-SHA256",
+];
+
 /// A surf-alike HTTP client that additionally supports proxies (HTTP(S), SOCKS4 and SOCKS5)
 #[derive(Clone, Debug)]
 pub struct WitnetHttpClient {
     client: isahc::HttpClient,
+    min_tls_version: Option<TlsVersion>,
 }
 
 impl WitnetHttpClient {
@@ -24,7 +51,7 @@ impl WitnetHttpClient {
             self.client
                 .send_async(request.req)
                 .await
-                .map_err(|e| WitnetHttpError::HttpRequestError { msg: e.to_string() })?,
+                .map_err(WitnetHttpError::from_request_error)?,
         ))
     }
 }
@@ -47,6 +74,13 @@ pub enum WitnetHttpError {
         /// An error message.
         msg: String,
     },
+    /// The TLS handshake failed, e.g. because the certificate of the server is expired,
+    /// self-signed or issued for another host.
+    #[fail(display = "TLS error. Underlying error: {}", msg)]
+    TlsError {
+        /// An error message.
+        msg: String,
+    },
     /// The provided proxy URI is invalid.
     #[fail(
         display = "The provided proxy address is not a valid URI ({}). Underlying error: {}",
@@ -92,26 +126,136 @@ pub enum WitnetHttpError {
     },
 }
 
+impl WitnetHttpError {
+    /// Classify an error coming from sending a request, telling TLS errors apart from the rest.
+    fn from_request_error(error: isahc::Error) -> Self {
+        let msg = error.to_string();
+
+        match error.kind() {
+            isahc::error::ErrorKind::BadClientCertificate
+            | isahc::error::ErrorKind::BadServerCertificate
+            | isahc::error::ErrorKind::TlsEngine => WitnetHttpError::TlsError { msg },
+            _ => WitnetHttpError::HttpRequestError { msg },
+        }
+    }
+}
+
+/// Timeouts applied by a `WitnetHttpClient` to every request. `None` means no timeout.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct WitnetHttpTimeouts {
+    /// Maximum time to wait for the connection to the server (or to the proxy) to be established.
+    pub connect: Option<Duration>,
+    /// Maximum time to wait without receiving any data once the connection is established. A
+    /// response that keeps streaming data, however slowly, never triggers this timeout.
+    pub read: Option<Duration>,
+}
+
+/// How a `WitnetHttpClient` resolves the addresses of the hosts it connects to. The default is to
+/// rely on the system resolver.
+#[derive(Clone, Debug, Default)]
+pub struct WitnetHttpResolver {
+    /// Only connect over the given IP version. `None` means using any of them.
+    pub ip_version: Option<IpVersion>,
+    /// Fixed addresses for some hosts and ports, which are then never resolved through DNS.
+    pub overrides: Vec<(String, u16, IpAddr)>,
+}
+
+/// Minimum TLS version that a `WitnetHttpClient` can require from servers.
+///
+/// isahc does not expose the minimum TLS version setting of libcurl (`CURLOPT_SSLVERSION`), so it
+/// is enforced by only offering cipher suites that require that version in the handshake.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TlsVersion {
+    /// TLS 1.2 or later.
+    V1_2,
+}
+
+/// Cipher suites to offer so as to require `min_tls_version`, if any.
+fn tls_ciphers(min_tls_version: Option<TlsVersion>) -> Option<&'static [&'static str]> {
+    match min_tls_version? {
+        TlsVersion::V1_2 => Some(&TLS_1_2_CIPHERS),
+    }
+}
+
 impl WitnetHttpClient {
     /// Create a new `WitnetHttpClient`
     pub fn new(
         proxy: impl Into<Option<isahc::http::Uri>>,
         follow_redirects: bool,
+    ) -> Result<Self, WitnetHttpError> {
+        Self::with_timeouts(proxy, follow_redirects, WitnetHttpTimeouts::default())
+    }
+
+    /// Create a new `WitnetHttpClient` that applies the provided connect and read timeouts
+    pub fn with_timeouts(
+        proxy: impl Into<Option<isahc::http::Uri>>,
+        follow_redirects: bool,
+        timeouts: WitnetHttpTimeouts,
+    ) -> Result<Self, WitnetHttpError> {
+        Self::with_settings(
+            proxy,
+            follow_redirects,
+            timeouts,
+            WitnetHttpResolver::default(),
+            None,
+        )
+    }
+
+    /// Create a new `WitnetHttpClient` that applies the provided timeouts, resolves hosts as told
+    /// by `resolver` and refuses servers that do not support `min_tls_version`
+    pub fn with_settings(
+        proxy: impl Into<Option<isahc::http::Uri>>,
+        follow_redirects: bool,
+        timeouts: WitnetHttpTimeouts,
+        resolver: WitnetHttpResolver,
+        min_tls_version: Option<TlsVersion>,
     ) -> Result<Self, WitnetHttpError> {
         // Build an `isahc::HttpClient`. Will use the proxy URI, if any
-        let client = isahc::HttpClient::builder()
-            .proxy(proxy)
-            .redirect_policy(if follow_redirects {
-                RedirectPolicy::Limit(MAX_REDIRECTS)
-            } else {
-                RedirectPolicy::None
-            })
+        let mut builder =
+            isahc::HttpClient::builder()
+                .proxy(proxy)
+                .redirect_policy(if follow_redirects {
+                    RedirectPolicy::Limit(MAX_REDIRECTS)
+                } else {
+                    RedirectPolicy::None
+                });
+        if let Some(connect_timeout) = timeouts.connect {
+            builder = builder.connect_timeout(connect_timeout);
+        }
+        if let Some(read_timeout) = timeouts.read {
+            // Abort the transfer if it stays below 1 byte per second for the whole period
+            builder = builder.low_speed_timeout(1, read_timeout);
+        }
+        if let Some(ip_version) = resolver.ip_version {
+            builder = builder.ip_version(ip_version);
+        }
+        if !resolver.overrides.is_empty() {
+            let resolve_map = resolver
+                .overrides
+                .into_iter()
+                .fold(ResolveMap::new(), |resolve_map, (host, port, address)| {
+                    resolve_map.add(host, port, address)
+                });
+            builder = builder.dns_resolve(resolve_map);
+        }
+        if let Some(ciphers) = tls_ciphers(min_tls_version) {
+            builder = builder.ssl_ciphers(ciphers.iter().copied());
+        }
+        let client = builder
             .build()
             .map_err(|err| WitnetHttpError::ClientBuildError {
                 msg: err.to_string(),
             })?;
 
-        Ok(Self { client })
+        Ok(Self {
+            client,
+            min_tls_version,
+        })
+    }
+
+    /// Minimum TLS version that this client requires from servers, if any.
+    pub fn min_tls_version(&self) -> Option<TlsVersion> {
+        self.min_tls_version
     }
 }
 
@@ -136,6 +280,33 @@ impl WitnetHttpRequest {
             req: f(Builder::new())?,
         })
     }
+
+    /// Get the headers of this request.
+    pub fn headers(&self) -> &http::HeaderMap {
+        self.req.headers()
+    }
+
+    /// Get the method of this request.
+    pub fn method(&self) -> &http::Method {
+        self.req.method()
+    }
+
+    /// Get the URI of this request.
+    pub fn uri(&self) -> &http::Uri {
+        self.req.uri()
+    }
+
+    /// Consume this request and read its whole body.
+    pub async fn into_body_bytes(self) -> Result<Vec<u8>, WitnetHttpError> {
+        let mut body = Vec::new();
+        self.req
+            .into_body()
+            .read_to_end(&mut body)
+            .await
+            .map_err(|e| WitnetHttpError::TakeBodyError { msg: e.to_string() })?;
+
+        Ok(body)
+    }
 }
 
 impl From<isahc::Request<isahc::AsyncBody>> for WitnetHttpRequest {
@@ -155,6 +326,12 @@ impl WitnetHttpResponse {
     pub fn inner(self) -> isahc::Response<isahc::AsyncBody> {
         self.res
     }
+
+    /// Get the URI that this response was ultimately served from, which differs from the URI of
+    /// the request if any redirects were followed.
+    pub fn effective_uri(&self) -> Option<&http::Uri> {
+        self.res.effective_uri()
+    }
 }
 
 impl From<isahc::Response<isahc::AsyncBody>> for WitnetHttpResponse {
@@ -201,3 +378,170 @@ impl From<WitnetHttpVersion> for isahc::http::version::Version {
         version.version
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{Read, Write},
+        net::{TcpListener, TcpStream},
+        time::Instant,
+    };
+
+    use super::*;
+
+    /// Accept connections and reply to every request after `delay` with an empty response.
+    fn serve_after(delay: Duration) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                std::thread::spawn(move || {
+                    let mut buf = [0u8; 1024];
+                    let _ = stream.read(&mut buf);
+                    std::thread::sleep(delay);
+                    let _ = stream.write_all(
+                        b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                    );
+                });
+            }
+        });
+
+        url
+    }
+
+    fn get(client: &WitnetHttpClient, url: &str) -> Result<WitnetHttpResponse, WitnetHttpError> {
+        let request = WitnetHttpRequest::build(|builder| {
+            builder.method("GET").uri(url).body(WitnetHttpBody::empty())
+        })
+        .unwrap();
+
+        futures::executor::block_on(client.send(request))
+    }
+
+    #[test]
+    fn read_timeout_triggers_on_slow_response() {
+        let url = serve_after(Duration::from_secs(10));
+        let client = WitnetHttpClient::with_timeouts(
+            None,
+            false,
+            WitnetHttpTimeouts {
+                connect: Some(Duration::from_secs(10)),
+                read: Some(Duration::from_secs(1)),
+            },
+        )
+        .unwrap();
+
+        let start = Instant::now();
+        assert!(get(&client, &url).is_err());
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn connect_timeout_does_not_apply_to_reads() {
+        let url = serve_after(Duration::from_millis(1500));
+        let client = WitnetHttpClient::with_timeouts(
+            None,
+            false,
+            WitnetHttpTimeouts {
+                connect: Some(Duration::from_millis(500)),
+                read: Some(Duration::from_secs(10)),
+            },
+        )
+        .unwrap();
+
+        assert!(get(&client, &url).is_ok());
+    }
+
+    #[test]
+    fn connect_timeout_triggers_on_unreachable_host() {
+        // Fill the backlog of a listener that never accepts, so that further connections to it
+        // hang until the connect timeout kicks in
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let mut pending = vec![];
+        while let Ok(stream) = TcpStream::connect_timeout(&address, Duration::from_millis(100)) {
+            pending.push(stream);
+            assert!(
+                pending.len() < 10_000,
+                "the listener backlog never fills up"
+            );
+        }
+
+        let url = format!("http://{}/", address);
+        let client = WitnetHttpClient::with_timeouts(
+            None,
+            false,
+            WitnetHttpTimeouts {
+                connect: Some(Duration::from_secs(1)),
+                read: Some(Duration::from_secs(30)),
+            },
+        )
+        .unwrap();
+
+        let start = Instant::now();
+        assert!(get(&client, &url).is_err());
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    /// Accept a single connection and return the cipher suites offered in its TLS ClientHello.
+    fn offered_cipher_suites(client: &WitnetHttpClient) -> Vec<u16> {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("https://{}/", listener.local_addr().unwrap());
+
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            // Record header (5 bytes) followed by the handshake message
+            let mut header = [0u8; 5];
+            stream.read_exact(&mut header).unwrap();
+            let mut hello = vec![0u8; usize::from(u16::from_be_bytes([header[3], header[4]]))];
+            stream.read_exact(&mut hello).unwrap();
+
+            // Skip handshake header (4), client version (2) and random (32), then the session id
+            let session_id_len = usize::from(hello[38]);
+            let suites = &hello[39 + session_id_len..];
+            let suites_len = usize::from(u16::from_be_bytes([suites[0], suites[1]]));
+
+            suites[2..2 + suites_len]
+                .chunks(2)
+                .map(|suite| u16::from_be_bytes([suite[0], suite[1]]))
+                .collect()
+        });
+
+        // The handshake never completes, as the server hangs up after reading the ClientHello
+        assert!(get(client, &url).is_err());
+
+        server.join().unwrap()
+    }
+
+    #[test]
+    fn min_tls_version_does_not_offer_tls_1_1_ciphers() {
+        // Cipher suites that TLS 1.1 and lower can negotiate and are offered by default
+        let legacy_suites = [
+            0x000a, 0x002f, 0x0033, 0x0035, 0x0039, 0xc009, 0xc00a, 0xc013, 0xc014,
+        ];
+        let client = |min_tls_version| {
+            WitnetHttpClient::with_settings(
+                None,
+                false,
+                WitnetHttpTimeouts::default(),
+                WitnetHttpResolver::default(),
+                min_tls_version,
+            )
+            .unwrap()
+        };
+
+        let default_client = client(None);
+        assert_eq!(default_client.min_tls_version(), None);
+        assert!(offered_cipher_suites(&default_client)
+            .iter()
+            .any(|suite| legacy_suites.contains(suite)));
+
+        let tls_1_2_client = client(Some(TlsVersion::V1_2));
+        assert_eq!(tls_1_2_client.min_tls_version(), Some(TlsVersion::V1_2));
+        let suites = offered_cipher_suites(&tls_1_2_client);
+        assert!(!suites.is_empty());
+        assert!(!suites.iter().any(|suite| legacy_suites.contains(suite)));
+    }
+}
@@ -23,6 +23,9 @@ pub enum Error {
     /// The error ocurred when serializaing the request params to json.
     #[fail(display = "request params failed to serialize to json")]
     SerializeFailed(#[cause] JsonError),
+    /// The response does not match the type expected for the method.
+    #[fail(display = "response failed to deserialize from json: {}", _0)]
+    DeserializeFailed(#[cause] JsonError),
     /// The request timed out after the given duration.
     #[fail(display = "request timed out after {} milliseconds", _0)]
     RequestTimedOut(u128),
@@ -15,7 +15,7 @@ use async_jsonrpc_client::{
 };
 use futures::StreamExt;
 use futures_util::compat::Compat01As03;
-use rand::seq::SliceRandom;
+use rand::{seq::SliceRandom, Rng};
 use serde::Serialize;
 use serde_json::value;
 
@@ -25,11 +25,54 @@ use super::Error;
 
 const DEFAULT_BACKOFF_TIME_MILLIS: u64 = 250;
 const MAX_BACKOFF_TIME_MILLIS: u64 = 15_000;
+const DEFAULT_BACKOFF_JITTER_MILLIS: u64 = 250;
+
+/// Settings for the time to wait between reconnection attempts.
+///
+/// The backoff time starts at `initial` and grows by 25% after every reconnection attempt, up to
+/// `max`, until a request succeeds. A random delay of up to `jitter` is added on top of it, so that
+/// many clients that lose their connection at the same time do not retry in lockstep.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Backoff {
+    /// Backoff time before the first reconnection attempt.
+    pub initial: Duration,
+    /// Upper bound of the backoff time, not counting the jitter.
+    pub max: Duration,
+    /// Upper bound of the random delay added to the backoff time.
+    pub jitter: Duration,
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self {
+            initial: Duration::from_millis(DEFAULT_BACKOFF_TIME_MILLIS),
+            max: Duration::from_millis(MAX_BACKOFF_TIME_MILLIS),
+            jitter: Duration::from_millis(DEFAULT_BACKOFF_JITTER_MILLIS),
+        }
+    }
+}
+
+impl Backoff {
+    /// Backoff time that follows `time` after a failed reconnection attempt.
+    fn increase(&self, time: Duration) -> Duration {
+        cmp::min(time * 125 / 100, self.max)
+    }
+
+    /// Add a random delay of up to `jitter` to a backoff time.
+    fn with_jitter(&self, time: Duration) -> Duration {
+        time + self
+            .jitter
+            .mul_f64(rand::thread_rng().gen_range(0f64..=1f64))
+    }
+}
 
 /// Represents a JSONRPC client connection, and wraps some related metadata.
 struct Connection {
-    /// Current backoff time (seconds between reconnection attempts).
+    /// Current backoff time (seconds between reconnection attempts), without jitter.
     backoff: Duration,
+    /// Minimum time since the last reconnection attempt before trying again, i.e. `backoff`
+    /// plus jitter.
+    cooldown: Duration,
     /// The TCP Socket for the connection.
     socket: TcpSocket,
     /// Used to calculate the time since the last reconnection, and prevent multiple reconnections
@@ -48,6 +91,7 @@ pub struct JsonRpcClient {
     pending_subscriptions: HashMap<String, Subscribe>,
     urls: Vec<String>,
     connection: Connection,
+    backoff: Backoff,
 }
 
 impl JsonRpcClient {
@@ -62,6 +106,16 @@ impl JsonRpcClient {
     pub fn start_with_subscriptions(
         urls: Vec<String>,
         subscriptions: Arc<Mutex<HashMap<String, Subscribe>>>,
+    ) -> Result<Addr<JsonRpcClient>, Error> {
+        Self::start_with_backoff(urls, subscriptions, Backoff::default())
+    }
+
+    /// Start JSON-RPC async client actor providing the URL of the server, some subscriptions, and
+    /// the settings for the time to wait between reconnection attempts.
+    pub fn start_with_backoff(
+        urls: Vec<String>,
+        subscriptions: Arc<Mutex<HashMap<String, Subscribe>>>,
+        backoff: Backoff,
     ) -> Result<Addr<JsonRpcClient>, Error> {
         log::info!("Configuring JSONRPC client with URLs: {:?}", &urls);
         let timestamp = Instant::now();
@@ -79,11 +133,13 @@ impl JsonRpcClient {
             pending_subscriptions: Default::default(),
             urls,
             connection: Connection {
-                backoff: Duration::from_millis(DEFAULT_BACKOFF_TIME_MILLIS),
+                backoff: backoff.initial,
+                cooldown: backoff.with_jitter(backoff.initial),
                 socket,
                 timestamp,
                 url,
             },
+            backoff,
         };
 
         Ok(Actor::start(client))
@@ -93,7 +149,7 @@ impl JsonRpcClient {
     pub fn reconnect(&mut self, ctx: &mut <Self as Actor>::Context) {
         let timestamp = Instant::now();
         // Apply exponential back-off on retries
-        let reconnection_cooldown = self.connection.backoff;
+        let reconnection_cooldown = self.connection.cooldown;
         if timestamp.duration_since(self.connection.timestamp) < reconnection_cooldown {
             log::debug!(
                 "Ignoring reconnect request: last reconnection attempt was less than {} seconds ago", reconnection_cooldown.as_secs_f32()
@@ -117,6 +173,8 @@ impl JsonRpcClient {
         self.connection.socket = socket;
         self.connection.timestamp = timestamp;
         self.connection.url = url;
+        // Wait longer before the next attempt in case this one fails too
+        self.increase_backoff_time();
 
         // Recover active subscriptions
         let active_subscriptions = self
@@ -190,23 +248,22 @@ impl JsonRpcClient {
     }
 
     fn increase_backoff_time(&mut self) {
-        let time = core::cmp::min(
-            self.connection.backoff * 125 / 100,
-            Duration::from_millis(MAX_BACKOFF_TIME_MILLIS),
-        );
-        self.set_backoff_time(time);
+        self.set_backoff_time(self.backoff.increase(self.connection.backoff));
     }
 
     fn reset_backoff_time(&mut self) {
-        self.set_backoff_time(Duration::from_millis(DEFAULT_BACKOFF_TIME_MILLIS));
+        if self.connection.backoff != self.backoff.initial {
+            self.set_backoff_time(self.backoff.initial);
+        }
     }
 
     fn set_backoff_time(&mut self, time: Duration) {
+        self.connection.backoff = time;
+        self.connection.cooldown = self.backoff.with_jitter(time);
         log::trace!(
             "Connection backoff time is now set to {} seconds",
-            time.as_secs_f32()
+            self.connection.cooldown.as_secs_f32()
         );
-        self.connection.backoff = time;
     }
 }
 
@@ -327,8 +384,7 @@ impl Handler<Request> for JsonRpcClient {
                 .map_err(|err| {
                     log::error!("JSONRPC Request error: {:?}", err);
                     if is_connection_error(&err) {
-                        // Backoff time is increased
-                        act.increase_backoff_time();
+                        // Backoff time is increased on every reconnection attempt
                         act.reconnect(ctx);
                     }
 
@@ -340,13 +396,39 @@ impl Handler<Request> for JsonRpcClient {
     }
 }
 
+/// Predicate that tells whether a notification must be forwarded to the recipient of a
+/// subscription.
+pub type NotificationFilter = Arc<dyn Fn(&Value) -> bool + Send + Sync>;
+
 /// A message representing a subscription to notifications.
 ///
 /// This ties together:
 /// - The JSONRPC request that needs to be sent to the server for initiating the subscription.
 /// - A `Recipient` for JSONRPC notifications.
+/// - An optional filter, so that only the notifications that match it reach the `Recipient`.
 #[derive(Clone)]
-pub struct Subscribe(pub Request, pub Recipient<NotifySubscriptionTopic>);
+pub struct Subscribe(
+    pub Request,
+    pub Recipient<NotifySubscriptionTopic>,
+    pub Option<NotificationFilter>,
+);
+
+impl Subscribe {
+    /// Create a subscription that forwards every notification to `recipient`.
+    pub fn new(request: Request, recipient: Recipient<NotifySubscriptionTopic>) -> Self {
+        Self(request, recipient, None)
+    }
+
+    /// Only forward the notifications for which `filter` returns `true`. Other notifications are
+    /// dropped by the client, without ever being sent to the recipient.
+    pub fn filter<F>(mut self, filter: F) -> Self
+    where
+        F: Fn(&Value) -> bool + Send + Sync + 'static,
+    {
+        self.2 = Some(Arc::new(filter));
+        self
+    }
+}
 
 impl Message for Subscribe {
     type Result = ();
@@ -438,11 +520,15 @@ impl StreamHandler<Result<NotifySubscriptionId, Error>> for JsonRpcClient {
                 value,
             }) => {
                 if let Ok(subscriptions) = (*self.active_subscriptions).lock() {
-                    if let Some(Subscribe(ref request, ref recipient)) =
+                    if let Some(Subscribe(ref request, ref recipient, ref filter)) =
                         subscriptions.get(&subscription_id)
                     {
                         let topic = subscription_topic_from_request(request);
-                        recipient.do_send(NotifySubscriptionTopic { topic, value });
+                        if filter.as_ref().map_or(true, |filter| filter(&value)) {
+                            recipient.do_send(NotifySubscriptionTopic { topic, value });
+                        } else {
+                            log::trace!("Filtered out {} notification: {:?}", topic, value);
+                        }
                     }
                 }
             }
@@ -508,8 +594,201 @@ where
 
 #[cfg(test)]
 mod tests {
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+    };
+
     use super::*;
 
+    /// Drop the first `rejected` connections right after accepting them, and answer every request
+    /// received through the next ones with a `null` result. Return the address of the server and
+    /// the instants at which connections were accepted.
+    fn serve_after_rejecting(rejected: usize) -> (String, Arc<Mutex<Vec<Instant>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        let accepted = Arc::new(Mutex::new(vec![]));
+
+        let accepted_clone = accepted.clone();
+        std::thread::spawn(move || {
+            for (i, stream) in listener.incoming().enumerate() {
+                let mut stream = stream.unwrap();
+                accepted_clone.lock().unwrap().push(Instant::now());
+                if i < rejected {
+                    continue;
+                }
+                std::thread::spawn(move || {
+                    let reader = BufReader::new(stream.try_clone().unwrap());
+                    for line in reader.lines() {
+                        let request: Value = match line.map(|line| serde_json::from_str(&line)) {
+                            Ok(Ok(request)) => request,
+                            _ => return,
+                        };
+                        let response = serde_json::json!({
+                            "jsonrpc": "2.0",
+                            "result": null,
+                            "id": request["id"],
+                        });
+                        if writeln!(stream, "{}", response).is_err() {
+                            return;
+                        }
+                    }
+                });
+            }
+        });
+
+        (address, accepted)
+    }
+
+    /// Answer any subscription request with a subscription ID, and then send a notification with
+    /// each of `values` through that subscription. Return the address of the server.
+    fn serve_notifications(values: Vec<Value>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let values = values.clone();
+                std::thread::spawn(move || {
+                    let reader = BufReader::new(stream.try_clone().unwrap());
+                    for line in reader.lines() {
+                        let request: Value = match line.map(|line| serde_json::from_str(&line)) {
+                            Ok(Ok(request)) => request,
+                            _ => return,
+                        };
+                        let response = serde_json::json!({
+                            "jsonrpc": "2.0",
+                            "result": "1",
+                            "id": request["id"],
+                        });
+                        writeln!(stream, "{}", response).unwrap();
+                        // Give the client some time to start listening to the subscription
+                        std::thread::sleep(Duration::from_millis(200));
+                        for value in &values {
+                            let notification = serde_json::json!({
+                                "jsonrpc": "2.0",
+                                "method": "witnet_subscription",
+                                "params": {"result": value, "subscription": "1"},
+                            });
+                            writeln!(stream, "{}", notification).unwrap();
+                        }
+                    }
+                });
+            }
+        });
+
+        address
+    }
+
+    /// Actor that stores the values of the notifications that it receives.
+    struct Collector(Arc<Mutex<Vec<Value>>>);
+
+    impl Actor for Collector {
+        type Context = Context<Self>;
+    }
+
+    impl Handler<NotifySubscriptionTopic> for Collector {
+        type Result = ();
+
+        fn handle(&mut self, msg: NotifySubscriptionTopic, _ctx: &mut Self::Context) {
+            self.0.lock().unwrap().push(msg.value);
+        }
+    }
+
+    #[test]
+    fn backoff_is_capped() {
+        let backoff = Backoff {
+            initial: Duration::from_millis(100),
+            max: Duration::from_millis(200),
+            jitter: Duration::from_millis(50),
+        };
+
+        let micros: Vec<_> =
+            std::iter::successors(Some(backoff.initial), |time| Some(backoff.increase(*time)))
+                .take(6)
+                .map(|time| time.as_micros())
+                .collect();
+        assert_eq!(
+            micros,
+            [100_000, 125_000, 156_250, 195_312, 200_000, 200_000]
+        );
+
+        for _ in 0..1_000 {
+            let time = backoff.with_jitter(backoff.max);
+            assert!(time >= backoff.max && time <= backoff.max + backoff.jitter);
+        }
+    }
+
+    #[test]
+    fn reconnection_attempts_are_increasingly_delayed() {
+        let rejected = 5;
+        let (address, accepted) = serve_after_rejecting(rejected);
+        let backoff = Backoff {
+            initial: Duration::from_millis(100),
+            max: Duration::from_millis(200),
+            jitter: Duration::ZERO,
+        };
+
+        actix::System::new().block_on(async move {
+            let client =
+                JsonRpcClient::start_with_backoff(vec![address], Default::default(), backoff)
+                    .unwrap();
+
+            // Keep sending requests, as every failed one triggers a reconnection attempt
+            loop {
+                let request = Request::method("syncStatus").timeout(Duration::from_millis(200));
+                if let Ok(Ok(_)) = client.send(request).await {
+                    break;
+                }
+                actix::clock::sleep(Duration::from_millis(10)).await;
+            }
+        });
+
+        let accepted = accepted.lock().unwrap();
+        assert_eq!(accepted.len(), rejected + 1);
+        let expected =
+            std::iter::successors(Some(backoff.initial), |time| Some(backoff.increase(*time)));
+        for (instants, min_delay) in accepted.windows(2).zip(expected) {
+            let delay = instants[1].duration_since(instants[0]);
+            assert!(
+                delay >= min_delay,
+                "reconnected after {:?}, expected at least {:?}",
+                delay,
+                min_delay
+            );
+        }
+    }
+
+    #[test]
+    fn filtered_notifications_do_not_reach_the_recipient() {
+        let address = serve_notifications((1..=5).map(|n| serde_json::json!({ "n": n })).collect());
+        let received = Arc::new(Mutex::new(vec![]));
+
+        let received_clone = received.clone();
+        actix::System::new().block_on(async move {
+            let client = JsonRpcClient::start(&address).unwrap();
+            let recipient = Collector(received_clone.clone()).start().recipient();
+            let request = Request::method("witnet_subscribe").value(serde_json::json!(["blocks"]));
+            client.do_send(
+                Subscribe::new(request, recipient)
+                    .filter(|value| value["n"].as_u64().unwrap_or_default() % 2 == 1),
+            );
+
+            // Wait for as many notifications as there are matching ones, so that any notification
+            // that is not filtered out shows up in the assertion below
+            let deadline = Instant::now() + Duration::from_secs(5);
+            while received_clone.lock().unwrap().len() < 3 && Instant::now() < deadline {
+                actix::clock::sleep(Duration::from_millis(10)).await;
+            }
+        });
+
+        assert_eq!(
+            *received.lock().unwrap(),
+            [1, 3, 5].map(|n| serde_json::json!({ "n": n }))
+        );
+    }
+
     #[test]
     fn pick_random_from_empty_list() {
         let list = Vec::<()>::new();
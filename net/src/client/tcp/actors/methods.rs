@@ -0,0 +1,172 @@
+//! Typed wrappers around the JSON-RPC methods of a Witnet node.
+//!
+//! Sending a `Request` to a `JsonRpcClient` takes the name of the method as a string and returns
+//! an untyped JSON value, so a typo in either of them only shows up at runtime. The functions in
+//! this module fix the method names and deserialize the responses into the types used by the node.
+use actix::Addr;
+use serde::de::DeserializeOwned;
+use witnet_data_structures::{
+    chain::{Epoch, PublicKeyHash, SyncStatus},
+    transaction_factory::NodeBalance,
+};
+
+use super::{
+    jsonrpc::{JsonRpcClient, Request},
+    Error,
+};
+
+/// Name of the method that tells the synchronization status of the node.
+pub const SYNC_STATUS: &str = "syncStatus";
+/// Name of the method that lists the hashes of the blocks in the chain.
+pub const GET_BLOCK_CHAIN: &str = "getBlockChain";
+/// Name of the method that tells the balance of an address.
+pub const GET_BALANCE: &str = "getBalance";
+
+/// Send a request through a `JsonRpcClient` and deserialize the response into `T`.
+pub async fn call<T: DeserializeOwned>(
+    client: &Addr<JsonRpcClient>,
+    request: Request,
+) -> Result<T, Error> {
+    let value = client.send(request).await??;
+
+    serde_json::from_value(value).map_err(Error::DeserializeFailed)
+}
+
+/// Get the synchronization status of the node.
+pub async fn sync_status(client: &Addr<JsonRpcClient>) -> Result<SyncStatus, Error> {
+    call(client, Request::method(SYNC_STATUS)).await
+}
+
+/// Get the `(epoch, block_hash)` pairs of the blocks in the chain. A negative `epoch` counts from
+/// the last epoch, and a negative `limit` returns the last blocks in the range. A `limit` of 0
+/// means no limit.
+pub async fn get_block_chain(
+    client: &Addr<JsonRpcClient>,
+    epoch: i64,
+    limit: i64,
+) -> Result<Vec<(Epoch, String)>, Error> {
+    let request = Request::method(GET_BLOCK_CHAIN).value(serde_json::json!({
+        "epoch": epoch,
+        "limit": limit,
+    }));
+
+    call(client, request).await
+}
+
+/// Get the balance of an address. If `simple` is set, the node does not tell apart the confirmed
+/// balance from the total balance.
+pub async fn get_balance(
+    client: &Addr<JsonRpcClient>,
+    pkh: &PublicKeyHash,
+    simple: bool,
+) -> Result<NodeBalance, Error> {
+    let request = Request::method(GET_BALANCE).value(serde_json::json!([pkh.to_string(), simple]));
+
+    call(client, request).await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+    };
+
+    use serde_json::{json, Value};
+    use witnet_data_structures::chain::StateMachine;
+
+    use super::*;
+
+    /// Answer every request with the canned result for its method, or with a "method not found"
+    /// error if there is none. Return the address of the server.
+    fn serve_canned(responses: HashMap<&'static str, Value>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let responses = responses.clone();
+                std::thread::spawn(move || {
+                    let reader = BufReader::new(stream.try_clone().unwrap());
+                    for line in reader.lines() {
+                        let request: Value = match line.map(|line| serde_json::from_str(&line)) {
+                            Ok(Ok(request)) => request,
+                            _ => return,
+                        };
+                        let method = request["method"].as_str().unwrap_or_default();
+                        let response = match responses.get(method) {
+                            Some(result) => json!({
+                                "jsonrpc": "2.0",
+                                "result": result,
+                                "id": request["id"],
+                            }),
+                            None => json!({
+                                "jsonrpc": "2.0",
+                                "error": {"code": -32601, "message": "Method not found"},
+                                "id": request["id"],
+                            }),
+                        };
+                        if writeln!(stream, "{}", response).is_err() {
+                            return;
+                        }
+                    }
+                });
+            }
+        });
+
+        address
+    }
+
+    #[test]
+    fn typed_methods_deserialize_responses() {
+        let hash = "1111111111111111111111111111111111111111111111111111111111111111";
+        let address = serve_canned(
+            [
+                (
+                    SYNC_STATUS,
+                    json!({
+                        "chain_beacon": {"checkpoint": 42, "hashPrevBlock": hash},
+                        "current_epoch": 43,
+                        "node_state": "Synced",
+                    }),
+                ),
+                (GET_BLOCK_CHAIN, json!([[41, hash], [42, hash]])),
+                (GET_BALANCE, json!({"confirmed": 10, "total": 15})),
+            ]
+            .into_iter()
+            .collect(),
+        );
+
+        actix::System::new().block_on(async move {
+            let client = JsonRpcClient::start(&address).unwrap();
+
+            let status = sync_status(&client).await.unwrap();
+            assert_eq!(status.chain_beacon.checkpoint, 42);
+            assert_eq!(status.current_epoch, Some(43));
+            assert_eq!(status.node_state, StateMachine::Synced);
+
+            let block_chain = get_block_chain(&client, -2, 0).await.unwrap();
+            assert_eq!(
+                block_chain,
+                vec![(41, String::from(hash)), (42, String::from(hash))]
+            );
+
+            let balance = get_balance(&client, &PublicKeyHash::default(), false)
+                .await
+                .unwrap();
+            assert_eq!(
+                balance,
+                NodeBalance::One {
+                    confirmed: Some(10),
+                    total: 15
+                }
+            );
+
+            // Responses that do not match the expected type are reported as such
+            let result: Result<Vec<u64>, _> = call(&client, Request::method(SYNC_STATUS)).await;
+            assert!(matches!(result, Err(Error::DeserializeFailed(_))));
+        });
+    }
+}
@@ -2,6 +2,7 @@
 
 pub mod error;
 pub mod jsonrpc;
+pub mod methods;
 
 pub use error::Error;
 pub use jsonrpc::JsonRpcClient;
@@ -47,11 +47,13 @@ use crate::{
             GetBlocksEpochRange, GetConsolidatedPeers, GetDataRequestInfo, GetEpoch,
             GetHighestCheckpointBeacon, GetItemBlock, GetItemSuperblock, GetItemTransaction,
             GetKnownPeers, GetMemoryTransaction, GetMempool, GetNodeStats, GetProtocolInfo,
-            GetReputation, GetSignalingInfo, GetState, GetSupplyInfo, GetSupplyInfo2, GetUtxoInfo,
-            InitializePeers, IsConfirmedBlock, MagicEither, QueryStakePowers, QueryStakes,
-            QueryStakesFilter, Rewind, SnapshotExport, SnapshotImport, StakeAuthorization,
+            GetReputation, GetRetrievalStats, GetSignalingInfo, GetState, GetSupplyInfo,
+            GetSupplyInfo2, GetUtxoInfo, InitializePeers, IsConfirmedBlock, MagicEither,
+            QueryStakePowers, QueryStakes, QueryStakesFilter, Rewind, SnapshotExport,
+            SnapshotImport, StakeAuthorization,
         },
         peers_manager::PeersManager,
+        rad_manager::RadManager,
         sessions_manager::SessionsManager,
     },
     config_mngr, signature_mngr,
@@ -132,6 +134,9 @@ pub fn attach_regular_methods<H>(
         "nodeStats",
         |_params: Params| Box::pin(node_stats()),
     );
+    server.add_actix_method(system, "retrievalStats", |_params: Params| {
+        Box::pin(retrieval_stats())
+    });
     server.add_actix_method(system, "getMempool", |params: Params| {
         Box::pin(get_mempool(params.parse()))
     });
@@ -1725,6 +1730,19 @@ pub async fn node_stats() -> JsonRpcResult {
         .await
 }
 
+/// Get the counts of successful and failed data retrievals, the latter grouped by error category
+pub async fn retrieval_stats() -> JsonRpcResult {
+    let rad_manager_addr = RadManager::from_registry();
+
+    let stats = rad_manager_addr
+        .send(GetRetrievalStats)
+        .await
+        .map_err(internal_error)?
+        .map_err(internal_error_s)?;
+
+    serde_json::to_value(stats).map_err(internal_error_s)
+}
+
 /// Get all the pending transactions
 pub async fn get_mempool(params: Result<(), Error>) -> JsonRpcResult {
     match params {
@@ -2676,6 +2694,7 @@ mod tests {
                 "nodeStats",
                 "peers",
                 "priority",
+                "retrievalStats",
                 "rewind",
                 "sendRequest",
                 "sendValue",
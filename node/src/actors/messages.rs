@@ -42,7 +42,7 @@ use witnet_p2p::{
     error::SessionsError,
     sessions::{GetConsolidatedPeersResult, SessionStatus, SessionType},
 };
-use witnet_rad::{error::RadError, types::RadonTypes};
+use witnet_rad::{error::RadError, metrics::RetrievalStats, types::RadonTypes};
 
 use crate::{
     actors::{
@@ -1082,6 +1082,13 @@ pub struct ResolveRA {
     pub too_many_witnesses: bool,
 }
 
+/// Message for getting the counts of retrieval outcomes from the `RadManager`.
+pub struct GetRetrievalStats;
+
+impl Message for GetRetrievalStats {
+    type Result = Result<RetrievalStats, failure::Error>;
+}
+
 /// Message for running the tally step of a data request.
 #[derive(Debug)]
 pub struct RunTally {
@@ -1,19 +1,24 @@
 //! Message handlers for `RadManager`
 
-use std::time::Duration;
+use std::{
+    future::Future,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use actix::{Handler, ResponseFuture};
-use futures::FutureExt;
 use witnet_data_structures::radon_report::{RadonReport, ReportContext, RetrievalMetadata, Stage};
 use witnet_rad::{
+    chaining::{has_chained_retrievals, resolve_source_references},
     conditions::{evaluate_tally_precondition_clause, TallyPreconditionClauseResult},
     error::RadError,
+    metrics::{RetrievalCounters, RetrievalMetrics, RetrievalStats},
     script::RadonScriptExecutionSettings,
     types::RadonTypes,
 };
 use witnet_validations::validations::run_tally;
 
-use crate::actors::messages::{ResolveRA, RunTally};
+use crate::actors::messages::{GetRetrievalStats, ResolveRA, RunTally};
 
 use super::RadManager;
 
@@ -21,6 +26,25 @@ use super::RadManager;
 // This value must be lower than half an epoch, and having enough time to broadcasting the commit.
 const MAX_RETRIEVAL_TIMEOUT: Duration = Duration::from_millis(10000);
 
+/// Add a timeout to a source retrieval.
+///
+/// In case of timeout, the response is set to a `RetrieveTimeout` error. Timeouts never reach the
+/// retrieval metrics hook, so they are recorded here.
+async fn with_timeout(
+    retrieval: impl Future<Output = Result<RadonReport<RadonTypes>, RadError>>,
+    timeout: Duration,
+    counters: &RetrievalCounters,
+) -> Result<RadonReport<RadonTypes>, RadError> {
+    tokio::time::timeout(timeout, retrieval)
+        .await
+        .unwrap_or_else(|_| {
+            let timed_out = Err(RadError::RetrieveTimeout);
+            counters.record_retrieval(&timed_out);
+
+            timed_out
+        })
+}
+
 impl Handler<ResolveRA> for RadManager {
     // This must be ResponseFuture, otherwise the actor dies on panic
     type Result = ResponseFuture<Result<RadonReport<RadonTypes>, RadError>>;
@@ -28,6 +52,8 @@ impl Handler<ResolveRA> for RadManager {
     fn handle(&mut self, msg: ResolveRA, _ctx: &mut Self::Context) -> Self::Result {
         // Fetching these values this early makes lifetimes easier for the fut block below
         let witnessing = self.witnessing.clone();
+        let bandwidth = self.bandwidth.clone();
+        let counters = self.retrieval_counters.clone();
 
         // The result of the RAD aggregation is computed asynchronously, because the async block
         // returns a future
@@ -50,29 +76,58 @@ impl Handler<ResolveRA> for RadManager {
             let settings = RadonScriptExecutionSettings::disable_all();
             let retrieval_context =
                 ReportContext::from_stage(Stage::Retrieval(RetrievalMetadata::default()));
-            let retrieve_responses_fut = sources
-                .iter()
-                .map(|retrieve| {
-                    witnet_rad::run_paranoid_retrieval(
-                        retrieve,
-                        aggregate.clone(),
-                        settings,
-                        active_wips.clone(),
-                        witnessing.clone(),
-                    )
-                })
-                .map(|fut| {
-                    tokio::time::timeout(timeout, fut).map(|response| {
-                        // In case of timeout, set response to "RetrieveTimeout" error
-                        response.unwrap_or(Err(RadError::RetrieveTimeout))
-                    })
-                });
-
-            // Perform retrievals in parallel for the sake of synchronization between sources
-            //  (increasing the likeliness of multiple sources returning results that are closer to each
-            //  other).
             let retrieve_responses: Vec<RadonReport<RadonTypes>> =
-                futures::future::join_all(retrieve_responses_fut)
+                if has_chained_retrievals(&sources, &active_wips) {
+                    // Sources that need the results of others are retrieved one after the other,
+                    // sharing the same timeout so that all of them are done in time
+                    let deadline = Instant::now() + timeout;
+                    let mut retrieve_responses = Vec::with_capacity(sources.len());
+                    for source in &sources {
+                        let response = match resolve_source_references(source, &retrieve_responses)
+                        {
+                            Ok(source) => {
+                                with_timeout(
+                                    witnet_rad::run_paranoid_retrieval(
+                                        &source,
+                                        aggregate.clone(),
+                                        settings,
+                                        active_wips.clone(),
+                                        witnessing.clone(),
+                                        bandwidth.clone(),
+                                        Some(counters.clone() as Arc<dyn RetrievalMetrics>),
+                                    ),
+                                    deadline.saturating_duration_since(Instant::now()),
+                                    &counters,
+                                )
+                                .await
+                            }
+                            Err(error) => Err(error),
+                        };
+                        retrieve_responses.push(response.unwrap_or_else(|error| {
+                            RadonReport::from_result(Err(error), &retrieval_context)
+                        }));
+                    }
+
+                    retrieve_responses
+                } else {
+                    // Perform retrievals in parallel for the sake of synchronization between
+                    // sources (increasing the likeliness of multiple sources returning results
+                    // that are closer to each other).
+                    futures::future::join_all(sources.iter().map(|source| {
+                        with_timeout(
+                            witnet_rad::run_paranoid_retrieval(
+                                source,
+                                aggregate.clone(),
+                                settings,
+                                active_wips.clone(),
+                                witnessing.clone(),
+                                bandwidth.clone(),
+                                Some(counters.clone() as Arc<dyn RetrievalMetrics>),
+                            ),
+                            timeout,
+                            &counters,
+                        )
+                    }))
                     .await
                     .into_iter()
                     .map(|retrieve| {
@@ -80,7 +135,8 @@ impl Handler<ResolveRA> for RadManager {
                             RadonReport::from_result(Err(error), &retrieval_context)
                         })
                     })
-                    .collect();
+                    .collect()
+                };
 
             // Evaluate tally precondition to ensure that at least 20% of the data sources are not errors.
             // This stage does not need to evaluate the postcondition.
@@ -128,6 +184,14 @@ impl Handler<ResolveRA> for RadManager {
     }
 }
 
+impl Handler<GetRetrievalStats> for RadManager {
+    type Result = Result<RetrievalStats, failure::Error>;
+
+    fn handle(&mut self, _msg: GetRetrievalStats, _ctx: &mut Self::Context) -> Self::Result {
+        Ok(self.retrieval_counters.snapshot())
+    }
+}
+
 impl Handler<RunTally> for RadManager {
     // This must be ResponseFuture, otherwise the actor dies on panic
     type Result = ResponseFuture<RadonReport<RadonTypes>>;
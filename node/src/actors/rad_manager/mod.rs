@@ -6,8 +6,11 @@
 //! [Data Requests]: https://docs.witnet.io/protocol/data-requests/overview/
 //! [RAD Engine]: https://docs.witnet.io/protocol/data-requests/overview/#the-rad-engine
 
+use std::sync::Arc;
+
 use crate::utils::stop_system_if_panicking;
 use witnet_data_structures::witnessing::WitnessingConfig;
+use witnet_rad::{bandwidth::BandwidthLimiter, metrics::RetrievalCounters};
 
 mod actor;
 mod handlers;
@@ -17,6 +20,10 @@ mod handlers;
 pub struct RadManager {
     /// Contains configuration for witnessing, namely about transports and the paranoid threshold.
     pub witnessing: WitnessingConfig<witnet_rad::Uri>,
+    /// Shared by all retrievals so as to cap their total download rate, if configured.
+    pub bandwidth: Option<Arc<BandwidthLimiter>>,
+    /// Counts the outcomes of all the retrievals performed by this node.
+    pub retrieval_counters: Arc<RetrievalCounters>,
 }
 
 impl RadManager {
@@ -30,7 +37,15 @@ impl RadManager {
 
     /// Construct a `RadManager` from existing witnessing configuration.
     pub fn from_config(config: WitnessingConfig<witnet_rad::Uri>) -> Self {
-        Self { witnessing: config }
+        let bandwidth = config
+            .max_download_rate
+            .map(|rate| Arc::new(BandwidthLimiter::new(rate)));
+
+        Self {
+            witnessing: config,
+            bandwidth,
+            retrieval_counters: Default::default(),
+        }
     }
 }
 
@@ -369,6 +369,13 @@ pub fn create_appropriate_backend(
             let mut options = backends::rocks::Options::default();
             options.create_if_missing(true);
             options.set_max_open_files(conf.max_open_files);
+            if let Some(compression) = conf.compression {
+                options.set_compression_type(match compression {
+                    config::StorageCompression::None => backends::rocks::CompressionType::None,
+                    config::StorageCompression::Lz4 => backends::rocks::CompressionType::Lz4,
+                    config::StorageCompression::Zstd => backends::rocks::CompressionType::Zstd,
+                });
+            }
             let db = backends::rocks::Backend::open(&options, path).map_err(|e| as_failure!(e))?;
 
             wrap_storage_as_nodestorage(db, conf)
@@ -1,4 +1,7 @@
+use std::time::Duration;
+
 use witnet_config::config::Witnessing;
+use witnet_data_structures::witnessing::IpVersion;
 use witnet_node::actors::rad_manager::RadManager;
 use witnet_validations::witnessing::validate_witnessing_config;
 
@@ -11,6 +14,17 @@ fn from_config_test_success_helper(
         allow_unproxied,
         paranoid_percentage: 51,
         proxies,
+        max_download_rate: 0,
+        retry_max_attempts: 1,
+        retry_base_delay: Duration::from_millis(500),
+        require_tls: false,
+        user_agent: None,
+        redacted_headers: vec![],
+        connect_timeout: Duration::from_secs(0),
+        read_timeout: Duration::from_secs(0),
+        ip_version: IpVersion::Any,
+        resolve: vec![],
+        min_tls_version: None,
     }
     .into_config();
     let config = validate_witnessing_config::<String, witnet_rad::Uri>(&config).unwrap();
@@ -35,6 +49,17 @@ fn from_config_test_error_helper(
             allow_unproxied,
             paranoid_percentage: 51,
             proxies,
+            max_download_rate: 0,
+            retry_max_attempts: 1,
+            retry_base_delay: Duration::from_millis(500),
+            require_tls: false,
+            user_agent: None,
+            redacted_headers: vec![],
+            connect_timeout: Duration::from_secs(0),
+            read_timeout: Duration::from_secs(0),
+            ip_version: IpVersion::Any,
+            resolve: vec![],
+            min_tls_version: None,
         }
         .into_config();
         let config = validate_witnessing_config::<String, witnet_rad::Uri>(&config).unwrap();
@@ -72,6 +72,48 @@
 //!     duration: Option<u32>,
 //! }
 //! ```
+//! * You can rename the fields of the generated struct when (de)serializing them with serde,
+//!   without renaming the fields of the original struct:
+//! ```rust
+//! use partial_struct::PartialStruct;
+//! use serde::Deserialize;
+//!
+//! #[derive(PartialStruct)]
+//! #[partial_struct(derive(Deserialize))]
+//! struct Config {
+//!     #[partial_struct(rename = "timeout-ms")]
+//!     timeout: u32,
+//! }
+//! ```
+//! * Generic parameters and their bounds are copied into the generated struct. If the generated
+//!   struct needs different bounds, e.g. because the original ones only make sense for the full
+//!   struct, they can be replaced:
+//! ```rust
+//! use partial_struct::PartialStruct;
+//!
+//! #[derive(PartialStruct)]
+//! #[partial_struct(bound = "T: Clone")]
+//! struct Config<T: Clone + Default> {
+//!     item: T,
+//! }
+//! ```
+//! * The fields of a partial struct can be merged into the full struct, overwriting only those
+//!   that are set:
+//! ```rust
+//! use partial_struct::PartialStruct;
+//!
+//! #[derive(PartialStruct)]
+//! struct Config {
+//!     timeout: u32,
+//!     retries: u32,
+//! }
+//!
+//! let mut config = Config { timeout: 10, retries: 3 };
+//! let partial = PartialConfig { timeout: Some(20), retries: None };
+//! config.apply_partial(partial);
+//!
+//! assert_eq!((config.timeout, config.retries), (20, 3));
+//! ```
 //! * You can combine several options for the fields:
 //! ```rust
 //! use partial_struct::PartialStruct;
@@ -95,7 +137,8 @@ use quote::quote;
 pub fn partial_struct(input: TokenStream) -> TokenStream {
     let ast = syn::parse_macro_input!(input as syn::DeriveInput);
     let output = get_partial(&ast);
-    let result = quote!(#output);
+    let merge = get_merge(&ast);
+    let result = quote!(#output #(#merge)*);
 
     result.into()
 }
@@ -106,7 +149,10 @@ fn get_partial(input: &syn::DeriveInput) -> syn::DeriveInput {
     let (attrs, _) = get_attributes(&input.attrs);
     let data = get_data(&input.data);
     let vis = input.vis.clone();
-    let generics = input.generics.clone();
+    let mut generics = input.generics.clone();
+    if let Some(bound) = get_bound(&input.attrs) {
+        replace_bounds(&mut generics, bound);
+    }
 
     syn::DeriveInput {
         attrs,
@@ -143,6 +189,14 @@ fn get_attributes(orig: &[syn::Attribute]) -> (Vec<syn::Attribute>, Action) {
                                     }
                                     _ => panic!("ty literal is not a string"),
                                 },
+                                "rename" => match params.lit {
+                                    syn::Lit::Str(ref lit) => {
+                                        attrs.push(syn::parse_quote!(#[serde(rename = #lit)]));
+                                    }
+                                    _ => panic!("rename literal is not a string"),
+                                },
+                                // Handled by `get_bound`
+                                "bound" => (),
                                 name => {
                                     panic!("unknown name-value option {}", name);
                                 }
@@ -162,6 +216,59 @@ fn get_attributes(orig: &[syn::Attribute]) -> (Vec<syn::Attribute>, Action) {
     (attrs, action)
 }
 
+/// Get the bounds given to the partial struct with `#[partial_struct(bound = "...")]`, if any.
+fn get_bound(orig: &[syn::Attribute]) -> Option<syn::WhereClause> {
+    let mut bound = None;
+
+    for attr in orig {
+        let meta = match attr.parse_meta() {
+            Ok(syn::Meta::List(meta)) if &*meta.ident.to_string() == "partial_struct" => meta,
+            _ => continue,
+        };
+        for nested in &meta.nested {
+            if let syn::NestedMeta::Meta(syn::Meta::NameValue(params)) = nested {
+                if params.ident != "bound" {
+                    continue;
+                }
+                match params.lit {
+                    syn::Lit::Str(ref lit) => {
+                        let s = format!("where {}", lit.value());
+                        let v = syn::parse_str(&s).expect("bound literal failed to parse");
+                        bound = Some(v);
+                    }
+                    _ => panic!("bound literal is not a string"),
+                }
+            }
+        }
+    }
+
+    bound
+}
+
+/// Replace the bounds of the generic parameters of the partial struct, both inline and in its
+/// `where` clause, with the given ones.
+fn replace_bounds(generics: &mut syn::Generics, bound: syn::WhereClause) {
+    for param in generics.params.iter_mut() {
+        match param {
+            syn::GenericParam::Type(param) => {
+                param.colon_token = None;
+                param.bounds.clear();
+            }
+            syn::GenericParam::Lifetime(param) => {
+                param.colon_token = None;
+                param.bounds.clear();
+            }
+            syn::GenericParam::Const(_) => (),
+        }
+    }
+
+    generics.where_clause = if bound.predicates.is_empty() {
+        None
+    } else {
+        Some(bound)
+    };
+}
+
 /// Get the body that will be used for the partial struct. It iterates
 /// over its fields, and depending on each field attributes one of
 /// these actions will happen:
@@ -193,6 +300,100 @@ fn get_data(orig: &syn::Data) -> syn::Data {
     data
 }
 
+/// Get the implementations of `merge_into` for the partial struct and `apply_partial` for the
+/// struct given as input. Fields are merged depending on their attributes:
+///
+/// * Fields wrapped in an `Option` overwrite the full struct only if they are `Some`
+/// * Skipped fields that are an `Option` overwrite the full struct only if they are `Some`, while
+///   any other skipped fields cannot tell whether they were set, so they are never merged
+/// * Fields set to the same type as in the full struct always overwrite it
+/// * Fields set to any other type are expected to be partial structs themselves, and are merged
+///   into the full struct with their own `merge_into`
+fn get_merge(input: &syn::DeriveInput) -> Vec<syn::ItemImpl> {
+    let fields = match &input.data {
+        syn::Data::Struct(data) => &data.fields,
+        _ => return vec![],
+    };
+
+    let stmts: Vec<_> = fields
+        .iter()
+        .enumerate()
+        .filter_map(|(i, field)| -> Option<syn::Stmt> {
+            let member = match &field.ident {
+                Some(ident) => syn::Member::Named(ident.clone()),
+                None => syn::Member::Unnamed(syn::Index::from(i)),
+            };
+            let orig_ty = &field.ty;
+
+            match get_attributes(&field.attrs).1 {
+                Action::WrapWithOption => Some(syn::parse_quote!(
+                    if let Some(value) = self.#member { full.#member = value; }
+                )),
+                Action::Skip if is_option(orig_ty) => Some(syn::parse_quote!(
+                    if self.#member.is_some() { full.#member = self.#member; }
+                )),
+                Action::Skip => None,
+                Action::ChangeToType(ref ty)
+                    if quote!(#ty).to_string() != quote!(#orig_ty).to_string() =>
+                {
+                    Some(syn::parse_quote!(self.#member.merge_into(&mut full.#member);))
+                }
+                Action::ChangeToType(_) => Some(syn::parse_quote!(full.#member = self.#member;)),
+            }
+        })
+        .collect();
+
+    let ident = &input.ident;
+    let partial_ident = get_ident(ident);
+    // Both structs must be well-formed, so the bounds of both of them apply
+    let mut generics = input.generics.clone();
+    if let Some(bound) = get_bound(&input.attrs) {
+        generics
+            .make_where_clause()
+            .predicates
+            .extend(bound.predicates);
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    vec![
+        syn::parse_quote! {
+            impl #impl_generics #partial_ident #ty_generics #where_clause {
+                /// Overwrite the fields of `full` with the fields of this partial struct that are
+                /// set, leaving the rest of them untouched.
+                #[allow(dead_code, unused_variables)]
+                pub fn merge_into(self, full: &mut #ident #ty_generics) {
+                    #(#stmts)*
+                }
+            }
+        },
+        syn::parse_quote! {
+            impl #impl_generics #ident #ty_generics #where_clause {
+                /// Overwrite the fields of this struct with the fields of `partial` that are set,
+                /// leaving the rest of them untouched.
+                #[allow(dead_code)]
+                pub fn apply_partial(&mut self, partial: #partial_ident #ty_generics) {
+                    partial.merge_into(self);
+                }
+            }
+        },
+    ]
+}
+
+/// Tell whether a type is an `Option`, as far as its name tells.
+fn is_option(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(ty) => {
+            ty.qself.is_none()
+                && ty
+                    .path
+                    .segments
+                    .last()
+                    .map_or(false, |segment| segment.value().ident == "Option")
+        }
+        _ => false,
+    }
+}
+
 /// Get the name that will be used for the partial struct. It just
 /// prepends `Partial` to the given identifier `ident`.
 fn get_ident(ident: &syn::Ident) -> syn::Ident {
@@ -0,0 +1,6 @@
+/// Every file in `tests/ui` must compile and run without panicking.
+#[test]
+fn test_partial_derive_compiles() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/ui/*.rs");
+}
@@ -84,3 +84,66 @@ fn test_partial_attr_partial() {
 
     assert_eq!(p.f, PartialAnotherObj);
 }
+
+#[test]
+fn test_partial_merge() {
+    #[derive(PartialStruct, Debug, PartialEq)]
+    #[partial_struct(derive(Default))]
+    struct Obj {
+        a: u32,
+        b: String,
+        #[partial_struct(skip)]
+        c: Option<u32>,
+        #[partial_struct(skip)]
+        d: bool,
+        #[partial_struct(ty = "PartialAnotherObj")]
+        e: AnotherObj,
+    }
+
+    #[derive(PartialStruct, Debug, PartialEq)]
+    #[partial_struct(derive(Default))]
+    struct AnotherObj {
+        f: u32,
+        g: u32,
+    }
+
+    let obj = || Obj {
+        a: 1,
+        b: String::from("b"),
+        c: Some(2),
+        d: true,
+        e: AnotherObj { f: 3, g: 4 },
+    };
+
+    // Only the fields that are set overwrite the full struct
+    let mut full = obj();
+    PartialObj {
+        a: Some(10),
+        e: PartialAnotherObj {
+            f: None,
+            g: Some(40),
+        },
+        ..PartialObj::default()
+    }
+    .merge_into(&mut full);
+    assert_eq!(
+        full,
+        Obj {
+            a: 10,
+            e: AnotherObj { f: 3, g: 40 },
+            ..obj()
+        }
+    );
+
+    // Merging a partial struct that has no fields set leaves the full struct untouched, including
+    // the skipped fields that are not options
+    let mut full = obj();
+    full.apply_partial(PartialObj::default());
+    assert_eq!(full, obj());
+    PartialObj {
+        d: false,
+        ..PartialObj::default()
+    }
+    .merge_into(&mut full);
+    assert_eq!(full, obj());
+}
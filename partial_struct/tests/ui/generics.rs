@@ -0,0 +1,62 @@
+use std::fmt::Debug;
+
+use partial_struct::PartialStruct;
+use serde::Deserialize;
+
+#[derive(PartialStruct)]
+#[partial_struct(derive(Debug, Default, PartialEq, Deserialize))]
+#[allow(dead_code)]
+struct Config<T>
+where
+    T: Debug,
+{
+    item: T,
+    #[partial_struct(skip)]
+    #[partial_struct(serde(default))]
+    items: Vec<T>,
+}
+
+/// A bound that only makes sense for the full struct
+trait Validate {
+    fn validate(&self) -> bool;
+}
+
+#[derive(PartialStruct)]
+#[partial_struct(bound = "T: Clone")]
+#[partial_struct(derive(Debug, Clone, PartialEq))]
+#[allow(dead_code)]
+struct Checked<T: Validate + Clone> {
+    item: T,
+}
+
+#[derive(PartialStruct)]
+#[allow(dead_code)]
+struct Borrowed<'a, T: ?Sized> {
+    item: &'a T,
+}
+
+#[derive(PartialStruct)]
+#[partial_struct(bound = "")]
+#[allow(dead_code)]
+struct Unbounded<T: Validate>(T);
+
+fn main() {
+    let partial: PartialConfig<u32> = serde_json::from_str(r#"{"item": 7}"#).unwrap();
+    assert_eq!(
+        partial,
+        PartialConfig {
+            item: Some(7),
+            items: vec![],
+        }
+    );
+
+    // `u32` does not implement `Validate`, which is only required by the full structs
+    let partial = PartialChecked { item: Some(7u32) };
+    assert_eq!(partial.clone().item, Some(7));
+    let partial = PartialBorrowed {
+        item: Some("seven"),
+    };
+    assert_eq!(partial.item, Some("seven"));
+    let PartialUnbounded(item) = PartialUnbounded(None::<u32>);
+    assert_eq!(item, None);
+}
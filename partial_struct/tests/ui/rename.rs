@@ -0,0 +1,32 @@
+use partial_struct::PartialStruct;
+use serde::{Deserialize, Serialize};
+
+#[derive(PartialStruct)]
+#[partial_struct(derive(Debug, PartialEq, Deserialize, Serialize))]
+#[allow(dead_code)]
+struct Config {
+    #[partial_struct(rename = "timeout-ms")]
+    timeout: u32,
+    retries: u32,
+}
+
+fn main() {
+    let partial: PartialConfig =
+        serde_json::from_str(r#"{"timeout-ms": 1000, "retries": 3}"#).unwrap();
+    assert_eq!(
+        partial,
+        PartialConfig {
+            timeout: Some(1000),
+            retries: Some(3),
+        }
+    );
+
+    assert_eq!(
+        serde_json::to_string(&partial).unwrap(),
+        r#"{"timeout-ms":1000,"retries":3}"#
+    );
+
+    // The original name of the field is not used anymore
+    let partial: PartialConfig = serde_json::from_str(r#"{"timeout": 1000}"#).unwrap();
+    assert_eq!(partial.timeout, None);
+}
@@ -10,25 +10,53 @@
 #![deny(unused_mut)]
 #![deny(missing_docs)]
 
+use std::mem::MaybeUninit;
 use std::ops::{Deref, DerefMut};
 use std::str;
 
 use memzero::Memzero;
+use subtle::ConstantTimeEq;
 
 #[cfg(feature = "serde")]
 mod serde;
 
 /// Protected set of bytes
-#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+///
+/// The bytes can be mutated through `AsMut` and `DerefMut`, but only as a slice of fixed length:
+/// the underlying buffer is never grown nor reallocated, as that would leave a copy of the bytes
+/// behind that would never be zeroed out.
+///
+/// Equality is checked in constant time, so that comparing secrets does not leak how many of their
+/// leading bytes match. Only their lengths are not hidden. Beware that ordering and hashing are not
+/// constant time.
+// The derived `Hash` is consistent with the equality implemented below, which only differs from
+// the derived one in its timing
+#[allow(clippy::derived_hash_with_manual_eq)]
+#[derive(Clone, PartialOrd, Ord, Hash)]
 pub struct Protected(Memzero<Vec<u8>>);
 
 impl Protected {
     /// Create new protected set of bytes.
+    ///
+    /// The spare capacity of the vector, which may hold leftovers of a secret if the vector was
+    /// truncated, is zeroed out right away. Only the bytes within its length need to be zeroed out
+    /// when dropped, because it never changes.
     pub fn new<T: Into<Vec<u8>>>(m: T) -> Self {
-        Protected(m.into().into())
+        let mut bytes = m.into();
+        bytes.spare_capacity_mut().fill(MaybeUninit::new(0));
+
+        Protected(bytes.into())
     }
 }
 
+impl PartialEq for Protected {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_ref().ct_eq(other.as_ref()).into()
+    }
+}
+
+impl Eq for Protected {}
+
 impl<T: Into<Vec<u8>>> From<T> for Protected {
     fn from(x: T) -> Self {
         Self::new(x.into())
@@ -74,7 +102,11 @@ impl From<ProtectedString> for Protected {
 }
 
 /// Protected string
-#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+///
+/// Like in [`Protected`](Protected), equality is checked in constant time, but ordering and hashing
+/// are not.
+#[allow(clippy::derived_hash_with_manual_eq)]
+#[derive(Clone, PartialOrd, Ord, Hash)]
 pub struct ProtectedString(Protected);
 
 impl ProtectedString {
@@ -86,6 +118,14 @@ impl ProtectedString {
     }
 }
 
+impl PartialEq for ProtectedString {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl Eq for ProtectedString {}
+
 impl<T: ToString> From<T> for ProtectedString {
     fn from(x: T) -> Self {
         Self::new(x.to_string())
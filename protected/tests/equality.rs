@@ -0,0 +1,27 @@
+use witnet_protected::{Protected, ProtectedString};
+
+#[test]
+fn test_protected_equality() {
+    let secret = Protected::new(vec![1, 2, 3, 4]);
+
+    assert_eq!(secret, Protected::new(vec![1, 2, 3, 4]));
+    assert_eq!(secret, secret.clone());
+    assert_ne!(secret, Protected::new(vec![1, 2, 3, 5]));
+    assert_ne!(secret, Protected::new(vec![0, 2, 3, 4]));
+    assert_ne!(secret, Protected::new(vec![1, 2, 3]));
+    assert_ne!(secret, Protected::new(vec![1, 2, 3, 4, 0]));
+    assert_eq!(Protected::new(vec![]), Protected::new(vec![]));
+}
+
+#[test]
+fn test_protected_string_equality() {
+    let secret = ProtectedString::new("correct horse battery staple");
+
+    assert_eq!(secret, ProtectedString::new("correct horse battery staple"));
+    assert_ne!(
+        secret,
+        ProtectedString::new("correct horse battery stapler")
+    );
+    assert_ne!(secret, ProtectedString::new("Correct horse battery staple"));
+    assert_ne!(secret, ProtectedString::new(""));
+}
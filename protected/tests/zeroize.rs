@@ -0,0 +1,69 @@
+//! Check the contents of every buffer freed by this test binary, to make sure that no secret is
+//! left behind in the heap.
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use witnet_protected::{Protected, ProtectedString};
+
+const SECRET: &[u8] = b"correct horse battery staple";
+
+/// Whether any freed buffer contained a piece of `SECRET`.
+static SECRET_FREED: AtomicBool = AtomicBool::new(false);
+
+/// Allocator that zeroes out new buffers, so that they can always be read, and looks for pieces of
+/// `SECRET` in the buffers that are freed.
+struct InspectingAllocator;
+
+unsafe impl GlobalAlloc for InspectingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        let freed = std::slice::from_raw_parts(ptr, layout.size());
+        if SECRET
+            .windows(8)
+            .any(|piece| freed.windows(piece.len()).any(|window| window == piece))
+        {
+            SECRET_FREED.store(true, Ordering::SeqCst);
+        }
+
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: InspectingAllocator = InspectingAllocator;
+
+// Everything is checked in a single test, so that other tests running in parallel cannot reset
+// `SECRET_FREED` nor free their own copies of `SECRET`
+#[test]
+fn test_no_secret_left_in_freed_buffers() {
+    // Mutate the secret in place
+    let mut protected = Protected::new(SECRET.to_vec());
+    protected.as_mut()[0] ^= 0xFF;
+    protected[1] ^= 0xFF;
+    assert_eq!(&protected[2..], &SECRET[2..]);
+    let cloned = protected.clone();
+    drop(protected);
+    drop(cloned);
+    assert!(!SECRET_FREED.load(Ordering::SeqCst));
+
+    // The secret is also left in the spare capacity of a truncated vector
+    let mut truncated = SECRET.to_vec();
+    truncated.truncate(4);
+    drop(Protected::new(truncated));
+    assert!(!SECRET_FREED.load(Ordering::SeqCst));
+
+    let mut string = String::with_capacity(2 * SECRET.len());
+    string.push_str(std::str::from_utf8(SECRET).unwrap());
+    string.truncate(4);
+    drop(ProtectedString::new(string));
+    assert!(!SECRET_FREED.load(Ordering::SeqCst));
+
+    // Make sure that the allocator does notice secrets that are not protected
+    drop(SECRET.to_vec());
+    assert!(SECRET_FREED.load(Ordering::SeqCst));
+}
@@ -0,0 +1,147 @@
+#[macro_use]
+extern crate bencher;
+use bencher::Bencher;
+use witnet_data_structures::{
+    chain::{tapi::all_wips_active, RADFilter, RADTally},
+    radon_report::ReportContext,
+};
+use witnet_rad::{
+    cbor_to_vec,
+    filters::RadonFilters,
+    operators::RadonOpCodes,
+    reducers::RadonReducers,
+    run_tally,
+    script::{execute_radon_script, unpack_radon_script, RadonScriptExecutionSettings},
+    types::{float::RadonFloat, integer::RadonInteger, string::RadonString, RadonTypes},
+    CborValue,
+};
+
+const ARRAY_LENGTH: usize = 100;
+
+/// A response like those of typical price feeds, with some noise around the value that matters.
+fn json_response() -> RadonTypes {
+    let markets = (0..20)
+        .map(|i| {
+            format!(
+                r#"{{"symbol":"TOKEN{}","last":{}.5,"volume":{}}}"#,
+                i,
+                i,
+                i * 1000
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+
+    RadonTypes::from(RadonString::from(format!(
+        r#"{{"status":"ok","data":{{"markets":[{}],"ticker":{{"symbol":"WIT","price":0.012345,"change":-1.5}}}}}}"#,
+        markets
+    )))
+}
+
+/// Parse the JSON response, walk down to the price and round it.
+fn json_script() -> Vec<u8> {
+    let call = |op_code: RadonOpCodes, arg: &str| {
+        CborValue::Array(vec![
+            CborValue::Integer(op_code as i128),
+            CborValue::Text(arg.to_string()),
+        ])
+    };
+
+    cbor_to_vec(&CborValue::Array(vec![
+        CborValue::Integer(RadonOpCodes::StringParseJSONMap as i128),
+        call(RadonOpCodes::MapGetMap, "data"),
+        call(RadonOpCodes::MapGetMap, "ticker"),
+        call(RadonOpCodes::MapGetFloat, "price"),
+        CborValue::Array(vec![
+            CborValue::Integer(RadonOpCodes::FloatMultiply as i128),
+            CborValue::Integer(1_000_000),
+        ]),
+        CborValue::Integer(RadonOpCodes::FloatRound as i128),
+    ]))
+    .unwrap()
+}
+
+fn floats() -> Vec<RadonTypes> {
+    (0..ARRAY_LENGTH)
+        .map(|i| RadonTypes::from(RadonFloat::from(100.0 + (i % 7) as f64 * 0.1)))
+        .collect()
+}
+
+fn integers() -> Vec<RadonTypes> {
+    (0..ARRAY_LENGTH)
+        .map(|i| RadonTypes::from(RadonInteger::from((i % 3) as i128)))
+        .collect()
+}
+
+fn tally(filters: Vec<RADFilter>, reducer: RadonReducers) -> RADTally {
+    RADTally {
+        filters,
+        reducer: reducer as u32,
+    }
+}
+
+fn deviation_standard_filter() -> RADFilter {
+    RADFilter {
+        op: RadonFilters::DeviationStandard as u32,
+        args: cbor_to_vec(&CborValue::Float(1.5)).unwrap(),
+    }
+}
+
+fn run_json_script(b: &mut Bencher, settings: RadonScriptExecutionSettings) {
+    let input = json_response();
+    let script = unpack_radon_script(&json_script()).unwrap();
+    let active_wips = all_wips_active();
+
+    b.iter(|| {
+        let mut context = ReportContext::from_active_wips(active_wips.clone());
+        execute_radon_script(input.clone(), &script, &mut context, settings).unwrap()
+    });
+}
+
+fn json_script_disable_all(b: &mut Bencher) {
+    run_json_script(b, RadonScriptExecutionSettings::disable_all());
+}
+
+fn json_script_enable_all(b: &mut Bencher) {
+    run_json_script(b, RadonScriptExecutionSettings::enable_all());
+}
+
+fn run_tally_reducer(b: &mut Bencher, values: Vec<RadonTypes>, tally: RADTally) {
+    let active_wips = all_wips_active();
+
+    b.iter(|| run_tally(values.clone(), &tally, &active_wips).unwrap());
+}
+
+fn tally_average_mean(b: &mut Bencher) {
+    run_tally_reducer(b, floats(), tally(vec![], RadonReducers::AverageMean));
+}
+
+fn tally_average_median(b: &mut Bencher) {
+    run_tally_reducer(b, floats(), tally(vec![], RadonReducers::AverageMedian));
+}
+
+fn tally_mode(b: &mut Bencher) {
+    run_tally_reducer(b, integers(), tally(vec![], RadonReducers::Mode));
+}
+
+fn tally_deviation_standard_average_mean(b: &mut Bencher) {
+    run_tally_reducer(
+        b,
+        floats(),
+        tally(
+            vec![deviation_standard_filter()],
+            RadonReducers::AverageMean,
+        ),
+    );
+}
+
+benchmark_main!(benches);
+benchmark_group!(
+    benches,
+    json_script_disable_all,
+    json_script_enable_all,
+    tally_average_mean,
+    tally_average_median,
+    tally_mode,
+    tally_deviation_standard_average_mean,
+);
@@ -0,0 +1,194 @@
+//! Throttling of the bandwidth used by data retrieval.
+use std::{
+    fmt, io,
+    sync::{Arc, Mutex, PoisonError},
+    time::{Duration, Instant},
+};
+
+use futures::{future::BoxFuture, AsyncRead, AsyncReadExt, FutureExt};
+
+/// Maximum number of bytes read from a response body before consulting the limiter again.
+const CHUNK_SIZE: usize = 8 * 1024;
+
+/// Source of time for a `BandwidthLimiter`.
+///
+/// This exists so that tests can simulate the passing of time instead of actually waiting.
+pub trait Clock: Send + Sync {
+    /// Current instant.
+    fn now(&self) -> Instant;
+    /// Wait for some time to pass.
+    fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()>;
+}
+
+/// The wall clock, using actual timers for sleeping.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()> {
+        futures_timer::Delay::new(duration).boxed()
+    }
+}
+
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+/// Token bucket rate limiter that caps the total download rate of all the retrievals sharing it.
+///
+/// The bucket holds up to one second worth of bytes, so short responses are not delayed at all
+/// while the node is otherwise idle.
+pub struct BandwidthLimiter {
+    rate: u64,
+    bucket: Mutex<Bucket>,
+    clock: Arc<dyn Clock>,
+}
+
+impl fmt::Debug for BandwidthLimiter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BandwidthLimiter")
+            .field("rate", &self.rate)
+            .finish()
+    }
+}
+
+impl BandwidthLimiter {
+    /// Create a limiter allowing up to `rate` bytes per second.
+    pub fn new(rate: u64) -> Self {
+        Self::with_clock(rate, Arc::new(SystemClock))
+    }
+
+    /// Create a limiter allowing up to `rate` bytes per second, using a custom clock.
+    pub fn with_clock(rate: u64, clock: Arc<dyn Clock>) -> Self {
+        let rate = rate.max(1);
+        let bucket = Bucket {
+            tokens: rate as f64,
+            last_refill: clock.now(),
+        };
+
+        Self {
+            rate,
+            bucket: Mutex::new(bucket),
+            clock,
+        }
+    }
+
+    /// Maximum number of bytes per second allowed by this limiter.
+    pub fn rate(&self) -> u64 {
+        self.rate
+    }
+
+    /// Take `bytes` tokens out of the bucket and tell how long the caller needs to wait before
+    /// going on. The bucket can go into debt, so that concurrent readers queue up one after the
+    /// other instead of starving each other.
+    fn reserve(&self, bytes: usize) -> Duration {
+        let rate = self.rate as f64;
+        let now = self.clock.now();
+        let mut bucket = self.bucket.lock().unwrap_or_else(PoisonError::into_inner);
+
+        let elapsed = now.saturating_duration_since(bucket.last_refill);
+        bucket.tokens = (bucket.tokens + elapsed.as_secs_f64() * rate).min(rate);
+        bucket.last_refill = bucket.last_refill.max(now);
+        bucket.tokens -= bytes as f64;
+
+        if bucket.tokens < 0.0 {
+            Duration::from_secs_f64(-bucket.tokens / rate)
+        } else {
+            Duration::ZERO
+        }
+    }
+
+    /// Account for `bytes` having been downloaded, waiting as long as needed to keep the overall
+    /// download rate below the limit.
+    pub async fn throttle(&self, bytes: usize) {
+        let wait = self.reserve(bytes);
+        if !wait.is_zero() {
+            self.clock.sleep(wait).await;
+        }
+    }
+
+    /// Throttled equivalent of `AsyncReadExt::read_to_string`.
+    pub async fn read_to_string<R>(&self, reader: &mut R, buf: &mut String) -> io::Result<usize>
+    where
+        R: AsyncRead + Unpin,
+    {
+        let mut bytes = Vec::new();
+        let mut chunk = [0u8; CHUNK_SIZE];
+        loop {
+            let read = reader.read(&mut chunk).await?;
+            if read == 0 {
+                break;
+            }
+            self.throttle(read).await;
+            bytes.extend_from_slice(&chunk[..read]);
+        }
+
+        let string =
+            String::from_utf8(bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        buf.push_str(&string);
+
+        Ok(string.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::{executor::block_on, io::Cursor};
+
+    /// A clock that jumps forward instantly whenever it is asked to sleep.
+    struct FastClock(Mutex<Instant>);
+
+    impl Clock for FastClock {
+        fn now(&self) -> Instant {
+            *self.0.lock().unwrap()
+        }
+
+        fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()> {
+            *self.0.lock().unwrap() += duration;
+            futures::future::ready(()).boxed()
+        }
+    }
+
+    #[test]
+    fn test_read_to_string_respects_rate() {
+        let start = Instant::now();
+        let clock = Arc::new(FastClock(Mutex::new(start)));
+        let rate = 64 * 1024;
+        let limiter = BandwidthLimiter::with_clock(rate, clock.clone());
+
+        // 1 MiB response, of which the first second worth of bytes is served from the full bucket
+        let response = "a".repeat(1024 * 1024);
+        let mut reader = Cursor::new(response.clone().into_bytes());
+        let mut read = String::new();
+        let len = block_on(limiter.read_to_string(&mut reader, &mut read)).unwrap();
+
+        assert_eq!(len, response.len());
+        assert_eq!(read, response);
+        let elapsed = clock.now() - start;
+        assert!(
+            (elapsed.as_secs_f64() - 15.0).abs() < 0.001,
+            "elapsed: {:?}",
+            elapsed
+        );
+    }
+
+    #[test]
+    fn test_small_read_is_not_delayed() {
+        let start = Instant::now();
+        let clock = Arc::new(FastClock(Mutex::new(start)));
+        let limiter = BandwidthLimiter::with_clock(1024, clock.clone());
+
+        let mut reader = Cursor::new(b"{\"price\":1.5}".to_vec());
+        let mut read = String::new();
+        block_on(limiter.read_to_string(&mut reader, &mut read)).unwrap();
+
+        assert_eq!(read, "{\"price\":1.5}");
+        assert_eq!(clock.now(), start);
+    }
+}
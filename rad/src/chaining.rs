@@ -0,0 +1,207 @@
+//! Chained retrievals, in which the URL or the body of a source is templated with the results of
+//! the sources that come before it in the same request.
+//!
+//! A source references the result of the source at index `N` by writing `\N\` anywhere in its URL
+//! or body. Chained retrievals are only available once WIP-0029 is active: before that, those
+//! placeholders are sent as is.
+use witnet_data_structures::{
+    chain::{tapi::ActiveWips, RADRetrieve},
+    radon_report::RadonReport,
+};
+
+use crate::{
+    error::RadError,
+    types::{RadonType, RadonTypes},
+    Result,
+};
+
+/// Split a template into literal chunks and source references.
+fn parse_template(template: &str) -> Vec<Segment<'_>> {
+    let mut segments = vec![];
+    let mut rest = template;
+    while let Some(start) = rest.find('\\') {
+        let after = &rest[start + 1..];
+        let digits = after
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(after.len());
+        let reference = after[..digits].parse::<usize>().ok();
+        match reference {
+            Some(reference) if after[digits..].starts_with('\\') => {
+                segments.push(Segment::Literal(&rest[..start]));
+                segments.push(Segment::Reference(reference));
+                rest = &after[digits + 1..];
+            }
+            _ => {
+                segments.push(Segment::Literal(&rest[..=start]));
+                rest = after;
+            }
+        }
+    }
+    segments.push(Segment::Literal(rest));
+
+    segments
+}
+
+enum Segment<'a> {
+    Literal(&'a str),
+    Reference(usize),
+}
+
+/// Indexes of the sources whose results are referenced by a source.
+pub fn source_references(retrieve: &RADRetrieve) -> Vec<usize> {
+    // Bodies that are not valid UTF-8 cannot be templated
+    let body = std::str::from_utf8(&retrieve.body).unwrap_or_default();
+    let mut references: Vec<usize> = [retrieve.url.as_str(), body]
+        .iter()
+        .flat_map(|template| parse_template(template))
+        .filter_map(|segment| match segment {
+            Segment::Reference(reference) => Some(reference),
+            Segment::Literal(_) => None,
+        })
+        .collect();
+    references.sort_unstable();
+    references.dedup();
+
+    references
+}
+
+/// Tell whether any of the sources of a request needs the results of other sources, in which case
+/// the sources must be retrieved one after the other instead of in parallel.
+pub fn has_chained_retrievals(sources: &[RADRetrieve], active_wips: &ActiveWips) -> bool {
+    active_wips.wip0029()
+        && sources
+            .iter()
+            .any(|retrieve| !source_references(retrieve).is_empty())
+}
+
+/// Text that replaces a reference to a source with the given result.
+fn reference_text(reference: usize, report: Option<&RadonReport<RadonTypes>>) -> Result<String> {
+    let error = |message: String| RadError::SourceReference { reference, message };
+    let report = report.ok_or_else(|| {
+        error(String::from(
+            "sources can only reference the sources that come before them",
+        ))
+    })?;
+
+    match &report.result {
+        RadonTypes::String(string) => Ok(string.value()),
+        RadonTypes::Integer(integer) => Ok(integer.value().to_string()),
+        RadonTypes::Float(float) => Ok(float.value().to_string()),
+        RadonTypes::Boolean(boolean) => Ok(boolean.value().to_string()),
+        RadonTypes::Bytes(bytes) => Ok(hex::encode(bytes.value())),
+        RadonTypes::RadonError(inner) => Err(error(format!("the source failed: {}", inner))),
+        other => Err(error(format!(
+            "a {} cannot be used in a template",
+            other.radon_type_name()
+        ))),
+    }
+}
+
+fn fill_template(template: &str, previous: &[RadonReport<RadonTypes>]) -> Result<String> {
+    parse_template(template)
+        .into_iter()
+        .map(|segment| match segment {
+            Segment::Literal(literal) => Ok(literal.to_string()),
+            Segment::Reference(reference) => reference_text(reference, previous.get(reference)),
+        })
+        .collect()
+}
+
+/// Replace the source references in the URL and the body of a source with the results of the
+/// sources that come before it, which are given in `previous`.
+///
+/// Strings are inserted as is, so any escaping that the URL or the body may need must be done by
+/// the script of the referenced source. Bytes are inserted as hexadecimal strings.
+pub fn resolve_source_references(
+    retrieve: &RADRetrieve,
+    previous: &[RadonReport<RadonTypes>],
+) -> Result<RADRetrieve> {
+    let url = fill_template(&retrieve.url, previous)?;
+    let body = match std::str::from_utf8(&retrieve.body) {
+        Ok(body) => fill_template(body, previous)?.into_bytes(),
+        Err(_) => retrieve.body.clone(),
+    };
+
+    Ok(RADRetrieve {
+        url,
+        body,
+        ..retrieve.clone()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use witnet_data_structures::{chain::RADType, radon_report::ReportContext};
+
+    use crate::types::{float::RadonFloat, map::RadonMap, string::RadonString};
+
+    use super::*;
+
+    fn retrieve_with(url: &str, body: &str) -> RADRetrieve {
+        RADRetrieve {
+            kind: RADType::HttpPost,
+            url: url.to_string(),
+            script: vec![128],
+            body: body.as_bytes().to_vec(),
+            headers: vec![],
+        }
+    }
+
+    fn report(value: RadonTypes) -> RadonReport<RadonTypes> {
+        RadonReport::from_result(Ok(value), &ReportContext::default())
+    }
+
+    #[test]
+    fn test_source_references() {
+        let retrieve = retrieve_with(
+            r"https://example.com/\1\/price?token=\0\&sep=\x\",
+            r#"{"token":"\0\"}"#,
+        );
+
+        assert_eq!(source_references(&retrieve), vec![0, 1]);
+        assert!(source_references(&retrieve_with(r"https://example.com/a\b", "")).is_empty());
+    }
+
+    #[test]
+    fn test_resolve_source_references() {
+        let previous = vec![
+            report(RadonString::from("abc").into()),
+            report(RadonFloat::from(1.5).into()),
+        ];
+        let retrieve = retrieve_with(
+            r"https://example.com/price?token=\0\&min=\1\&keep=\x",
+            r#"{"token":"\0\"}"#,
+        );
+
+        let resolved = resolve_source_references(&retrieve, &previous).unwrap();
+        assert_eq!(
+            resolved.url,
+            r"https://example.com/price?token=abc&min=1.5&keep=\x"
+        );
+        assert_eq!(resolved.body, br#"{"token":"abc"}"#.to_vec());
+        assert_eq!(resolved.script, retrieve.script);
+    }
+
+    #[test]
+    fn test_resolve_source_references_errors() {
+        let previous = vec![
+            report(RadonMap::default().into()),
+            RadonReport::from_result(Err(RadError::RetrieveTimeout), &ReportContext::default()),
+        ];
+        let resolve = |url: &str| resolve_source_references(&retrieve_with(url, ""), &previous);
+
+        assert!(matches!(
+            resolve(r"https://example.com/\0\"),
+            Err(RadError::SourceReference { reference: 0, .. })
+        ));
+        assert!(matches!(
+            resolve(r"https://example.com/\1\"),
+            Err(RadError::SourceReference { reference: 1, .. })
+        ));
+        // Only the sources that come before can be referenced
+        assert!(matches!(
+            resolve(r"https://example.com/\2\"),
+            Err(RadError::SourceReference { reference: 2, .. })
+        ));
+    }
+}
@@ -50,9 +50,49 @@ pub enum RadError {
     /// The given index is not present in a RadonArray
     #[fail(display = "Failed to get item at index `{}` from RadonArray", index)]
     ArrayIndexOutOfBounds { index: i32 },
+    /// The array does not have as many items as required
+    #[fail(display = "Array has {} items, but at least {} are required", len, min)]
+    ArrayTooShort { len: usize, min: usize },
     /// The given key is not present in a RadonMap
     #[fail(display = "Failed to get key `{}` from RadonMap", key)]
     MapKeyNotFound { key: String },
+    /// A segment of the path given to `GetPath` cannot be applied to the value found at that point
+    #[fail(
+        display = "Cannot apply path segment #{} ({:?}) to a value of type {}",
+        position, segment, input_type
+    )]
+    InvalidPathSegment {
+        position: usize,
+        segment: SerdeCborValue,
+        input_type: &'static str,
+    },
+    /// The value found at the key path given to `ArrayGroupBy` cannot be used as a map key
+    #[fail(
+        display = "ArrayGroupBy keys must be strings, integers or booleans, but found a {}",
+        key_type
+    )]
+    UnsupportedGroupByKey { key_type: &'static str },
+    /// A StringFormat template has an unbalanced brace or a placeholder that is not an index
+    #[fail(
+        display = "Malformed StringFormat template `{}`: placeholders look like `{{0}}` and literal braces must be doubled",
+        template
+    )]
+    MalformedFormatTemplate { template: String },
+    /// The lower bound of a range is greater than its upper bound
+    #[fail(
+        display = "Invalid range: the lower bound `{}` is greater than the upper bound `{}`",
+        low, high
+    )]
+    InvalidRange { low: String, high: String },
+    /// The separator given to StringSplit is empty
+    #[fail(display = "StringSplit needs a non-empty separator")]
+    EmptySeparator,
+    /// Strict decoding found a float that is NaN or infinite
+    #[fail(display = "Floats must be finite, but found {}", value)]
+    NonFiniteFloat { value: String },
+    /// Strict decoding found a map key that is not a string
+    #[fail(display = "Map keys must be strings, but found {}", key)]
+    NonStringMapKey { key: String },
     /// The given subscript does not return RadonBoolean in an ArrayFilter
     #[fail(
         display = "ArrayFilter subscript output was not RadonBoolean (was `{}`)",
@@ -65,6 +105,13 @@ pub enum RadError {
         description
     )]
     BufferIsNotValue { description: String },
+    /// The bytes of a script are not valid CBOR. The original `serde_cbor` error is kept as a
+    /// string, because it does not implement `Clone` nor `PartialEq`
+    #[fail(
+        display = "Failed to decode script as CBOR at byte offset {}: {}",
+        position, inner
+    )]
+    ScriptDecode { inner: String, position: u64 },
     /// No operator found in compound call
     #[fail(display = "No operator found in compound call")]
     NoOperatorInCompoundCall,
@@ -92,6 +139,22 @@ pub enum RadError {
     /// The given retrieval code is unknown
     #[fail(display = "Retrieval code is unknown")]
     UnknownRetrieval,
+    /// The request has no retrieval sources
+    #[fail(display = "The request has no retrieval sources")]
+    NoRetrievalSources,
+    /// The retrieval has fields that cannot be used with its kind, or lacks mandatory ones
+    #[fail(display = "Malformed retrieval: {}", message)]
+    MalformedRetrieval { message: String },
+    /// A RAD request written with operator, filter and reducer names cannot be converted into its
+    /// numeric form, or the other way around
+    #[fail(display = "Malformed named RAD request: {}", message)]
+    MalformedNamedRequest { message: String },
+    /// A chained retrieval references the result of a source that cannot be used in its template
+    #[fail(
+        display = "Cannot use the result of source #{}: {}",
+        reference, message
+    )]
+    SourceReference { reference: usize, message: String },
     /// The given hash function is not implemented
     #[fail(display = "Hash function `{}` is not implemented", function)]
     UnsupportedHashFunction { function: String },
@@ -120,6 +183,9 @@ pub enum RadError {
     /// The sort operator is not implemented for non-string arrays
     #[fail(display = "ArraySort is not supported for RadonArray `{:?}`", array)]
     UnsupportedSortOp { array: RadonArray },
+    /// The sort keys produced by the ArraySort subscript cannot be compared with each other
+    #[fail(display = "ArraySort failed: {}", message)]
+    Sort { message: String },
     /// The operator is not implemented for non-homogeneous arrays
     #[fail(
         display = "`{}` is not supported for RadonArray with non homogeneous types",
@@ -141,6 +207,12 @@ pub enum RadError {
         operator
     )]
     UnsupportedReducerInAT { operator: u8 },
+    /// This operator, filter or reducer is not available until a certain WIP is activated
+    #[fail(
+        display = "Operator code `{}` cannot be used until {} is activated",
+        code, wip
+    )]
+    OperatorNotActivated { code: u8, wip: &'static str },
     /// There was a tie after applying the mode reducer
     #[fail(
         display = "There was a tie after applying the mode reducer on values: `{:?}`",
@@ -150,6 +222,21 @@ pub enum RadError {
     /// Tried to apply mod reducer on an empty array
     #[fail(display = "Tried to apply mode reducer on an empty array")]
     EmptyArray,
+    /// The weights passed to a weighted reducer add up to zero
+    #[fail(display = "The weights passed to reducer `{}` add up to zero", reducer)]
+    ZeroWeight { reducer: String },
+    /// A reducer that is only defined for positive numbers got a negative number or zero
+    #[fail(
+        display = "Reducer `{}` is not defined for non-positive input `{}`",
+        reducer, value
+    )]
+    NegativeInput { reducer: String, value: f64 },
+    /// The smoothing factor passed to an exponential moving average is not in the (0, 1] range
+    #[fail(
+        display = "Reducer `{}` needs a smoothing factor in the (0, 1] range, but got `{}`",
+        reducer, alpha
+    )]
+    InvalidSmoothingFactor { reducer: String, alpha: f64 },
     /// The given arguments are not valid for the given operator
     #[fail(
         display = "Wrong `{}::{}()` arguments: `{:?}`",
@@ -187,6 +274,12 @@ pub enum RadError {
         message
     )]
     ParseBool { message: String },
+    /// Failed to parse an RFC 3339 timestamp
+    #[fail(
+        display = "Failed to parse timestamp `{}` with error message: {}",
+        timestamp, message
+    )]
+    ParseTimestamp { timestamp: String, message: String },
     /// Overflow error
     #[fail(display = "Overflow error")]
     Overflow,
@@ -230,6 +323,24 @@ pub enum RadError {
         inner: url::ParseError,
         url: String,
     },
+    /// The retrieval URL uses a scheme other than `http` or `https`
+    #[fail(
+        display = "Unsupported URL scheme {:?} for retrieval: url={:?}",
+        scheme, url
+    )]
+    UnsupportedScheme { scheme: String, url: String },
+    /// The retrieval URL does not use TLS, but the node is configured to require it
+    #[fail(
+        display = "Retrieval over an insecure transport is not allowed: url={:?}",
+        url
+    )]
+    InsecureTransport { url: String },
+    /// The TLS handshake with a data source failed, e.g. because of an invalid certificate
+    #[fail(display = "TLS error when retrieving {:?}: {}", url, message)]
+    TlsError { url: String, message: String },
+    /// The HTTP request of a retrieval was only captured, so there is no response to run on
+    #[fail(display = "HTTP request was captured but not sent: url={:?}", url)]
+    RequestCaptured { url: String },
     /// Timeout during retrieval phase
     #[fail(display = "Timeout during retrieval phase")]
     RetrieveTimeout,
@@ -311,6 +422,9 @@ pub enum RadError {
     /// The script contains too many calls.
     #[fail(display = "The script contains too many calls")]
     ScriptTooManyCalls,
+    /// The script nests too many subscripts or executes too many calls.
+    #[fail(display = "The script nests too many subscripts or executes too many calls")]
+    ScriptTooComplex,
     /// At least one of the source scripts is not a valid CBOR-encoded value.
     #[fail(display = "At least one of the source scripts is not a valid CBOR-encoded value")]
     SourceScriptNotCBOR,
@@ -436,6 +550,7 @@ impl RadError {
         Ok(RadonError::new(match kind {
             RadonErrors::RequestTooManySources => RadError::RequestTooManySources,
             RadonErrors::ScriptTooManyCalls => RadError::ScriptTooManyCalls,
+            RadonErrors::ScriptTooComplex => RadError::ScriptTooComplex,
             RadonErrors::Overflow => RadError::Overflow,
             RadonErrors::InsufficientCommits => RadError::InsufficientCommits,
             RadonErrors::TooManyWitnesses => RadError::TooManyWitnesses,
@@ -500,7 +615,11 @@ impl RadError {
         }))
     }
 
-    pub fn try_into_cbor_array(&self) -> Result<Vec<SerdeCborValue>, RadError> {
+    /// Split this error into its error code and its arguments, as encoded on chain. This is the
+    /// reverse of `try_from_kind_and_cbor_args`.
+    pub fn to_kind_and_cbor_args(
+        &self,
+    ) -> Result<(RadonErrors, Option<Vec<SerdeCborValue>>), RadError> {
         fn serialize_args<T: serde::Serialize + std::fmt::Debug>(
             args: T,
         ) -> Result<SerdeCborValue, RadError> {
@@ -509,7 +628,7 @@ impl RadError {
             })
         }
 
-        let kind = u8::from(self.try_into_error_code()?);
+        let kind = self.try_into_error_code()?;
 
         let args = match self {
             RadError::UnsupportedOperator {
@@ -555,22 +674,26 @@ impl RadError {
             _ => None,
         };
 
-        let mut v = vec![SerdeCborValue::Integer(i128::from(kind))];
-
-        match args {
-            None => {}
-            Some(SerdeCborValue::Array(a)) => {
-                // Append arguments to resulting array. The format of the resulting array is:
-                // [kind, arg0, arg1, arg2, ...]
-                v.extend(a);
-            }
+        let args = match args {
+            None => None,
+            Some(SerdeCborValue::Array(a)) => Some(a),
             Some(value) => {
                 // This can only happen if `serialize_args` is called with a non-tuple argument
                 // For example:
                 // `serialize_args(x)` is invalid, it should be `serialize_args((x,))`
                 panic!("Args should be an array, is {:?}", value);
             }
-        }
+        };
+
+        Ok((kind, args))
+    }
+
+    pub fn try_into_cbor_array(&self) -> Result<Vec<SerdeCborValue>, RadError> {
+        let (kind, args) = self.to_kind_and_cbor_args()?;
+
+        // The format of the resulting array is: [kind, arg0, arg1, arg2, ...]
+        let mut v = vec![SerdeCborValue::Integer(i128::from(u8::from(kind)))];
+        v.extend(args.unwrap_or_default());
 
         Ok(v)
     }
@@ -583,6 +706,7 @@ impl RadError {
             RadError::SourceScriptNotRADON => RadonErrors::SourceScriptNotRADON,
             RadError::RequestTooManySources => RadonErrors::RequestTooManySources,
             RadError::ScriptTooManyCalls => RadonErrors::ScriptTooManyCalls,
+            RadError::ScriptTooComplex => RadonErrors::ScriptTooComplex,
             RadError::UnsupportedOperator { .. } => RadonErrors::UnsupportedOperator,
             RadError::HttpStatus { .. } => RadonErrors::HTTPError,
             RadError::Underflow => RadonErrors::Underflow,
@@ -731,6 +855,7 @@ impl TryFrom<Result<RadonTypes, RadError>> for RadonTypes {
 mod tests {
     use num_enum::TryFromPrimitive;
     use serde_cbor::Value;
+    use witnet_data_structures::chain::tapi::current_active_wips;
 
     use super::*;
 
@@ -853,6 +978,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn kind_and_cbor_args_round_trip() {
+        let cases = vec![
+            (
+                RadError::HttpStatus { status_code: 404 },
+                RadonErrors::HTTPError,
+                Some(vec![Value::Integer(404)]),
+            ),
+            (RadError::Overflow, RadonErrors::Overflow, None),
+            (RadError::NoReveals, RadonErrors::NoReveals, None),
+        ];
+
+        for (rad_error, kind, args) in cases {
+            let kind_and_args = rad_error.to_kind_and_cbor_args().unwrap();
+            assert_eq!(kind_and_args, (kind, args.clone()));
+
+            let decoded = RadError::try_from_kind_and_cbor_args(kind, args).unwrap();
+            assert_eq!(decoded.into_inner(), rad_error);
+        }
+    }
+
     #[test]
     fn unhandled_intercept_wrong_single_quote_escape() {
         use crate::RadonString;
@@ -887,4 +1033,76 @@ mod tests {
 
         assert_eq!(deserialized_rad_error.unwrap(), expected_rad_error);
     }
+
+    #[test]
+    fn radon_error_encoding_is_canonical() {
+        let active_wips = crate::all_wips_active();
+        let encode = |rad_error: RadError| {
+            RadonError::new(rad_error)
+                .encode_tagged_bytes_with_active_wips(&active_wips)
+                .unwrap()
+        };
+
+        // Before WIP0029, NaNs are encoded as they are
+        let insufficient_consensus = |achieved_bits: u64| RadError::InsufficientConsensus {
+            achieved: f64::from_bits(achieved_bits),
+            required: 51.0,
+        };
+        let encode_legacy =
+            |rad_error: RadError| RadonError::new(rad_error).encode_tagged_bytes().unwrap();
+        assert_ne!(
+            encode_legacy(insufficient_consensus(0x7ff8_0000_0000_0000)),
+            encode_legacy(insufficient_consensus(0xfff8_0000_0000_0000))
+        );
+        let encode_before_wip0029 = |rad_error: RadError| {
+            RadonError::new(rad_error)
+                .encode_tagged_bytes_with_active_wips(&current_active_wips())
+                .unwrap()
+        };
+        assert_eq!(
+            encode_before_wip0029(insufficient_consensus(0xfff8_0000_0000_0000)),
+            encode_legacy(insufficient_consensus(0xfff8_0000_0000_0000))
+        );
+
+        // NaNs with different signs and payloads are encoded as the same canonical NaN
+        let encoded = encode(insufficient_consensus(0x7ff8_0000_0000_0000));
+        assert_eq!(
+            encoded,
+            encode(insufficient_consensus(0xfff8_0000_0000_0000))
+        );
+        assert_eq!(
+            encoded,
+            encode(insufficient_consensus(0x7ff0_0000_0000_0001))
+        );
+        assert_eq!(&encoded[..5], &[0xd8, 0x27, 0x83, 0x18, 0x51]);
+
+        // Map arguments are encoded with definite lengths and sorted keys, regardless of the
+        // order in which they were built
+        let unsupported_operator = |keys: &[&str]| {
+            let map = keys
+                .iter()
+                .map(|key| (Value::Text(key.to_string()), Value::Integer(1)))
+                .collect();
+
+            RadError::UnsupportedOperator {
+                input_type: "RadonMap".to_string(),
+                operator: "MapGetInteger".to_string(),
+                args: Some(vec![Value::Map(map)]),
+            }
+        };
+        let encoded = encode(unsupported_operator(&["b", "a"]));
+        assert_eq!(encoded, encode(unsupported_operator(&["a", "b"])));
+        assert_eq!(
+            encoded,
+            [
+                vec![0xd8, 0x27, 0x84, 0x18, 0x20],
+                vec![0x68],
+                b"RadonMap".to_vec(),
+                vec![0x6d],
+                b"MapGetInteger".to_vec(),
+                vec![0x81, 0xa2, 0x61, b'a', 0x01, 0x61, b'b', 0x01],
+            ]
+            .concat()
+        );
+    }
 }
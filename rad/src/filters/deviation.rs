@@ -7,7 +7,7 @@ use crate::{
 };
 use serde_cbor::Value;
 use std::convert::TryFrom;
-use witnet_data_structures::radon_report::{ReportContext, Stage};
+use witnet_data_structures::radon_report::ReportContext;
 
 // FIXME: Allow for now, wait for https://github.com/rust-lang/rust/issues/67058 to reach stable
 #[allow(clippy::cast_precision_loss)]
@@ -88,9 +88,7 @@ pub fn standard_filter(
                 }
             }
 
-            if let Stage::Tally(ref mut metadata) = context.stage {
-                metadata.update_liars(bool_vec);
-            }
+            context.stage.update_liars(bool_vec);
 
             Ok(RadonArray::from(result).into())
         }
@@ -127,9 +125,7 @@ fn keep_rows(
         bool_vec.push(!row_true);
     }
 
-    if let Stage::Tally(ref mut metadata) = context.stage {
-        metadata.update_liars(bool_vec);
-    }
+    context.stage.update_liars(bool_vec);
 
     RadonTypes::from(RadonArray::from(result))
 }
@@ -238,7 +234,7 @@ mod tests {
     use super::*;
     use crate::types::{float::RadonFloat, integer::RadonInteger, string::RadonString};
     use std::f64;
-    use witnet_data_structures::radon_report::TallyMetaData;
+    use witnet_data_structures::radon_report::{Stage, TallyMetaData};
 
     #[test]
     fn test_filter_deviation_standard_no_arg() {
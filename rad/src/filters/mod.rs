@@ -2,6 +2,7 @@ use std::fmt;
 
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 use serde_cbor::Value;
+use strum_macros::{EnumString, IntoStaticStr};
 use witnet_data_structures::radon_report::ReportContext;
 
 use crate::{
@@ -12,7 +13,9 @@ use crate::{
 pub mod deviation;
 pub mod mode;
 
-#[derive(Debug, PartialEq, Eq, TryFromPrimitive, IntoPrimitive)]
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, TryFromPrimitive, IntoPrimitive, EnumString, IntoStaticStr,
+)]
 #[repr(u8)]
 pub enum RadonFilters {
     // Implemented
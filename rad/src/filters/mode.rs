@@ -3,7 +3,7 @@ use crate::{
     reducers::mode::mode,
     types::{array::RadonArray, RadonType, RadonTypes},
 };
-use witnet_data_structures::radon_report::{ReportContext, Stage};
+use witnet_data_structures::radon_report::ReportContext;
 
 pub fn mode_filter(
     input: &RadonArray,
@@ -22,9 +22,7 @@ pub fn mode_filter(
         })
         .collect();
 
-    if let Stage::Tally(ref mut metadata) = context.stage {
-        metadata.update_liars(liars);
-    }
+    context.stage.update_liars(liars);
 
     Ok(RadonArray::from(filtered_vec).into())
 }
@@ -33,7 +31,7 @@ pub fn mode_filter(
 mod tests {
     use super::*;
     use crate::types::{integer::RadonInteger, string::RadonString};
-    use witnet_data_structures::radon_report::TallyMetaData;
+    use witnet_data_structures::radon_report::{Stage, TallyMetaData};
 
     // Helper function which works with Rust integers, to remove RadonTypes from tests
     fn imode(
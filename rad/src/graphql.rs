@@ -0,0 +1,242 @@
+//! GraphQL retrievals, which send a query and its variables to a single endpoint as a JSON object
+//! in the body of an HTTP POST request.
+//!
+//! The body of a `RADType::GraphQl` retrieval is the CBOR encoding of an array holding the query
+//! and, optionally, a map with the values of its variables, e.g.
+//! `["query($id: ID!) { token(id: $id) { price } }", {"id": "0x1234"}]`. The JSON body is built
+//! from it when the request is sent. Only queries are supported: mutations and subscriptions are
+//! rejected, as witnesses must never change the state of a data source.
+use serde_cbor::Value;
+use serde_json::Value as JsonValue;
+
+use crate::{error::RadError, Result};
+
+/// Decode the body of a GraphQL retrieval into its query and its variables, and check that the
+/// query looks well-formed.
+pub fn parse_graphql_body(body: &[u8]) -> Result<(String, serde_json::Map<String, JsonValue>)> {
+    let malformed = |message: String| RadError::MalformedRetrieval {
+        message: format!("invalid GraphQL body: {}", message),
+    };
+
+    let expected_array = || {
+        malformed(String::from(
+            "expected an array with a query string and an optional variables map",
+        ))
+    };
+
+    let value = serde_cbor::from_slice::<Value>(body).map_err(|e| malformed(e.to_string()))?;
+    let mut items = match value {
+        Value::Array(items) if (1..=2).contains(&items.len()) => items,
+        _ => return Err(expected_array()),
+    };
+    let variables = if items.len() == 2 { items.pop() } else { None }
+        .unwrap_or_else(|| Value::Map(Default::default()));
+    let query = match items.pop() {
+        Some(Value::Text(query)) => query,
+        _ => return Err(malformed(String::from("the query must be a string"))),
+    };
+
+    validate_query(&query).map_err(malformed)?;
+    let variables = match cbor_to_json(variables).map_err(malformed)? {
+        JsonValue::Object(variables) => variables,
+        _ => return Err(malformed(String::from("the variables must be a map"))),
+    };
+
+    Ok((query, variables))
+}
+
+/// Build the JSON body of the HTTP POST request for a GraphQL retrieval.
+pub fn graphql_request_body(body: &[u8]) -> Result<String> {
+    let (query, variables) = parse_graphql_body(body)?;
+
+    Ok(serde_json::json!({
+        "query": query,
+        "variables": variables,
+    })
+    .to_string())
+}
+
+/// Lightweight syntax check of a GraphQL query: delimiters must be balanced, strings terminated,
+/// there must be at least one selection set, and there must be no mutations nor subscriptions.
+fn validate_query(query: &str) -> std::result::Result<(), String> {
+    let mut closers = vec![];
+    let mut selection_sets = 0;
+    let mut chars = query.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '#' => {
+                // Comments run until the end of the line
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            '"' => loop {
+                match chars.next() {
+                    Some('\\') => {
+                        chars.next();
+                    }
+                    Some('"') => break,
+                    Some(_) => {}
+                    None => return Err(String::from("unterminated string")),
+                }
+            },
+            '{' => {
+                closers.push('}');
+                selection_sets += 1;
+            }
+            '(' => closers.push(')'),
+            '[' => closers.push(']'),
+            '}' | ')' | ']' => {
+                if closers.pop() != Some(c) {
+                    return Err(format!("unexpected `{}`", c));
+                }
+            }
+            // Keywords outside of any selection set tell the type of each operation
+            c if closers.is_empty() && (c.is_ascii_alphabetic() || c == '_') => {
+                let mut word = String::from(c);
+                while let Some(&c) = chars.peek() {
+                    if c.is_ascii_alphanumeric() || c == '_' {
+                        word.push(c);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                if word == "mutation" || word == "subscription" {
+                    return Err(format!("{} operations are not supported", word));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(closer) = closers.last() {
+        Err(format!("missing `{}`", closer))
+    } else if selection_sets == 0 {
+        Err(String::from("the query has no selection set"))
+    } else {
+        Ok(())
+    }
+}
+
+/// Convert the CBOR value of a GraphQL variable into JSON.
+pub(crate) fn cbor_to_json(value: Value) -> std::result::Result<JsonValue, String> {
+    Ok(match value {
+        Value::Null => JsonValue::Null,
+        Value::Bool(boolean) => JsonValue::Bool(boolean),
+        Value::Integer(integer) => {
+            if let Ok(integer) = i64::try_from(integer) {
+                JsonValue::from(integer)
+            } else if let Ok(integer) = u64::try_from(integer) {
+                JsonValue::from(integer)
+            } else {
+                return Err(format!("integer {} is out of range", integer));
+            }
+        }
+        Value::Float(float) => serde_json::Number::from_f64(float)
+            .map(JsonValue::Number)
+            .ok_or_else(|| format!("float {} cannot be represented in JSON", float))?,
+        Value::Text(text) => JsonValue::String(text),
+        Value::Array(items) => JsonValue::Array(
+            items
+                .into_iter()
+                .map(cbor_to_json)
+                .collect::<std::result::Result<_, _>>()?,
+        ),
+        Value::Map(map) => JsonValue::Object(
+            map.into_iter()
+                .map(|(key, value)| match key {
+                    Value::Text(key) => Ok((key, cbor_to_json(value)?)),
+                    _ => Err(String::from("map keys must be strings")),
+                })
+                .collect::<std::result::Result<_, _>>()?,
+        ),
+        other => return Err(format!("unsupported value {:?}", other)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    fn body(values: Vec<Value>) -> Vec<u8> {
+        serde_cbor::to_vec(&Value::Array(values)).unwrap()
+    }
+
+    #[test]
+    fn test_graphql_request_body() {
+        let mut variables = BTreeMap::new();
+        variables.insert(
+            Value::Text(String::from("id")),
+            Value::Text(String::from("0x1234")),
+        );
+        let query = "query($id: ID!) { token(id: $id) { price } }";
+
+        let json = graphql_request_body(&body(vec![
+            Value::Text(String::from(query)),
+            Value::Map(variables),
+        ]))
+        .unwrap();
+        assert_eq!(
+            serde_json::from_str::<JsonValue>(&json).unwrap(),
+            serde_json::json!({"query": query, "variables": {"id": "0x1234"}})
+        );
+
+        // Variables are optional
+        let json = graphql_request_body(&body(vec![Value::Text(String::from("{ a }"))])).unwrap();
+        assert_eq!(json, r#"{"query":"{ a }","variables":{}}"#);
+    }
+
+    #[test]
+    fn test_graphql_malformed_queries() {
+        let parse = |query: &str| parse_graphql_body(&body(vec![Value::Text(String::from(query))]));
+
+        for query in [
+            "",
+            "query",
+            "{ token { price }",
+            "{ token(id: 1 { price } }",
+            "{ token }}",
+            r#"{ token(id: "1) { price } }"#,
+            "mutation { burn(id: 1) { ok } }",
+            "subscription { price }",
+        ] {
+            assert!(
+                matches!(parse(query), Err(RadError::MalformedRetrieval { .. })),
+                "{:?} should be rejected",
+                query
+            );
+        }
+
+        // Delimiters within strings and comments do not count
+        assert!(parse("# not a mutation {\n{ token(id: \"}\") { price } }").is_ok());
+    }
+
+    #[test]
+    fn test_graphql_malformed_body() {
+        let malformed = |body: &[u8]| {
+            matches!(
+                parse_graphql_body(body),
+                Err(RadError::MalformedRetrieval { .. })
+            )
+        };
+
+        assert!(malformed(b"{ token { price } }"));
+        assert!(malformed(&body(vec![])));
+        assert!(malformed(&body(vec![Value::Integer(1)])));
+        assert!(malformed(&body(vec![
+            Value::Text(String::from("{ a }")),
+            Value::Text(String::from("not a map")),
+        ])));
+        assert!(malformed(&body(vec![
+            Value::Text(String::from("{ a }")),
+            Value::Map(BTreeMap::new()),
+            Value::Null,
+        ])));
+    }
+}
@@ -0,0 +1,19 @@
+use sha3::{Digest, Keccak256};
+
+/// Keccak-256, as used by Ethereum. This is the original Keccak submission and its padding differs
+/// from that of the standardized SHA3-256.
+pub fn keccak_256(input: &[u8]) -> Vec<u8> {
+    Keccak256::digest(input).to_vec()
+}
+
+#[test]
+fn test_keccak_256() {
+    assert_eq!(
+        hex::encode(keccak_256(b"")),
+        "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+    );
+    assert_eq!(
+        hex::encode(keccak_256(b"hello")),
+        "1c8aff950685c2ed4bc3174f3472287b56d9517b9c948127319a09a7a36deac8"
+    );
+}
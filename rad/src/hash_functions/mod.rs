@@ -5,6 +5,10 @@ use num_enum::{IntoPrimitive, TryFromPrimitive};
 use crate::error::RadError;
 use crate::hash_functions::sha2::sha2_256;
 
+pub use self::{keccak::keccak_256, ripemd::hash160};
+
+mod keccak;
+mod ripemd;
 mod sha2;
 
 #[derive(Debug, PartialEq, Eq, TryFromPrimitive, IntoPrimitive)]
@@ -0,0 +1,39 @@
+use ripemd160::{Digest, Ripemd160};
+
+use crate::hash_functions::sha2::sha2_256;
+
+pub fn ripemd_160(input: &[u8]) -> Vec<u8> {
+    Ripemd160::digest(input).to_vec()
+}
+
+/// RIPEMD-160 of the SHA2-256 of the input, as used in Bitcoin addresses.
+pub fn hash160(input: &[u8]) -> Vec<u8> {
+    ripemd_160(&sha2_256(input))
+}
+
+#[test]
+fn test_ripemd_160() {
+    assert_eq!(
+        hex::encode(ripemd_160(b"")),
+        "9c1185a5c5e9fc54612808977ee8f548b2258d31"
+    );
+    assert_eq!(
+        hex::encode(ripemd_160(b"abc")),
+        "8eb208f7e05d987a9b044a8e98c6b087f15a0bfc"
+    );
+}
+
+#[test]
+fn test_hash160() {
+    assert_eq!(
+        hex::encode(hash160(b"")),
+        "b472a266d0bd89c13706a4132ccfb16f7c3b9fcb"
+    );
+    // Compressed public key of the secp256k1 generator point
+    let public_key =
+        hex::decode("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798").unwrap();
+    assert_eq!(
+        hex::encode(hash160(&public_key)),
+        "751e76e8199196d454941c45d1b3a323f1433bd6"
+    );
+}
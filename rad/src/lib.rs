@@ -2,7 +2,12 @@
 
 extern crate witnet_data_structures;
 
-use futures::{executor::block_on, future::join_all, AsyncReadExt};
+use futures::{
+    executor::block_on,
+    future::{join_all, select, Either},
+    AsyncReadExt, Future,
+};
+use rand::{rngs::OsRng, RngCore};
 use serde::Serialize;
 pub use serde_cbor::{to_vec as cbor_to_vec, Value as CborValue};
 #[cfg(test)]
@@ -12,32 +17,56 @@ use witnet_data_structures::{
         tapi::{current_active_wips, ActiveWips},
         RADAggregate, RADRequest, RADRetrieve, RADTally, RADType,
     },
-    radon_report::{RadonReport, ReportContext, RetrievalMetadata, Stage, TallyMetaData},
-    witnessing::WitnessingConfig,
+    radon_report::{
+        AggregationMetaData, CapturedHttpRequest, RadonReport, ReportContext, RetrievalMetadata,
+        Stage, TallyMetaData,
+    },
+    witnessing::{IpVersion, TlsVersion, WitnessingConfig},
+};
+use witnet_net::client::http::{
+    IpVersion as HttpIpVersion, TlsVersion as HttpTlsVersion, WitnetHttpClient, WitnetHttpError,
+    WitnetHttpResolver, WitnetHttpTimeouts,
 };
-use witnet_net::client::http::WitnetHttpClient;
 pub use witnet_net::Uri;
 
 use crate::{
+    bandwidth::BandwidthLimiter,
+    chaining::{has_chained_retrievals, resolve_source_references, source_references},
     conditions::{evaluate_tally_precondition_clause, TallyPreconditionClauseResult},
     error::RadError,
+    metrics::RetrievalMetrics,
+    recording::RecordingMode,
+    retry::AttemptError,
     script::{
         create_radon_script_from_filters_and_reducer, execute_radon_script, unpack_radon_script,
-        RadonScriptExecutionSettings,
+        unpack_radon_script_with_active_wips, RadonScriptExecutionSettings,
     },
     types::{array::RadonArray, bytes::RadonBytes, map::RadonMap, string::RadonString, RadonTypes},
     user_agents::UserAgent,
 };
 use core::convert::From;
-use std::collections::BTreeMap;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use witnet_net::client::http::{WitnetHttpBody, WitnetHttpRequest};
 
+pub mod bandwidth;
+pub mod chaining;
 pub mod conditions;
 pub mod error;
 pub mod filters;
+pub mod graphql;
 pub mod hash_functions;
+pub mod metrics;
+pub mod named;
 pub mod operators;
+pub mod recording;
+pub mod redaction;
 pub mod reducers;
+pub mod retry;
+pub mod schema;
 pub mod script;
 pub mod types;
 pub mod user_agents;
@@ -51,27 +80,66 @@ pub struct RADRequestExecutionReport {
     pub aggregate: RadonReport<RadonTypes>,
     /// Vector of reports about retrieval of data sources.
     pub retrieve: Vec<RadonReport<RadonTypes>>,
+    /// How long it took to run the script of each data source, in the same order as `retrieve`.
+    /// Entries are `None` unless timing is enabled, and for sources that failed before running their
+    /// script.
+    pub timing: Vec<Option<Duration>>,
     /// Report about aggregation of reports (reveals, actually).
     pub tally: RadonReport<RadonTypes>,
 }
 
+/// Minimum ratio of data sources that must not be errors for the aggregation stage to take place,
+/// as used by witnesses.
+pub const DEFAULT_AGGREGATION_PRECONDITION_THRESHOLD: f64 = 0.2;
+
 /// Executes a data request locally.
 /// The `inputs_injection` allows for disabling the actual retrieval of the data sources and
 /// the provided strings will be fed to the retrieval scripts instead. It is therefore expected that
 /// the length of `sources_injection` matches that of `request.retrieve`.
+///
+/// The `precondition_threshold` is the minimum ratio of data sources that must succeed (or fail
+/// with the same error) for the aggregation to run, measured against the number of data sources.
+/// If not given, `DEFAULT_AGGREGATION_PRECONDITION_THRESHOLD` is used.
+///
+/// This threshold is unrelated to the `min_consensus_percentage` of the data request, which the
+/// tally of an actual data request checks against the reveals of all of its witnesses. As this
+/// function simulates a single witness, its tally stage only ever sees one reveal, so that
+/// percentage plays no role here.
+///
+/// The `active_wips` allow pinning the set of active WIPs, which makes the results reproducible
+/// regardless of the global protocol state. If not given, `current_active_wips()` is used.
 pub fn try_data_request(
     request: &RADRequest,
     settings: RadonScriptExecutionSettings,
     inputs_injection: Option<&[&str]>,
     witnessing: Option<WitnessingConfig<witnet_net::Uri>>,
     too_many_witnesses: bool,
+    precondition_threshold: Option<f64>,
+    active_wips: Option<ActiveWips>,
 ) -> RADRequestExecutionReport {
     #[cfg(not(test))]
-    let active_wips = current_active_wips();
+    let active_wips = active_wips.unwrap_or_else(current_active_wips);
     #[cfg(test)]
-    let active_wips = all_wips_active();
+    let active_wips = active_wips.unwrap_or_else(all_wips_active);
+    let bandwidth = witnessing
+        .as_ref()
+        .and_then(|witnessing| witnessing.max_download_rate)
+        .map(|rate| Arc::new(BandwidthLimiter::new(rate)));
     let mut retrieval_context =
         ReportContext::from_stage(Stage::Retrieval(RetrievalMetadata::default()));
+    retrieval_context.set_active_wips(active_wips.clone());
+    let witnessing = witnessing.unwrap_or_default();
+    // In paranoid mode, all the sources share the same client for each transport, so that
+    // connections to the same host are reused. Otherwise, each source only builds the client of the
+    // transport that it uses. If the clients cannot be built, every source reports the error on
+    // its own.
+    let clients = if witnessing.paranoid {
+        configured_transports(&witnessing)
+            .and_then(|transports| transport_clients(transports, &witnessing, &active_wips))
+            .ok()
+    } else {
+        None
+    };
     let retrieve_responses = if let Some(inputs) = inputs_injection {
         assert_eq!(inputs.len(), request.retrieve.len(), "Tried to locally run a data request with a number of injected sources different than the number of retrieval paths ({} != {})", inputs.len(), request.retrieve.len());
 
@@ -83,18 +151,50 @@ pub fn try_data_request(
                 run_retrieval_with_data_report(retrieve, input, &mut retrieval_context, settings)
             })
             .collect()
+    } else if has_chained_retrievals(&request.retrieve, &active_wips) {
+        // Sources that need the results of others are retrieved one after the other. They all
+        // share the time that a single source may take, so that chaining sources does not make
+        // the whole retrieval take any longer than retrieving them in parallel.
+        let deadline = chained_retrieval_budget(&witnessing).map(|budget| Instant::now() + budget);
+        let mut reports: Vec<RadonReport<RadonTypes>> = Vec::with_capacity(request.retrieve.len());
+        for retrieve in &request.retrieve {
+            let report = match resolve_source_references(retrieve, &reports) {
+                Ok(retrieve) => block_on(with_deadline(
+                    paranoid_retrieval(
+                        &retrieve,
+                        request.aggregate.clone(),
+                        settings,
+                        active_wips.clone(),
+                        witnessing.clone(),
+                        bandwidth.clone(),
+                        clients.as_deref(),
+                    ),
+                    deadline,
+                )),
+                Err(error) => Err(error),
+            };
+            reports.push(
+                report.unwrap_or_else(|error| {
+                    RadonReport::from_result(Err(error), &retrieval_context)
+                }),
+            );
+        }
+
+        reports.into_iter().map(Ok).collect()
     } else {
         block_on(join_all(
             request
                 .retrieve
                 .iter()
                 .map(|retrieve| {
-                    run_paranoid_retrieval(
+                    paranoid_retrieval(
                         retrieve,
                         request.aggregate.clone(),
                         settings,
                         active_wips.clone(),
-                        witnessing.clone().unwrap_or_default(),
+                        witnessing.clone(),
+                        bandwidth.clone(),
+                        clients.as_deref(),
                     )
                 })
                 .collect::<Vec<_>>(),
@@ -112,11 +212,15 @@ pub fn try_data_request(
     // Evaluate aggregation pre-condition by using the same logic than for tally pre-condition,
     // to ensure that at least 20% of the data sources are not errors.
     // Aggregation stage does not need to evaluate any post-condition.
+    // The ratio is measured against the number of data sources, whether the threshold is given or
+    // not, so that explicitly passing the default threshold is the same as leaving it unset.
+    let precondition_threshold =
+        precondition_threshold.unwrap_or(DEFAULT_AGGREGATION_PRECONDITION_THRESHOLD);
     let clause_result = evaluate_tally_precondition_clause(
         retrieval_reports.clone(),
-        0.2,
-        1,
-        &current_active_wips(),
+        precondition_threshold,
+        retrieval_reports.len(),
+        &active_wips,
         too_many_witnesses,
     );
 
@@ -156,8 +260,14 @@ pub fn try_data_request(
     let tally_report =
         tally_result.unwrap_or_else(|error| RadonReport::from_result(Err(error), &tally_context));
 
+    let timing = retrieval_reports
+        .iter()
+        .map(|report| report.context.running_time())
+        .collect();
+
     RADRequestExecutionReport {
         retrieve: retrieval_reports,
+        timing,
         aggregate: aggregation_report,
         tally: tally_report,
     }
@@ -228,6 +338,7 @@ pub fn run_retrieval_with_data_report(
         RADType::HttpHead => {
             headers_response_with_data_report(retrieve, response, context, settings)
         }
+        RADType::GraphQl => string_response_with_data_report(retrieve, response, context, settings),
         _ => Err(RadError::UnknownRetrieval),
     }
 }
@@ -245,20 +356,76 @@ pub fn run_retrieval_with_data(
         .map(RadonReport::into_inner)
 }
 
+/// Parse the URL of an HTTP retrieval, making sure that it uses one of the supported schemes, so
+/// that we handle malformed or unsupported URLs nicely before they hit any library.
+fn parse_retrieval_url(retrieve: &RADRetrieve) -> Result<url::Url> {
+    let url = url::Url::parse(&retrieve.url).map_err(|err| RadError::UrlParseError {
+        inner: err,
+        url: retrieve.url.clone(),
+    })?;
+
+    match url.scheme() {
+        "http" | "https" => Ok(url),
+        scheme => Err(RadError::UnsupportedScheme {
+            scheme: scheme.to_string(),
+            url: retrieve.url.clone(),
+        }),
+    }
+}
+
 /// Handle generic HTTP (GET/POST/HEAD) response
+#[allow(clippy::too_many_arguments)]
 async fn http_response(
     retrieve: &RADRetrieve,
+    url: url::Url,
     context: &mut ReportContext<RadonTypes>,
     settings: RadonScriptExecutionSettings,
     client: Option<WitnetHttpClient>,
+    recording: Option<&RecordingMode>,
+    bandwidth: Option<&BandwidthLimiter>,
+    witnessing: Option<&WitnessingConfig<witnet_net::Uri>>,
 ) -> Result<RadonReport<RadonTypes>> {
-    // Validate URL to make sure that we handle malformed URLs nicely before they hit any library
-    if let Err(err) = url::Url::parse(&retrieve.url) {
-        Err(RadError::UrlParseError {
-            inner: err,
+    let require_tls = witnessing.map_or(false, |witnessing| witnessing.require_tls);
+    if require_tls && url.scheme() != "https" {
+        return Err(RadError::InsecureTransport {
             url: retrieve.url.clone(),
-        })?
-    };
+        });
+    }
+
+    // When replaying, serve the recorded response without performing any networking at all
+    if let Some(RecordingMode::Replay(recording)) = recording {
+        let response = recording.get(retrieve).ok_or_else(|| RadError::HttpOther {
+            message: format!("No recorded response for URL {}", retrieve.url),
+        })?;
+
+        return run_retrieval_with_data_report(retrieve, response, context, settings);
+    }
+
+    let user_agent = witnessing.and_then(|witnessing| witnessing.user_agent.as_deref());
+    let redacted_headers =
+        witnessing.map_or(&[][..], |witnessing| witnessing.redacted_headers.as_slice());
+    log::trace!(
+        "Retrieving source {} with headers {:?}",
+        retrieve.url,
+        redaction::redact_headers(&retrieve.headers, redacted_headers)
+    );
+
+    // When capturing, report the request that would have been sent instead of sending it
+    if let Some(RecordingMode::Capture) = recording {
+        let request = build_http_request(retrieve, user_agent)?;
+        let http_request = capture_http_request(request).await?;
+        if let Stage::Retrieval(metadata) = &mut context.stage {
+            metadata.http_request = Some(http_request);
+        }
+
+        // No response was obtained, so there is nothing for the script to run on
+        return Ok(RadonReport::from_result(
+            Err(RadError::RequestCaptured {
+                url: retrieve.url.clone(),
+            }),
+            context,
+        ));
+    }
 
     // Use the provided HTTP client, or instantiate a new one if none
     let client = match client {
@@ -270,76 +437,94 @@ async fn http_response(
                 .map(|active_wips| active_wips.wip0025())
                 .unwrap_or(true);
 
-            WitnetHttpClient::new(None, follow_redirects).map_err(|err| RadError::HttpOther {
+            let timeouts = witnessing.map(http_timeouts).unwrap_or_default();
+            let resolver = witnessing.map(http_resolver).unwrap_or_default();
+            let min_tls_version = witnessing.and_then(http_min_tls_version);
+
+            WitnetHttpClient::with_settings(
+                None,
+                follow_redirects,
+                timeouts,
+                resolver,
+                min_tls_version,
+            )
+            .map_err(|err| RadError::HttpOther {
                 message: err.to_string(),
             })?
         }
     };
 
-    let request = WitnetHttpRequest::build(|builder| {
-        // Populate the builder and generate the body for different types of retrievals
-        let (builder, body) = match retrieve.kind {
-            RADType::HttpGet => (
-                builder.method("GET").uri(&retrieve.url),
-                WitnetHttpBody::empty(),
-            ),
-            RADType::HttpPost => {
-                // Using `Vec<u8>` as the body sets the content type header to `application/octet-stream`
-                (
-                    builder.method("POST").uri(&retrieve.url),
-                    WitnetHttpBody::from(retrieve.body.clone()),
-                )
-            }
-            RADType::HttpHead => (
-                builder.method("HEAD").uri(&retrieve.url),
-                WitnetHttpBody::empty(),
-            ),
-            _ => panic!(
-                "Called http_response with invalid retrieval kind {:?}",
-                retrieve.kind
-            ),
-        };
-
-        // Add random user agent
-        let mut builder = builder.header("User-Agent", UserAgent::random());
-
-        // Add extra_headers from retrieve.headers
-        for (name, value) in &retrieve.headers {
-            // Handle invalid header names and values with a specific and friendly error message
-            validate_header(name, value)?;
-
-            builder = builder.header(name, value);
+    // Only connection errors and 5xx status codes are worth retrying, as anything else would fail
+    // again in the exact same way
+    let client = &client;
+    let retry_policy = witnessing.and_then(|witnessing| witnessing.retry.as_ref());
+    let (response, final_url) = retry::retry(retry_policy, move || async move {
+        let request = build_http_request(retrieve, user_agent).map_err(AttemptError::Permanent)?;
+
+        // TLS errors, such as expired certificates, are not going away by retrying
+        let response = client.send(request).await.map_err(|x| match x {
+            WitnetHttpError::TlsError { msg } => AttemptError::Permanent(RadError::TlsError {
+                url: retrieve.url.clone(),
+                message: msg,
+            }),
+            x => AttemptError::Transient(RadError::HttpOther {
+                message: x.to_string(),
+            }),
+        })?;
+        let final_url = response.effective_uri().map(ToString::to_string);
+        let response = response.inner();
+
+        let status = response.status();
+        if status.is_server_error() {
+            Err(AttemptError::Transient(RadError::HttpStatus {
+                status_code: status.into(),
+            }))
+        } else if !status.is_success() {
+            Err(AttemptError::Permanent(RadError::HttpStatus {
+                status_code: status.into(),
+            }))
+        } else {
+            Ok((response, final_url))
         }
+    })
+    .await?;
 
-        // Finally attach the body to complete building the HTTP request
-        builder.body(body).map_err(|e| RadError::HttpOther {
-            message: e.to_string(),
-        })
-    })?;
-
-    let response = client
-        .send(request)
-        .await
-        .map_err(|x| RadError::HttpOther {
-            message: x.to_string(),
-        })?
-        .inner();
-
-    if !response.status().is_success() {
-        return Err(RadError::HttpStatus {
-            status_code: response.status().into(),
-        });
+    // Keep track of where the request ended up, as redirects could be used to tamper with sources
+    if let Stage::Retrieval(metadata) = &mut context.stage {
+        metadata.final_url = Some(final_url.unwrap_or_else(|| url.to_string()));
     }
 
     // If at some point we want to support the retrieval of non-UTF8 data (e.g. raw bytes), this is
     // where we need to decide how to read the response body
     let (_parts, mut body) = response.into_parts();
     let mut response_string = String::default();
-    body.read_to_string(&mut response_string)
-        .await
-        .map_err(|x| RadError::HttpOther {
-            message: x.to_string(),
-        })?;
+    match bandwidth {
+        Some(bandwidth) => {
+            bandwidth
+                .read_to_string(&mut body, &mut response_string)
+                .await
+        }
+        None => body.read_to_string(&mut response_string).await,
+    }
+    .map_err(|x| RadError::HttpOther {
+        message: x.to_string(),
+    })?;
+
+    // The headers are handed to the script as they are, as witnesses must agree on them, but they
+    // may carry credentials that must not end up in logs
+    if retrieve.kind == RADType::HttpHead {
+        log::trace!(
+            "Retrieved headers from source {}: {:?}",
+            retrieve.url,
+            redaction::redact_header_lines(&response_string, redacted_headers)
+        );
+    }
+
+    if let Some(RecordingMode::Record(recording)) = recording {
+        if let Ok(mut recording) = recording.lock() {
+            recording.insert(retrieve, &response_string);
+        }
+    }
 
     let result = run_retrieval_with_data_report(retrieve, &response_string, context, settings);
 
@@ -357,17 +542,23 @@ async fn http_response(
     result
 }
 
-/// Handle Rng response
+/// Handle Rng response. The 32 random bytes are taken from `rng` if provided, or from `OsRng`
+/// otherwise.
 async fn rng_response(
     context: &mut ReportContext<RadonTypes>,
     settings: RadonScriptExecutionSettings,
+    rng: Option<&mut (dyn RngCore + Send)>,
 ) -> Result<RadonReport<RadonTypes>> {
     // Set the execution start timestamp, if enabled by `timing` setting
     if settings.timing {
         context.start();
     }
 
-    let random_bytes: [u8; 32] = rand::random();
+    let mut random_bytes = [0u8; 32];
+    match rng {
+        Some(rng) => rng.fill_bytes(&mut random_bytes),
+        None => OsRng.fill_bytes(&mut random_bytes),
+    }
     let random_bytes = RadonTypes::from(RadonBytes::from(random_bytes.to_vec()));
 
     // Set the completion timestamp, if enabled by `timing` settings
@@ -379,20 +570,103 @@ async fn rng_response(
 }
 
 /// Run retrieval stage of a data request, return `Result<RadonReport>`.
+///
+/// RNG sources get their randomness from `rng` if provided, which allows seeding the generator
+/// for reproducing a specific result in tests. Otherwise, `OsRng` is used.
 pub async fn run_retrieval_report(
     retrieve: &RADRetrieve,
     settings: RadonScriptExecutionSettings,
     active_wips: ActiveWips,
     client: Option<WitnetHttpClient>,
+    rng: Option<&mut (dyn RngCore + Send)>,
+) -> Result<RadonReport<RadonTypes>> {
+    retrieval_report(
+        retrieve,
+        settings,
+        active_wips,
+        client,
+        None,
+        None,
+        None,
+        rng,
+    )
+    .await
+}
+
+/// Run retrieval stage of a data request through a long-lived HTTP client, return
+/// `Result<RadonReport>`.
+///
+/// Clones of a `WitnetHttpClient` share its connection pool, so running many retrievals with the
+/// same client avoids opening a new connection (and going through a new TLS handshake) for each of
+/// them. The client is used as is, so any proxy, redirect policy or timeouts must be set on it.
+pub async fn run_retrieval_report_with_client(
+    retrieve: &RADRetrieve,
+    settings: RadonScriptExecutionSettings,
+    active_wips: ActiveWips,
+    client: &WitnetHttpClient,
+) -> Result<RadonReport<RadonTypes>> {
+    retrieval_report(
+        retrieve,
+        settings,
+        active_wips,
+        Some(client.clone()),
+        None,
+        None,
+        None,
+        None,
+    )
+    .await
+}
+
+/// Run retrieval stage of a data request while either recording the HTTP responses into, or
+/// replaying them from, an `HttpRecording`. Return `Result<RadonReport>`.
+///
+/// In replay mode, no external network requests are performed, which makes tests that depend on
+/// live data sources reproducible.
+pub async fn run_retrieval_report_with_recording(
+    retrieve: &RADRetrieve,
+    settings: RadonScriptExecutionSettings,
+    active_wips: ActiveWips,
+    client: Option<WitnetHttpClient>,
+    recording: &RecordingMode,
+) -> Result<RadonReport<RadonTypes>> {
+    retrieval_report(
+        retrieve,
+        settings,
+        active_wips,
+        client,
+        Some(recording),
+        None,
+        None,
+        None,
+    )
+    .await
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn retrieval_report(
+    retrieve: &RADRetrieve,
+    settings: RadonScriptExecutionSettings,
+    active_wips: ActiveWips,
+    client: Option<WitnetHttpClient>,
+    recording: Option<&RecordingMode>,
+    bandwidth: Option<&BandwidthLimiter>,
+    witnessing: Option<&WitnessingConfig<witnet_net::Uri>>,
+    rng: Option<&mut (dyn RngCore + Send)>,
 ) -> Result<RadonReport<RadonTypes>> {
     let context = &mut ReportContext::from_stage(Stage::Retrieval(RetrievalMetadata::default()));
     context.set_active_wips(active_wips);
 
     match retrieve.kind {
-        RADType::HttpGet => http_response(retrieve, context, settings, client).await,
-        RADType::Rng => rng_response(context, settings).await,
-        RADType::HttpPost => http_response(retrieve, context, settings, client).await,
-        RADType::HttpHead => http_response(retrieve, context, settings, client).await,
+        RADType::HttpGet | RADType::HttpPost | RADType::HttpHead | RADType::GraphQl => {
+            let url = parse_retrieval_url(retrieve)?;
+
+            http_response(
+                retrieve, url, context, settings, client, recording, bandwidth, witnessing,
+            )
+            .await
+        }
+        RADType::Rng => rng_response(context, settings, rng).await,
         _ => Err(RadError::UnknownRetrieval),
     }
 }
@@ -405,6 +679,7 @@ pub async fn run_retrieval(retrieve: &RADRetrieve, active_wips: ActiveWips) -> R
         RadonScriptExecutionSettings::disable_all(),
         active_wips,
         None,
+        None,
     )
     .await
     .map(RadonReport::into_inner)
@@ -416,40 +691,169 @@ pub async fn run_retrieval(retrieve: &RADRetrieve, active_wips: ActiveWips) -> R
 /// The idea behind this is to avoid producing commitments for data requests with sources that act
 /// in an inconsistent way, i.e. they return very different values when queried through different
 /// HTTP transports at once.
+///
+/// Transient failures are retried according to the retry policy in `witnessing`, if any, and
+/// plain HTTP sources are rejected if `witnessing` requires TLS. If a `BandwidthLimiter` is
+/// provided, the download of the responses from all transports counts towards its limit. If a
+/// `RetrievalMetrics` hook is provided, the final outcome is reported to it.
 pub async fn run_paranoid_retrieval(
     retrieve: &RADRetrieve,
     aggregate: RADAggregate,
     settings: RadonScriptExecutionSettings,
     active_wips: ActiveWips,
     witnessing: WitnessingConfig<witnet_net::Uri>,
+    bandwidth: Option<Arc<BandwidthLimiter>>,
+    metrics: Option<Arc<dyn RetrievalMetrics>>,
 ) -> Result<RadonReport<RadonTypes>> {
-    // We can skip paranoid checks for retrieval types that don't use networking (e.g. RNG)
-    if !retrieve.kind.is_http() {
-        return run_retrieval_report(retrieve, settings, active_wips, None).await;
+    let result = paranoid_retrieval(
+        retrieve,
+        aggregate,
+        settings,
+        active_wips,
+        witnessing,
+        bandwidth,
+        None,
+    )
+    .await;
+
+    if let Some(metrics) = metrics {
+        metrics.record_retrieval(&result);
     }
 
-    let futures: Result<Vec<_>> = witnessing
+    result
+}
+
+/// An HTTP client, along with the transport that it goes through (`None` for direct connections).
+type TransportClient = (Option<witnet_net::Uri>, WitnetHttpClient);
+
+/// All the transports in `witnessing`.
+fn configured_transports(
+    witnessing: &WitnessingConfig<witnet_net::Uri>,
+) -> Result<Vec<Option<witnet_net::Uri>>> {
+    witnessing
         .transports_as::<witnet_net::Uri>()
         .map_err(|(_, err)| RadError::HttpOther {
             message: err.to_string(),
-        })?
+        })
+}
+
+/// The transports that the next retrieval goes through: all of them in paranoid mode, or else a
+/// single one chosen according to the rotation policy.
+fn used_transports(
+    witnessing: &WitnessingConfig<witnet_net::Uri>,
+) -> Result<Vec<Option<witnet_net::Uri>>> {
+    let transports = configured_transports(witnessing)?;
+    if witnessing.paranoid || transports.is_empty() {
+        return Ok(transports);
+    }
+    let index = witnessing.rotation.next_transport(transports.len());
+
+    Ok(transports.into_iter().skip(index).take(1).collect())
+}
+
+/// Build one HTTP client for each of the given transports.
+fn transport_clients(
+    transports: Vec<Option<witnet_net::Uri>>,
+    witnessing: &WitnessingConfig<witnet_net::Uri>,
+    active_wips: &ActiveWips,
+) -> Result<Vec<TransportClient>> {
+    let follow_redirects = active_wips.wip0025();
+
+    transports
         .into_iter()
         .map(|transport| {
-            let follow_redirects = active_wips.wip0025();
-
-            WitnetHttpClient::new(transport, follow_redirects)
-                .map_err(|err| RadError::HttpOther {
-                    message: err.to_string(),
-                })
-                .map(|client| {
-                    run_retrieval_report(retrieve, settings, active_wips.clone(), Some(client))
-                })
+            WitnetHttpClient::with_settings(
+                transport.clone(),
+                follow_redirects,
+                http_timeouts(witnessing),
+                http_resolver(witnessing),
+                http_min_tls_version(witnessing),
+            )
+            .map(|client| (transport, client))
+            .map_err(|err| RadError::HttpOther {
+                message: err.to_string(),
+            })
         })
-        .collect();
+        .collect()
+}
+
+/// Retrieve a source through every transport, using the given clients (one per transport) or
+/// building new ones, only for the transports that are actually used, if none are given.
+async fn paranoid_retrieval(
+    retrieve: &RADRetrieve,
+    aggregate: RADAggregate,
+    settings: RadonScriptExecutionSettings,
+    active_wips: ActiveWips,
+    witnessing: WitnessingConfig<witnet_net::Uri>,
+    bandwidth: Option<Arc<BandwidthLimiter>>,
+    clients: Option<&[TransportClient]>,
+) -> Result<RadonReport<RadonTypes>> {
+    // We can skip paranoid checks for retrieval types that don't use networking (e.g. RNG)
+    if !retrieve.kind.is_http() {
+        return run_retrieval_report(retrieve, settings, active_wips, None, None).await;
+    }
+
+    // Fail early on malformed URLs, otherwise the error would be reported once per transport and
+    // end up hidden behind an `InconsistentSource` error
+    parse_retrieval_url(retrieve)?;
+
+    // Unless paranoid mode is disabled, in which case a single transport, chosen according to the
+    // rotation policy, is trusted on its own
+    let new_clients;
+    let clients = match clients {
+        Some(clients) if witnessing.paranoid || clients.is_empty() => clients,
+        Some(clients) => {
+            let index = witnessing.rotation.next_transport(clients.len());
+            &clients[index..=index]
+        }
+        None => {
+            new_clients =
+                transport_clients(used_transports(&witnessing)?, &witnessing, &active_wips)?;
+            new_clients.as_slice()
+        }
+    };
+    let futures = clients.iter().map(|(_, client)| {
+        retrieval_report(
+            retrieve,
+            settings,
+            active_wips.clone(),
+            Some(client.clone()),
+            None,
+            bandwidth.as_deref(),
+            Some(&witnessing),
+            None,
+        )
+    });
+
+    let values = join_all(futures).await;
 
-    let values = join_all(futures?).await;
+    evaluate_paranoid_retrieval_with_diagnostics(
+        values,
+        aggregate,
+        settings,
+        witnessing.paranoid_threshold,
+    )
+    .map_err(|error| {
+        if let ParanoidRetrievalError::Disagreement(disagreement) = &error {
+            let outliers = disagreement
+                .outlier_transports()
+                .into_iter()
+                .map(|index| match clients.get(index) {
+                    Some((Some(proxy), _)) => proxy.to_string(),
+                    _ => String::from("direct connection"),
+                })
+                .collect::<Vec<_>>();
+            log::warn!(
+                "Transports disagreed when retrieving {}: outliers {:?}, consensus {:?}, values {:?}",
+                retrieve.url,
+                outliers,
+                disagreement.consensus,
+                disagreement.values,
+            );
+        }
 
-    evaluate_paranoid_retrieval(values, aggregate, settings, witnessing.paranoid_threshold)
+        RadError::from(error)
+    })
 }
 
 /// Evaluate whether the values obtained when retrieving a data source through multiple transports
@@ -463,12 +867,72 @@ pub async fn run_paranoid_retrieval(
 /// 3. The values that we got from different transports cannot be aggregated together.
 /// 4. The result of applying the aggregation on the data coming from the different transports
 ///    reached a level of consensus that is lower than the configured paranoid threshold.
+///
+/// The HTTP requests sent through different transports may end up at different hosts after
+/// following redirects, e.g. because of CDNs or geo-DNS. That is not an error, but the hosts are
+/// logged and recorded in the `divergent_final_hosts` of the report. Only the scheme and the host
+/// are compared, as the rest of the URL may legitimately change on every request, e.g. when
+/// redirecting to signed or tokenized URLs.
 fn evaluate_paranoid_retrieval(
     data: Vec<Result<RadonReport<RadonTypes>>>,
     aggregate: RADAggregate,
     settings: RadonScriptExecutionSettings,
     paranoid: f32,
 ) -> Result<RadonReport<RadonTypes>> {
+    evaluate_paranoid_retrieval_with_diagnostics(data, aggregate, settings, paranoid)
+        .map_err(RadError::from)
+}
+
+/// Which transports of a paranoid retrieval disagreed with the rest, and how much.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TransportDisagreement {
+    /// The value retrieved through each of the transports that were used, in the same order as
+    /// they are configured. Failed retrievals appear as `RadonTypes::RadonError`.
+    pub values: Vec<RadonTypes>,
+    /// Whether each transport was found to be an outlier. When the values could not be aggregated
+    /// at all, there is no way to tell the outliers apart and none of them is flagged.
+    pub outliers: Vec<bool>,
+    /// Consensus that was achieved among the transports, if the values could be aggregated.
+    pub consensus: Option<f32>,
+}
+
+impl TransportDisagreement {
+    /// Indexes of the transports that were found to be outliers.
+    pub fn outlier_transports(&self) -> Vec<usize> {
+        self.outliers
+            .iter()
+            .enumerate()
+            .filter_map(|(index, &outlier)| outlier.then_some(index))
+            .collect()
+    }
+}
+
+/// The ways in which a paranoid retrieval can fail.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParanoidRetrievalError {
+    /// The retrieval failed on its own, regardless of the transports.
+    Retrieval(RadError),
+    /// The transports disagreed with each other. This is reported as `InconsistentSource`.
+    Disagreement(TransportDisagreement),
+}
+
+impl From<ParanoidRetrievalError> for RadError {
+    fn from(error: ParanoidRetrievalError) -> Self {
+        match error {
+            ParanoidRetrievalError::Retrieval(error) => error,
+            ParanoidRetrievalError::Disagreement(_) => RadError::InconsistentSource,
+        }
+    }
+}
+
+/// Same as `evaluate_paranoid_retrieval`, but telling which transports disagreed with the rest
+/// whenever the result is `InconsistentSource`, so that misbehaving proxies can be identified.
+pub fn evaluate_paranoid_retrieval_with_diagnostics(
+    data: Vec<Result<RadonReport<RadonTypes>>>,
+    aggregate: RADAggregate,
+    settings: RadonScriptExecutionSettings,
+    paranoid: f32,
+) -> std::result::Result<RadonReport<RadonTypes>, ParanoidRetrievalError> {
     // If there was only one retrieved value, there's no actual need to run the tally, as this means
     // that only one transport was used and therefore the node is not in paranoid mode.
     // We can simply return the first report as is.
@@ -478,54 +942,96 @@ fn evaluate_paranoid_retrieval(
             .next()
             // Case 1
             .ok_or(RadError::InconsistentSource)
-            .and_then(|r| r);
+            .and_then(|r| r)
+            .map_err(ParanoidRetrievalError::Retrieval);
     }
 
-    // Case 2
-    let reports = data
-        .into_iter()
-        .collect::<Result<Vec<_>>>()
-        .or(Err(RadError::InconsistentSource))?;
-    let values = reports
+    let values: Vec<RadonTypes> = data
         .iter()
-        .cloned()
-        .map(RadonReport::into_inner)
+        .map(|report| match report {
+            Ok(report) => report.result.clone(),
+            Err(error) => {
+                RadonReport::from_result(Err(error.clone()), &ReportContext::default()).result
+            }
+        })
         .collect();
+    let disagreement = |outliers: Vec<bool>, consensus| {
+        ParanoidRetrievalError::Disagreement(TransportDisagreement {
+            values: values.clone(),
+            outliers,
+            consensus,
+        })
+    };
 
-    // This block is using a Tally context because Aggregate contexts currently do not keep
-    // track of outliers.
-    // Additionally, the `RADAggregate` struct is converted into `RADTally` for the same reason.
-    // In the future, if we think that is an interesting feature (e.g. for
-    // debugging data sources through `witnet_toolkit`), we can refactor `AggregateMetaData` and
-    // avoid these tricks here.
-    let mut context = ReportContext::from_stage(Stage::Tally(TallyMetaData::default()));
-    let consensus = RADTally::from(aggregate);
-    let tally = run_tally_with_context_report(values, &consensus, &mut context, settings)
-        // Case 3
-        .or(Err(RadError::InconsistentSource))?;
+    // Case 2
+    let failed = data.iter().map(Result::is_err).collect::<Vec<_>>();
+    if failed.contains(&true) {
+        return Err(disagreement(failed, None));
+    }
+    let reports = data.into_iter().flatten().collect::<Vec<_>>();
+
+    let final_hosts = reports
+        .iter()
+        .filter_map(|report| report.final_url().map(scheme_and_host))
+        .collect::<BTreeSet<_>>();
+    let divergent_final_hosts = if final_hosts.len() > 1 {
+        log::warn!(
+            "Paranoid retrieval landed on different hosts depending on the transport: {:?}",
+            final_hosts
+        );
+
+        final_hosts.into_iter().collect()
+    } else {
+        vec![]
+    };
+
+    let mut context = ReportContext::from_stage(Stage::Aggregation(AggregationMetaData::default()));
+    let aggregation =
+        run_aggregation_with_context_report(values.clone(), aggregate, &mut context, settings)
+            // Case 3
+            .map_err(|_| disagreement(vec![false; values.len()], None))?;
 
     // If the consensus of the data points is below the paranoid threshold of the node, we need
     // to resolve to the `InconsistentSource` error.
-    if let Stage::Tally(TallyMetaData { consensus, .. }) = context.stage {
+    if let Some(consensus) = aggregation.consensus() {
         if consensus < paranoid {
+            let liars = aggregation
+                .liars()
+                .map(<[bool]>::to_vec)
+                .unwrap_or_else(|| vec![false; values.len()]);
+
             // Case 4
-            return Err(RadError::InconsistentSource);
+            return Err(disagreement(liars, Some(consensus)));
         }
     }
 
     // If all the values pass the filters, return one of the reports, but swap the result for
-    // that of the tally, so the potentially committed value is already averaged across the
+    // that of the aggregation, so the potentially committed value is already averaged across the
     // multiple transports.
     // Case 1 as well
     let mut report = reports
         .into_iter()
         .next()
-        .ok_or(RadError::InconsistentSource)?;
-    report.result = tally.result;
+        .ok_or(ParanoidRetrievalError::Retrieval(
+            RadError::InconsistentSource,
+        ))?;
+    report.result = aggregation.result;
+    if let Stage::Retrieval(metadata) = &mut report.context.stage {
+        metadata.divergent_final_hosts = divergent_final_hosts;
+    }
 
     Ok(report)
 }
 
+/// The scheme and the host of a URL, which is all that paranoid retrievals compare of the URLs
+/// that the transports landed on. URLs that cannot be parsed are compared as a whole.
+fn scheme_and_host(url: &str) -> String {
+    match url::Url::parse(url) {
+        Ok(url) => format!("{}://{}", url.scheme(), url.host_str().unwrap_or_default()),
+        Err(_) => url.to_string(),
+    }
+}
+
 /// Run aggregate stage of a data request, return a tuple of `Result<RadonReport>` and `ReportContext`
 pub fn run_aggregation_report(
     radon_types_vec: Vec<RadonTypes>,
@@ -533,7 +1039,7 @@ pub fn run_aggregation_report(
     settings: RadonScriptExecutionSettings,
     active_wips: &ActiveWips,
 ) -> (Result<RadonReport<RadonTypes>>, ReportContext<RadonTypes>) {
-    let mut context = ReportContext::from_stage(Stage::Aggregation);
+    let mut context = ReportContext::from_stage(Stage::Aggregation(AggregationMetaData::default()));
     context.set_active_wips(active_wips.clone());
 
     let aux =
@@ -662,24 +1168,241 @@ pub fn run_tally(
     res.map(RadonReport::into_inner)
 }
 
-/// Centralizes validation of header names and values.
-///
-/// ASCII checks are always run before `try_from` to prevent panics in the `http` library.
-fn validate_header(name: &str, value: &str) -> Result<()> {
-    let mut error_message = None;
-    if name.is_ascii() {
-        if let Err(err) = http::HeaderName::try_from(name) {
-            error_message = Some(err.to_string())
-        } else if value.is_ascii() {
-            if let Err(err) = http::HeaderValue::try_from(value) {
-                error_message = Some(err.to_string())
-            }
-        } else {
-            error_message = Some("invalid HTTP header value".to_string())
-        }
-    } else {
-        error_message = Some("invalid HTTP header name".to_string())
-    };
+/// Total time that the sources of a request with chained retrievals may take, which is the time
+/// that a single source may spend connecting and then waiting for data. `None` means no limit, as
+/// for sources retrieved in parallel.
+fn chained_retrieval_budget(witnessing: &WitnessingConfig<witnet_net::Uri>) -> Option<Duration> {
+    match (witnessing.connect_timeout, witnessing.read_timeout) {
+        (None, None) => None,
+        (connect, read) => Some(connect.unwrap_or_default() + read.unwrap_or_default()),
+    }
+}
+
+/// Run a retrieval, failing with `RadError::RetrieveTimeout` if it is not done by the deadline.
+async fn with_deadline(
+    retrieval: impl Future<Output = Result<RadonReport<RadonTypes>>>,
+    deadline: Option<Instant>,
+) -> Result<RadonReport<RadonTypes>> {
+    let deadline = match deadline {
+        Some(deadline) => deadline,
+        None => return retrieval.await,
+    };
+    let remaining = deadline.saturating_duration_since(Instant::now());
+    futures::pin_mut!(retrieval);
+
+    match select(retrieval, futures_timer::Delay::new(remaining)).await {
+        Either::Left((result, _)) => result,
+        Either::Right(_) => Err(RadError::RetrieveTimeout),
+    }
+}
+
+/// Connect and read timeouts to apply to the HTTP clients used for retrieval.
+fn http_timeouts(witnessing: &WitnessingConfig<witnet_net::Uri>) -> WitnetHttpTimeouts {
+    WitnetHttpTimeouts {
+        connect: witnessing.connect_timeout,
+        read: witnessing.read_timeout,
+    }
+}
+
+/// Minimum TLS version to require from data sources in the HTTP clients used for retrieval.
+fn http_min_tls_version(witnessing: &WitnessingConfig<witnet_net::Uri>) -> Option<HttpTlsVersion> {
+    witnessing
+        .min_tls_version
+        .map(|min_tls_version| match min_tls_version {
+            TlsVersion::V1_2 => HttpTlsVersion::V1_2,
+        })
+}
+
+/// IP version and DNS overrides to apply to the HTTP clients used for retrieval.
+fn http_resolver(witnessing: &WitnessingConfig<witnet_net::Uri>) -> WitnetHttpResolver {
+    WitnetHttpResolver {
+        ip_version: match witnessing.ip_version {
+            IpVersion::Any => None,
+            IpVersion::V4 => Some(HttpIpVersion::V4),
+            IpVersion::V6 => Some(HttpIpVersion::V6),
+        },
+        overrides: witnessing
+            .resolve
+            .iter()
+            .map(|resolve| (resolve.host.clone(), resolve.port, resolve.address))
+            .collect(),
+    }
+}
+
+/// Turn an HTTP request into a `CapturedHttpRequest` that can be inspected and serialized.
+async fn capture_http_request(request: WitnetHttpRequest) -> Result<CapturedHttpRequest> {
+    let method = request.method().to_string();
+    let url = request.uri().to_string();
+    let headers = request
+        .headers()
+        .iter()
+        .map(|(name, value)| {
+            (
+                name.to_string(),
+                String::from_utf8_lossy(value.as_bytes()).into_owned(),
+            )
+        })
+        .collect();
+    let body = request
+        .into_body_bytes()
+        .await
+        .map_err(|err| RadError::HttpOther {
+            message: err.to_string(),
+        })?;
+
+    Ok(CapturedHttpRequest {
+        method,
+        url,
+        headers,
+        body,
+    })
+}
+
+/// Build the HTTP request for an HTTP retrieval, including its headers and body.
+///
+/// If no `user_agent` is provided, a common one is picked at random.
+fn build_http_request(
+    retrieve: &RADRetrieve,
+    user_agent: Option<&str>,
+) -> Result<WitnetHttpRequest> {
+    WitnetHttpRequest::build(|builder| {
+        // Populate the builder and generate the body for different types of retrievals
+        let (builder, body) = match retrieve.kind {
+            RADType::HttpGet => (
+                builder.method("GET").uri(&retrieve.url),
+                WitnetHttpBody::empty(),
+            ),
+            // The body is sent as is, and its content type is the one set by the `Content-Type`
+            // header of the retrieval, if any (e.g. `application/json` for JSON-RPC endpoints)
+            RADType::HttpPost => (
+                builder.method("POST").uri(&retrieve.url),
+                WitnetHttpBody::from(retrieve.body.clone()),
+            ),
+            RADType::HttpHead => (
+                builder.method("HEAD").uri(&retrieve.url),
+                WitnetHttpBody::empty(),
+            ),
+            RADType::GraphQl => (
+                builder
+                    .method("POST")
+                    .uri(&retrieve.url)
+                    .header("Content-Type", "application/json"),
+                WitnetHttpBody::from(graphql::graphql_request_body(&retrieve.body)?),
+            ),
+            _ => panic!(
+                "Called http_response with invalid retrieval kind {:?}",
+                retrieve.kind
+            ),
+        };
+
+        // Add the pinned user agent, or a random one
+        let user_agent = user_agent.unwrap_or_else(UserAgent::random);
+        let mut builder = builder.header("User-Agent", user_agent);
+
+        // Add extra_headers from retrieve.headers
+        for (name, value) in &retrieve.headers {
+            // Handle invalid header names and values with a specific and friendly error message
+            validate_header(name, value)?;
+
+            builder = builder.header(name, value);
+        }
+
+        // Finally attach the body to complete building the HTTP request
+        builder.body(body).map_err(|e| RadError::HttpOther {
+            message: e.to_string(),
+        })
+    })
+}
+
+/// Check whether a `RADRequest` would be accepted by the protocol rules given by `active_wips`,
+/// without needing any chain state.
+///
+/// This checks that there is at least one source, that the kind and fields of every source make
+/// sense together, that their scripts can be decoded and only use activated operators, that their
+/// headers are valid, that chained sources only reference the sources that come before them, and
+/// that the aggregation and tally filters and reducers are known. Legacy
+/// rules that only ever applied to old blocks (e.g. `RADType::Unknown` retrievals) are not
+/// supported, so such requests are rejected.
+pub fn validate_rad_request(rad_request: &RADRequest, active_wips: &ActiveWips) -> Result<()> {
+    if rad_request.retrieve.is_empty() {
+        return Err(RadError::NoRetrievalSources);
+    }
+
+    for (index, retrieve) in rad_request.retrieve.iter().enumerate() {
+        if retrieve.kind == RADType::Unknown {
+            return Err(RadError::UnknownRetrieval);
+        }
+
+        let fields_check = if active_wips.wip0020() {
+            retrieve.check_fields()
+        } else {
+            retrieve.check_fields_before_wip0020()
+        };
+        fields_check.map_err(|err| RadError::MalformedRetrieval {
+            message: err.to_string(),
+        })?;
+
+        unpack_radon_script_with_active_wips(&retrieve.script, active_wips)?;
+
+        if retrieve.kind == RADType::GraphQl {
+            if !active_wips.wip0029() {
+                return Err(RadError::MalformedRetrieval {
+                    message: String::from("GraphQL retrievals are not available before WIP0029"),
+                });
+            }
+            graphql::parse_graphql_body(&retrieve.body)?;
+        }
+
+        for (name, value) in &retrieve.headers {
+            validate_header(name, value)?;
+        }
+
+        if active_wips.wip0029() {
+            if let Some(&reference) = source_references(retrieve)
+                .iter()
+                .find(|&&reference| reference >= index)
+            {
+                return Err(RadError::SourceReference {
+                    reference,
+                    message: String::from(
+                        "sources can only reference the sources that come before them",
+                    ),
+                });
+            }
+        }
+    }
+
+    for (filters, reducer) in [
+        (
+            &rad_request.aggregate.filters,
+            rad_request.aggregate.reducer,
+        ),
+        (&rad_request.tally.filters, rad_request.tally.reducer),
+    ] {
+        create_radon_script_from_filters_and_reducer(filters, reducer, active_wips)?;
+    }
+
+    Ok(())
+}
+
+/// Centralizes validation of header names and values.
+///
+/// ASCII checks are always run before `try_from` to prevent panics in the `http` library.
+fn validate_header(name: &str, value: &str) -> Result<()> {
+    let mut error_message = None;
+    if name.is_ascii() {
+        if let Err(err) = http::HeaderName::try_from(name) {
+            error_message = Some(err.to_string())
+        } else if value.is_ascii() {
+            if let Err(err) = http::HeaderValue::try_from(value) {
+                error_message = Some(err.to_string())
+            }
+        } else {
+            error_message = Some("invalid HTTP header value".to_string())
+        }
+    } else {
+        error_message = Some("invalid HTTP header name".to_string())
+    };
 
     if let Some(error_message) = error_message {
         Err(RadError::InvalidHttpHeader {
@@ -854,11 +1577,12 @@ mod tests {
         chain::RADFilter,
         radon_error::{RadonError, RadonErrors},
         radon_report::RadonReport,
+        witnessing::{RetryPolicy, TransportRotation},
     };
 
     use crate::{
         filters::RadonFilters,
-        operators::RadonOpCodes,
+        operators::{float::RoundingMode, RadonOpCodes},
         reducers::RadonReducers,
         types::{float::RadonFloat, integer::RadonInteger, RadonType},
     };
@@ -903,6 +1627,154 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_run_retrieval_record_and_replay() {
+        let script_r = Value::Array(vec![
+            Value::Integer(RadonOpCodes::StringParseJSONMap as i128),
+            Value::Array(vec![
+                Value::Integer(RadonOpCodes::MapGetFloat as i128),
+                Value::Text("price".to_string()),
+            ]),
+        ]);
+        let retrieve = RADRetrieve {
+            kind: RADType::HttpGet,
+            url: "https://example.com/ticker?pair=wit-usd".to_string(),
+            script: serde_cbor::to_vec(&script_r).unwrap(),
+            body: vec![],
+            headers: vec![],
+        };
+        let response = r#"{"pair":"wit-usd","price":0.0123}"#;
+        let settings = RadonScriptExecutionSettings::disable_all();
+
+        // Record a mock response as if it had been obtained in a real run, and persist it
+        let mut recorded = recording::HttpRecording::default();
+        recorded.insert(&retrieve, response);
+        let path =
+            std::env::temp_dir().join(format!("witnet_rad_replay_{}.json", std::process::id()));
+        recorded.save(&path).unwrap();
+
+        let mode = RecordingMode::replay(recording::HttpRecording::load(&path).unwrap());
+        std::fs::remove_file(&path).unwrap();
+        let replayed = block_on(run_retrieval_report_with_recording(
+            &retrieve,
+            settings,
+            current_active_wips(),
+            None,
+            &mode,
+        ))
+        .unwrap();
+
+        let mut context = ReportContext::from_stage(Stage::Retrieval(RetrievalMetadata::default()));
+        context.set_active_wips(current_active_wips());
+        let expected =
+            run_retrieval_with_data_report(&retrieve, response, &mut context, settings).unwrap();
+
+        assert_eq!(replayed.result, expected.result);
+        assert_eq!(replayed.result, RadonTypes::from(RadonFloat::from(0.0123)));
+
+        // Sources missing from the recording fail instead of falling back to networking
+        let missing = RADRetrieve {
+            url: "https://example.com/ticker?pair=wit-eur".to_string(),
+            ..retrieve
+        };
+        let error = block_on(run_retrieval_report_with_recording(
+            &missing,
+            settings,
+            current_active_wips(),
+            None,
+            &mode,
+        ))
+        .unwrap_err();
+        assert!(matches!(error, RadError::HttpOther { .. }));
+    }
+
+    #[test]
+    fn test_radon_report_compact_round_trip() {
+        let script_r = Value::Array(vec![
+            Value::Integer(RadonOpCodes::StringParseJSONArray as i128),
+            Value::Array(vec![
+                Value::Integer(RadonOpCodes::ArrayMap as i128),
+                Value::Array(vec![Value::Array(vec![
+                    Value::Integer(RadonOpCodes::MapGetFloat as i128),
+                    Value::Text("price".to_string()),
+                ])]),
+            ]),
+            Value::Array(vec![
+                Value::Integer(RadonOpCodes::ArrayReduce as i128),
+                Value::Integer(RadonReducers::AverageMean as i128),
+            ]),
+        ]);
+        let retrieve = RADRetrieve {
+            kind: RADType::HttpGet,
+            url: "https://example.com/tickers".to_string(),
+            script: serde_cbor::to_vec(&script_r).unwrap(),
+            body: vec![],
+            headers: vec![],
+        };
+        let response = r#"[{"exchange":"binance","price":1.5},{"exchange":"kraken","price":1.7}]"#;
+        let mut context = ReportContext::from_stage(Stage::Retrieval(RetrievalMetadata::default()));
+        context.set_active_wips(current_active_wips());
+        let report = run_retrieval_with_data_report(
+            &retrieve,
+            response,
+            &mut context,
+            RadonScriptExecutionSettings::enable_all(),
+        )
+        .unwrap();
+
+        let compact = report.to_compact_bytes().unwrap();
+        let decoded = RadonReport::<RadonTypes>::from_compact_bytes(&compact).unwrap();
+        // Compare through `serde_json::Value` because the order of the active WIPs is not stable
+        assert_eq!(
+            serde_json::to_value(&decoded).unwrap(),
+            serde_json::to_value(&report).unwrap()
+        );
+        assert!(compact.len() < serde_json::to_vec(&report).unwrap().len());
+    }
+
+    #[test]
+    fn test_radon_report_compact_stage_metadata() {
+        // The metadata of the retrieval and aggregation stages survives a round trip
+        let context = ReportContext::from_stage(Stage::Retrieval(RetrievalMetadata {
+            recovered_errors: vec![RadonTypes::from(RadonError::new(RadError::HttpStatus {
+                status_code: 502,
+            }))],
+            http_request: Some(CapturedHttpRequest {
+                method: String::from("GET"),
+                url: String::from("https://example.com/price"),
+                headers: vec![(String::from("Accept"), String::from("*/*"))],
+                body: vec![],
+            }),
+            final_url: Some(String::from("https://example.com/v2/price")),
+            divergent_final_hosts: vec![
+                String::from("https://eu.example.com"),
+                String::from("https://example.com"),
+            ],
+            ..Default::default()
+        }));
+        let report = RadonReport::from_result(Ok(RadonString::from("abc").into()), &context);
+        let decoded =
+            RadonReport::<RadonTypes>::from_compact_bytes(&report.to_compact_bytes().unwrap())
+                .unwrap();
+        assert_eq!(
+            serde_json::to_value(&decoded).unwrap(),
+            serde_json::to_value(&report).unwrap()
+        );
+
+        let context = ReportContext::from_stage(Stage::Aggregation(AggregationMetaData {
+            consensus: 0.75,
+            liars: vec![false, false, false, true],
+        }));
+        let report = RadonReport::from_result(Ok(RadonString::from("abc").into()), &context);
+        let decoded =
+            RadonReport::<RadonTypes>::from_compact_bytes(&report.to_compact_bytes().unwrap())
+                .unwrap();
+        assert_eq!(
+            serde_json::to_value(&decoded).unwrap(),
+            serde_json::to_value(&report).unwrap()
+        );
+    }
+
     #[test]
     fn test_run_consensus_and_aggregation() {
         let f_1 = RadonTypes::Float(RadonFloat::from(1f64));
@@ -1553,6 +2425,8 @@ mod tests {
             None,
             None,
             false,
+            None,
+            None,
         );
         let tally_result = report.tally.into_inner();
 
@@ -1563,6 +2437,31 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_run_retrieval_report_rng_seeded() {
+        let retrieve = RADRetrieve {
+            kind: RADType::Rng,
+            url: String::from(""),
+            script: vec![128],
+            body: vec![],
+            headers: vec![],
+        };
+        // Always yields the same little-endian `u64`
+        let mut rng = rand::rngs::mock::StepRng::new(0x0807_0605_0403_0201, 0);
+
+        let report = block_on(run_retrieval_report(
+            &retrieve,
+            RadonScriptExecutionSettings::disable_all(),
+            all_wips_active(),
+            None,
+            Some(&mut rng),
+        ))
+        .unwrap();
+        let expected = RadonTypes::from(RadonBytes::from([1, 2, 3, 4, 5, 6, 7, 8].repeat(4)));
+
+        assert_eq!(report.into_inner(), expected);
+    }
+
     #[test]
     fn test_try_data_request_http_post_non_ascii_header_key() {
         let script_r = Value::Array(vec![]);
@@ -1597,6 +2496,8 @@ mod tests {
             None,
             None,
             false,
+            None,
+            None,
         );
         let tally_result = report.tally.into_inner();
 
@@ -1650,6 +2551,8 @@ mod tests {
             None,
             None,
             false,
+            None,
+            None,
         );
         let tally_result = report.tally.into_inner();
 
@@ -1703,6 +2606,8 @@ mod tests {
             None,
             None,
             false,
+            None,
+            None,
         );
         let tally_result = report.tally.into_inner();
 
@@ -1756,6 +2661,8 @@ mod tests {
             None,
             None,
             false,
+            None,
+            None,
         );
         let tally_result = report.tally.into_inner();
 
@@ -1822,40 +2729,193 @@ mod tests {
             Some(&["1", "1", "error"]),
             None,
             false,
+            None,
+            None,
         );
         let tally_result = report.tally.into_inner();
 
         assert_eq!(tally_result, RadonTypes::Integer(RadonInteger::from(1)));
     }
 
-    fn reports_from_values(results: Vec<RadonTypes>) -> Vec<Result<RadonReport<RadonTypes>>> {
-        let context = ReportContext::from_stage(Stage::Retrieval(RetrievalMetadata::default()));
+    #[test]
+    fn test_try_data_request_precondition_threshold() {
+        let retrieve = |operator: RadonOpCodes| RADRetrieve {
+            kind: RADType::HttpGet,
+            url: String::from(""),
+            script: cbor_to_vec(&Value::Array(vec![Value::Integer(operator as i128)])).unwrap(),
+            body: vec![],
+            headers: vec![],
+        };
+        let request = RADRequest {
+            time_lock: 0,
+            retrieve: vec![
+                retrieve(RadonOpCodes::StringAsInteger),
+                retrieve(RadonOpCodes::StringAsInteger),
+                retrieve(RadonOpCodes::StringAsInteger),
+                retrieve(RadonOpCodes::StringAsFloat),
+                retrieve(RadonOpCodes::StringAsBoolean),
+            ],
+            aggregate: RADAggregate {
+                filters: vec![],
+                reducer: RadonReducers::Mode as u32,
+            },
+            tally: RADTally {
+                filters: vec![],
+                reducer: RadonReducers::Mode as u32,
+            },
+        };
+        // The most frequent type (integer) is only featured in 2 out of 5 sources
+        let inputs = ["1", "1", "error", "1.5", "true"];
+        let try_with_threshold = |threshold| {
+            try_data_request(
+                &request,
+                RadonScriptExecutionSettings::enable_all(),
+                Some(&inputs),
+                None,
+                false,
+                threshold,
+                None,
+            )
+            .aggregate
+            .into_inner()
+        };
 
-        results
-            .into_iter()
-            .map(Ok)
-            .map(|result| RadonReport::from_result(result, &context))
-            .map(Ok)
-            .collect()
+        assert_eq!(
+            try_with_threshold(Some(0.2)),
+            RadonTypes::Integer(RadonInteger::from(1))
+        );
+        // Passing the default threshold is the same as not passing any
+        assert_eq!(
+            try_with_threshold(Some(DEFAULT_AGGREGATION_PRECONDITION_THRESHOLD)),
+            try_with_threshold(None)
+        );
+        assert!(matches!(
+            try_with_threshold(Some(0.5)),
+            RadonTypes::RadonError(error)
+                if matches!(error.inner(), RadError::InsufficientConsensus { .. })
+        ));
     }
 
-    fn aggregate_deviation_standard_and_average_mean(threshold: f32) -> RADAggregate {
-        let args = serde_cbor::to_vec(&serde_cbor::Value::from(threshold)).unwrap();
-        let filter = RADFilter {
-            op: RadonFilters::DeviationStandard as u32,
-            args,
+    #[test]
+    fn test_try_data_request_timing() {
+        let script = cbor_to_vec(&Value::Array(vec![Value::Integer(
+            RadonOpCodes::StringAsInteger as i128,
+        )]))
+        .unwrap();
+        let retrieve = RADRetrieve {
+            kind: RADType::HttpGet,
+            url: String::from(""),
+            script,
+            body: vec![],
+            headers: vec![],
+        };
+        let request = rad_request_with_mode(vec![retrieve.clone(), retrieve]);
+        let timing_with = |settings| {
+            try_data_request(
+                &request,
+                settings,
+                Some(&["1", "2"]),
+                None,
+                false,
+                None,
+                None,
+            )
+            .timing
         };
 
-        RADAggregate {
-            filters: vec![filter],
-            reducer: RadonReducers::AverageMean as u32,
-        }
+        let timing = timing_with(RadonScriptExecutionSettings::enable_all());
+        assert_eq!(timing.len(), 2);
+        assert!(timing.iter().all(Option::is_some));
+
+        assert_eq!(
+            timing_with(RadonScriptExecutionSettings::disable_all()),
+            vec![None, None]
+        );
     }
 
     #[test]
-    fn test_evaluate_paranoid_retrieval_happy_path() {
-        let settings = RadonScriptExecutionSettings::disable_all();
-        let data = reports_from_values(vec![
+    fn test_try_data_request_pinned_active_wips() {
+        // Rounding modes are only supported since WIP0029
+        let script = cbor_to_vec(&Value::Array(vec![
+            Value::Integer(RadonOpCodes::StringAsFloat as i128),
+            Value::Array(vec![
+                Value::Integer(RadonOpCodes::FloatRound as i128),
+                Value::Integer(RoundingMode::HalfEven as i128),
+            ]),
+        ]))
+        .unwrap();
+        let request = RADRequest {
+            time_lock: 0,
+            retrieve: vec![RADRetrieve {
+                kind: RADType::HttpGet,
+                url: String::from(""),
+                script,
+                body: vec![],
+                headers: vec![],
+            }],
+            aggregate: RADAggregate {
+                filters: vec![],
+                reducer: RadonReducers::Mode as u32,
+            },
+            tally: RADTally {
+                filters: vec![],
+                reducer: RadonReducers::Mode as u32,
+            },
+        };
+        let try_with_active_wips = |active_wips| {
+            try_data_request(
+                &request,
+                RadonScriptExecutionSettings::enable_all(),
+                Some(&["2.5"]),
+                None,
+                false,
+                None,
+                Some(active_wips),
+            )
+            .retrieve[0]
+                .clone()
+                .into_inner()
+        };
+
+        assert_eq!(
+            try_with_active_wips(all_wips_active()),
+            RadonTypes::Integer(RadonInteger::from(2))
+        );
+        assert!(matches!(
+            try_with_active_wips(current_active_wips()),
+            RadonTypes::RadonError(error)
+                if matches!(error.inner(), RadError::UnsupportedOperator { .. })
+        ));
+    }
+
+    fn reports_from_values(results: Vec<RadonTypes>) -> Vec<Result<RadonReport<RadonTypes>>> {
+        let context = ReportContext::from_stage(Stage::Retrieval(RetrievalMetadata::default()));
+
+        results
+            .into_iter()
+            .map(Ok)
+            .map(|result| RadonReport::from_result(result, &context))
+            .map(Ok)
+            .collect()
+    }
+
+    fn aggregate_deviation_standard_and_average_mean(threshold: f32) -> RADAggregate {
+        let args = serde_cbor::to_vec(&serde_cbor::Value::from(threshold)).unwrap();
+        let filter = RADFilter {
+            op: RadonFilters::DeviationStandard as u32,
+            args,
+        };
+
+        RADAggregate {
+            filters: vec![filter],
+            reducer: RadonReducers::AverageMean as u32,
+        }
+    }
+
+    #[test]
+    fn test_evaluate_paranoid_retrieval_happy_path() {
+        let settings = RadonScriptExecutionSettings::disable_all();
+        let data = reports_from_values(vec![
             RadonTypes::from(RadonFloat::from(100)),
             RadonTypes::from(RadonFloat::from(105)),
         ]);
@@ -1903,4 +2963,1092 @@ mod tests {
 
         assert_eq!(actual_result, expected_result);
     }
+
+    #[test]
+    fn test_evaluate_paranoid_retrieval_flags_outlier_transport() {
+        let settings = RadonScriptExecutionSettings::disable_all();
+        let data = reports_from_values(vec![
+            RadonTypes::from(RadonFloat::from(100)),
+            RadonTypes::from(RadonFloat::from(300)),
+            RadonTypes::from(RadonFloat::from(105)),
+        ]);
+        let aggregate = aggregate_deviation_standard_and_average_mean(1.1);
+
+        let error = evaluate_paranoid_retrieval_with_diagnostics(data, aggregate, settings, 0.67)
+            .unwrap_err();
+        let disagreement = match error {
+            ParanoidRetrievalError::Disagreement(disagreement) => disagreement,
+            other => panic!("Expected a disagreement, got {:?}", other),
+        };
+        assert_eq!(disagreement.outlier_transports(), vec![1]);
+        assert_eq!(
+            disagreement.values[1],
+            RadonTypes::from(RadonFloat::from(300))
+        );
+        assert!(disagreement.consensus.unwrap() < 0.67);
+    }
+
+    #[test]
+    fn test_evaluate_paranoid_retrieval_flags_failed_transport() {
+        let settings = RadonScriptExecutionSettings::disable_all();
+        let mut data = reports_from_values(vec![
+            RadonTypes::from(RadonFloat::from(100)),
+            RadonTypes::from(RadonFloat::from(100)),
+        ]);
+        data[0] = Err(RadError::RetrieveTimeout);
+        let aggregate = aggregate_deviation_standard_and_average_mean(1.1);
+
+        let error = evaluate_paranoid_retrieval_with_diagnostics(data, aggregate, settings, 0.7)
+            .unwrap_err();
+        assert!(matches!(
+            &error,
+            ParanoidRetrievalError::Disagreement(disagreement)
+                if disagreement.outlier_transports() == vec![0]
+        ));
+        assert_eq!(RadError::from(error), RadError::InconsistentSource);
+    }
+
+    #[test]
+    fn test_aggregation_report_tracks_liars() {
+        let values = vec![
+            RadonTypes::from(RadonFloat::from(100)),
+            RadonTypes::from(RadonFloat::from(300)),
+            RadonTypes::from(RadonFloat::from(105)),
+        ];
+        let aggregate = aggregate_deviation_standard_and_average_mean(1.1);
+
+        let (report, _) = run_aggregation_report(
+            values,
+            aggregate,
+            RadonScriptExecutionSettings::disable_all(),
+            &all_wips_active(),
+        );
+        let report = report.unwrap();
+
+        assert_eq!(report.liars(), Some(&[false, true, false][..]));
+        assert_eq!(report.consensus(), Some(2.0 / 3.0));
+        assert_eq!(report.result, RadonTypes::from(RadonFloat::from(102.5)));
+    }
+
+    #[test]
+    fn test_evaluate_paranoid_retrieval_different_final_urls() {
+        let settings = RadonScriptExecutionSettings::disable_all();
+        let aggregate = aggregate_deviation_standard_and_average_mean(1.1);
+        let with_final_urls = |final_urls: &[&str]| {
+            let mut data = reports_from_values(vec![
+                RadonTypes::from(RadonFloat::from(100)),
+                RadonTypes::from(RadonFloat::from(100)),
+            ]);
+            for (report, final_url) in data.iter_mut().zip(final_urls) {
+                if let Ok(RadonReport {
+                    context:
+                        ReportContext {
+                            stage: Stage::Retrieval(metadata),
+                            ..
+                        },
+                    ..
+                }) = report
+                {
+                    metadata.final_url = Some(final_url.to_string());
+                }
+            }
+
+            evaluate_paranoid_retrieval(data, aggregate.clone(), settings, 0.7)
+        };
+
+        let divergent_final_hosts = |final_urls: &[&str]| {
+            let report = with_final_urls(final_urls).unwrap();
+            assert_eq!(report.result, RadonTypes::from(RadonFloat::from(100)));
+            match report.context.stage {
+                Stage::Retrieval(metadata) => metadata.divergent_final_hosts,
+                stage => panic!("Unexpected stage {:?}", stage),
+            }
+        };
+
+        assert!(
+            divergent_final_hosts(&["https://example.com/", "https://example.com/"]).is_empty()
+        );
+        // Redirects to signed or cache-busting URLs are only compared by scheme and host
+        assert!(divergent_final_hosts(&[
+            "https://example.com/price?signature=abc",
+            "https://example.com/price?signature=def"
+        ])
+        .is_empty());
+        // Landing on different hosts, e.g. because of a CDN, does not make the retrieval fail
+        assert_eq!(
+            divergent_final_hosts(&["https://example.com/", "http://example.com/"]),
+            vec!["http://example.com", "https://example.com"]
+        );
+        assert_eq!(
+            divergent_final_hosts(&["https://example.com/", "https://eu.example.com/"]),
+            vec!["https://eu.example.com", "https://example.com"]
+        );
+    }
+
+    #[test]
+    fn test_build_http_post_request_json_content_type() {
+        let body = Vec::from(r#"{"jsonrpc":"2.0","method":"eth_blockNumber","id":1}"#);
+        let retrieve = RADRetrieve {
+            kind: RADType::HttpPost,
+            url: String::from("http://127.0.0.1"),
+            script: vec![128],
+            body: body.clone(),
+            headers: vec![(
+                String::from("Content-Type"),
+                String::from("application/json"),
+            )],
+        };
+        let request = block_on(capture_http_request(
+            build_http_request(&retrieve, None).unwrap(),
+        ))
+        .unwrap();
+
+        // JSON-RPC endpoints get the JSON body as is, with the content type set by the retrieval
+        // instead of `application/octet-stream`
+        let content_types = request
+            .headers
+            .iter()
+            .filter(|(name, _)| name.eq_ignore_ascii_case("Content-Type"))
+            .map(|(_, value)| value.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(content_types, vec!["application/json"]);
+        assert_eq!(request.body, body);
+    }
+
+    #[test]
+    fn test_build_http_post_request_json_content_type_invalid_utf8() {
+        // The content type does not change how the body is sent, so bodies that are not valid
+        // UTF-8 are still sent as they are
+        let retrieve = RADRetrieve {
+            kind: RADType::HttpPost,
+            url: String::from("http://127.0.0.1"),
+            script: vec![128],
+            body: vec![0xC3],
+            headers: vec![(
+                String::from("content-type"),
+                String::from("application/json; charset=utf-8"),
+            )],
+        };
+        let request = block_on(capture_http_request(
+            build_http_request(&retrieve, None).unwrap(),
+        ))
+        .unwrap();
+
+        assert_eq!(request.body, vec![0xC3]);
+    }
+
+    #[test]
+    fn test_build_graphql_request() {
+        let retrieve = RADRetrieve {
+            kind: RADType::GraphQl,
+            url: String::from("http://127.0.0.1/graphql"),
+            script: vec![128],
+            body: serde_cbor::to_vec(&Value::Array(vec![Value::Text(String::from(
+                "{ token { price } }",
+            ))]))
+            .unwrap(),
+            headers: vec![],
+        };
+        let request = build_http_request(&retrieve, None).unwrap();
+
+        assert_eq!(request.method(), "POST");
+        assert_eq!(
+            request.headers().get("content-type").unwrap(),
+            "application/json"
+        );
+    }
+
+    #[test]
+    fn test_build_http_post_request_binary_body() {
+        // Binary bodies are still accepted when no content type is set
+        let retrieve = RADRetrieve {
+            kind: RADType::HttpPost,
+            url: String::from("http://127.0.0.1"),
+            script: vec![128],
+            body: vec![0xC3],
+            headers: vec![],
+        };
+        let request = build_http_request(&retrieve, None).unwrap();
+
+        assert!(request.headers().get("content-type").is_none());
+    }
+
+    #[test]
+    fn test_build_http_request_pinned_user_agent() {
+        let retrieve = RADRetrieve {
+            kind: RADType::HttpGet,
+            url: String::from("http://127.0.0.1"),
+            script: vec![128],
+            body: vec![],
+            headers: vec![],
+        };
+        let user_agent = |pinned| {
+            build_http_request(&retrieve, pinned)
+                .unwrap()
+                .headers()
+                .get("user-agent")
+                .unwrap()
+                .to_str()
+                .unwrap()
+                .to_string()
+        };
+
+        for _ in 0..10 {
+            assert_eq!(user_agent(Some("witnet-test/1.0")), "witnet-test/1.0");
+            assert!(UserAgent::all().contains(&user_agent(None).as_str()));
+        }
+    }
+
+    fn rad_request_with_mode(retrieve: Vec<RADRetrieve>) -> RADRequest {
+        RADRequest {
+            time_lock: 0,
+            retrieve,
+            aggregate: RADAggregate {
+                filters: vec![],
+                reducer: RadonReducers::Mode as u32,
+            },
+            tally: RADTally {
+                filters: vec![RADFilter {
+                    op: RadonFilters::Mode as u32,
+                    args: vec![],
+                }],
+                reducer: RadonReducers::Mode as u32,
+            },
+        }
+    }
+
+    #[test]
+    fn test_validate_rad_request() {
+        let active_wips = all_wips_active();
+        let request = rad_request_with_mode(vec![retrieve_price(String::from(
+            "https://example.com/price",
+        ))]);
+        assert_eq!(validate_rad_request(&request, &active_wips), Ok(()));
+
+        assert_eq!(
+            validate_rad_request(&rad_request_with_mode(vec![]), &active_wips),
+            Err(RadError::NoRetrievalSources)
+        );
+
+        let mut with_body = request.clone();
+        with_body.retrieve[0].body = b"body".to_vec();
+        assert!(matches!(
+            validate_rad_request(&with_body, &active_wips),
+            Err(RadError::MalformedRetrieval { .. })
+        ));
+
+        let mut with_bad_script = request;
+        with_bad_script.retrieve[0].script = vec![0xff];
+        assert!(matches!(
+            validate_rad_request(&with_bad_script, &active_wips),
+            Err(RadError::ScriptNotArray { .. } | RadError::ScriptDecode { .. })
+        ));
+    }
+
+    #[test]
+    fn test_validate_rad_request_graphql() {
+        let graphql_body = |query: &str| {
+            serde_cbor::to_vec(&Value::Array(vec![Value::Text(String::from(query))])).unwrap()
+        };
+        let mut retrieve = retrieve_price(String::from("https://example.com/graphql"));
+        retrieve.kind = RADType::GraphQl;
+        retrieve.body = graphql_body("{ token(id: 1) { price } }");
+        let request = rad_request_with_mode(vec![retrieve]);
+        let mut active_wips = all_wips_active();
+        assert_eq!(validate_rad_request(&request, &active_wips), Ok(()));
+
+        let mut malformed = request.clone();
+        malformed.retrieve[0].body = graphql_body("{ token(id: 1 { price } }");
+        assert!(matches!(
+            validate_rad_request(&malformed, &active_wips),
+            Err(RadError::MalformedRetrieval { .. })
+        ));
+
+        let mut without_query = request.clone();
+        without_query.retrieve[0].body = vec![];
+        assert!(matches!(
+            validate_rad_request(&without_query, &active_wips),
+            Err(RadError::MalformedRetrieval { .. })
+        ));
+
+        active_wips.active_wips.remove("WIP0029");
+        assert!(matches!(
+            validate_rad_request(&request, &active_wips),
+            Err(RadError::MalformedRetrieval { .. })
+        ));
+    }
+
+    #[test]
+    fn test_validate_rad_request_geometric_mean() {
+        let geometric_mean = RadonReducers::AverageGeometricMean as u32;
+        let mut retrieve = retrieve_price(String::from("https://example.com/prices"));
+        retrieve.script = serde_cbor::to_vec(&Value::Array(vec![
+            Value::Integer(RadonOpCodes::StringParseJSONArray as i128),
+            Value::Array(vec![
+                Value::Integer(RadonOpCodes::ArrayReduce as i128),
+                Value::Integer(i128::from(geometric_mean)),
+            ]),
+        ]))
+        .unwrap();
+        let request = rad_request_with_mode(vec![retrieve]);
+        let active_wips = all_wips_active();
+
+        // Retrieval scripts can use the geometric mean
+        assert_eq!(validate_rad_request(&request, &active_wips), Ok(()));
+
+        // But neither the aggregation nor the tally can
+        let expected = Err(RadError::UnsupportedReducerInAT {
+            operator: RadonReducers::AverageGeometricMean as u8,
+        });
+        let mut in_aggregation = request.clone();
+        in_aggregation.aggregate.reducer = geometric_mean;
+        assert_eq!(
+            validate_rad_request(&in_aggregation, &active_wips),
+            expected
+        );
+        let mut in_tally = request;
+        in_tally.tally.reducer = geometric_mean;
+        assert_eq!(validate_rad_request(&in_tally, &active_wips), expected);
+    }
+
+    #[test]
+    fn test_validate_rad_request_unknown_filter() {
+        let mut request = rad_request_with_mode(vec![retrieve_price(String::from(
+            "https://example.com/price",
+        ))]);
+        request.tally.filters[0].op = 0x99;
+
+        assert_eq!(
+            validate_rad_request(&request, &all_wips_active()),
+            Err(RadError::UnknownFilter { code: 0x99 })
+        );
+    }
+
+    #[test]
+    fn test_validate_rad_request_non_ascii_header() {
+        let mut retrieve = retrieve_price(String::from("https://example.com/price"));
+        retrieve.headers = vec![(String::from("Acción"), String::from("value"))];
+        let request = rad_request_with_mode(vec![retrieve]);
+
+        assert_eq!(
+            validate_rad_request(&request, &all_wips_active()),
+            Err(RadError::InvalidHttpHeader {
+                name: String::from("Acción"),
+                value: String::from("value"),
+                error: String::from("invalid HTTP header name"),
+            })
+        );
+    }
+
+    #[test]
+    fn test_retrieval_capture_mode() {
+        let retrieve = RADRetrieve {
+            kind: RADType::HttpPost,
+            // Nothing listens on this port, so sending the request would fail
+            url: String::from("http://127.0.0.1:1/price"),
+            script: vec![128],
+            body: br#"{"symbol":"WIT"}"#.to_vec(),
+            headers: vec![
+                (
+                    String::from("Content-Type"),
+                    String::from("application/json"),
+                ),
+                (String::from("X-Api-Key"), String::from("secret")),
+            ],
+        };
+        let witnessing = WitnessingConfig {
+            user_agent: Some(String::from("witnet-test/1.0")),
+            ..Default::default()
+        };
+
+        let report = block_on(retrieval_report(
+            &retrieve,
+            RadonScriptExecutionSettings::disable_all(),
+            all_wips_active(),
+            None,
+            Some(&RecordingMode::Capture),
+            None,
+            Some(&witnessing),
+            None,
+        ))
+        .unwrap();
+
+        // The request was not sent, so there is no actual result
+        assert!(matches!(
+            report.result,
+            RadonTypes::RadonError(ref error)
+                if matches!(error.inner(), RadError::RequestCaptured { url } if url == "http://127.0.0.1:1/price")
+        ));
+        let http_request = match report.context.stage {
+            Stage::Retrieval(metadata) => metadata.http_request.unwrap(),
+            stage => panic!("Unexpected stage {:?}", stage),
+        };
+        let header = |name: &str| {
+            http_request
+                .headers
+                .iter()
+                .find(|(header, _)| header.eq_ignore_ascii_case(name))
+                .map(|(_, value)| value.as_str())
+        };
+        assert_eq!(http_request.method, "POST");
+        assert_eq!(http_request.url, "http://127.0.0.1:1/price");
+        assert_eq!(header("User-Agent"), Some("witnet-test/1.0"));
+        assert_eq!(header("Content-Type"), Some("application/json"));
+        assert_eq!(header("X-Api-Key"), Some("secret"));
+        assert_eq!(http_request.body, br#"{"symbol":"WIT"}"#.to_vec());
+    }
+
+    /// Serve one of `responses` on every incoming connection, in order, and repeat the last one
+    /// once they are exhausted. Return the URL of the server and a counter of the requests served.
+    fn serve_responses(
+        responses: Vec<(u16, &'static str)>,
+    ) -> (String, Arc<std::sync::atomic::AtomicUsize>) {
+        use std::{
+            io::{Read, Write},
+            sync::atomic::{AtomicUsize, Ordering},
+        };
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let served = Arc::new(AtomicUsize::new(0));
+        let counter = served.clone();
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut request = Vec::new();
+                let mut buf = [0u8; 1024];
+                while !request.ends_with(b"\r\n\r\n") {
+                    let read = stream.read(&mut buf).unwrap();
+                    if read == 0 {
+                        break;
+                    }
+                    request.extend_from_slice(&buf[..read]);
+                }
+
+                let index = counter.fetch_add(1, Ordering::SeqCst);
+                let (status, body) = responses[index.min(responses.len() - 1)];
+                let response = format!(
+                    "HTTP/1.1 {} Status\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+
+        (url, served)
+    }
+
+    /// Serve the same response to every request, keeping connections alive, and count how many
+    /// connections were opened.
+    fn serve_keep_alive(body: &'static str) -> (String, Arc<std::sync::atomic::AtomicUsize>) {
+        use std::{
+            io::{Read, Write},
+            sync::atomic::{AtomicUsize, Ordering},
+        };
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let connections = Arc::new(AtomicUsize::new(0));
+        let counter = connections.clone();
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                counter.fetch_add(1, Ordering::SeqCst);
+                let mut stream = stream.unwrap();
+                std::thread::spawn(move || loop {
+                    let mut request = Vec::new();
+                    let mut buf = [0u8; 1024];
+                    while !request.ends_with(b"\r\n\r\n") {
+                        match stream.read(&mut buf) {
+                            Ok(0) | Err(_) => return,
+                            Ok(read) => request.extend_from_slice(&buf[..read]),
+                        }
+                    }
+
+                    let response = format!(
+                        "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{}",
+                        body.len(),
+                        body
+                    );
+                    if stream.write_all(response.as_bytes()).is_err() {
+                        return;
+                    }
+                });
+            }
+        });
+
+        (url, connections)
+    }
+
+    fn retrieve_price(url: String) -> RADRetrieve {
+        let script = Value::Array(vec![
+            Value::Integer(RadonOpCodes::StringParseJSONMap as i128),
+            Value::Array(vec![
+                Value::Integer(RadonOpCodes::MapGetFloat as i128),
+                Value::Text("price".to_string()),
+            ]),
+        ]);
+
+        RADRetrieve {
+            kind: RADType::HttpGet,
+            url,
+            script: serde_cbor::to_vec(&script).unwrap(),
+            body: vec![],
+            headers: vec![],
+        }
+    }
+
+    /// Needs to reach a public endpoint that serves a self-signed certificate.
+    #[cfg(feature = "network-tests")]
+    #[test]
+    fn test_retrieval_self_signed_certificate() {
+        let retrieve = RADRetrieve {
+            kind: RADType::HttpGet,
+            url: String::from("https://self-signed.badssl.com/"),
+            script: vec![128],
+            body: vec![],
+            headers: vec![],
+        };
+
+        let result = block_on(run_retrieval_report(
+            &retrieve,
+            RadonScriptExecutionSettings::disable_all(),
+            all_wips_active(),
+            None,
+            None,
+        ));
+
+        assert!(
+            matches!(&result, Err(RadError::TlsError { url, .. }) if url == &retrieve.url),
+            "{:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn test_retrieval_with_resolve_override() {
+        let (url, served) = serve_responses(vec![(200, r#"{"price":1.5}"#)]);
+        let port = url::Url::parse(&url).unwrap().port().unwrap();
+        // The `.invalid` top level domain is guaranteed to never resolve through DNS
+        let retrieve = retrieve_price(format!("http://price.witnet.invalid:{}/", port));
+        let aggregate = RADAggregate {
+            filters: vec![],
+            reducer: RadonReducers::Mode as u32,
+        };
+        let witnessing = WitnessingConfig {
+            ip_version: IpVersion::V4,
+            resolve: vec![format!("price.witnet.invalid:{}:127.0.0.1", port)
+                .parse()
+                .unwrap()],
+            ..Default::default()
+        };
+
+        let result = block_on(run_paranoid_retrieval(
+            &retrieve,
+            aggregate,
+            RadonScriptExecutionSettings::disable_all(),
+            all_wips_active(),
+            witnessing,
+            None,
+            None,
+        ))
+        .map(RadonReport::into_inner);
+
+        assert_eq!(result, Ok(RadonTypes::from(RadonFloat::from(1.5))));
+        assert_eq!(served.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_paranoid_retrieval_disabled() {
+        let (url, served) = serve_responses(vec![(200, r#"{"price":1.5}"#)]);
+        let retrieve = retrieve_price(url);
+        let aggregate = RADAggregate {
+            filters: vec![],
+            reducer: RadonReducers::Mode as u32,
+        };
+        let retrieve_with = |paranoid| {
+            // Nothing listens on the proxy, so any retrieval going through it fails
+            let witnessing = WitnessingConfig {
+                transports: vec![None, Some("http://127.0.0.1:1".parse().unwrap())],
+                paranoid,
+                ..Default::default()
+            };
+
+            block_on(run_paranoid_retrieval(
+                &retrieve,
+                aggregate.clone(),
+                RadonScriptExecutionSettings::disable_all(),
+                all_wips_active(),
+                witnessing,
+                None,
+                None,
+            ))
+            .map(RadonReport::into_inner)
+        };
+
+        assert_eq!(
+            retrieve_with(false),
+            Ok(RadonTypes::from(RadonFloat::from(1.5)))
+        );
+        assert_eq!(served.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        assert_eq!(retrieve_with(true), Err(RadError::InconsistentSource));
+    }
+
+    #[test]
+    fn test_paranoid_retrieval_round_robin() {
+        use std::sync::atomic::Ordering;
+
+        // The servers that act as proxies answer any request on their own, so the number of
+        // requests that each of them serves tells which transport was used
+        let (url, direct) = serve_responses(vec![(200, r#"{"price":1.5}"#)]);
+        let (proxy_a, served_a) = serve_responses(vec![(200, r#"{"price":1.5}"#)]);
+        let (proxy_b, served_b) = serve_responses(vec![(200, r#"{"price":1.5}"#)]);
+        let retrieve = retrieve_price(url);
+        let aggregate = RADAggregate {
+            filters: vec![],
+            reducer: RadonReducers::Mode as u32,
+        };
+        let witnessing = WitnessingConfig {
+            transports: vec![
+                None,
+                Some(proxy_a.parse().unwrap()),
+                Some(proxy_b.parse().unwrap()),
+            ],
+            paranoid: false,
+            rotation: TransportRotation::round_robin(),
+            ..Default::default()
+        };
+
+        let mut served = vec![];
+        for _ in 0..4 {
+            let result = block_on(run_paranoid_retrieval(
+                &retrieve,
+                aggregate.clone(),
+                RadonScriptExecutionSettings::disable_all(),
+                all_wips_active(),
+                witnessing.clone(),
+                None,
+                None,
+            ))
+            .map(RadonReport::into_inner);
+            assert_eq!(result, Ok(RadonTypes::from(RadonFloat::from(1.5))));
+
+            served.push([
+                direct.load(Ordering::SeqCst),
+                served_a.load(Ordering::SeqCst),
+                served_b.load(Ordering::SeqCst),
+            ]);
+        }
+
+        assert_eq!(served, vec![[1, 0, 0], [1, 1, 0], [1, 1, 1], [2, 1, 1]]);
+    }
+
+    #[test]
+    fn test_retrieval_require_tls() {
+        let (url, _served) = serve_responses(vec![(200, r#"{"price":1.5}"#)]);
+        let retrieve = retrieve_price(url.clone());
+        let aggregate = RADAggregate {
+            filters: vec![],
+            reducer: RadonReducers::Mode as u32,
+        };
+        let retrieve_with = |require_tls| {
+            let witnessing = WitnessingConfig {
+                require_tls,
+                ..Default::default()
+            };
+
+            block_on(run_paranoid_retrieval(
+                &retrieve,
+                aggregate.clone(),
+                RadonScriptExecutionSettings::disable_all(),
+                all_wips_active(),
+                witnessing,
+                None,
+                None,
+            ))
+            .map(RadonReport::into_inner)
+        };
+
+        assert_eq!(
+            retrieve_with(true),
+            Err(RadError::InsecureTransport { url })
+        );
+        assert_eq!(
+            retrieve_with(false),
+            Ok(RadonTypes::from(RadonFloat::from(1.5)))
+        );
+    }
+
+    /// Run a retrieval both in single transport mode and in paranoid mode with two transports.
+    #[test]
+    fn test_used_transports() {
+        let proxy = |port: u16| -> witnet_net::Uri {
+            format!("http://127.0.0.1:{}", port).parse().unwrap()
+        };
+        let mut witnessing = WitnessingConfig {
+            transports: vec![None, Some(proxy(1)), Some(proxy(2))],
+            rotation: TransportRotation::round_robin(),
+            ..Default::default()
+        };
+
+        // Paranoid retrievals go through all the transports
+        assert_eq!(used_transports(&witnessing).unwrap(), witnessing.transports);
+
+        // Otherwise, the clients are only built for the transport picked by the rotation policy,
+        // which keeps track of its proxy so that it can be told apart from the rest
+        witnessing.paranoid = false;
+        assert_eq!(used_transports(&witnessing).unwrap(), vec![None]);
+        assert_eq!(used_transports(&witnessing).unwrap(), vec![Some(proxy(1))]);
+        let clients = transport_clients(
+            used_transports(&witnessing).unwrap(),
+            &witnessing,
+            &all_wips_active(),
+        )
+        .unwrap();
+        assert_eq!(clients.len(), 1);
+        assert_eq!(clients[0].0, Some(proxy(2)));
+    }
+
+    fn retrieve_single_and_paranoid(
+        retrieve: &RADRetrieve,
+    ) -> (Result<RadonTypes>, Result<RadonTypes>) {
+        let single = block_on(run_retrieval_report(
+            retrieve,
+            RadonScriptExecutionSettings::disable_all(),
+            all_wips_active(),
+            None,
+            None,
+        ))
+        .map(RadonReport::into_inner);
+
+        let witnessing = WitnessingConfig {
+            transports: vec![None, Some("http://127.0.0.1:1".parse().unwrap())],
+            ..Default::default()
+        };
+        let aggregate = RADAggregate {
+            filters: vec![],
+            reducer: RadonReducers::Mode as u32,
+        };
+        let paranoid = block_on(run_paranoid_retrieval(
+            retrieve,
+            aggregate,
+            RadonScriptExecutionSettings::disable_all(),
+            all_wips_active(),
+            witnessing,
+            None,
+            None,
+        ))
+        .map(RadonReport::into_inner);
+
+        (single, paranoid)
+    }
+
+    #[test]
+    fn test_retrieval_unsupported_scheme() {
+        let url = String::from("file:///etc/passwd");
+        let (single, paranoid) = retrieve_single_and_paranoid(&retrieve_price(url.clone()));
+        let expected = Err(RadError::UnsupportedScheme {
+            scheme: String::from("file"),
+            url,
+        });
+
+        assert_eq!(single, expected);
+        assert_eq!(paranoid, expected);
+
+        let (single, _) =
+            retrieve_single_and_paranoid(&retrieve_price(String::from("ftp://127.0.0.1/price")));
+        assert!(
+            matches!(single, Err(RadError::UnsupportedScheme { scheme, .. }) if scheme == "ftp")
+        );
+    }
+
+    #[test]
+    fn test_retrieval_malformed_url() {
+        let (single, paranoid) =
+            retrieve_single_and_paranoid(&retrieve_price(String::from("http://[::1")));
+
+        assert!(matches!(single, Err(RadError::UrlParseError { .. })));
+        assert!(matches!(paranoid, Err(RadError::UrlParseError { .. })));
+    }
+
+    fn paranoid_retrieval_with_retries(retrieve: &RADRetrieve) -> Result<RadonTypes> {
+        let witnessing = WitnessingConfig {
+            retry: Some(RetryPolicy {
+                max_attempts: 3,
+                base_delay: std::time::Duration::from_millis(1),
+            }),
+            ..Default::default()
+        };
+        let aggregate = RADAggregate {
+            filters: vec![],
+            reducer: RadonReducers::Mode as u32,
+        };
+
+        block_on(run_paranoid_retrieval(
+            retrieve,
+            aggregate,
+            RadonScriptExecutionSettings::disable_all(),
+            all_wips_active(),
+            witnessing,
+            None,
+            None,
+        ))
+        .map(RadonReport::into_inner)
+    }
+
+    /// Redirect every incoming connection to `location`. Return the URL of the server.
+    fn serve_redirect(location: String) -> String {
+        use std::io::{Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut buf = [0u8; 1024];
+                let _ = stream.read(&mut buf);
+                let response = format!(
+                    "HTTP/1.1 302 Found\r\nLocation: {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                    location
+                );
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+
+        url
+    }
+
+    #[test]
+    fn test_retrieval_final_url() {
+        let (target, _served) = serve_responses(vec![(200, r#"{"price":1.5}"#)]);
+        let redirect = serve_redirect(target.clone());
+        let retrieve = |url: &str| {
+            block_on(run_retrieval_report(
+                &retrieve_price(url.to_string()),
+                RadonScriptExecutionSettings::disable_all(),
+                all_wips_active(),
+                None,
+                None,
+            ))
+            .unwrap()
+        };
+
+        let report = retrieve(&redirect);
+        assert_eq!(report.result, RadonTypes::from(RadonFloat::from(1.5)));
+        assert_eq!(report.final_url(), Some(target.as_str()));
+
+        // Without redirects, the final URL is the one that was requested
+        let report = retrieve(&target);
+        assert_eq!(report.final_url(), Some(target.as_str()));
+    }
+
+    /// Answer every incoming connection with a JSON object containing the path that was requested.
+    /// Return the URL of the server.
+    fn serve_request_path() -> String {
+        use std::io::{Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut buf = [0u8; 1024];
+                let read = stream.read(&mut buf).unwrap();
+                let request = String::from_utf8_lossy(&buf[..read]);
+                let path = request.split(' ').nth(1).unwrap_or_default();
+                let body = format!(r#"{{"path":"{}"}}"#, path);
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+
+        url
+    }
+
+    #[test]
+    fn test_run_retrieval_report_with_client_reuses_connections() {
+        let (url, connections) = serve_keep_alive(r#"{"price":1.5}"#);
+        let retrieve = retrieve_price(url);
+        let client = WitnetHttpClient::new(None, true).unwrap();
+
+        for _ in 0..3 {
+            let report = block_on(run_retrieval_report_with_client(
+                &retrieve,
+                RadonScriptExecutionSettings::disable_all(),
+                all_wips_active(),
+                &client,
+            ))
+            .unwrap();
+            assert_eq!(report.result, RadonTypes::from(RadonFloat::from(1.5)));
+        }
+
+        assert_eq!(connections.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_try_data_request_shares_client_across_sources() {
+        let script = Value::Array(vec![
+            Value::Integer(RadonOpCodes::StringParseJSONMap as i128),
+            Value::Array(vec![
+                Value::Integer(RadonOpCodes::MapGetString as i128),
+                Value::Text(String::from("token")),
+            ]),
+        ]);
+        let script = serde_cbor::to_vec(&script).unwrap();
+        let (url, connections) = serve_keep_alive(r#"{"token":"abc"}"#);
+        // The second source references the first one, so they are retrieved one after the other
+        let request = rad_request_with_mode(vec![
+            RADRetrieve {
+                kind: RADType::HttpGet,
+                url: url.clone(),
+                script: script.clone(),
+                body: vec![],
+                headers: vec![],
+            },
+            RADRetrieve {
+                kind: RADType::HttpGet,
+                url: format!(r"{}?token=\0\", url),
+                script,
+                body: vec![],
+                headers: vec![],
+            },
+        ]);
+
+        let report = try_data_request(
+            &request,
+            RadonScriptExecutionSettings::disable_all(),
+            None,
+            None,
+            false,
+            None,
+            Some(all_wips_active()),
+        );
+
+        assert_eq!(
+            report.tally.into_inner(),
+            RadonTypes::from(RadonString::from("abc"))
+        );
+        assert_eq!(connections.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_try_data_request_chained_retrieval() {
+        let get_string = |key: &str| {
+            let script = Value::Array(vec![
+                Value::Integer(RadonOpCodes::StringParseJSONMap as i128),
+                Value::Array(vec![
+                    Value::Integer(RadonOpCodes::MapGetString as i128),
+                    Value::Text(key.to_string()),
+                ]),
+            ]);
+
+            serde_cbor::to_vec(&script).unwrap()
+        };
+        let (token_url, _served) = serve_responses(vec![(200, r#"{"token":"abc"}"#)]);
+        let echo_url = serve_request_path();
+        let request = rad_request_with_mode(vec![
+            RADRetrieve {
+                kind: RADType::HttpGet,
+                url: token_url,
+                script: get_string("token"),
+                body: vec![],
+                headers: vec![],
+            },
+            RADRetrieve {
+                kind: RADType::HttpGet,
+                url: format!(r"{}price?token=\0\", echo_url),
+                script: get_string("path"),
+                body: vec![],
+                headers: vec![],
+            },
+        ]);
+        let run = |active_wips| {
+            try_data_request(
+                &request,
+                RadonScriptExecutionSettings::disable_all(),
+                None,
+                None,
+                false,
+                None,
+                Some(active_wips),
+            )
+            .retrieve
+            .into_iter()
+            .map(RadonReport::into_inner)
+            .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            run(all_wips_active()),
+            vec![
+                RadonTypes::from(RadonString::from("abc")),
+                RadonTypes::from(RadonString::from("/price?token=abc")),
+            ]
+        );
+
+        // Before WIP0029, the reference is sent as is
+        let mut active_wips = all_wips_active();
+        active_wips.active_wips.remove("WIP0029");
+        assert_ne!(
+            run(active_wips)[1],
+            RadonTypes::from(RadonString::from("/price?token=abc"))
+        );
+    }
+
+    #[test]
+    fn test_chained_retrievals_share_deadline() {
+        let witnessing = WitnessingConfig {
+            connect_timeout: Some(Duration::from_secs(1)),
+            read_timeout: Some(Duration::from_secs(2)),
+            ..WitnessingConfig::default()
+        };
+        assert_eq!(
+            chained_retrieval_budget(&witnessing),
+            Some(Duration::from_secs(3))
+        );
+        assert_eq!(chained_retrieval_budget(&WitnessingConfig::default()), None);
+
+        // Once the deadline has passed, the next sources time out right away
+        let deadline = Some(Instant::now());
+        let result = block_on(with_deadline(futures::future::pending(), deadline));
+        assert_eq!(
+            result.map(RadonReport::into_inner),
+            Err(RadError::RetrieveTimeout)
+        );
+
+        let value = RadonTypes::from(RadonString::from("abc"));
+        let report = RadonReport::from_result(Ok(value.clone()), &ReportContext::default());
+        let result = block_on(with_deadline(futures::future::ready(Ok(report)), None));
+        assert_eq!(result.map(RadonReport::into_inner), Ok(value));
+    }
+
+    #[test]
+    fn test_retrieval_retries_server_errors() {
+        let (url, served) = serve_responses(vec![(503, ""), (503, ""), (200, r#"{"price":1.5}"#)]);
+
+        let result = paranoid_retrieval_with_retries(&retrieve_price(url));
+
+        assert_eq!(result, Ok(RadonTypes::from(RadonFloat::from(1.5))));
+        assert_eq!(served.load(std::sync::atomic::Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_retrieval_does_not_retry_client_errors() {
+        let (url, served) = serve_responses(vec![(404, ""), (200, r#"{"price":1.5}"#)]);
+
+        let result = paranoid_retrieval_with_retries(&retrieve_price(url));
+
+        assert_eq!(result, Err(RadError::HttpStatus { status_code: 404 }));
+        assert_eq!(served.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
 }
@@ -0,0 +1,106 @@
+//! Hooks for collecting metrics about the outcome of data retrievals.
+use std::{
+    collections::BTreeMap,
+    sync::{Mutex, PoisonError},
+};
+
+use serde::Serialize;
+use witnet_data_structures::radon_report::RadonReport;
+
+use crate::{error::RadError, types::RadonTypes, Result};
+
+/// Receives the outcome of every data retrieval performed by the RAD engine.
+pub trait RetrievalMetrics: Send + Sync {
+    /// Called once for every retrieval that completed, be it successfully or not.
+    fn record_retrieval(&self, result: &Result<RadonReport<RadonTypes>>);
+}
+
+/// Aggregated retrieval outcomes.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct RetrievalStats {
+    /// Number of retrievals that produced a value.
+    pub successes: u64,
+    /// Number of failed retrievals, by error category.
+    pub failures: BTreeMap<String, u64>,
+}
+
+/// Simple in-memory implementation of `RetrievalMetrics` that counts outcomes.
+#[derive(Debug, Default)]
+pub struct RetrievalCounters {
+    stats: Mutex<RetrievalStats>,
+}
+
+impl RetrievalCounters {
+    /// Get a copy of the counts collected so far.
+    pub fn snapshot(&self) -> RetrievalStats {
+        self.stats
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+}
+
+impl RetrievalMetrics for RetrievalCounters {
+    fn record_retrieval(&self, result: &Result<RadonReport<RadonTypes>>) {
+        // Errors raised inside the retrieval script are intercepted into the result of the report,
+        // so they need to be looked for there as well
+        let error = match result {
+            Ok(report) => match &report.result {
+                RadonTypes::RadonError(error) => Some(error.inner()),
+                _ => None,
+            },
+            Err(error) => Some(error),
+        };
+
+        let mut stats = self.stats.lock().unwrap_or_else(PoisonError::into_inner);
+        match error {
+            None => stats.successes += 1,
+            Some(error) => *stats.failures.entry(error_category(error)).or_default() += 1,
+        }
+    }
+}
+
+/// Name of the category of an error, as in `RadonErrors`. Errors that cannot be committed are
+/// reported as `Unhandled`.
+pub fn error_category(error: &RadError) -> String {
+    error
+        .try_into_error_code()
+        .map(|code| format!("{:?}", code))
+        .unwrap_or_else(|_| String::from("Unhandled"))
+}
+
+#[cfg(test)]
+mod tests {
+    use witnet_data_structures::radon_report::ReportContext;
+
+    use super::*;
+    use crate::types::integer::RadonInteger;
+
+    #[test]
+    fn test_retrieval_counters() {
+        let counters = RetrievalCounters::default();
+        let context = ReportContext::default();
+
+        let success = Ok(RadonReport::from_result(
+            Ok(RadonTypes::from(RadonInteger::from(1))),
+            &context,
+        ));
+        let intercepted = Ok(RadonReport::from_result(
+            Err(RadError::HttpStatus { status_code: 404 }),
+            &context,
+        ));
+        let timeout = Err(RadError::RetrieveTimeout);
+
+        counters.record_retrieval(&success);
+        counters.record_retrieval(&success);
+        counters.record_retrieval(&intercepted);
+        counters.record_retrieval(&timeout);
+        counters.record_retrieval(&Err(RadError::InconsistentSource));
+
+        let stats = counters.snapshot();
+        assert_eq!(stats.successes, 2);
+        assert_eq!(stats.failures.get("HTTPError"), Some(&1));
+        assert_eq!(stats.failures.get("RetrieveTimeout"), Some(&1));
+        assert_eq!(stats.failures.values().sum::<u64>(), 3);
+    }
+}
@@ -0,0 +1,484 @@
+//! Human-friendly JSON representation of `RADRequest`, in which RADON operators, filters and
+//! reducers are written by their names instead of their codes, e.g. `"StringParseJSONMap"` instead
+//! of `0x77`, or `["ArrayReduce", "AverageMean"]` instead of `[0x1B, 0x03]`.
+//!
+//! This is only meant for authoring and reading requests by hand: the numeric form remains the
+//! canonical one, and named requests must be converted into a `RADRequest` before being used.
+//!
+//! Every call in a script is written either as the name of its operator, or as an array holding
+//! the name of its operator followed by its arguments. Subscripts, as well as the filters and
+//! reducers passed as arguments to `ArrayFilter` and `ArrayReduce`, are named too. Filter arguments
+//! are written as the JSON value they encode, if any.
+use std::{convert::TryFrom, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+use serde_cbor::Value;
+use serde_json::Value as JsonValue;
+use witnet_data_structures::chain::{
+    RADAggregate, RADFilter, RADRequest, RADRetrieve, RADTally, RADType,
+};
+
+use crate::{
+    error::RadError,
+    filters::RadonFilters,
+    graphql::cbor_to_json,
+    operators::RadonOpCodes,
+    reducers::RadonReducers,
+    script::{unpack_radon_script, unpack_subscript, RadonCall},
+    Result,
+};
+
+/// `RADRequest` with named operators, filters and reducers.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct NamedRADRequest {
+    /// Commitments for this request will not be accepted in any block proposed for an epoch
+    /// whose opening timestamp predates the specified time lock.
+    #[serde(default)]
+    pub time_lock: u64,
+    /// List of retrieval sources and scripts
+    pub retrieve: Vec<NamedRADRetrieve>,
+    /// Aggregate script
+    pub aggregate: NamedRADReduction,
+    /// Tally script
+    pub tally: NamedRADReduction,
+}
+
+/// `RADRetrieve` with a named script.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct NamedRADRetrieve {
+    /// Kind of retrieval
+    pub kind: RADType,
+    /// URL
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub url: String,
+    /// RADON script, as a list of named calls
+    #[serde(default)]
+    pub script: Vec<JsonValue>,
+    /// Body of a HTTP-POST request
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub body: Vec<u8>,
+    /// Extra headers of a HTTP-GET, HTTP-POST or HTTP-HEAD request
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub headers: Vec<(String, String)>,
+}
+
+/// `RADAggregate` or `RADTally` with named filters and reducer.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct NamedRADReduction {
+    /// List of filters to be applied in sequence
+    #[serde(default)]
+    pub filters: Vec<NamedRADFilter>,
+    /// Name of the reducer to be applied to the final result
+    pub reducer: String,
+}
+
+/// `RADFilter` with a named filter and decoded arguments.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct NamedRADFilter {
+    /// Name of the filter
+    pub op: String,
+    /// Filter arguments, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub args: Option<JsonValue>,
+}
+
+impl TryFrom<&RADRequest> for NamedRADRequest {
+    type Error = RadError;
+
+    fn try_from(request: &RADRequest) -> Result<Self> {
+        Ok(Self {
+            time_lock: request.time_lock,
+            retrieve: request
+                .retrieve
+                .iter()
+                .map(NamedRADRetrieve::try_from)
+                .collect::<Result<_>>()?,
+            aggregate: NamedRADReduction::new(
+                &request.aggregate.filters,
+                request.aggregate.reducer,
+            )?,
+            tally: NamedRADReduction::new(&request.tally.filters, request.tally.reducer)?,
+        })
+    }
+}
+
+impl TryFrom<&NamedRADRequest> for RADRequest {
+    type Error = RadError;
+
+    fn try_from(request: &NamedRADRequest) -> Result<Self> {
+        let (filters, reducer) = request.aggregate.to_filters_and_reducer()?;
+        let aggregate = RADAggregate { filters, reducer };
+        let (filters, reducer) = request.tally.to_filters_and_reducer()?;
+        let tally = RADTally { filters, reducer };
+
+        Ok(Self {
+            time_lock: request.time_lock,
+            retrieve: request
+                .retrieve
+                .iter()
+                .map(RADRetrieve::try_from)
+                .collect::<Result<_>>()?,
+            aggregate,
+            tally,
+        })
+    }
+}
+
+impl TryFrom<&RADRetrieve> for NamedRADRetrieve {
+    type Error = RadError;
+
+    fn try_from(retrieve: &RADRetrieve) -> Result<Self> {
+        Ok(Self {
+            kind: retrieve.kind.clone(),
+            url: retrieve.url.clone(),
+            script: name_script(&unpack_radon_script(&retrieve.script)?)?,
+            body: retrieve.body.clone(),
+            headers: retrieve.headers.clone(),
+        })
+    }
+}
+
+impl TryFrom<&NamedRADRetrieve> for RADRetrieve {
+    type Error = RadError;
+
+    fn try_from(retrieve: &NamedRADRetrieve) -> Result<Self> {
+        Ok(Self {
+            kind: retrieve.kind.clone(),
+            url: retrieve.url.clone(),
+            script: encode(&Value::Array(pack_named_script(&retrieve.script)?))?,
+            body: retrieve.body.clone(),
+            headers: retrieve.headers.clone(),
+        })
+    }
+}
+
+impl NamedRADReduction {
+    /// Name the filters and the reducer of a `RADAggregate` or a `RADTally`.
+    pub fn new(filters: &[RADFilter], reducer: u32) -> Result<Self> {
+        let filters = filters
+            .iter()
+            .map(|filter| {
+                let args = if filter.args.is_empty() {
+                    None
+                } else {
+                    let args = serde_cbor::from_slice(&filter.args)
+                        .map_err(|e| malformed(format!("invalid filter arguments: {}", e)))?;
+                    Some(cbor_to_json(args).map_err(malformed)?)
+                };
+
+                Ok(NamedRADFilter {
+                    op: filter_name(i128::from(filter.op))?.to_string(),
+                    args,
+                })
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
+            filters,
+            reducer: reducer_name(i128::from(reducer))?.to_string(),
+        })
+    }
+
+    /// Recover the filters and the reducer of a `RADAggregate` or a `RADTally`.
+    pub fn to_filters_and_reducer(&self) -> Result<(Vec<RADFilter>, u32)> {
+        let filters = self
+            .filters
+            .iter()
+            .map(|filter| {
+                let args = match &filter.args {
+                    Some(args) => encode(&json_to_cbor(args)?)?,
+                    None => vec![],
+                };
+
+                Ok(RADFilter {
+                    op: u32::from(u8::from(filter_code(&filter.op)?)),
+                    args,
+                })
+            })
+            .collect::<Result<_>>()?;
+
+        Ok((filters, u32::from(u8::from(reducer_code(&self.reducer)?))))
+    }
+}
+
+/// Name the operators of an unpacked RADON script.
+pub fn name_script(script: &[RadonCall]) -> Result<Vec<JsonValue>> {
+    script.iter().map(name_call).collect()
+}
+
+/// Pack a named RADON script into the CBOR values of its calls, as expected by
+/// `unpack_radon_script` once they are wrapped into an array and encoded.
+pub fn pack_named_script(script: &[JsonValue]) -> Result<Vec<Value>> {
+    script.iter().map(pack_named_call).collect()
+}
+
+fn name_call((op_code, args): &RadonCall) -> Result<JsonValue> {
+    let name = JsonValue::from(<&str>::from(op_code));
+    let args = match args {
+        Some(args) => args,
+        None => return Ok(name),
+    };
+
+    let mut call = vec![name];
+    for (index, arg) in args.iter().enumerate() {
+        call.push(name_argument(*op_code, index, arg)?);
+    }
+
+    Ok(JsonValue::Array(call))
+}
+
+fn name_argument(op_code: RadonOpCodes, index: usize, arg: &Value) -> Result<JsonValue> {
+    match (op_code, index, arg) {
+        (
+            RadonOpCodes::ArrayFilter
+            | RadonOpCodes::ArrayMap
+            | RadonOpCodes::ArraySort
+            | RadonOpCodes::MapUnion,
+            0,
+            Value::Array(_),
+        ) => Ok(JsonValue::Array(name_script(&unpack_subscript(arg)?)?)),
+        (RadonOpCodes::ArrayFilter, 0, Value::Integer(code)) => {
+            Ok(JsonValue::from(filter_name(*code)?))
+        }
+        (RadonOpCodes::ArrayReduce, 0, Value::Integer(code)) => {
+            Ok(JsonValue::from(reducer_name(*code)?))
+        }
+        _ => cbor_to_json(arg.clone()).map_err(malformed),
+    }
+}
+
+fn pack_named_call(call: &JsonValue) -> Result<Value> {
+    match call {
+        JsonValue::String(name) => Ok(Value::Integer(i128::from(op_code(name)? as u8))),
+        JsonValue::Array(items) => {
+            let (name, args) = match items.split_first() {
+                Some((JsonValue::String(name), args)) => (name, args),
+                _ => {
+                    return Err(malformed(String::from(
+                        "compound calls must start with the name of an operator",
+                    )))
+                }
+            };
+            let op_code = op_code(name)?;
+
+            let mut call = vec![Value::Integer(i128::from(op_code as u8))];
+            for (index, arg) in args.iter().enumerate() {
+                call.push(pack_named_argument(op_code, index, arg)?);
+            }
+
+            Ok(Value::Array(call))
+        }
+        other => Err(malformed(format!(
+            "expected an operator name or a compound call, found {}",
+            other
+        ))),
+    }
+}
+
+fn pack_named_argument(op_code: RadonOpCodes, index: usize, arg: &JsonValue) -> Result<Value> {
+    match (op_code, index, arg) {
+        (
+            RadonOpCodes::ArrayFilter
+            | RadonOpCodes::ArrayMap
+            | RadonOpCodes::ArraySort
+            | RadonOpCodes::MapUnion,
+            0,
+            JsonValue::Array(subscript),
+        ) => Ok(Value::Array(pack_named_script(subscript)?)),
+        (RadonOpCodes::ArrayFilter, 0, JsonValue::String(name)) => {
+            Ok(Value::Integer(i128::from(u8::from(filter_code(name)?))))
+        }
+        (RadonOpCodes::ArrayReduce, 0, JsonValue::String(name)) => {
+            Ok(Value::Integer(i128::from(u8::from(reducer_code(name)?))))
+        }
+        _ => json_to_cbor(arg),
+    }
+}
+
+fn op_code(name: &str) -> Result<RadonOpCodes> {
+    RadonOpCodes::from_str(name).map_err(|_| malformed(format!("unknown operator `{}`", name)))
+}
+
+fn filter_code(name: &str) -> Result<RadonFilters> {
+    RadonFilters::from_str(name).map_err(|_| malformed(format!("unknown filter `{}`", name)))
+}
+
+fn reducer_code(name: &str) -> Result<RadonReducers> {
+    RadonReducers::from_str(name).map_err(|_| malformed(format!("unknown reducer `{}`", name)))
+}
+
+fn filter_name(code: i128) -> Result<&'static str> {
+    u8::try_from(code)
+        .ok()
+        .and_then(|code| RadonFilters::try_from(code).ok())
+        .map(<&str>::from)
+        .ok_or(RadError::UnknownFilter { code })
+}
+
+fn reducer_name(code: i128) -> Result<&'static str> {
+    u8::try_from(code)
+        .ok()
+        .and_then(|code| RadonReducers::try_from(code).ok())
+        .map(<&str>::from)
+        .ok_or(RadError::UnknownReducer { code })
+}
+
+/// Convert a JSON value into CBOR. Integers are kept as such, and any other number becomes a
+/// float.
+fn json_to_cbor(value: &JsonValue) -> Result<Value> {
+    Ok(match value {
+        JsonValue::Null => Value::Null,
+        JsonValue::Bool(boolean) => Value::Bool(*boolean),
+        JsonValue::Number(number) => {
+            if let Some(integer) = number.as_i64() {
+                Value::Integer(i128::from(integer))
+            } else if let Some(integer) = number.as_u64() {
+                Value::Integer(i128::from(integer))
+            } else {
+                // Numbers that are not integers can always be represented as `f64`
+                Value::Float(number.as_f64().unwrap_or_default())
+            }
+        }
+        JsonValue::String(text) => Value::Text(text.clone()),
+        JsonValue::Array(items) => {
+            Value::Array(items.iter().map(json_to_cbor).collect::<Result<_>>()?)
+        }
+        JsonValue::Object(map) => Value::Map(
+            map.iter()
+                .map(|(key, value)| Ok((Value::Text(key.clone()), json_to_cbor(value)?)))
+                .collect::<Result<_>>()?,
+        ),
+    })
+}
+
+fn encode(value: &Value) -> Result<Vec<u8>> {
+    serde_cbor::to_vec(value).map_err(|e| malformed(e.to_string()))
+}
+
+fn malformed(message: String) -> RadError {
+    RadError::MalformedNamedRequest { message }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn example_request() -> RADRequest {
+        let script = Value::Array(vec![
+            Value::Integer(RadonOpCodes::StringParseJSONMap as i128),
+            Value::Array(vec![
+                Value::Integer(RadonOpCodes::MapGetArray as i128),
+                Value::Text(String::from("prices")),
+            ]),
+            Value::Array(vec![
+                Value::Integer(RadonOpCodes::ArrayMap as i128),
+                Value::Array(vec![
+                    Value::Array(vec![
+                        Value::Integer(RadonOpCodes::MapGetFloat as i128),
+                        Value::Text(String::from("usd")),
+                    ]),
+                    Value::Array(vec![
+                        Value::Integer(RadonOpCodes::FloatMultiply as i128),
+                        Value::Float(1.5),
+                    ]),
+                ]),
+            ]),
+            Value::Array(vec![
+                Value::Integer(RadonOpCodes::ArrayFilter as i128),
+                Value::Integer(RadonFilters::DeviationStandard as i128),
+                Value::Float(2.0),
+            ]),
+            Value::Array(vec![
+                Value::Integer(RadonOpCodes::ArrayReduce as i128),
+                Value::Integer(RadonReducers::AverageMean as i128),
+            ]),
+            Value::Integer(RadonOpCodes::FloatRound as i128),
+        ]);
+
+        RADRequest {
+            time_lock: 0,
+            retrieve: vec![RADRetrieve {
+                kind: RADType::HttpGet,
+                url: String::from("https://example.com/prices"),
+                script: serde_cbor::to_vec(&script).unwrap(),
+                ..RADRetrieve::default()
+            }],
+            aggregate: RADAggregate {
+                filters: vec![RADFilter {
+                    op: RadonFilters::DeviationStandard as u32,
+                    args: serde_cbor::to_vec(&Value::Float(1.5)).unwrap(),
+                }],
+                reducer: RadonReducers::AverageMedian as u32,
+            },
+            tally: RADTally {
+                filters: vec![RADFilter {
+                    op: RadonFilters::Mode as u32,
+                    args: vec![],
+                }],
+                reducer: RadonReducers::Mode as u32,
+            },
+        }
+    }
+
+    #[test]
+    fn test_named_request_round_trip() {
+        let request = example_request();
+
+        let named = NamedRADRequest::try_from(&request).unwrap();
+        assert_eq!(
+            named.retrieve[0].script,
+            vec![
+                json!("StringParseJSONMap"),
+                json!(["MapGetArray", "prices"]),
+                json!(["ArrayMap", [["MapGetFloat", "usd"], ["FloatMultiply", 1.5]]]),
+                json!(["ArrayFilter", "DeviationStandard", 2.0]),
+                json!(["ArrayReduce", "AverageMean"]),
+                json!("FloatRound"),
+            ]
+        );
+        assert_eq!(
+            serde_json::to_value(&named.aggregate).unwrap(),
+            json!({
+                "filters": [{"op": "DeviationStandard", "args": 1.5}],
+                "reducer": "AverageMedian",
+            })
+        );
+        assert_eq!(
+            serde_json::to_value(&named.tally).unwrap(),
+            json!({"filters": [{"op": "Mode"}], "reducer": "Mode"})
+        );
+
+        // Going through JSON text does not lose anything, not even the type of floats that happen
+        // to have an integer value
+        let json = serde_json::to_string(&named).unwrap();
+        let named: NamedRADRequest = serde_json::from_str(&json).unwrap();
+        assert_eq!(RADRequest::try_from(&named).unwrap(), request);
+    }
+
+    #[test]
+    fn test_named_request_unknown_names() {
+        let mut named = NamedRADRequest::try_from(&example_request()).unwrap();
+        named.retrieve[0].script.push(json!("FloatFrobnicate"));
+        assert!(matches!(
+            RADRequest::try_from(&named),
+            Err(RadError::MalformedNamedRequest { .. })
+        ));
+
+        let mut named = NamedRADRequest::try_from(&example_request()).unwrap();
+        named.tally.reducer = String::from("AverageMode");
+        assert!(matches!(
+            RADRequest::try_from(&named),
+            Err(RadError::MalformedNamedRequest { .. })
+        ));
+
+        // Names are only allowed where operators, filters and reducers are expected
+        let mut named = NamedRADRequest::try_from(&example_request()).unwrap();
+        named.retrieve[0].script.push(json!(42));
+        assert!(matches!(
+            RADRequest::try_from(&named),
+            Err(RadError::MalformedNamedRequest { .. })
+        ));
+    }
+}
@@ -1,5 +1,6 @@
 use std::{
     clone::Clone,
+    collections::BTreeMap,
     convert::{TryFrom, TryInto},
     iter,
 };
@@ -10,10 +11,13 @@ use witnet_data_structures::radon_report::{RadonReport, ReportContext, Stage};
 use crate::{
     error::RadError,
     filters::{self, RadonFilters},
-    operators::{string, RadonOpCodes},
-    reducers::{self, RadonReducers},
+    operators::{get_path, string, RadonOpCodes},
+    reducers::{self, average, deviation, median, RadonReducers},
     script::{execute_radon_script, unpack_subscript, RadonCall, RadonScriptExecutionSettings},
-    types::{array::RadonArray, integer::RadonInteger, string::RadonString, RadonType, RadonTypes},
+    types::{
+        array::RadonArray, float::RadonFloat, integer::RadonInteger, map::RadonMap,
+        string::RadonString, RadonType, RadonTypes,
+    },
 };
 
 pub fn count(input: &RadonArray) -> RadonInteger {
@@ -31,18 +35,15 @@ pub fn reduce(
         args: args.to_vec(),
     };
 
-    if args.len() != 1 {
-        return Err(wrong_args());
-    }
-
-    let arg = args[0].to_owned();
-    let reducer_integer = from_value::<u8>(arg).map_err(|_| wrong_args())?;
+    // The reducer code can be followed by the arguments of the reducer
+    let (arg, reducer_args) = args.split_first().ok_or_else(wrong_args)?;
+    let reducer_integer = from_value::<u8>(arg.to_owned()).map_err(|_| wrong_args())?;
     let reducer_code = RadonReducers::try_from(reducer_integer).map_err(|_| wrong_args())?;
 
-    reducers::reduce(input, reducer_code, context)
+    reducers::reduce_with_args(input, reducer_code, reducer_args, context)
 }
 
-fn inner_get(input: &RadonArray, args: &[Value]) -> Result<RadonTypes, RadError> {
+pub(crate) fn inner_get(input: &RadonArray, args: &[Value]) -> Result<RadonTypes, RadError> {
     let wrong_args = || RadError::WrongArguments {
         input_type: RadonArray::radon_type_name(),
         operator: "Get".to_string(),
@@ -221,6 +222,142 @@ pub fn filter(
     }
 }
 
+/// Pass the input `RadonArray` through if it has at least as many items as the only argument,
+/// or fail with `RadError::ArrayTooShort` otherwise.
+pub fn min_length(input: &RadonArray, args: &[Value]) -> Result<RadonArray, RadError> {
+    let wrong_args = || RadError::WrongArguments {
+        input_type: RadonArray::radon_type_name(),
+        operator: "MinLength".to_string(),
+        args: args.to_vec(),
+    };
+
+    let min = match args {
+        [Value::Integer(min)] => usize::try_from(*min).map_err(|_| wrong_args())?,
+        _ => return Err(wrong_args()),
+    };
+    let len = input.value().len();
+
+    if len < min {
+        Err(RadError::ArrayTooShort { len, min })
+    } else {
+        Ok(input.clone())
+    }
+}
+
+/// Flatten the arrays nested in the input `RadonArray`, down to the depth given as the only
+/// argument: a depth of 1 only flattens the arrays that are direct items of the input.
+///
+/// Items of other types are kept as they are, so the output can mix items of different types.
+pub fn flatten(input: &RadonArray, args: &[Value]) -> Result<RadonArray, RadError> {
+    let wrong_args = || RadError::WrongArguments {
+        input_type: RadonArray::radon_type_name(),
+        operator: "Flatten".to_string(),
+        args: args.to_vec(),
+    };
+
+    let depth = match args {
+        [Value::Integer(depth)] => u8::try_from(*depth).map_err(|_| wrong_args())?,
+        _ => return Err(wrong_args()),
+    };
+
+    Ok(RadonArray::from(flatten_items(input.value(), depth)))
+}
+
+fn flatten_items(items: Vec<RadonTypes>, depth: u8) -> Vec<RadonTypes> {
+    items
+        .into_iter()
+        .flat_map(|item| match item {
+            RadonTypes::Array(array) if depth > 0 => flatten_items(array.value(), depth - 1),
+            item => vec![item],
+        })
+        .collect()
+}
+
+/// Group the items of the input `RadonArray` into a `RadonMap`, using as key the value found in
+/// every item at the path given as the only argument (as in `GetPath`). Every key is mapped to a
+/// `RadonArray` with the items that share that key, in their original order.
+///
+/// Keys can be strings, integers or booleans, and the non-string ones are turned into their string
+/// representation. As `RadonMap` keeps its keys sorted, the output does not depend on the order in
+/// which keys first appear in the input.
+pub fn group_by(input: &RadonArray, args: &[Value]) -> Result<RadonMap, RadError> {
+    if !matches!(args, [Value::Array(_)]) {
+        return Err(RadError::WrongArguments {
+            input_type: RadonArray::radon_type_name(),
+            operator: "GroupBy".to_string(),
+            args: args.to_vec(),
+        });
+    }
+
+    let mut groups = BTreeMap::<String, Vec<RadonTypes>>::new();
+    for item in input.value() {
+        let key = match get_path(item.clone(), args)? {
+            RadonTypes::String(key) => key.value(),
+            RadonTypes::Integer(key) => key.value().to_string(),
+            RadonTypes::Boolean(key) => key.value().to_string(),
+            key => {
+                return Err(RadError::UnsupportedGroupByKey {
+                    key_type: key.radon_type_name(),
+                })
+            }
+        };
+        groups.entry(key).or_default().push(item);
+    }
+
+    Ok(RadonMap::from(
+        groups
+            .into_iter()
+            .map(|(key, items)| (key, RadonTypes::from(RadonArray::from(items))))
+            .collect::<BTreeMap<_, _>>(),
+    ))
+}
+
+/// Summarize a numeric array into a `RadonMap` containing its `min`, `max`, `mean`, `median` and
+/// `stddev`, all of them as `RadonFloat`.
+///
+/// This is meant for inspecting data sources during the retrieval stage.
+// FIXME: Allow for now, since there is no safe cast function from an i128 to float yet
+#[allow(clippy::cast_precision_loss)]
+pub fn stats(input: &RadonArray) -> Result<RadonTypes, RadError> {
+    let floats = input
+        .value()
+        .iter()
+        .map(|item| match item {
+            RadonTypes::Float(float) => Ok(float.value()),
+            RadonTypes::Integer(integer) => Ok(integer.value() as f64),
+            _ => Err(RadError::MismatchingTypes {
+                method: RadonOpCodes::ArrayStats.to_string(),
+                expected: RadonFloat::radon_type_name(),
+                found: item.radon_type_name(),
+            }),
+        })
+        .collect::<Result<Vec<f64>, RadError>>()?;
+    if floats.is_empty() {
+        return Err(RadError::EmptyArray);
+    }
+
+    let min = floats.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = floats.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let floats = RadonArray::from(
+        floats
+            .into_iter()
+            .map(|float| RadonFloat::from(float).into())
+            .collect::<Vec<RadonTypes>>(),
+    );
+
+    let mut summary = BTreeMap::new();
+    summary.insert(String::from("min"), RadonFloat::from(min).into());
+    summary.insert(String::from("max"), RadonFloat::from(max).into());
+    summary.insert(
+        String::from("mean"),
+        average::mean(&floats, average::MeanReturnPolicy::ReturnFloat)?,
+    );
+    summary.insert(String::from("median"), median::median(&floats)?);
+    summary.insert(String::from("stddev"), deviation::standard(&floats)?);
+
+    Ok(RadonMap::from(summary).into())
+}
+
 pub fn sort(
     input: &RadonArray,
     args: &[Value],
@@ -272,6 +409,15 @@ pub fn sort(
             operator: "ArraySort".to_string(),
         });
     }
+    // Keys of different types have no total order, so they cannot be sorted deterministically
+    if !mapped_array.is_homogeneous() {
+        return Err(RadError::Sort {
+            message: format!(
+                "subscript produced sort keys of mixed types: {:?}",
+                mapped_array_value
+            ),
+        });
+    }
 
     // Distinguish depending the type
     match &mapped_array_value.first() {
@@ -414,7 +560,7 @@ mod tests {
             Operable,
             RadonOpCodes::{
                 IntegerGreaterThan, IntegerMultiply, MapGetBoolean, MapGetFloat, MapGetInteger,
-                MapGetString,
+                MapGetString, StringMatch,
             },
         },
         types::{
@@ -438,6 +584,203 @@ mod tests {
         assert_eq!(count(empty), RadonInteger::from(0));
     }
 
+    #[test]
+    fn test_array_min_length() {
+        let input = RadonArray::from(vec![
+            RadonFloat::from(1f64).into(),
+            RadonFloat::from(2f64).into(),
+        ]);
+
+        for min in [0, 1, 2] {
+            assert_eq!(
+                min_length(&input, &[Value::Integer(min)]),
+                Ok(input.clone())
+            );
+        }
+
+        let output = input
+            .operate(&(RadonOpCodes::ArrayMinLength, Some(vec![Value::Integer(2)])))
+            .unwrap();
+        assert_eq!(output, RadonTypes::from(input));
+    }
+
+    #[test]
+    fn test_array_group_by() {
+        let trade = |exchange: &str, price: f64| {
+            RadonTypes::from(RadonMap::from(
+                vec![
+                    (
+                        String::from("exchange"),
+                        RadonTypes::from(RadonString::from(exchange)),
+                    ),
+                    (
+                        String::from("price"),
+                        RadonTypes::from(RadonFloat::from(price)),
+                    ),
+                ]
+                .into_iter()
+                .collect::<BTreeMap<_, _>>(),
+            ))
+        };
+        let input = RadonArray::from(vec![
+            trade("kraken", 1.0),
+            trade("binance", 2.0),
+            trade("kraken", 3.0),
+        ]);
+        let args = vec![Value::Array(vec![Value::Text(String::from("exchange"))])];
+
+        let output = group_by(&input, &args).unwrap();
+        let expected = RadonMap::from(
+            vec![
+                (
+                    String::from("binance"),
+                    RadonTypes::from(RadonArray::from(vec![trade("binance", 2.0)])),
+                ),
+                (
+                    String::from("kraken"),
+                    RadonTypes::from(RadonArray::from(vec![
+                        trade("kraken", 1.0),
+                        trade("kraken", 3.0),
+                    ])),
+                ),
+            ]
+            .into_iter()
+            .collect::<BTreeMap<_, _>>(),
+        );
+        assert_eq!(output, expected);
+        assert_eq!(
+            output.value().keys().collect::<Vec<_>>(),
+            vec!["binance", "kraken"]
+        );
+
+        let output = input
+            .operate(&(RadonOpCodes::ArrayGroupBy, Some(args)))
+            .unwrap();
+        assert_eq!(output, RadonTypes::from(expected));
+    }
+
+    #[test]
+    fn test_array_group_by_errors() {
+        let input = RadonArray::from(vec![RadonTypes::from(RadonArray::from(vec![
+            RadonFloat::from(1f64).into(),
+        ]))]);
+
+        assert_eq!(
+            group_by(&input, &[Value::Array(vec![Value::Integer(0)])]),
+            Err(RadError::UnsupportedGroupByKey {
+                key_type: RadonFloat::radon_type_name()
+            })
+        );
+        assert!(matches!(
+            group_by(&input, &[Value::Text(String::from("exchange"))]),
+            Err(RadError::WrongArguments { .. })
+        ));
+        assert!(matches!(
+            group_by(
+                &input,
+                &[Value::Array(vec![Value::Text(String::from("exchange"))])]
+            ),
+            Err(RadError::InvalidPathSegment { .. })
+        ));
+    }
+
+    #[test]
+    fn test_array_min_length_too_short() {
+        let input = RadonArray::from(vec![RadonFloat::from(1f64).into()]);
+
+        assert_eq!(
+            min_length(&input, &[Value::Integer(3)]),
+            Err(RadError::ArrayTooShort { len: 1, min: 3 })
+        );
+        assert_eq!(
+            min_length(&RadonArray::from(vec![]), &[Value::Integer(1)]),
+            Err(RadError::ArrayTooShort { len: 0, min: 1 })
+        );
+        for args in [
+            vec![],
+            vec![Value::Integer(-1)],
+            vec![Value::Text("3".into())],
+        ] {
+            assert!(matches!(
+                min_length(&input, &args),
+                Err(RadError::WrongArguments { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_array_flatten() {
+        let integers = |values: &[i128]| {
+            RadonArray::from(
+                values
+                    .iter()
+                    .map(|value| RadonTypes::from(RadonInteger::from(*value)))
+                    .collect::<Vec<_>>(),
+            )
+        };
+        let depth = |depth: i128| [Value::Integer(depth)];
+
+        let input = RadonArray::from(vec![
+            RadonTypes::from(integers(&[1, 2])),
+            RadonTypes::from(integers(&[3])),
+        ]);
+        assert_eq!(flatten(&input, &depth(1)), Ok(integers(&[1, 2, 3])));
+        assert_eq!(flatten(&input, &depth(0)), Ok(input.clone()));
+
+        // Only as many levels as requested are flattened
+        let nested = RadonArray::from(vec![
+            RadonTypes::from(RadonArray::from(vec![RadonTypes::from(integers(&[1]))])),
+            RadonTypes::from(RadonInteger::from(2)),
+        ]);
+        assert_eq!(
+            flatten(&nested, &depth(1)),
+            Ok(RadonArray::from(vec![
+                RadonTypes::from(integers(&[1])),
+                RadonTypes::from(RadonInteger::from(2)),
+            ]))
+        );
+        assert_eq!(flatten(&nested, &depth(2)), Ok(integers(&[1, 2])));
+
+        // Items of different types can end up together
+        let mixed = RadonArray::from(vec![
+            RadonTypes::from(integers(&[1])),
+            RadonTypes::from(RadonBoolean::from(true)),
+        ]);
+        assert_eq!(
+            flatten(&mixed, &depth(1)),
+            Ok(RadonArray::from(vec![
+                RadonTypes::from(RadonInteger::from(1)),
+                RadonTypes::from(RadonBoolean::from(true)),
+            ]))
+        );
+
+        assert!(matches!(
+            flatten(&input, &depth(-1)),
+            Err(RadError::WrongArguments { .. })
+        ));
+    }
+
+    #[test]
+    fn test_reduce_sum() {
+        let input = &RadonArray::from(vec![
+            RadonInteger::from(1).into(),
+            RadonInteger::from(2).into(),
+            RadonInteger::from(3).into(),
+        ]);
+        let args = &[Value::Integer(RadonReducers::Sum as i128)];
+        let mut context = ReportContext {
+            active_wips: Some(crate::all_wips_active()),
+            ..Default::default()
+        };
+
+        let result = reduce(input, args, &mut context);
+        assert_eq!(result, Ok(RadonTypes::from(RadonInteger::from(6))));
+
+        // Before WIP-0029, the Sum reducer does not exist
+        let result = reduce(input, args, &mut ReportContext::default());
+        assert!(matches!(result, Err(RadError::UnsupportedReducer { .. })));
+    }
+
     #[test]
     fn test_reduce_no_args() {
         let input = &RadonArray::from(vec![
@@ -864,6 +1207,54 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_filter_order_books_by_exchange() {
+        let order_books = RadonString::from(
+            r#"[{"exchange":"binance","price":1.5},{"exchange":"kraken","price":1.6},{"exchange":"bitstamp","price":1.4}]"#,
+        );
+        let input = string::parse_json_array(&order_books).unwrap();
+
+        let mut matches = BTreeMap::new();
+        matches.insert(Value::Text("kraken".to_string()), Value::Bool(true));
+        let script = vec![Value::Array(vec![
+            Value::Array(vec![
+                Value::Integer(MapGetString as i128),
+                Value::Text("exchange".to_string()),
+            ]),
+            Value::Array(vec![
+                Value::Integer(StringMatch as i128),
+                Value::Map(matches),
+                Value::Bool(false),
+            ]),
+        ])];
+        let output = filter(&input, &script, &mut ReportContext::default()).unwrap();
+
+        let expected = RadonTypes::Array(RadonArray::from(vec![input.value()[1].clone()]));
+
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_filter_order_books_non_boolean_subscript() {
+        let order_books = RadonString::from(
+            r#"[{"exchange":"binance","price":1.5},{"exchange":"kraken","price":1.6}]"#,
+        );
+        let input = string::parse_json_array(&order_books).unwrap();
+
+        let script = vec![Value::Array(vec![Value::Array(vec![
+            Value::Integer(MapGetString as i128),
+            Value::Text("exchange".to_string()),
+        ])])];
+        let result = filter(&input, &script, &mut ReportContext::default());
+
+        assert_eq!(
+            result.unwrap_err(),
+            RadError::ArrayFilterWrongSubscript {
+                value: RadonTypes::from(RadonString::from("binance")).to_string()
+            }
+        );
+    }
+
     #[test]
     fn test_filter_operator() {
         let input = RadonArray::from(vec![
@@ -885,6 +1276,50 @@ mod tests {
         assert_eq!(output, expected)
     }
 
+    #[test]
+    fn test_stats() {
+        let input = RadonArray::from(vec![
+            RadonInteger::from(1).into(),
+            RadonInteger::from(2).into(),
+            RadonInteger::from(3).into(),
+            RadonInteger::from(4).into(),
+        ]);
+        let output = stats(&input).unwrap();
+
+        let expected = RadonTypes::from(RadonMap::from(
+            vec![
+                ("max", 4.0),
+                ("mean", 2.5),
+                ("median", 2.5),
+                ("min", 1.0),
+                ("stddev", 1.25f64.sqrt()),
+            ]
+            .into_iter()
+            .map(|(key, value)| (String::from(key), RadonFloat::from(value).into()))
+            .collect::<BTreeMap<String, RadonTypes>>(),
+        ));
+
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_stats_empty_or_not_numeric() {
+        assert_eq!(
+            stats(&RadonArray::from(vec![])).unwrap_err(),
+            RadError::EmptyArray
+        );
+
+        let input = RadonArray::from(vec![RadonString::from("1").into()]);
+        assert_eq!(
+            stats(&input).unwrap_err(),
+            RadError::MismatchingTypes {
+                method: "ArrayStats".to_string(),
+                expected: "RadonFloat",
+                found: "RadonString",
+            }
+        );
+    }
+
     #[test]
     fn test_sort_map_string_values() {
         let mut map1 = BTreeMap::new();
@@ -939,6 +1374,67 @@ mod tests {
         assert_eq!(output, expected)
     }
 
+    #[test]
+    fn test_sort_map_by_key_is_stable() {
+        let order_book = |exchange: &str, volume: i128| {
+            let mut map = BTreeMap::new();
+            map.insert(
+                "exchange".to_string(),
+                RadonTypes::String(RadonString::from(exchange)),
+            );
+            map.insert(
+                "volume".to_string(),
+                RadonTypes::Integer(RadonInteger::from(volume)),
+            );
+            RadonTypes::from(RadonMap::from(map))
+        };
+
+        let input = RadonArray::from(vec![
+            order_book("kraken", 7),
+            order_book("binance", 3),
+            order_book("bitstamp", 7),
+            order_book("coinbase", 3),
+        ]);
+        let script = vec![Value::Array(vec![Value::Array(vec![
+            Value::Integer(MapGetInteger as i128),
+            Value::Text("volume".to_string()),
+        ])])];
+        let output = sort(&input, &script, &mut ReportContext::default()).unwrap();
+
+        // Elements with equal keys keep their original relative order
+        let expected = RadonTypes::from(RadonArray::from(vec![
+            order_book("binance", 3),
+            order_book("coinbase", 3),
+            order_book("kraken", 7),
+            order_book("bitstamp", 7),
+        ]));
+
+        assert_eq!(output, expected)
+    }
+
+    #[test]
+    fn test_sort_mixed_key_types() {
+        let input = RadonArray::from(vec![
+            RadonArray::from(vec![
+                RadonInteger::from(1).into(),
+                RadonInteger::from(1).into(),
+            ])
+            .into(),
+            RadonArray::from(vec![
+                RadonString::from("A").into(),
+                RadonString::from("A").into(),
+            ])
+            .into(),
+        ]);
+        let script = vec![Value::Array(vec![Value::Array(vec![
+            Value::Integer(RadonOpCodes::ArrayReduce as i128),
+            Value::Integer(RadonReducers::Mode as i128),
+        ])])];
+        let result = sort(&input, &script, &mut ReportContext::default());
+
+        assert!(matches!(result, Err(RadError::Sort { .. })));
+    }
+
     #[test]
     fn test_sort_map_integer_values() {
         let mut map1 = BTreeMap::new();
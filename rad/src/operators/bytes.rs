@@ -27,9 +27,24 @@ pub fn hash(input: &RadonBytes, args: &[Value]) -> Result<RadonBytes, RadError>
 
     Ok(RadonBytes::from(digest))
 }
+
+/// Keccak-256 digest of the input, as used by Ethereum.
+pub fn keccak256(input: &RadonBytes) -> RadonBytes {
+    RadonBytes::from(hash_functions::keccak_256(input.value().as_slice()))
+}
+
+/// RIPEMD-160 digest of the SHA2-256 digest of the input.
+pub fn hash160(input: &RadonBytes) -> RadonBytes {
+    RadonBytes::from(hash_functions::hash160(input.value().as_slice()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        operators::{operate, RadonOpCodes},
+        types::RadonTypes,
+    };
 
     #[test]
     fn test_bytes_to_string() {
@@ -64,4 +79,23 @@ mod tests {
             "Hash function `RadonHashFunctions::Fail` is not implemented"
         );
     }
+    #[test]
+    fn test_hash_keccak256_and_hash160() {
+        let bytes = RadonTypes::from(RadonBytes::from(b"hello".to_vec()));
+        let string = RadonTypes::from(RadonString::from("hello"));
+        let keccak256 = "1c8aff950685c2ed4bc3174f3472287b56d9517b9c948127319a09a7a36deac8";
+        let hash160 = "b6a9c8c230722b7c748331a8b450f05566dc7d0f";
+
+        for input in [bytes, string] {
+            for (op_code, expected) in [
+                (RadonOpCodes::HashKeccak256, keccak256),
+                (RadonOpCodes::HashHash160, hash160),
+            ] {
+                let output = operate(input.clone(), &(op_code, None)).unwrap();
+                let expected = RadonTypes::from(RadonBytes::from(hex::decode(expected).unwrap()));
+
+                assert_eq!(output, expected);
+            }
+        }
+    }
 }
@@ -1,5 +1,6 @@
 use std::{borrow::ToOwned, convert::TryFrom};
 
+use num_enum::TryFromPrimitive;
 use serde_cbor::value::{from_value, Value};
 
 use crate::{
@@ -101,6 +102,154 @@ pub fn round(input: &RadonFloat) -> RadonInteger {
     RadonInteger::from(input.value().round() as i128)
 }
 
+/// Rounding modes that can be passed as an argument to `FloatRound`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, TryFromPrimitive)]
+#[repr(u8)]
+pub enum RoundingMode {
+    /// Round to the nearest integer, and round ties away from zero: 2.5 becomes 3 and -2.5 becomes
+    /// -3. This is the behavior of `FloatRound` when no rounding mode is given.
+    HalfUp = 0x00,
+    /// Round to the nearest integer, and round ties to the nearest even integer: both 1.5 and 2.5
+    /// become 2.
+    HalfEven = 0x01,
+    /// Round towards negative infinity.
+    Floor = 0x02,
+    /// Round towards positive infinity.
+    Ceil = 0x03,
+}
+
+/// Round the input using the rounding mode given as the only argument.
+// FIXME: Allow for now, wait for https://github.com/rust-lang/rust/issues/67058 to reach stable
+#[allow(clippy::cast_possible_truncation)]
+pub fn round_with_mode(input: &RadonFloat, args: &[Value]) -> Result<RadonInteger, RadError> {
+    let wrong_args = || RadError::WrongArguments {
+        input_type: RadonFloat::radon_type_name(),
+        operator: "Round".to_string(),
+        args: args.to_vec(),
+    };
+
+    let mode = match args {
+        [Value::Integer(code)] => u8::try_from(*code)
+            .ok()
+            .and_then(|code| RoundingMode::try_from_primitive(code).ok())
+            .ok_or_else(wrong_args)?,
+        _ => return Err(wrong_args()),
+    };
+    let value = input.value();
+    let rounded = match mode {
+        RoundingMode::HalfUp => value.round(),
+        RoundingMode::HalfEven => value.round_ties_even(),
+        RoundingMode::Floor => value.floor(),
+        RoundingMode::Ceil => value.ceil(),
+    };
+
+    Ok(RadonInteger::from(rounded as i128))
+}
+
+/// Number of decimals that are enough to write down the exact value of any `f64`, which is that of
+/// the smallest subnormal number (2^-1074).
+const F64_MAX_DECIMALS: usize = 1074;
+
+/// Format the input with the number of decimals given as the only argument.
+///
+/// The exact binary value of the float is rounded to the nearest decimal, and ties are rounded to
+/// the nearest even digit. This does not depend on the platform, unlike displaying the float after
+/// decoding it.
+pub fn to_fixed(input: &RadonFloat, args: &[Value]) -> Result<RadonString, RadError> {
+    let wrong_args = || RadError::WrongArguments {
+        input_type: RadonFloat::radon_type_name(),
+        operator: "FloatToString".to_string(),
+        args: args.to_vec(),
+    };
+
+    let decimals = match args {
+        [Value::Integer(decimals)] => u8::try_from(*decimals).map_err(|_| wrong_args())?,
+        _ => return Err(wrong_args()),
+    };
+
+    Ok(RadonString::from(round_half_to_even(
+        input.value(),
+        usize::from(decimals),
+    )))
+}
+
+/// Write down a float with the given number of decimals, rounding ties to the nearest even digit.
+///
+/// The rounding is done here rather than by `format!`, whose handling of ties has changed across
+/// versions of Rust. `format!` is only used to write down the exact value of the float, which needs
+/// no rounding at all.
+fn round_half_to_even(value: f64, decimals: usize) -> String {
+    if !value.is_finite() {
+        return value.to_string();
+    }
+
+    let exact = format!("{:.*}", F64_MAX_DECIMALS, value.abs());
+    let (integer, fraction) = exact.split_at(exact.len() - F64_MAX_DECIMALS - 1);
+    let (kept, dropped) = fraction[1..].split_at(decimals);
+    let mut digits: Vec<u8> = integer.bytes().chain(kept.bytes()).collect();
+
+    let round_up = match dropped.as_bytes().split_first() {
+        Some((b'5', rest)) if rest.iter().all(|digit| *digit == b'0') => {
+            // A tie, so round to the nearest even digit
+            digits.last().map_or(false, |last| (last - b'0') % 2 == 1)
+        }
+        Some((first, _)) => *first >= b'5',
+        None => false,
+    };
+    if round_up {
+        let mut position = digits.len();
+        loop {
+            if position == 0 {
+                digits.insert(0, b'1');
+                break;
+            }
+            position -= 1;
+            if digits[position] == b'9' {
+                digits[position] = b'0';
+            } else {
+                digits[position] += 1;
+                break;
+            }
+        }
+    }
+
+    let (integer, fraction) = digits.split_at(digits.len() - decimals);
+    let sign = if value.is_sign_negative() { "-" } else { "" };
+    let integer = String::from_utf8_lossy(integer);
+    let fraction = String::from_utf8_lossy(fraction);
+    if decimals == 0 {
+        format!("{}{}", sign, integer)
+    } else {
+        format!("{}{}.{}", sign, integer, fraction)
+    }
+}
+
+/// Restrict the input to the range given by the two arguments, both inclusive.
+pub fn clamp(input: &RadonFloat, args: &[Value]) -> Result<RadonFloat, RadError> {
+    let wrong_args = || RadError::WrongArguments {
+        input_type: RadonFloat::radon_type_name(),
+        operator: "Clamp".to_string(),
+        args: args.to_vec(),
+    };
+
+    let (low, high) = match args {
+        [low, high] => (
+            from_value::<f64>(low.to_owned()).map_err(|_| wrong_args())?,
+            from_value::<f64>(high.to_owned()).map_err(|_| wrong_args())?,
+        ),
+        _ => return Err(wrong_args()),
+    };
+    // NaN bounds must be rejected as well, as they would make `f64::clamp` panic
+    if low.is_nan() || high.is_nan() || low > high {
+        return Err(RadError::InvalidRange {
+            low: low.to_string(),
+            high: high.to_string(),
+        });
+    }
+
+    Ok(RadonFloat::from(input.value().clamp(low, high)))
+}
+
 // No safe cast function from a float to integer yet, but this may just be fine since we are truncating anyway
 #[allow(clippy::cast_possible_truncation)]
 pub fn truncate(input: &RadonFloat) -> RadonInteger {
@@ -124,6 +273,43 @@ fn test_float_to_string() {
     assert_eq!(to_string(rad_int).unwrap(), rad_string);
 }
 
+#[test]
+fn test_float_to_fixed() {
+    let to_fixed_with = |value: f64, decimals: i128| {
+        to_fixed(&RadonFloat::from(value), &[Value::Integer(decimals)]).unwrap()
+    };
+
+    // 1.005 is actually stored as 1.00499999999999989...
+    assert_eq!(to_fixed_with(1.005, 2), RadonString::from("1.00"));
+    // Exact ties are rounded to the nearest even digit
+    assert_eq!(to_fixed_with(0.125, 2), RadonString::from("0.12"));
+    assert_eq!(to_fixed_with(0.375, 2), RadonString::from("0.38"));
+    assert_eq!(to_fixed_with(2.5, 0), RadonString::from("2"));
+    assert_eq!(to_fixed_with(-1.5, 0), RadonString::from("-2"));
+    assert_eq!(to_fixed_with(10.0, 3), RadonString::from("10.000"));
+    // Rounding may carry over to the integer part
+    assert_eq!(to_fixed_with(9.96, 1), RadonString::from("10.0"));
+    assert_eq!(to_fixed_with(-99.5, 0), RadonString::from("-100"));
+    assert_eq!(to_fixed_with(0.5, 0), RadonString::from("0"));
+    assert_eq!(
+        to_fixed_with(f64::MIN_POSITIVE, 3),
+        RadonString::from("0.000")
+    );
+    assert_eq!(to_fixed_with(f64::NAN, 2), RadonString::from("NaN"));
+    assert_eq!(
+        to_fixed_with(f64::NEG_INFINITY, 2),
+        RadonString::from("-inf")
+    );
+
+    let input = RadonFloat::from(1.0);
+    for args in [vec![], vec![Value::Integer(-1)], vec![Value::Integer(256)]] {
+        assert!(matches!(
+            to_fixed(&input, &args),
+            Err(RadError::WrongArguments { .. })
+        ));
+    }
+}
+
 #[test]
 fn test_float_multiply() {
     let rad_int = RadonFloat::from(10.0);
@@ -247,6 +433,54 @@ fn test_float_round() {
     assert_eq!(round(&float3), RadonInteger::from(11));
 }
 
+#[test]
+fn test_float_round_with_mode() {
+    let round_with = |value: f64, mode: RoundingMode| {
+        round_with_mode(
+            &RadonFloat::from(value),
+            &[Value::Integer(i128::from(mode as u8))],
+        )
+        .unwrap()
+    };
+
+    assert_eq!(round_with(2.5, RoundingMode::HalfUp), RadonInteger::from(3));
+    assert_eq!(
+        round_with(2.5, RoundingMode::HalfEven),
+        RadonInteger::from(2)
+    );
+    assert_eq!(round_with(2.5, RoundingMode::Floor), RadonInteger::from(2));
+    assert_eq!(round_with(2.5, RoundingMode::Ceil), RadonInteger::from(3));
+
+    assert_eq!(
+        round_with(-2.5, RoundingMode::HalfUp),
+        RadonInteger::from(-3)
+    );
+    assert_eq!(
+        round_with(-2.5, RoundingMode::HalfEven),
+        RadonInteger::from(-2)
+    );
+    assert_eq!(
+        round_with(-2.5, RoundingMode::Floor),
+        RadonInteger::from(-3)
+    );
+    assert_eq!(round_with(-2.5, RoundingMode::Ceil), RadonInteger::from(-2));
+    assert_eq!(
+        round_with(3.5, RoundingMode::HalfEven),
+        RadonInteger::from(4)
+    );
+
+    // The default mode is the same as not giving any mode
+    let input = RadonFloat::from(2.5);
+    assert_eq!(round_with(2.5, RoundingMode::HalfUp), round(&input));
+
+    for args in [vec![], vec![Value::Integer(4)], vec![Value::Integer(-1)]] {
+        assert!(matches!(
+            round_with_mode(&input, &args),
+            Err(RadError::WrongArguments { .. })
+        ));
+    }
+}
+
 #[test]
 fn test_float_trunc() {
     let float1 = RadonFloat::from(10.0);
@@ -257,3 +491,38 @@ fn test_float_trunc() {
     assert_eq!(truncate(&float2), RadonInteger::from(10));
     assert_eq!(truncate(&float3), RadonInteger::from(-10));
 }
+
+#[test]
+fn test_float_clamp() {
+    let range = [Value::Float(0.0), Value::Float(100.0)];
+
+    assert_eq!(
+        clamp(&RadonFloat::from(150.0), &range),
+        Ok(RadonFloat::from(100.0))
+    );
+    assert_eq!(
+        clamp(&RadonFloat::from(-0.5), &range),
+        Ok(RadonFloat::from(0.0))
+    );
+    assert_eq!(
+        clamp(&RadonFloat::from(42.5), &range),
+        Ok(RadonFloat::from(42.5))
+    );
+    assert_eq!(
+        clamp(
+            &RadonFloat::from(42.5),
+            &[Value::Float(100.0), Value::Float(0.0)]
+        ),
+        Err(RadError::InvalidRange {
+            low: String::from("100"),
+            high: String::from("0"),
+        })
+    );
+    assert!(matches!(
+        clamp(
+            &RadonFloat::from(42.5),
+            &[Value::Float(f64::NAN), Value::Float(0.0)]
+        ),
+        Err(RadError::InvalidRange { .. })
+    ));
+}
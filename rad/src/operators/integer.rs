@@ -5,8 +5,8 @@ use serde_cbor::value::{from_value, Value};
 use crate::{
     error::RadError,
     types::{
-        boolean::RadonBoolean, float::RadonFloat, integer::RadonInteger, string::RadonString,
-        RadonType,
+        boolean::RadonBoolean, bytes::RadonBytes, float::RadonFloat, integer::RadonInteger,
+        string::RadonString, RadonType,
     },
 };
 
@@ -114,6 +114,142 @@ pub fn power(input: &RadonInteger, args: &[Value]) -> Result<RadonInteger, RadEr
     }
 }
 
+pub fn bit_and(input: &RadonInteger, args: &[Value]) -> Result<RadonInteger, RadError> {
+    let wrong_args = || RadError::WrongArguments {
+        input_type: RadonInteger::radon_type_name(),
+        operator: "BitAnd".to_string(),
+        args: args.to_vec(),
+    };
+
+    let arg = args.first().ok_or_else(wrong_args)?.to_owned();
+    let mask = from_value::<i128>(arg).map_err(|_| wrong_args())?;
+
+    Ok(RadonInteger::from(input.value() & mask))
+}
+
+pub fn bit_or(input: &RadonInteger, args: &[Value]) -> Result<RadonInteger, RadError> {
+    let wrong_args = || RadError::WrongArguments {
+        input_type: RadonInteger::radon_type_name(),
+        operator: "BitOr".to_string(),
+        args: args.to_vec(),
+    };
+
+    let arg = args.first().ok_or_else(wrong_args)?.to_owned();
+    let mask = from_value::<i128>(arg).map_err(|_| wrong_args())?;
+
+    Ok(RadonInteger::from(input.value() | mask))
+}
+
+/// Shifting by 128 bits or more saturates to zero instead of overflowing.
+pub fn shift_left(input: &RadonInteger, args: &[Value]) -> Result<RadonInteger, RadError> {
+    let wrong_args = || RadError::WrongArguments {
+        input_type: RadonInteger::radon_type_name(),
+        operator: "ShiftLeft".to_string(),
+        args: args.to_vec(),
+    };
+
+    let bits = read_shift_bits(args).ok_or_else(wrong_args)?;
+    let result = u32::try_from(bits)
+        .ok()
+        .and_then(|bits| input.value().checked_shl(bits))
+        .unwrap_or(0);
+
+    Ok(RadonInteger::from(result))
+}
+
+/// Shifting by 128 bits or more saturates to zero instead of overflowing.
+pub fn shift_right(input: &RadonInteger, args: &[Value]) -> Result<RadonInteger, RadError> {
+    let wrong_args = || RadError::WrongArguments {
+        input_type: RadonInteger::radon_type_name(),
+        operator: "ShiftRight".to_string(),
+        args: args.to_vec(),
+    };
+
+    let bits = read_shift_bits(args).ok_or_else(wrong_args)?;
+    let result = u32::try_from(bits)
+        .ok()
+        .and_then(|bits| input.value().checked_shr(bits))
+        .unwrap_or(0);
+
+    Ok(RadonInteger::from(result))
+}
+
+/// Encode an integer into a fixed number of bytes.
+///
+/// The first argument is the width in bytes, from 1 to 16. The second, optional argument tells
+/// whether to use big-endian (`true`, the default) or little-endian (`false`) byte order.
+/// Negative values are encoded in two's complement. Values that do not fit into the given width
+/// cause an overflow error.
+pub fn to_bytes(input: &RadonInteger, args: &[Value]) -> Result<RadonBytes, RadError> {
+    let wrong_args = || RadError::WrongArguments {
+        input_type: RadonInteger::radon_type_name(),
+        operator: "ToBytes".to_string(),
+        args: args.to_vec(),
+    };
+
+    let arg = args.first().ok_or_else(wrong_args)?.to_owned();
+    let width = from_value::<usize>(arg).map_err(|_| wrong_args())?;
+    if width == 0 || width > 16 {
+        return Err(wrong_args());
+    }
+    let big_endian = match args.get(1) {
+        Some(arg) => from_value::<bool>(arg.to_owned()).map_err(|_| wrong_args())?,
+        None => true,
+    };
+
+    let value = input.value();
+    let full = value.to_be_bytes();
+    let (dropped, kept) = full.split_at(16 - width);
+    // Dropped bytes must be pure sign extension, and negative values must keep their sign bit
+    let fits = if value < 0 {
+        dropped.iter().all(|byte| *byte == 0xFF) && kept[0] & 0x80 != 0
+    } else {
+        dropped.iter().all(|byte| *byte == 0x00)
+    };
+    if !fits {
+        return Err(RadError::Overflow);
+    }
+
+    let mut bytes = kept.to_vec();
+    if !big_endian {
+        bytes.reverse();
+    }
+
+    Ok(RadonBytes::from(bytes))
+}
+
+/// Restrict the input to the range given by the two arguments, both inclusive.
+pub fn clamp(input: &RadonInteger, args: &[Value]) -> Result<RadonInteger, RadError> {
+    let wrong_args = || RadError::WrongArguments {
+        input_type: RadonInteger::radon_type_name(),
+        operator: "Clamp".to_string(),
+        args: args.to_vec(),
+    };
+
+    let (low, high) = match args {
+        [low, high] => (
+            from_value::<i128>(low.to_owned()).map_err(|_| wrong_args())?,
+            from_value::<i128>(high.to_owned()).map_err(|_| wrong_args())?,
+        ),
+        _ => return Err(wrong_args()),
+    };
+    if low > high {
+        return Err(RadError::InvalidRange {
+            low: low.to_string(),
+            high: high.to_string(),
+        });
+    }
+
+    Ok(RadonInteger::from(input.value().clamp(low, high)))
+}
+
+/// Read the number of bits to shift by, which cannot be negative.
+fn read_shift_bits(args: &[Value]) -> Option<i128> {
+    let arg = args.first()?.to_owned();
+
+    from_value::<i128>(arg).ok().filter(|bits| *bits >= 0)
+}
+
 #[test]
 fn test_integer_absolute() {
     let positive_integer = RadonInteger::from(10);
@@ -266,3 +402,121 @@ fn test_integer_power() {
         "Overflow error".to_string(),
     );
 }
+
+#[test]
+fn test_integer_bit_and_or() {
+    let rad_int = RadonInteger::from(0b1010);
+
+    assert_eq!(
+        bit_and(&rad_int, &[Value::Integer(0b0110)]).unwrap(),
+        RadonInteger::from(0b0010)
+    );
+    assert_eq!(
+        bit_or(&rad_int, &[Value::Integer(0b0101)]).unwrap(),
+        RadonInteger::from(0b1111)
+    );
+    assert!(bit_and(&rad_int, &[Value::Text("1".to_string())]).is_err());
+}
+
+#[test]
+fn test_integer_extract_bit() {
+    let rad_int = RadonInteger::from(0b1010);
+
+    // Bit 3 of 0b1010 is set, and bit 2 is not
+    let shifted = shift_right(&rad_int, &[Value::Integer(3)]).unwrap();
+    assert_eq!(
+        bit_and(&shifted, &[Value::Integer(1)]).unwrap(),
+        RadonInteger::from(1)
+    );
+    let shifted = shift_right(&rad_int, &[Value::Integer(2)]).unwrap();
+    assert_eq!(
+        bit_and(&shifted, &[Value::Integer(1)]).unwrap(),
+        RadonInteger::from(0)
+    );
+}
+
+#[test]
+fn test_integer_shift_saturates() {
+    let rad_int = RadonInteger::from(0b1010);
+
+    assert_eq!(
+        shift_left(&rad_int, &[Value::Integer(2)]).unwrap(),
+        RadonInteger::from(0b101000)
+    );
+    assert_eq!(
+        shift_left(&rad_int, &[Value::Integer(128)]).unwrap(),
+        RadonInteger::from(0)
+    );
+    assert_eq!(
+        shift_right(&rad_int, &[Value::Integer(i128::MAX)]).unwrap(),
+        RadonInteger::from(0)
+    );
+    assert!(shift_right(&rad_int, &[Value::Integer(-1)]).is_err());
+}
+
+#[test]
+fn test_integer_to_bytes() {
+    let rad_int = RadonInteger::from(258);
+
+    assert_eq!(
+        to_bytes(&rad_int, &[Value::Integer(2), Value::Bool(true)]).unwrap(),
+        RadonBytes::from(vec![0x01, 0x02])
+    );
+    assert_eq!(
+        to_bytes(&rad_int, &[Value::Integer(4), Value::Bool(false)]).unwrap(),
+        RadonBytes::from(vec![0x02, 0x01, 0x00, 0x00])
+    );
+    assert_eq!(
+        to_bytes(&RadonInteger::from(-2), &[Value::Integer(2)]).unwrap(),
+        RadonBytes::from(vec![0xFF, 0xFE])
+    );
+}
+
+#[test]
+fn test_integer_to_bytes_overflow() {
+    assert_eq!(
+        to_bytes(&RadonInteger::from(258), &[Value::Integer(1)]).unwrap_err(),
+        RadError::Overflow
+    );
+    assert_eq!(
+        to_bytes(&RadonInteger::from(-129), &[Value::Integer(1)]).unwrap_err(),
+        RadError::Overflow
+    );
+    assert_eq!(
+        to_bytes(&RadonInteger::from(255), &[Value::Integer(1)]).unwrap(),
+        RadonBytes::from(vec![0xFF])
+    );
+    assert!(to_bytes(&RadonInteger::from(1), &[Value::Integer(17)]).is_err());
+}
+
+#[test]
+fn test_integer_clamp() {
+    let range = [Value::Integer(0), Value::Integer(100)];
+
+    assert_eq!(
+        clamp(&RadonInteger::from(150), &range),
+        Ok(RadonInteger::from(100))
+    );
+    assert_eq!(
+        clamp(&RadonInteger::from(-5), &range),
+        Ok(RadonInteger::from(0))
+    );
+    assert_eq!(
+        clamp(&RadonInteger::from(42), &range),
+        Ok(RadonInteger::from(42))
+    );
+    assert_eq!(
+        clamp(
+            &RadonInteger::from(42),
+            &[Value::Integer(100), Value::Integer(0)]
+        ),
+        Err(RadError::InvalidRange {
+            low: String::from("100"),
+            high: String::from("0"),
+        })
+    );
+    assert!(matches!(
+        clamp(&RadonInteger::from(42), &[Value::Integer(0)]),
+        Err(RadError::WrongArguments { .. })
+    ));
+}
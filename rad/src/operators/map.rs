@@ -1,14 +1,19 @@
 use std::convert::TryInto;
 
 use serde_cbor::value::{from_value, Value};
+use witnet_data_structures::radon_report::ReportContext;
 
 use crate::{
     error::RadError,
     operators::string,
-    types::{array::RadonArray, map::RadonMap, string::RadonString, RadonType, RadonTypes},
+    script::{execute_radon_script, unpack_subscript, RadonScriptExecutionSettings},
+    types::{
+        array::RadonArray, boolean::RadonBoolean, bytes::RadonBytes, float::RadonFloat,
+        integer::RadonInteger, map::RadonMap, string::RadonString, RadonType, RadonTypes,
+    },
 };
 
-fn inner_get(input: &RadonMap, args: &[Value]) -> Result<RadonTypes, RadError> {
+pub(crate) fn inner_get(input: &RadonMap, args: &[Value]) -> Result<RadonTypes, RadError> {
     let wrong_args = || RadError::WrongArguments {
         input_type: RadonMap::radon_type_name(),
         operator: "Get".to_string(),
@@ -51,6 +56,43 @@ where
     get_numeric_string(input, args).and_then(O::try_from)
 }
 
+/// Get an entry from the input `RadonMap`, as specified by the first argument, which is used as
+/// the search key, or the second argument if there is no such entry.
+///
+/// The type of the default value determines the expected type of the entry, so this still fails
+/// with a `RadError::Decode` if the entry exists but cannot be converted into that type.
+pub fn get_or_default(input: &RadonMap, args: &[Value]) -> Result<RadonTypes, RadError> {
+    let wrong_args = || RadError::WrongArguments {
+        input_type: RadonMap::radon_type_name(),
+        operator: "GetOrDefault".to_string(),
+        args: args.to_vec(),
+    };
+
+    let (key, default) = match args {
+        [key, default] => (
+            from_value::<String>(key.to_owned()).map_err(|_| wrong_args())?,
+            RadonTypes::try_from(default.to_owned()).map_err(|_| wrong_args())?,
+        ),
+        _ => return Err(wrong_args()),
+    };
+
+    let item = match input.value().get(&key) {
+        Some(item) => item.clone(),
+        None => return Ok(default),
+    };
+
+    match default {
+        RadonTypes::Array(_) => RadonArray::try_from(item).map(Into::into),
+        RadonTypes::Boolean(_) => RadonBoolean::try_from(item).map(Into::into),
+        RadonTypes::Bytes(_) => RadonBytes::try_from(item).map(Into::into),
+        RadonTypes::Float(_) => RadonFloat::try_from(item).map(Into::into),
+        RadonTypes::Integer(_) => RadonInteger::try_from(item).map(Into::into),
+        RadonTypes::Map(_) => RadonMap::try_from(item).map(Into::into),
+        RadonTypes::String(_) => RadonString::try_from(item).map(Into::into),
+        RadonTypes::RadonError(_) => Err(wrong_args()),
+    }
+}
+
 /// Try to get a `RadonTypes` from an entry in the input `RadonMap`, as specified by the first
 /// argument, which is used as the search key.
 ///
@@ -81,6 +123,61 @@ pub fn values(input: &RadonMap) -> RadonArray {
     RadonArray::from(v)
 }
 
+/// Merge the input `RadonMap` with a second one, which is taken from the only argument. That
+/// argument can be either a map literal, or a subscript that is run on the input map and must
+/// return a `RadonMap`.
+///
+/// The result contains all the entries of both maps. When a key exists in both of them, the value
+/// from the second map overrides the value from the input map. Entries are always kept sorted by
+/// key, so the serialization of the result does not depend on which map each entry came from.
+pub fn union(
+    input: &RadonMap,
+    args: &[Value],
+    context: &mut ReportContext<RadonTypes>,
+) -> Result<RadonMap, RadError> {
+    let wrong_args = || RadError::WrongArguments {
+        input_type: RadonMap::radon_type_name(),
+        operator: "Union".to_string(),
+        args: args.to_vec(),
+    };
+
+    let other = match args {
+        [map @ Value::Map(_)] => RadonMap::try_from(map.clone())?,
+        [subscript @ Value::Array(_)] => {
+            let subscript_err = |e| RadError::Subscript {
+                input_type: RadonMap::radon_type_name().to_string(),
+                operator: "Union".to_string(),
+                inner: Box::new(e),
+            };
+            let subscript = unpack_subscript(subscript).map_err(subscript_err)?;
+
+            let settings = RadonScriptExecutionSettings::tailored_to_stage(&context.stage);
+            let report = execute_radon_script(
+                RadonTypes::from(input.clone()),
+                subscript.as_slice(),
+                context,
+                settings,
+            )?;
+            let result = report.into_inner();
+            if let RadonTypes::RadonError(error) = result {
+                return Err(error.into_inner());
+            }
+            let original_type = result.radon_type_name();
+
+            RadonMap::try_from(result).map_err(|_| RadError::Decode {
+                from: original_type,
+                to: RadonMap::radon_type_name(),
+            })?
+        }
+        _ => return Err(wrong_args()),
+    };
+
+    let mut merged = input.value();
+    merged.extend(other.value());
+
+    Ok(RadonMap::from(merged))
+}
+
 /// This module was introduced for encapsulating the interim legacy logic before WIP-0024 is
 /// introduced, for the sake of maintainability.
 ///
@@ -384,6 +481,114 @@ mod tests {
         (output, key1, item1)
     }
 
+    fn integer_map(entries: &[(&str, i128)]) -> RadonMap {
+        RadonMap::from(
+            entries
+                .iter()
+                .map(|(key, value)| (key.to_string(), RadonInteger::from(*value).into()))
+                .collect::<BTreeMap<String, RadonTypes>>(),
+        )
+    }
+
+    #[test]
+    fn test_map_union() {
+        let input = integer_map(&[("a", 1), ("b", 2)]);
+        let args = vec![Value::Map(
+            [
+                (Value::Text(String::from("b")), Value::Integer(3)),
+                (Value::Text(String::from("c")), Value::Integer(4)),
+            ]
+            .into_iter()
+            .collect(),
+        )];
+
+        let output = union(&input, &args, &mut ReportContext::default()).unwrap();
+
+        assert_eq!(output, integer_map(&[("a", 1), ("b", 3), ("c", 4)]));
+        // Keys are serialized in order no matter which map they came from
+        let encoded: Vec<u8> = RadonTypes::from(output).try_into().unwrap();
+        let expected: Vec<u8> = RadonTypes::from(integer_map(&[("c", 4), ("a", 1), ("b", 3)]))
+            .try_into()
+            .unwrap();
+        assert_eq!(encoded, expected);
+    }
+
+    #[test]
+    fn test_map_union_subscript() {
+        let mut map = integer_map(&[("a", 1), ("b", 2)]).value();
+        map.insert(
+            String::from("other"),
+            integer_map(&[("b", 3), ("c", 4)]).into(),
+        );
+        let input = RadonMap::from(map);
+        let args = vec![Value::Array(vec![Value::Array(vec![
+            Value::Integer(RadonOpCodes::MapGetMap as i128),
+            Value::Text(String::from("other")),
+        ])])];
+
+        let output = input
+            .operate(&(RadonOpCodes::MapUnion, Some(args)))
+            .unwrap();
+
+        let mut expected = integer_map(&[("a", 1), ("b", 3), ("c", 4)]).value();
+        expected.insert(
+            String::from("other"),
+            integer_map(&[("b", 3), ("c", 4)]).into(),
+        );
+        assert_eq!(output, RadonMap::from(expected).into());
+    }
+
+    #[test]
+    fn test_map_union_wrong_args() {
+        let input = integer_map(&[("a", 1)]);
+
+        for args in [vec![], vec![Value::Integer(1)]] {
+            assert!(matches!(
+                union(&input, &args, &mut ReportContext::default()),
+                Err(RadError::WrongArguments { .. })
+            ));
+        }
+
+        // The subscript must return a map
+        let args = vec![Value::Array(vec![Value::Integer(
+            RadonOpCodes::MapKeys as i128,
+        )])];
+        assert_eq!(
+            union(&input, &args, &mut ReportContext::default()),
+            Err(RadError::Decode {
+                from: "RadonArray",
+                to: "RadonMap",
+            })
+        );
+    }
+
+    #[test]
+    fn test_map_get_or_default() {
+        let mut map = BTreeMap::new();
+        map.insert(String::from("price"), RadonFloat::from(9.5).into());
+        map.insert(String::from("name"), RadonString::from("coffee").into());
+        let input = RadonMap::from(map);
+        let get_float = |key: &str| {
+            get_or_default(&input, &[Value::Text(String::from(key)), Value::Float(0.0)])
+        };
+
+        // Missing optional fields fall back to the default value
+        assert_eq!(get_float("discount"), Ok(RadonFloat::from(0.0).into()));
+        assert_eq!(get_float("price"), Ok(RadonFloat::from(9.5).into()));
+        // But present fields must still have the type of the default value
+        assert_eq!(
+            get_float("name"),
+            Err(RadError::Decode {
+                from: RadonString::radon_type_name(),
+                to: RadonFloat::radon_type_name(),
+            })
+        );
+        assert!(matches!(
+            get_or_default(&input, &[Value::Text(String::from("discount"))]),
+            Err(RadError::WrongArguments { .. })
+        ));
+    }
+
     #[test]
     fn test_map_get_array() {
         let (input, index, item) = radon_map_of_arrays();
@@ -2,9 +2,15 @@ use std::fmt;
 
 use num_enum::TryFromPrimitive;
 use serde::Serialize;
+use serde_cbor::value::Value;
+use strum_macros::{EnumString, IntoStaticStr};
 use witnet_data_structures::radon_report::ReportContext;
 
-use crate::{error::RadError, script::RadonCall, types::RadonTypes};
+use crate::{
+    error::RadError,
+    script::RadonCall,
+    types::{boolean::RadonBoolean, RadonType, RadonTypes},
+};
 
 pub mod array;
 pub mod boolean;
@@ -17,7 +23,9 @@ pub mod string;
 /// List of RADON operators.
 /// **WARNING: these codes are consensus-critical.** They can be renamed but they cannot be
 /// re-assigned without causing a non-backwards-compatible protocol upgrade.
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, TryFromPrimitive)]
+#[derive(
+    Copy, Clone, Debug, PartialEq, Eq, Serialize, TryFromPrimitive, EnumString, IntoStaticStr,
+)]
 #[repr(u8)]
 pub enum RadonOpCodes {
     /// Only for the sake of allowing catch-alls when matching
@@ -25,11 +33,19 @@ pub enum RadonOpCodes {
     ///////////////////////////////////////////////////////////////////////
     // Multi-type operator codes start at 0x00
     Identity = 0x00,
+    /// Length of an array, a string, a byte string or a map. Unlike `StringLength`, which counts
+    /// UTF-8 bytes, strings are measured in Unicode scalar values, so `"ñú"` has a length of 2.
+    Length = 0x01,
+    Equal = 0x02,
+    NotEqual = 0x03,
+    GetPath = 0x04,
+    HashKeccak256 = 0x05,
+    HashHash160 = 0x06,
     ///////////////////////////////////////////////////////////////////////
     // Array operator codes (start at 0x10)
     ArrayCount = 0x10,
     ArrayFilter = 0x11,
-    //    ArrayFlatten = 0x12,
+    ArrayFlatten = 0x12,
     ArrayGetArray = 0x13,
     ArrayGetBoolean = 0x14,
     ArrayGetBytes = 0x15,
@@ -42,6 +58,7 @@ pub enum RadonOpCodes {
     //    ArraySome = 0x1C,
     ArraySort = 0x1D,
     //    ArrayTake = 0x1E,
+    ArrayStats = 0x1F,
     ///////////////////////////////////////////////////////////////////////
     // Boolean operator codes (start at 0x20)
     BooleanAsString = 0x20,
@@ -65,6 +82,10 @@ pub enum RadonOpCodes {
     IntegerPower = 0x49,
     //    IntegerReciprocal = 0x4A,
     //    IntegerSum = 0x4B,
+    IntegerBitAnd = 0x4C,
+    IntegerBitOr = 0x4D,
+    IntegerShiftLeft = 0x4E,
+    IntegerShiftRight = 0x4F,
     ///////////////////////////////////////////////////////////////////////
     // Float operator codes (start at 0x50)
     FloatAbsolute = 0x50,
@@ -81,6 +102,8 @@ pub enum RadonOpCodes {
     FloatRound = 0x5B,
     //    FloatSum = 0x5C,
     FloatTruncate = 0x5D,
+    FloatToString = 0x5E,
+    FloatClamp = 0x5F,
     ///////////////////////////////////////////////////////////////////////
     // Map operator codes (start at 0x60)
     //    MapEntries = 0x60,
@@ -93,6 +116,8 @@ pub enum RadonOpCodes {
     MapGetString = 0x67,
     MapKeys = 0x68,
     MapValues = 0x69,
+    MapUnion = 0x6A,
+    MapGetOrDefault = 0x6B,
     ///////////////////////////////////////////////////////////////////////
     // String operator codes (start at 0x70)
     StringAsBoolean = 0x70,
@@ -106,6 +131,22 @@ pub enum RadonOpCodes {
     StringParseXMLMap = 0x78,
     StringToLowerCase = 0x79,
     StringToUpperCase = 0x7A,
+    StringUrlDecode = 0x7B,
+    StringParseJSONOrDefault = 0x7C,
+    StringFormat = 0x7D,
+    StringSplit = 0x7E,
+    StringParseTimestamp = 0x7F,
+    ///////////////////////////////////////////////////////////////////////
+    // Once the range for a type is exhausted, its operator codes continue at 0x80 plus the start
+    // of the original range (e.g. Integer operator codes continue at 0xC0)
+    ///////////////////////////////////////////////////////////////////////
+    // Array operator codes, continued (start at 0x90)
+    ArrayMinLength = 0x90,
+    ArrayGroupBy = 0x91,
+    ///////////////////////////////////////////////////////////////////////
+    // Integer operator codes, continued (start at 0xC0)
+    IntegerToBytes = 0xC0,
+    IntegerClamp = 0xC1,
 }
 
 impl fmt::Display for RadonOpCodes {
@@ -143,6 +184,70 @@ pub fn identity(input: RadonTypes) -> Result<RadonTypes, RadError> {
     Ok(input)
 }
 
+/// Tell whether the input is equal to the value given as the only argument.
+///
+/// Values of different types are never equal, so the integer `1` is not equal to the float `1.0`,
+/// and neither is the string `"1"`. Arguments that cannot be decoded as RADON values, such as
+/// `null`, are not equal to anything.
+pub fn equal(input: &RadonTypes, args: &[Value]) -> Result<RadonBoolean, RadError> {
+    let value = match args {
+        [value] => value,
+        _ => {
+            return Err(RadError::WrongArguments {
+                input_type: input.radon_type_name(),
+                operator: "Equal".to_string(),
+                args: args.to_vec(),
+            })
+        }
+    };
+    let equal = RadonTypes::try_from(value.clone()).map_or(false, |value| value == *input);
+
+    Ok(RadonBoolean::from(equal))
+}
+
+/// Negation of `equal`.
+pub fn not_equal(input: &RadonTypes, args: &[Value]) -> Result<RadonBoolean, RadError> {
+    let equal = equal(input, args).map_err(|_| RadError::WrongArguments {
+        input_type: input.radon_type_name(),
+        operator: "NotEqual".to_string(),
+        args: args.to_vec(),
+    })?;
+
+    Ok(RadonBoolean::from(!equal.value()))
+}
+
+/// Walk nested `RadonMap`s and `RadonArray`s following a path of map keys and array indices, which
+/// is given as a single array argument, and return the value found at the end of the path.
+pub fn get_path(input: RadonTypes, args: &[Value]) -> Result<RadonTypes, RadError> {
+    let path = match args {
+        [Value::Array(path)] => path,
+        _ => {
+            return Err(RadError::WrongArguments {
+                input_type: input.radon_type_name(),
+                operator: "GetPath".to_string(),
+                args: args.to_vec(),
+            })
+        }
+    };
+
+    path.iter()
+        .enumerate()
+        .try_fold(input, |current, (position, segment)| {
+            let segment_arg = std::slice::from_ref(segment);
+            match (&current, segment) {
+                (RadonTypes::Map(map), Value::Text(_)) => map::inner_get(map, segment_arg),
+                (RadonTypes::Array(array), Value::Integer(_)) => {
+                    array::inner_get(array, segment_arg)
+                }
+                _ => Err(RadError::InvalidPathSegment {
+                    position,
+                    segment: segment.clone(),
+                    input_type: current.radon_type_name(),
+                }),
+            }
+        })
+}
+
 /// This module contains tests to guarantee a smooth activation of WIP-0024.
 ///
 /// Because RADON scripts are never evaluated for old blocks (e.g. during synchronization), this
@@ -540,7 +645,15 @@ mod legacy {
 
 #[cfg(test)]
 mod tests {
-    use crate::{fromx::FromX, types::string::RadonString};
+    use std::collections::BTreeMap;
+
+    use crate::{
+        fromx::FromX,
+        types::{
+            array::RadonArray, bytes::RadonBytes, float::RadonFloat, integer::RadonInteger,
+            map::RadonMap, string::RadonString,
+        },
+    };
 
     use super::*;
 
@@ -553,6 +666,227 @@ mod tests {
         assert_eq!(output, expected);
     }
 
+    #[test]
+    fn test_get_path() {
+        let input = RadonTypes::from(RadonString::from(
+            r#"{"weather":[{"id":500,"main":"Rain"},{"id":701,"main":"Mist"}],"name":"Berlin"}"#,
+        ))
+        .as_operable()
+        .operate(&(RadonOpCodes::StringParseJSONMap, None))
+        .unwrap();
+        let path = |segments: Vec<Value>| vec![Value::Array(segments)];
+
+        let output = get_path(
+            input.clone(),
+            &path(vec![
+                Value::from(String::from("weather")),
+                Value::Integer(0),
+                Value::from(String::from("main")),
+            ]),
+        )
+        .unwrap();
+        assert_eq!(output, RadonTypes::from(RadonString::from("Rain")));
+
+        // The same, but through the operator dispatch
+        let call = (
+            RadonOpCodes::GetPath,
+            Some(path(vec![
+                Value::from(String::from("weather")),
+                Value::Integer(1),
+                Value::from(String::from("main")),
+            ])),
+        );
+        let output = operate(input.clone(), &call).unwrap();
+        assert_eq!(output, RadonTypes::from(RadonString::from("Mist")));
+
+        // An empty path leaves the input untouched
+        assert_eq!(get_path(input.clone(), &path(vec![])), Ok(input.clone()));
+    }
+
+    #[test]
+    fn test_get_path_errors() {
+        let input = RadonTypes::from(RadonString::from(r#"{"weather":[{"main":"Rain"}]}"#))
+            .as_operable()
+            .operate(&(RadonOpCodes::StringParseJSONMap, None))
+            .unwrap();
+        let weather = Value::from(String::from("weather"));
+        let path = |segments: Vec<Value>| vec![Value::Array(segments)];
+
+        assert_eq!(
+            get_path(
+                input.clone(),
+                &path(vec![weather.clone(), Value::Integer(3)])
+            ),
+            Err(RadError::ArrayIndexOutOfBounds { index: 3 })
+        );
+        assert_eq!(
+            get_path(
+                input.clone(),
+                &path(vec![
+                    weather.clone(),
+                    Value::Integer(0),
+                    Value::from(String::from("temp"))
+                ])
+            ),
+            Err(RadError::MapKeyNotFound {
+                key: String::from("temp")
+            })
+        );
+        // Indexing a map with an integer
+        assert_eq!(
+            get_path(input.clone(), &path(vec![Value::Integer(0)])),
+            Err(RadError::InvalidPathSegment {
+                position: 0,
+                segment: Value::Integer(0),
+                input_type: "RadonMap",
+            })
+        );
+        // Walking past a leaf
+        assert_eq!(
+            get_path(
+                input.clone(),
+                &path(vec![
+                    weather.clone(),
+                    Value::Integer(0),
+                    Value::from(String::from("main")),
+                    Value::Integer(0)
+                ])
+            ),
+            Err(RadError::InvalidPathSegment {
+                position: 3,
+                segment: Value::Integer(0),
+                input_type: "RadonString",
+            })
+        );
+        // The path must be a single array argument
+        assert!(matches!(
+            get_path(input, &[weather]),
+            Err(RadError::WrongArguments { .. })
+        ));
+    }
+
+    #[test]
+    fn test_equal() {
+        let equal = |input: RadonTypes, arg: Value| {
+            let is_equal = operate(
+                input.clone(),
+                &(RadonOpCodes::Equal, Some(vec![arg.clone()])),
+            );
+            let is_not_equal = operate(input, &(RadonOpCodes::NotEqual, Some(vec![arg])));
+            match (is_equal, is_not_equal) {
+                (Ok(RadonTypes::Boolean(is_equal)), Ok(RadonTypes::Boolean(is_not_equal))) => {
+                    assert_ne!(is_equal.value(), is_not_equal.value());
+                    is_equal.value()
+                }
+                other => panic!("Equal and NotEqual should return booleans, got {:?}", other),
+            }
+        };
+
+        assert!(equal(RadonInteger::from(7).into(), Value::Integer(7)));
+        assert!(!equal(RadonInteger::from(7).into(), Value::Integer(-7)));
+        assert!(equal(RadonFloat::from(1.5).into(), Value::Float(1.5)));
+        assert!(!equal(RadonFloat::from(1.5).into(), Value::Float(1.25)));
+        assert!(equal(
+            RadonString::from("USD").into(),
+            Value::Text(String::from("USD"))
+        ));
+        assert!(!equal(
+            RadonString::from("USD").into(),
+            Value::Text(String::from("usd"))
+        ));
+        assert!(equal(
+            RadonBytes::from(vec![0xde, 0xad]).into(),
+            Value::Bytes(vec![0xde, 0xad])
+        ));
+        assert!(!equal(
+            RadonBytes::from(vec![0xde, 0xad]).into(),
+            Value::Bytes(vec![0xde])
+        ));
+        assert!(equal(RadonBoolean::from(true).into(), Value::Bool(true)));
+        assert!(!equal(RadonBoolean::from(true).into(), Value::Bool(false)));
+
+        // Values of different types are never equal
+        assert!(!equal(RadonInteger::from(1).into(), Value::Float(1.0)));
+        assert!(!equal(RadonFloat::from(1.0).into(), Value::Integer(1)));
+        assert!(!equal(RadonString::from("1").into(), Value::Integer(1)));
+        assert!(!equal(
+            RadonBytes::from(vec![0x31]).into(),
+            Value::Text(String::from("1"))
+        ));
+        assert!(!equal(RadonBoolean::from(true).into(), Value::Integer(1)));
+        assert!(!equal(RadonBoolean::from(false).into(), Value::Null));
+
+        assert_eq!(
+            operate(
+                RadonInteger::from(1).into(),
+                &(RadonOpCodes::Equal, Some(vec![]))
+            ),
+            Err(RadError::WrongArguments {
+                input_type: "RadonInteger",
+                operator: String::from("Equal"),
+                args: vec![],
+            })
+        );
+        assert!(matches!(
+            operate(
+                RadonInteger::from(1).into(),
+                &(
+                    RadonOpCodes::NotEqual,
+                    Some(vec![Value::Integer(1), Value::Integer(2)])
+                )
+            ),
+            Err(RadError::WrongArguments { .. })
+        ));
+    }
+
+    #[test]
+    fn test_length() {
+        let length = |input: RadonTypes| {
+            operate(input, &(RadonOpCodes::Length, None)).map(|output| match output {
+                RadonTypes::Integer(integer) => integer.value(),
+                other => panic!("Length should return an integer, got {}", other),
+            })
+        };
+
+        let array = RadonArray::from(vec![
+            RadonInteger::from(1).into(),
+            RadonString::from("two").into(),
+        ]);
+        assert_eq!(length(array.into()), Ok(2));
+        assert_eq!(
+            length(RadonBytes::from(vec![0xde, 0xad, 0xbe]).into()),
+            Ok(3)
+        );
+        let map = RadonMap::from(BTreeMap::from([(
+            String::from("key"),
+            RadonTypes::from(RadonBoolean::from(true)),
+        )]));
+        assert_eq!(length(map.into()), Ok(1));
+        assert_eq!(length(RadonString::from("").into()), Ok(0));
+
+        // Strings are measured in Unicode scalar values, not in UTF-8 bytes
+        let multibyte = RadonString::from("añ€😀");
+        assert_eq!(multibyte.value().len(), 10);
+        assert_eq!(length(multibyte.clone().into()), Ok(4));
+        assert_eq!(
+            operate(multibyte.into(), &(RadonOpCodes::StringLength, None)),
+            Ok(RadonInteger::from(10).into())
+        );
+
+        assert_eq!(
+            length(RadonInteger::from(1234).into()),
+            Err(RadError::UnsupportedOperator {
+                input_type: String::from("RadonInteger"),
+                operator: String::from("Length"),
+                args: None,
+            })
+        );
+        assert!(matches!(
+            length(RadonBoolean::from(true).into()),
+            Err(RadError::UnsupportedOperator { .. })
+        ));
+    }
+
     #[test]
     pub fn test_operate() {
         let input = RadonTypes::from3::<RadonString, String>("Hello world!");
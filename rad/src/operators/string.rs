@@ -6,6 +6,7 @@ use std::{
 
 use serde_cbor::value::{from_value, Value};
 use serde_json::Value as JsonValue;
+use witnet_data_structures::radon_report::{ReportContext, Stage, TypeLike};
 
 use crate::{
     error::RadError,
@@ -40,6 +41,84 @@ pub fn parse_json_array(input: &RadonString) -> Result<RadonArray, RadError> {
     item.try_into()
 }
 
+/// Parse a JSON map, falling back to the default value given as the only argument if the input
+/// cannot be parsed as such.
+pub fn parse_json_map_or_default(
+    input: &RadonString,
+    args: &[Value],
+    context: &mut ReportContext<RadonTypes>,
+) -> Result<RadonTypes, RadError> {
+    let default = read_default_from_args(args, "ParseJSONMap")?;
+
+    Ok(recover_with_default(
+        parse_json_map(input).map(RadonTypes::from),
+        default,
+        context,
+    ))
+}
+
+/// Parse a JSON array, falling back to the default value given as the only argument if the input
+/// cannot be parsed as such.
+pub fn parse_json_array_or_default(
+    input: &RadonString,
+    args: &[Value],
+    context: &mut ReportContext<RadonTypes>,
+) -> Result<RadonTypes, RadError> {
+    let default = read_default_from_args(args, "ParseJSONArray")?;
+
+    Ok(recover_with_default(
+        parse_json_array(input).map(RadonTypes::from),
+        default,
+        context,
+    ))
+}
+
+/// Parse any JSON value, falling back to the default value given as the only argument if the input
+/// is not valid JSON.
+pub fn parse_json_or_default(
+    input: &RadonString,
+    args: &[Value],
+    context: &mut ReportContext<RadonTypes>,
+) -> Result<RadonTypes, RadError> {
+    let default = read_default_from_args(args, "ParseJSONOrDefault")?;
+
+    Ok(recover_with_default(parse_json(input), default, context))
+}
+
+fn read_default_from_args(args: &[Value], operator: &str) -> Result<RadonTypes, RadError> {
+    let wrong_args = || RadError::WrongArguments {
+        input_type: RadonString::radon_type_name(),
+        operator: operator.to_string(),
+        args: args.to_vec(),
+    };
+
+    match args {
+        [default] => RadonTypes::try_from(default.clone()).map_err(|_| wrong_args()),
+        _ => Err(wrong_args()),
+    }
+}
+
+/// Replace a failed result with a default value. If the context records recovered errors, the
+/// error is kept in the retrieval metadata so that it can still be traced.
+fn recover_with_default(
+    result: Result<RadonTypes, RadError>,
+    default: RadonTypes,
+    context: &mut ReportContext<RadonTypes>,
+) -> RadonTypes {
+    result.unwrap_or_else(|error| {
+        if !context.record_recovered_errors {
+            return default;
+        }
+        if let Stage::Retrieval(metadata) = &mut context.stage {
+            metadata
+                .recovered_errors
+                .push(RadonTypes::intercept(Err(error)));
+        }
+
+        default
+    })
+}
+
 fn add_children(
     map: &mut BTreeMap<String, RadonTypes>,
     text_children: Vec<RadonTypes>,
@@ -157,6 +236,21 @@ pub fn to_bool(input: &RadonString) -> Result<RadonBoolean, RadError> {
         .map_err(Into::into)
 }
 
+/// Converts a `RadonString` into a `RadonBoolean`, accepting `true` and `false` in any case, as well
+/// as `1` and `0`.
+pub fn as_bool(input: &RadonString) -> Result<RadonBoolean, RadError> {
+    let str_value = radon_trim(input);
+    if str_value.eq_ignore_ascii_case("true") || str_value == "1" {
+        Ok(RadonBoolean::from(true))
+    } else if str_value.eq_ignore_ascii_case("false") || str_value == "0" {
+        Ok(RadonBoolean::from(false))
+    } else {
+        Err(RadError::ParseBool {
+            message: format!("`{}` is not one of `true`, `false`, `1` or `0`", str_value),
+        })
+    }
+}
+
 /// Converts a `RadonString` into a `RadonFloat`, provided that the input string actually represents
 /// a valid floating point number.
 pub fn as_float(input: &RadonString, args: &Option<Vec<Value>>) -> Result<RadonFloat, RadError> {
@@ -203,6 +297,166 @@ pub fn to_uppercase(input: &RadonString) -> RadonString {
     RadonString::from(input.value().as_str().to_uppercase())
 }
 
+/// Decodes a percent-encoded (aka URL-encoded) string, e.g. `a%20b` into `a b`.
+///
+/// Fails if a `%` is not followed by two hexadecimal digits, or if the decoded bytes are not valid
+/// UTF-8. Note that `+` is left as is, as it only stands for a space in form encoding.
+pub fn url_decode(input: &RadonString) -> Result<RadonString, RadError> {
+    let error = || RadError::Decode {
+        from: "percent-encoded String",
+        to: "RadonString",
+    };
+
+    let encoded = input.value();
+    let encoded = encoded.as_bytes();
+    let mut decoded = Vec::with_capacity(encoded.len());
+    let mut i = 0;
+    while i < encoded.len() {
+        if encoded[i] == b'%' {
+            let hex_digits = encoded
+                .get(i + 1..i + 3)
+                .filter(|digits| digits.iter().all(u8::is_ascii_hexdigit))
+                .ok_or_else(error)?;
+            // Cannot fail, as all the bytes were just checked to be ASCII hexadecimal digits
+            let hex_digits = std::str::from_utf8(hex_digits).map_err(|_| error())?;
+            let byte = u8::from_str_radix(hex_digits, 16).map_err(|_| error())?;
+            decoded.push(byte);
+            i += 3;
+        } else {
+            decoded.push(encoded[i]);
+            i += 1;
+        }
+    }
+
+    String::from_utf8(decoded)
+        .map(RadonString::from)
+        .map_err(|_| error())
+}
+
+/// Fill the `{0}`, `{1}`... placeholders of a template with the items of an array argument.
+///
+/// Items can be strings, integers, floats or booleans, and each placeholder can appear any number
+/// of times. Literal braces are written as `{{` and `}}`.
+pub fn format(input: &RadonString, args: &[Value]) -> Result<RadonString, RadError> {
+    let wrong_args = || RadError::WrongArguments {
+        input_type: RadonString::radon_type_name(),
+        operator: "StringFormat".to_string(),
+        args: args.to_vec(),
+    };
+
+    let values = match args {
+        [Value::Array(values)] => values
+            .iter()
+            .map(|value| match value {
+                Value::Text(text) => Ok(text.clone()),
+                Value::Integer(integer) => Ok(integer.to_string()),
+                Value::Float(float) => Ok(float.to_string()),
+                Value::Bool(boolean) => Ok(boolean.to_string()),
+                _ => Err(wrong_args()),
+            })
+            .collect::<Result<Vec<_>, _>>()?,
+        _ => return Err(wrong_args()),
+    };
+
+    let template = input.value();
+    let malformed = || RadError::MalformedFormatTemplate {
+        template: template.clone(),
+    };
+    let mut output = String::with_capacity(template.len());
+    let mut chars = template.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' => {
+                let mut placeholder = String::new();
+                loop {
+                    match chars.next() {
+                        Some('{') if placeholder.is_empty() => {
+                            output.push('{');
+                            break;
+                        }
+                        Some('}') => {
+                            let index = placeholder.parse::<usize>().map_err(|_| malformed())?;
+                            let value = values.get(index).ok_or_else(|| {
+                                RadError::ArrayIndexOutOfBounds {
+                                    index: i32::try_from(index).unwrap_or(i32::MAX),
+                                }
+                            })?;
+                            output.push_str(value);
+                            break;
+                        }
+                        Some(digit) if digit.is_ascii_digit() => placeholder.push(digit),
+                        _ => return Err(malformed()),
+                    }
+                }
+            }
+            '}' => match chars.next() {
+                Some('}') => output.push('}'),
+                _ => return Err(malformed()),
+            },
+            c => output.push(c),
+        }
+    }
+
+    Ok(RadonString::from(output))
+}
+
+/// Format of the timestamps accepted by `parse_timestamp`, once a `Z` timezone designator has been
+/// replaced by `+00:00`.
+///
+/// This is spelled out instead of relying on the RFC 3339 parser of `chrono`, whose leniency (e.g.
+/// about the separator between the date and the time) has changed across versions, as every node
+/// must accept exactly the same timestamps.
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.f%:z";
+
+/// Parse an RFC 3339 timestamp, such as `2023-10-11T15:18:42Z` or `2023-10-11T17:18:42.5+02:00`,
+/// into the number of seconds since the Unix epoch. Fractional seconds are floored, so that
+/// `1969-12-31T23:59:59.5Z` (half a second before the epoch) becomes -1.
+///
+/// The timezone designator is mandatory, as assuming the local timezone of each node would make the
+/// result depend on the node that runs the script.
+pub fn parse_timestamp(input: &RadonString) -> Result<RadonInteger, RadError> {
+    let timestamp = input.value();
+    let trimmed = timestamp.trim();
+    let normalized = match trimmed.strip_suffix('Z') {
+        Some(datetime) => format!("{}+00:00", datetime),
+        None => trimmed.to_string(),
+    };
+
+    chrono::DateTime::parse_from_str(&normalized, TIMESTAMP_FORMAT)
+        .map(|datetime| RadonInteger::from(i128::from(datetime.timestamp())))
+        .map_err(|e| RadError::ParseTimestamp {
+            timestamp,
+            message: e.to_string(),
+        })
+}
+
+/// Split a string into an array of strings around every occurrence of a separator.
+///
+/// Leading and trailing separators produce empty strings at the start and the end of the array.
+pub fn split(input: &RadonString, args: &[Value]) -> Result<RadonArray, RadError> {
+    let wrong_args = || RadError::WrongArguments {
+        input_type: RadonString::radon_type_name(),
+        operator: "StringSplit".to_string(),
+        args: args.to_vec(),
+    };
+
+    let separator = match args {
+        [Value::Text(separator)] => separator,
+        _ => return Err(wrong_args()),
+    };
+    if separator.is_empty() {
+        return Err(RadError::EmptySeparator);
+    }
+
+    let items = input
+        .value()
+        .split(separator.as_str())
+        .map(|item| RadonTypes::from(RadonString::from(item)))
+        .collect::<Vec<_>>();
+
+    Ok(RadonArray::from(items))
+}
+
 pub fn hash(input: &RadonString, args: &[Value]) -> Result<RadonString, RadError> {
     let wrong_args = || RadError::WrongArguments {
         input_type: RadonString::radon_type_name(),
@@ -350,7 +604,11 @@ pub mod legacy {
 mod tests {
     use std::collections::BTreeMap;
 
-    use crate::types::{array::RadonArray, bytes::RadonBytes};
+    use crate::{
+        operators::{Operable, RadonOpCodes},
+        script::{execute_radon_script, RadonScriptExecutionSettings},
+        types::{array::RadonArray, bytes::RadonBytes},
+    };
 
     use super::*;
 
@@ -734,6 +992,46 @@ mod tests {
         assert_eq!(to_bool(&rad_string).unwrap(), rad_float);
     }
 
+    #[test]
+    fn test_string_as_bool() {
+        assert_eq!(
+            as_bool(&RadonString::from("TRUE")),
+            Ok(RadonBoolean::from(true))
+        );
+        assert_eq!(
+            as_bool(&RadonString::from("False\n")),
+            Ok(RadonBoolean::from(false))
+        );
+        assert_eq!(
+            as_bool(&RadonString::from("0")),
+            Ok(RadonBoolean::from(false))
+        );
+        assert_eq!(
+            as_bool(&RadonString::from("1")),
+            Ok(RadonBoolean::from(true))
+        );
+        assert!(matches!(
+            as_bool(&RadonString::from("maybe")),
+            Err(RadError::ParseBool { .. })
+        ));
+
+        // Before WIP-0029, only the lowercase words are accepted
+        let mut active_wips = crate::all_wips_active();
+        active_wips.active_wips.remove("WIP0029");
+        let mut context = ReportContext {
+            active_wips: Some(active_wips),
+            ..Default::default()
+        };
+        let call = (RadonOpCodes::StringAsBoolean, None);
+        assert!(RadonString::from("TRUE")
+            .operate_in_context(&call, &mut context)
+            .is_err());
+        assert_eq!(
+            RadonString::from("true").operate_in_context(&call, &mut context),
+            Ok(RadonTypes::from(RadonBoolean::from(true)))
+        );
+    }
+
     #[test]
     fn test_string_length() {
         let rad_string: RadonString = RadonString::from("Hello");
@@ -1253,4 +1551,313 @@ mod tests {
         let expected = (String::from("x"), String::from("y"));
         assert_eq!(separators, expected);
     }
+
+    #[test]
+    fn test_parse_json_or_default() {
+        let mut context = ReportContext::from_stage(Stage::Retrieval(Default::default()));
+        context.record_recovered_errors = true;
+        let default = Value::Integer(0);
+
+        let output = parse_json_array_or_default(
+            &RadonString::from("[1, 2]"),
+            &[default.clone()],
+            &mut context,
+        )
+        .unwrap();
+        let expected = RadonTypes::from(RadonArray::from(vec![
+            RadonInteger::from(1).into(),
+            RadonInteger::from(2).into(),
+        ]));
+        assert_eq!(output, expected);
+
+        let html = RadonString::from("<html><body>502 Bad Gateway</body></html>");
+        let output = parse_json_array_or_default(&html, &[default.clone()], &mut context).unwrap();
+        assert_eq!(output, RadonTypes::from(RadonInteger::from(0)));
+        let output = parse_json_map_or_default(&html, &[default], &mut context).unwrap();
+        assert_eq!(output, RadonTypes::from(RadonInteger::from(0)));
+
+        // Both parse errors have been recorded
+        if let Stage::Retrieval(metadata) = &context.stage {
+            assert_eq!(metadata.recovered_errors.len(), 2);
+            assert!(matches!(
+                metadata.recovered_errors[0],
+                RadonTypes::RadonError(_)
+            ));
+        } else {
+            panic!("Expected retrieval stage");
+        }
+    }
+
+    #[test]
+    fn test_parse_json_any_or_default() {
+        let mut context = ReportContext::from_stage(Stage::Retrieval(Default::default()));
+        context.record_recovered_errors = true;
+        let default = Value::Text(String::from("unavailable"));
+        let call = (
+            RadonOpCodes::StringParseJSONOrDefault,
+            Some(vec![default.clone()]),
+        );
+
+        let output = RadonString::from(r#"{"price": 1.5}"#)
+            .operate_in_context(&call, &mut context)
+            .unwrap();
+        let expected = RadonTypes::from(RadonMap::from(BTreeMap::from([(
+            String::from("price"),
+            RadonTypes::from(RadonFloat::from(1.5)),
+        )])));
+        assert_eq!(output, expected);
+
+        let output =
+            parse_json_or_default(&RadonString::from("42"), &[default.clone()], &mut context)
+                .unwrap();
+        assert_eq!(output, RadonTypes::from(RadonInteger::from(42)));
+
+        let output = RadonString::from("<html><body>502 Bad Gateway</body></html>")
+            .operate_in_context(&call, &mut context)
+            .unwrap();
+        assert_eq!(output, RadonTypes::from(RadonString::from("unavailable")));
+
+        // Only the failed parse has been recorded
+        if let Stage::Retrieval(metadata) = &context.stage {
+            assert_eq!(metadata.recovered_errors.len(), 1);
+        } else {
+            panic!("Expected retrieval stage");
+        }
+
+        assert!(matches!(
+            parse_json_or_default(&RadonString::from("[]"), &[], &mut context),
+            Err(RadError::WrongArguments { .. })
+        ));
+    }
+
+    #[test]
+    fn test_recovered_errors_follow_partial_results_setting() {
+        let script = [(
+            RadonOpCodes::StringParseJSONOrDefault,
+            Some(vec![Value::Integer(0)]),
+        )];
+        let recovered_errors = |settings| {
+            let mut context = ReportContext::from_stage(Stage::Retrieval(Default::default()));
+            let report = execute_radon_script(
+                RadonString::from("502 Bad Gateway").into(),
+                &script,
+                &mut context,
+                settings,
+            )
+            .unwrap();
+            assert_eq!(report.result, RadonTypes::from(RadonInteger::from(0)));
+
+            match context.stage {
+                Stage::Retrieval(metadata) => metadata.recovered_errors.len(),
+                _ => panic!("Expected retrieval stage"),
+            }
+        };
+
+        assert_eq!(
+            recovered_errors(RadonScriptExecutionSettings::enable_all()),
+            1
+        );
+        assert_eq!(
+            recovered_errors(RadonScriptExecutionSettings::all_but_partial_results()),
+            0
+        );
+        assert_eq!(
+            recovered_errors(RadonScriptExecutionSettings::disable_all()),
+            0
+        );
+    }
+
+    #[test]
+    fn test_parse_json_or_default_wrong_args() {
+        let mut context = ReportContext::default();
+        let input = RadonString::from("[1, 2]");
+
+        let output = parse_json_array_or_default(&input, &[], &mut context).unwrap_err();
+        assert_eq!(
+            output,
+            RadError::WrongArguments {
+                input_type: "RadonString",
+                operator: "ParseJSONArray".to_string(),
+                args: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn test_url_decode() {
+        let decoded = url_decode(&RadonString::from("hello%20world")).unwrap();
+        assert_eq!(decoded, RadonString::from("hello world"));
+
+        let decoded = url_decode(&RadonString::from("%41%42C+")).unwrap();
+        assert_eq!(decoded, RadonString::from("ABC+"));
+
+        let decoded = url_decode(&RadonString::from("caf%C3%A9")).unwrap();
+        assert_eq!(decoded, RadonString::from("café"));
+    }
+
+    #[test]
+    fn test_url_decode_malformed() {
+        let expected = RadError::Decode {
+            from: "percent-encoded String",
+            to: "RadonString",
+        };
+
+        assert_eq!(url_decode(&RadonString::from("%2")), Err(expected.clone()));
+        assert_eq!(url_decode(&RadonString::from("%zz")), Err(expected.clone()));
+        assert_eq!(url_decode(&RadonString::from("%+1")), Err(expected.clone()));
+        // Not valid UTF-8 once decoded
+        assert_eq!(url_decode(&RadonString::from("%C3")), Err(expected));
+    }
+
+    #[test]
+    fn test_parse_timestamp() {
+        let parse = |timestamp: &str| parse_timestamp(&RadonString::from(timestamp));
+        let expected = RadonInteger::from(1_697_037_522);
+
+        assert_eq!(parse("2023-10-11T15:18:42Z"), Ok(expected.clone()));
+        assert_eq!(parse("2023-10-11T10:18:42-05:00"), Ok(expected.clone()));
+        assert_eq!(parse("2023-10-11T20:48:42+05:30"), Ok(expected.clone()));
+        // Fractional seconds are floored
+        assert_eq!(parse("2023-10-11T15:18:42.987654Z"), Ok(expected));
+        assert_eq!(parse("1969-12-31T23:59:59Z"), Ok(RadonInteger::from(-1)));
+        assert_eq!(parse("1969-12-31T23:59:59.5Z"), Ok(RadonInteger::from(-1)));
+        assert_eq!(parse("1970-01-01T00:00:00.5Z"), Ok(RadonInteger::from(0)));
+    }
+
+    #[test]
+    fn test_parse_timestamp_malformed() {
+        for timestamp in [
+            "",
+            "1697037522",
+            "2023-10-11",
+            // The timezone is mandatory
+            "2023-10-11T15:18:42",
+            "2023-13-11T15:18:42Z",
+            "2023-10-11T25:18:42Z",
+            "2023-10-11T15:18:42+5:00",
+            // The date and the time must be separated by `T`
+            "2023-10-11 15:18:42Z",
+            "2023-10-11T15:18:42ZZ",
+        ] {
+            assert!(
+                matches!(
+                    parse_timestamp(&RadonString::from(timestamp)),
+                    Err(RadError::ParseTimestamp { .. })
+                ),
+                "{:?} should be rejected",
+                timestamp
+            );
+        }
+    }
+
+    #[test]
+    fn test_string_format() {
+        let template = RadonString::from(r#"{"query":"{0}","limit":{1}}"#);
+        // Literal braces around the JSON body must be escaped
+        let template_escaped = RadonString::from(r#"{{"query":"{0}","limit":{1}}}"#);
+        let args = vec![Value::Array(vec![
+            Value::Text(String::from("BTC/USD")),
+            Value::Integer(10),
+        ])];
+
+        assert_eq!(
+            format(&template_escaped, &args).unwrap(),
+            RadonString::from(r#"{"query":"BTC/USD","limit":10}"#)
+        );
+        assert_eq!(
+            format(&template, &args),
+            Err(RadError::MalformedFormatTemplate {
+                template: template.value()
+            })
+        );
+
+        // Placeholders can be repeated and appear in any order
+        let output = format(
+            &RadonString::from("{1}-{0}-{1}"),
+            &[Value::Array(vec![Value::Bool(true), Value::Float(1.5)])],
+        )
+        .unwrap();
+        assert_eq!(output, RadonString::from("1.5-true-1.5"));
+
+        // Through the operator dispatch
+        let call = (
+            RadonOpCodes::StringFormat,
+            Some(vec![Value::Array(vec![Value::Text(String::from("world"))])]),
+        );
+        let output = RadonString::from("hello {0}")
+            .operate_in_context(&call, &mut ReportContext::default())
+            .unwrap();
+        assert_eq!(output, RadonTypes::from(RadonString::from("hello world")));
+    }
+
+    #[test]
+    fn test_string_split() {
+        let strings = |items: &[&str]| {
+            RadonArray::from(
+                items
+                    .iter()
+                    .map(|item| RadonTypes::from(RadonString::from(*item)))
+                    .collect::<Vec<_>>(),
+            )
+        };
+        let comma = [Value::Text(String::from(","))];
+
+        assert_eq!(
+            split(&RadonString::from("1,2,3"), &comma).unwrap(),
+            strings(&["1", "2", "3"])
+        );
+        assert_eq!(
+            split(&RadonString::from("1,2,"), &comma).unwrap(),
+            strings(&["1", "2", ""])
+        );
+        assert_eq!(
+            split(&RadonString::from(""), &comma).unwrap(),
+            strings(&[""])
+        );
+        assert_eq!(
+            split(
+                &RadonString::from("a::b"),
+                &[Value::Text(String::from("::"))]
+            )
+            .unwrap(),
+            strings(&["a", "b"])
+        );
+        assert_eq!(
+            split(&RadonString::from("1,2"), &[Value::Text(String::new())]),
+            Err(RadError::EmptySeparator)
+        );
+        assert!(matches!(
+            split(&RadonString::from("1,2"), &[Value::Integer(0)]),
+            Err(RadError::WrongArguments { .. })
+        ));
+    }
+
+    #[test]
+    fn test_string_format_errors() {
+        let args = vec![Value::Array(vec![Value::Integer(1)])];
+
+        assert_eq!(
+            format(&RadonString::from("{0} {1}"), &args),
+            Err(RadError::ArrayIndexOutOfBounds { index: 1 })
+        );
+        for template in &["{", "{0", "{a}", "}", "{}", "{-1}"] {
+            assert_eq!(
+                format(&RadonString::from(*template), &args),
+                Err(RadError::MalformedFormatTemplate {
+                    template: template.to_string()
+                })
+            );
+        }
+        assert!(matches!(
+            format(&RadonString::from("{0}"), &[Value::Integer(1)]),
+            Err(RadError::WrongArguments { .. })
+        ));
+        assert!(matches!(
+            format(
+                &RadonString::from("{0}"),
+                &[Value::Array(vec![Value::Array(vec![])])]
+            ),
+            Err(RadError::WrongArguments { .. })
+        ));
+    }
 }
@@ -0,0 +1,169 @@
+//! Recording and replaying of HTTP responses for reproducible retrieval testing.
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use serde::{Deserialize, Serialize};
+use witnet_crypto::hash::calculate_sha256;
+use witnet_data_structures::chain::{RADRetrieve, RADType};
+
+/// A set of HTTP response bodies captured during retrieval, indexed by the method, URL and body of
+/// the requests they came from, so that requests to the same endpoint with different bodies (e.g.
+/// JSON-RPC or GraphQL queries) do not collide.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+pub struct HttpRecording {
+    responses: BTreeMap<String, String>,
+}
+
+impl HttpRecording {
+    /// Store the response body obtained from a retrieval, replacing any former response for the
+    /// same request.
+    pub fn insert(&mut self, retrieve: &RADRetrieve, response: &str) {
+        self.responses
+            .insert(recording_key(retrieve), String::from(response));
+    }
+
+    /// Get the response body that was recorded for a retrieval, if any.
+    pub fn get(&self, retrieve: &RADRetrieve) -> Option<&str> {
+        self.responses
+            .get(&recording_key(retrieve))
+            .map(String::as_str)
+    }
+
+    /// Number of recorded responses.
+    pub fn len(&self) -> usize {
+        self.responses.len()
+    }
+
+    /// Tell whether no responses have been recorded at all.
+    pub fn is_empty(&self) -> bool {
+        self.responses.is_empty()
+    }
+
+    /// Read a recording from a JSON file.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let contents = fs::read(path)?;
+
+        serde_json::from_slice(&contents).map_err(io::Error::from)
+    }
+
+    /// Write this recording into a JSON file.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let contents = serde_json::to_vec_pretty(self).map_err(io::Error::from)?;
+
+        fs::write(path, contents)
+    }
+}
+
+/// Key under which the response to a retrieval is recorded: its HTTP method, URL and the SHA-256
+/// hash of its body.
+fn recording_key(retrieve: &RADRetrieve) -> String {
+    let method = match retrieve.kind {
+        RADType::HttpGet => "GET",
+        RADType::HttpHead => "HEAD",
+        RADType::HttpPost | RADType::GraphQl => "POST",
+        RADType::Unknown | RADType::Rng => "NONE",
+    };
+    let body_hash = calculate_sha256(&retrieve.body);
+
+    format!("{} {} {}", method, retrieve.url, hex::encode(body_hash.0))
+}
+
+/// Tells the retrieval engine whether to capture the HTTP responses it gets, or to serve previously
+/// captured responses instead of performing any actual networking, or to skip networking
+/// altogether and only report the HTTP requests it would perform.
+#[derive(Clone, Debug)]
+pub enum RecordingMode {
+    /// Perform the HTTP requests as usual, and store every successful response in the recording.
+    Record(Arc<Mutex<HttpRecording>>),
+    /// Never hit the network, but rather take the responses from the recording.
+    Replay(Arc<HttpRecording>),
+    /// Never hit the network, but rather build the HTTP request and store it into the
+    /// `RetrievalMetadata` of the report. The retrieval script is not run, and the result of the
+    /// report is a `RadError::RequestCaptured`, so that it is not mistaken for a failing source.
+    Capture,
+}
+
+impl RecordingMode {
+    /// Start recording into an empty `HttpRecording`.
+    pub fn record() -> Self {
+        RecordingMode::Record(Arc::new(Mutex::new(HttpRecording::default())))
+    }
+
+    /// Replay the responses from an existing `HttpRecording`.
+    pub fn replay(recording: HttpRecording) -> Self {
+        RecordingMode::Replay(Arc::new(recording))
+    }
+
+    /// Get a copy of the responses recorded or being replayed so far.
+    pub fn recording(&self) -> HttpRecording {
+        match self {
+            RecordingMode::Record(recording) => recording
+                .lock()
+                .map(|recording| recording.clone())
+                .unwrap_or_default(),
+            RecordingMode::Replay(recording) => HttpRecording::clone(recording),
+            RecordingMode::Capture => HttpRecording::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn retrieve(kind: RADType, url: &str, body: &[u8]) -> RADRetrieve {
+        RADRetrieve {
+            kind,
+            url: String::from(url),
+            body: body.to_vec(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_recording_save_and_load() {
+        let price = retrieve(RADType::HttpGet, "https://example.com/price", b"");
+        let mut recording = HttpRecording::default();
+        recording.insert(&price, r#"{"price":1.5}"#);
+
+        let path =
+            std::env::temp_dir().join(format!("witnet_rad_recording_{}.json", std::process::id()));
+        recording.save(&path).unwrap();
+        let loaded = HttpRecording::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded, recording);
+        assert_eq!(loaded.get(&price), Some(r#"{"price":1.5}"#));
+        assert_eq!(
+            loaded.get(&retrieve(
+                RADType::HttpGet,
+                "https://example.com/other",
+                b""
+            )),
+            None
+        );
+    }
+
+    #[test]
+    fn test_recording_tells_requests_to_the_same_url_apart() {
+        let url = "https://example.com/rpc";
+        let balance = retrieve(RADType::HttpPost, url, br#"{"method":"balance"}"#);
+        let supply = retrieve(RADType::HttpPost, url, br#"{"method":"supply"}"#);
+        let mut recording = HttpRecording::default();
+        recording.insert(&balance, "1");
+        recording.insert(&supply, "2");
+
+        assert_eq!(recording.len(), 2);
+        assert_eq!(recording.get(&balance), Some("1"));
+        assert_eq!(recording.get(&supply), Some("2"));
+        // Same URL and body, but different method
+        assert_eq!(
+            recording.get(&retrieve(RADType::HttpGet, url, br#"{"method":"balance"}"#)),
+            None
+        );
+    }
+}
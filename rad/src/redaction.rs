@@ -0,0 +1,117 @@
+//! Redaction of sensitive HTTP headers, such as credentials passed to authenticated APIs, so that
+//! their values never end up in logs.
+//!
+//! Redaction only ever applies to logs: the headers returned by HTTP-HEAD retrievals are handed to
+//! scripts as they are, because every witness must commit to the same value.
+
+/// Headers whose values are redacted regardless of the configured denylist.
+pub const ALWAYS_REDACTED_HEADERS: [&str; 3] = ["Authorization", "Cookie", "Set-Cookie"];
+
+/// Text that replaces the value of a redacted header.
+pub const REDACTED_VALUE: &str = "***";
+
+/// Tell whether the value of the header with the given name must be redacted, either because it is
+/// one of `ALWAYS_REDACTED_HEADERS` or because it is in `denylist`. Names are case-insensitive.
+pub fn is_redacted_header(name: &str, denylist: &[String]) -> bool {
+    let name = name.trim();
+
+    ALWAYS_REDACTED_HEADERS
+        .iter()
+        .copied()
+        .chain(denylist.iter().map(String::as_str))
+        .any(|redacted| redacted.eq_ignore_ascii_case(name))
+}
+
+/// Value of a header as it can be safely shown.
+pub fn redact_header_value<'a>(name: &str, value: &'a str, denylist: &[String]) -> &'a str {
+    if is_redacted_header(name, denylist) {
+        REDACTED_VALUE
+    } else {
+        value
+    }
+}
+
+/// Copy of a list of headers in which the values of the sensitive ones have been redacted.
+pub fn redact_headers(headers: &[(String, String)], denylist: &[String]) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            (
+                name.clone(),
+                redact_header_value(name, value, denylist).to_string(),
+            )
+        })
+        .collect()
+}
+
+/// Redact the values of the sensitive headers in a header dump made of `Name: value` lines
+/// separated by `\r\n`, such as the response of an HTTP-HEAD retrieval, so that it can be logged.
+/// Lines without a colon are kept as is.
+pub fn redact_header_lines(headers: &str, denylist: &[String]) -> String {
+    headers
+        .split("\r\n")
+        .map(|line| match line.split_once(':') {
+            Some((name, _)) if is_redacted_header(name, denylist) => {
+                format!("{}: {}", name, REDACTED_VALUE)
+            }
+            _ => line.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("\r\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOKEN: &str = "Bearer s3cr3t-t0k3n";
+
+    #[test]
+    fn test_is_redacted_header() {
+        let denylist = vec![String::from("X-Api-Key")];
+
+        assert!(is_redacted_header("Authorization", &[]));
+        assert!(is_redacted_header("cookie", &[]));
+        assert!(is_redacted_header("Set-Cookie", &[]));
+        assert!(is_redacted_header(" x-api-key ", &denylist));
+        assert!(!is_redacted_header("x-api-key", &[]));
+        assert!(!is_redacted_header("Content-Type", &denylist));
+    }
+
+    #[test]
+    fn test_redact_headers() {
+        let denylist = vec![String::from("X-Api-Key")];
+        let headers = vec![
+            (String::from("authorization"), String::from(TOKEN)),
+            (String::from("X-API-KEY"), String::from(TOKEN)),
+            (String::from("Accept"), String::from("application/json")),
+        ];
+
+        let formatted = format!("{:?}", redact_headers(&headers, &denylist));
+        assert!(!formatted.contains(TOKEN));
+        assert_eq!(
+            redact_headers(&headers, &denylist),
+            vec![
+                (String::from("authorization"), String::from("***")),
+                (String::from("X-API-KEY"), String::from("***")),
+                (String::from("Accept"), String::from("application/json")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_redact_header_lines() {
+        let headers = format!(
+            "Content-Type: application/json\r\nAuthorization: {}\r\nSet-Cookie: session=abc",
+            TOKEN
+        );
+
+        let redacted = redact_header_lines(&headers, &[]);
+        assert!(!redacted.contains(TOKEN));
+        assert!(!redacted.contains("session=abc"));
+        assert_eq!(
+            redacted,
+            "Content-Type: application/json\r\nAuthorization: ***\r\nSet-Cookie: ***"
+        );
+    }
+}
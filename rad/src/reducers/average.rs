@@ -125,6 +125,135 @@ pub fn mean(input: &RadonArray, return_policy: MeanReturnPolicy) -> Result<Radon
     }
 }
 
+/// Computes the weighted average mean of a `RadonArray` of `[value, weight]` pairs, where both the
+/// value and the weight can be either `Integer` or `Float`. The result is always a `Float`.
+///
+/// An error is returned if the weights add up to zero, as the mean would be undefined.
+// FIXME: Allow for now, since there is no safe cast function from an i128 to float yet
+#[allow(clippy::cast_precision_loss)]
+pub fn mean_weighted(input: &RadonArray) -> Result<RadonTypes, RadError> {
+    let reducer = RadonReducers::AverageMeanWeighted.to_string();
+    let as_f64 = |item: &RadonTypes| match item {
+        RadonTypes::Float(float) => Ok(float.value()),
+        RadonTypes::Integer(integer) => Ok(integer.value() as f64),
+        _ => Err(RadError::MismatchingTypes {
+            method: reducer.clone(),
+            expected: RadonFloat::radon_type_name(),
+            found: item.clone().radon_type_name(),
+        }),
+    };
+
+    let (weighted_sum, total_weight) = input.value().iter().try_fold(
+        (0f64, 0f64),
+        |(weighted_sum, total_weight), pair| match pair {
+            RadonTypes::Array(pair) if pair.value().len() == 2 => {
+                let pair = pair.value();
+                let value = as_f64(&pair[0])?;
+                let weight = as_f64(&pair[1])?;
+
+                Ok((weighted_sum + value * weight, total_weight + weight))
+            }
+            _ => Err(RadError::UnsupportedReducer {
+                array: input.clone(),
+                reducer: reducer.clone(),
+            }),
+        },
+    )?;
+
+    if total_weight == 0f64 {
+        return Err(RadError::ZeroWeight { reducer });
+    }
+
+    Ok(RadonTypes::from(RadonFloat::from(
+        weighted_sum.div(total_weight),
+    )))
+}
+
+/// Computes the geometric mean of the `Integer` or `Float` values found in a `RadonArray`, which is
+/// better suited than the arithmetic mean for ratios and growth rates. The result is always a
+/// `Float`.
+///
+/// The geometric mean is undefined for negative numbers and zero, so an error is returned if any
+/// of those is found.
+///
+/// As `ln` and `exp` are not guaranteed to give bit-identical results across platforms, this
+/// reducer is only available in retrieval scripts, and never in the aggregation or tally stages.
+// FIXME: Allow for now, since there is no safe cast function from a usize to float yet
+#[allow(clippy::cast_precision_loss)]
+pub fn geometric_mean(input: &RadonArray) -> Result<RadonTypes, RadError> {
+    let reducer = RadonReducers::AverageGeometricMean.to_string();
+    let value = input.value();
+
+    if value.is_empty() {
+        return Ok(RadonTypes::from(RadonFloat::from(f64::NAN)));
+    }
+
+    // Add up logarithms instead of multiplying the values to prevent the product from overflowing
+    let log_sum = value.iter().try_fold(0f64, |log_sum, item| {
+        let item_value = match item {
+            RadonTypes::Float(f64_value) => f64_value.value(),
+            RadonTypes::Integer(i128_value) => i128_value.value() as f64,
+            _ => {
+                return Err(RadError::UnsupportedReducer {
+                    array: input.clone(),
+                    reducer: reducer.clone(),
+                })
+            }
+        };
+
+        if item_value > 0f64 {
+            Ok(log_sum + item_value.ln())
+        } else {
+            Err(RadError::NegativeInput {
+                reducer: reducer.clone(),
+                value: item_value,
+            })
+        }
+    })?;
+
+    Ok(RadonTypes::from(RadonFloat::from(
+        log_sum.div(value.len() as f64).exp(),
+    )))
+}
+
+/// Computes the exponential moving average of the `Integer` or `Float` values found in a
+/// `RadonArray`, using `alpha` as the smoothing factor: the average starts at the first value, and
+/// each of the following values moves it by `alpha` times its distance to that value. The result
+/// is always a `Float`.
+///
+/// Unlike the mean, the result depends on the order of the items, the last ones being the most
+/// relevant, so requests must sort their values beforehand, e.g. by timestamp. An error is returned
+/// if `alpha` is not in the (0, 1] range.
+// FIXME: Allow for now, since there is no safe cast function from an i128 to float yet
+#[allow(clippy::cast_precision_loss)]
+pub fn ema(input: &RadonArray, alpha: f64) -> Result<RadonTypes, RadError> {
+    let reducer = RadonReducers::AverageEma.to_string();
+
+    if alpha.is_nan() || alpha <= 0f64 || alpha > 1f64 {
+        return Err(RadError::InvalidSmoothingFactor { reducer, alpha });
+    }
+
+    let ema = input.value().iter().try_fold(None, |ema, item| {
+        let item_value = match item {
+            RadonTypes::Float(f64_value) => f64_value.value(),
+            RadonTypes::Integer(i128_value) => i128_value.value() as f64,
+            _ => {
+                return Err(RadError::UnsupportedReducer {
+                    array: input.clone(),
+                    reducer: reducer.clone(),
+                })
+            }
+        };
+
+        Ok(Some(match ema {
+            Some(ema) => alpha * item_value + (1f64 - alpha) * ema,
+            None => item_value,
+        }))
+    })?;
+
+    Ok(RadonTypes::from(RadonFloat::from(ema.unwrap_or(f64::NAN))))
+}
+
 #[cfg(test)]
 mod tests {
     use crate::types::{float::RadonFloat, integer::RadonInteger, string::RadonString};
@@ -320,4 +449,119 @@ mod tests {
 
         assert_eq!(output, expected);
     }
+
+    #[test]
+    fn test_average_mean_weighted() {
+        let pair = |value: RadonTypes, weight: RadonTypes| {
+            RadonTypes::from(RadonArray::from(vec![value, weight]))
+        };
+        let input = RadonArray::from(vec![
+            pair(RadonFloat::from(10f64).into(), RadonInteger::from(1).into()),
+            pair(RadonInteger::from(20).into(), RadonFloat::from(3f64).into()),
+            pair(
+                RadonFloat::from(40f64).into(),
+                RadonFloat::from(0.5f64).into(),
+            ),
+        ]);
+        let output = mean_weighted(&input).unwrap();
+
+        // (10 * 1 + 20 * 3 + 40 * 0.5) / (1 + 3 + 0.5) = 90 / 4.5 = 20
+        let expected = RadonTypes::from(RadonFloat::from(20f64));
+
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_average_mean_weighted_zero_weight() {
+        let input = RadonArray::from(vec![RadonTypes::from(RadonArray::from(vec![
+            RadonFloat::from(10f64).into(),
+            RadonFloat::from(0f64).into(),
+        ]))]);
+        let output = mean_weighted(&input).unwrap_err();
+
+        let expected = RadError::ZeroWeight {
+            reducer: RadonReducers::AverageMeanWeighted.to_string(),
+        };
+
+        assert_eq!(output, expected);
+        assert_eq!(mean_weighted(&RadonArray::from(vec![])), Err(expected));
+    }
+
+    #[test]
+    fn test_average_mean_weighted_not_pairs() {
+        let input = RadonArray::from(vec![
+            RadonFloat::from(1f64).into(),
+            RadonFloat::from(2f64).into(),
+        ]);
+        let output = mean_weighted(&input).unwrap_err();
+
+        let expected = RadError::UnsupportedReducer {
+            array: input,
+            reducer: RadonReducers::AverageMeanWeighted.to_string(),
+        };
+
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_average_geometric_mean() {
+        let input = RadonArray::from(vec![
+            RadonFloat::from(2f64).into(),
+            RadonFloat::from(8f64).into(),
+            RadonFloat::from(4f64).into(),
+        ]);
+        let output = geometric_mean(&input).unwrap();
+
+        // (2 * 8 * 4) ^ (1 / 3) = 64 ^ (1 / 3) = 4
+        match output {
+            RadonTypes::Float(float) => assert!((float.value() - 4f64).abs() < 1e-12),
+            other => panic!("Expected a RadonFloat, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_average_geometric_mean_non_positive() {
+        let input = RadonArray::from(vec![
+            RadonInteger::from(2i128).into(),
+            RadonInteger::from(0i128).into(),
+        ]);
+        let output = geometric_mean(&input).unwrap_err();
+
+        let expected = RadError::NegativeInput {
+            reducer: RadonReducers::AverageGeometricMean.to_string(),
+            value: 0f64,
+        };
+
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_average_ema() {
+        let input = RadonArray::from(vec![
+            RadonInteger::from(1i128).into(),
+            RadonInteger::from(2i128).into(),
+            RadonFloat::from(3f64).into(),
+            RadonFloat::from(4f64).into(),
+        ]);
+        let output = ema(&input, 0.5).unwrap();
+
+        // 1 -> 0.5 * 2 + 0.5 * 1 = 1.5 -> 0.5 * 3 + 0.5 * 1.5 = 2.25 -> 0.5 * 4 + 0.5 * 2.25 = 3.125
+        assert_eq!(output, RadonTypes::from(RadonFloat::from(3.125)));
+
+        // With an alpha of 1, only the last value matters
+        let output = ema(&input, 1f64).unwrap();
+        assert_eq!(output, RadonTypes::from(RadonFloat::from(4f64)));
+    }
+
+    #[test]
+    fn test_average_ema_invalid_alpha() {
+        let input = RadonArray::from(vec![RadonFloat::from(1f64).into()]);
+
+        for alpha in [0f64, -0.5, 1.5, f64::NAN] {
+            assert!(matches!(
+                ema(&input, alpha),
+                Err(RadError::InvalidSmoothingFactor { .. })
+            ));
+        }
+    }
 }
@@ -1,6 +1,8 @@
 use std::fmt;
 
 use num_enum::{IntoPrimitive, TryFromPrimitive};
+use serde_cbor::Value;
+use strum_macros::{EnumString, IntoStaticStr};
 
 use crate::{
     error::RadError,
@@ -13,21 +15,27 @@ pub mod deviation;
 pub mod hash_concatenate;
 pub mod median;
 pub mod mode;
+pub mod sum;
 
-#[derive(Debug, PartialEq, Eq, TryFromPrimitive, IntoPrimitive)]
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, TryFromPrimitive, IntoPrimitive, EnumString, IntoStaticStr,
+)]
 #[repr(u8)]
 pub enum RadonReducers {
     // Implemented
     Mode = 0x02,
     AverageMean = 0x03,
+    AverageMeanWeighted = 0x04,
     AverageMedian = 0x05,
     DeviationStandard = 0x07,
     HashConcatenate = 0x0b,
+    AverageGeometricMean = 0x0c,
+    Sum = 0x0d,
+    AverageEma = 0x0e,
 
     // Not implemented
     Min = 0x00,
     Max = 0x01,
-    AverageMeanWeighted = 0x04,
     AverageMedianWeighted = 0x06,
     DeviationAverageAbsolute = 0x08,
     DeviationMedianAbsolute = 0x09,
@@ -58,6 +66,18 @@ pub fn reduce(
                 average::mean(input, average::MeanReturnPolicy::RoundToInteger)
             }
             RadonReducers::Mode => mode::mode(input),
+            RadonReducers::AverageMeanWeighted => match &context.active_wips {
+                Some(active_wips) if active_wips.wip0029() => average::mean_weighted(input),
+                _ => error(),
+            },
+            RadonReducers::AverageGeometricMean => match &context.active_wips {
+                Some(active_wips) if active_wips.wip0029() => average::geometric_mean(input),
+                _ => error(),
+            },
+            RadonReducers::Sum => match &context.active_wips {
+                Some(active_wips) if active_wips.wip0029() => sum::sum(input),
+                _ => error(),
+            },
             RadonReducers::DeviationStandard => deviation::standard(input),
             RadonReducers::AverageMedian => match &context.active_wips {
                 Some(active_wips) if active_wips.wip0017() => median::median(input),
@@ -78,14 +98,51 @@ pub fn reduce(
     }
 }
 
+/// Apply a reducer along with its arguments. Only `AverageEma` takes arguments, namely its smoothing
+/// factor, so it can only be used through `ArrayReduce` and never in the aggregation or tally stages.
+/// Any other reducer must be given no arguments at all.
+pub fn reduce_with_args(
+    input: &RadonArray,
+    reducer_code: RadonReducers,
+    args: &[Value],
+    context: &mut ReportContext<RadonTypes>,
+) -> Result<RadonTypes, RadError> {
+    let wrong_args = || RadError::WrongArguments {
+        input_type: RadonArray::radon_type_name(),
+        operator: reducer_code.to_string(),
+        args: args.to_vec(),
+    };
+
+    match (reducer_code, args) {
+        (RadonReducers::AverageEma, [alpha]) => match &context.active_wips {
+            Some(active_wips) if active_wips.wip0029() => {
+                let alpha = match alpha {
+                    Value::Float(alpha) => *alpha,
+                    Value::Integer(alpha) => *alpha as f64,
+                    _ => return Err(wrong_args()),
+                };
+
+                average::ema(input, alpha)
+            }
+            _ => Err(RadError::UnsupportedReducer {
+                array: input.clone(),
+                reducer: reducer_code.to_string(),
+            }),
+        },
+        (_, []) => reduce(input, reducer_code, context),
+        _ => Err(wrong_args()),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
-        current_active_wips,
+        all_wips_active, current_active_wips,
         error::RadError,
-        reducers::{reduce, RadonReducers},
+        reducers::{reduce, reduce_with_args, RadonReducers},
         types::{array::RadonArray, float::RadonFloat, RadonTypes},
     };
+    use serde_cbor::Value;
     use witnet_data_structures::radon_report::ReportContext;
 
     #[test]
@@ -173,6 +230,74 @@ mod tests {
         assert_eq!(output, expected_err);
     }
 
+    #[test]
+    fn test_reduce_average_mean_weighted_wip0029() {
+        let mut context = ReportContext {
+            active_wips: Some(current_active_wips()),
+            ..Default::default()
+        };
+        let input = &RadonArray::from(vec![
+            RadonArray::from(vec![
+                RadonFloat::from(1f64).into(),
+                RadonFloat::from(1f64).into(),
+            ])
+            .into(),
+            RadonArray::from(vec![
+                RadonFloat::from(4f64).into(),
+                RadonFloat::from(2f64).into(),
+            ])
+            .into(),
+        ]);
+
+        let expected_err = RadError::UnsupportedReducer {
+            array: input.clone(),
+            reducer: "RadonReducers::AverageMeanWeighted".to_string(),
+        };
+        let output = reduce(input, RadonReducers::AverageMeanWeighted, &mut context).unwrap_err();
+        assert_eq!(output, expected_err);
+
+        context.active_wips = Some(all_wips_active());
+        let expected = RadonTypes::from(RadonFloat::from(3f64));
+        let output = reduce(input, RadonReducers::AverageMeanWeighted, &mut context).unwrap();
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_reduce_average_ema_wip0029() {
+        let mut context = ReportContext {
+            active_wips: Some(current_active_wips()),
+            ..Default::default()
+        };
+        let input = &RadonArray::from(vec![
+            RadonFloat::from(1f64).into(),
+            RadonFloat::from(2f64).into(),
+            RadonFloat::from(3f64).into(),
+            RadonFloat::from(4f64).into(),
+        ]);
+        let alpha = [Value::Float(0.5)];
+
+        let expected_err = RadError::UnsupportedReducer {
+            array: input.clone(),
+            reducer: "RadonReducers::AverageEma".to_string(),
+        };
+        let output =
+            reduce_with_args(input, RadonReducers::AverageEma, &alpha, &mut context).unwrap_err();
+        assert_eq!(output, expected_err);
+
+        context.active_wips = Some(all_wips_active());
+        let expected = RadonTypes::from(RadonFloat::from(3.125));
+        let output =
+            reduce_with_args(input, RadonReducers::AverageEma, &alpha, &mut context).unwrap();
+        assert_eq!(output, expected);
+
+        // The smoothing factor is mandatory, and no other reducer takes arguments
+        assert!(reduce_with_args(input, RadonReducers::AverageEma, &[], &mut context).is_err());
+        assert!(matches!(
+            reduce_with_args(input, RadonReducers::AverageMean, &alpha, &mut context),
+            Err(RadError::WrongArguments { .. })
+        ));
+    }
+
     #[test]
     fn test_reduce_mode_float() {
         let input = &RadonArray::from(vec![
@@ -0,0 +1,85 @@
+use crate::{
+    error::RadError,
+    reducers::RadonReducers,
+    types::{array::RadonArray, float::RadonFloat, integer::RadonInteger, RadonType, RadonTypes},
+};
+
+/// Adds up all the values found in a `RadonArray`, which is assumed to be homogeneous.
+///
+/// Integers are added without losing precision, and fail with `Overflow` if the sum does not fit.
+/// The sum of an empty array is the integer zero.
+pub fn sum(input: &RadonArray) -> Result<RadonTypes, RadError> {
+    let value = input.value();
+    let mismatching = |expected, item: &RadonTypes| RadError::MismatchingTypes {
+        method: RadonReducers::Sum.to_string(),
+        expected,
+        found: item.clone().radon_type_name(),
+    };
+
+    match value.first() {
+        None => Ok(RadonTypes::from(RadonInteger::from(0))),
+        Some(RadonTypes::Integer(_)) => value
+            .iter()
+            .try_fold(0i128, |sum, item| match item {
+                RadonTypes::Integer(integer) => {
+                    sum.checked_add(integer.value()).ok_or(RadError::Overflow)
+                }
+                _ => Err(mismatching(RadonInteger::radon_type_name(), item)),
+            })
+            .map(|sum| RadonTypes::from(RadonInteger::from(sum))),
+        Some(RadonTypes::Float(_)) => value
+            .iter()
+            .try_fold(0f64, |sum, item| match item {
+                RadonTypes::Float(float) => Ok(sum + float.value()),
+                _ => Err(mismatching(RadonFloat::radon_type_name(), item)),
+            })
+            .map(|sum| RadonTypes::from(RadonFloat::from(sum))),
+        Some(_) => Err(RadError::UnsupportedReducer {
+            array: input.clone(),
+            reducer: RadonReducers::Sum.to_string(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::string::RadonString;
+
+    use super::*;
+
+    #[test]
+    fn test_sum() {
+        let integers = RadonArray::from(vec![
+            RadonInteger::from(1).into(),
+            RadonInteger::from(2).into(),
+            RadonInteger::from(3).into(),
+        ]);
+        assert_eq!(sum(&integers), Ok(RadonInteger::from(6).into()));
+
+        let floats = RadonArray::from(vec![
+            RadonFloat::from(1.5).into(),
+            RadonFloat::from(2.25).into(),
+        ]);
+        assert_eq!(sum(&floats), Ok(RadonFloat::from(3.75).into()));
+
+        assert_eq!(
+            sum(&RadonArray::from(vec![])),
+            Ok(RadonInteger::from(0).into())
+        );
+    }
+
+    #[test]
+    fn test_sum_errors() {
+        let overflowing = RadonArray::from(vec![
+            RadonInteger::from(i128::MAX).into(),
+            RadonInteger::from(1).into(),
+        ]);
+        assert_eq!(sum(&overflowing), Err(RadError::Overflow));
+
+        let strings = RadonArray::from(vec![RadonString::from("1").into()]);
+        assert!(matches!(
+            sum(&strings),
+            Err(RadError::UnsupportedReducer { .. })
+        ));
+    }
+}
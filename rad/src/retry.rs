@@ -0,0 +1,161 @@
+//! Retrying of retrievals that fail because of transient errors.
+use std::{future::Future, time::Duration};
+
+use rand::Rng;
+use witnet_data_structures::witnessing::RetryPolicy;
+
+use crate::{error::RadError, Result};
+
+/// Upper bound for the exponent used in the exponential backoff, so that delays cannot overflow.
+const MAX_BACKOFF_EXPONENT: u32 = 16;
+
+/// The outcome of a failed attempt.
+#[derive(Debug)]
+pub enum AttemptError {
+    /// The attempt may succeed if it is tried again, e.g. after a 5xx status code or a connection
+    /// error.
+    Transient(RadError),
+    /// Trying again is pointless, e.g. after a 4xx status code or a parse error.
+    Permanent(RadError),
+}
+
+impl AttemptError {
+    fn into_inner(self) -> RadError {
+        match self {
+            AttemptError::Transient(error) | AttemptError::Permanent(error) => error,
+        }
+    }
+}
+
+/// Delay to wait before retry number `retry` (starting at 1), without jitter.
+pub fn backoff_delay(policy: &RetryPolicy, retry: u32) -> Duration {
+    let exponent = retry.saturating_sub(1).min(MAX_BACKOFF_EXPONENT);
+
+    policy.base_delay.saturating_mul(1 << exponent)
+}
+
+/// Subtract a random amount of up to half of the delay.
+fn with_jitter(delay: Duration) -> Duration {
+    let max_jitter = u64::try_from(delay.as_nanos() / 2).unwrap_or(u64::MAX);
+    if max_jitter == 0 {
+        return delay;
+    }
+    let jitter = rand::thread_rng().gen_range(0, max_jitter);
+
+    delay - Duration::from_nanos(jitter)
+}
+
+/// Run `attempt` until it succeeds, fails with a permanent error, or the maximum number of attempts
+/// in the policy is reached. If no policy is provided, `attempt` is run only once.
+///
+/// In case of failure, the error from the last attempt is returned.
+pub async fn retry<T, F, Fut>(policy: Option<&RetryPolicy>, mut attempt: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = std::result::Result<T, AttemptError>>,
+{
+    let max_attempts = policy.map_or(1, |policy| policy.max_attempts.max(1));
+    let mut attempts = 0;
+
+    loop {
+        attempts += 1;
+        match attempt().await {
+            Ok(value) => return Ok(value),
+            Err(AttemptError::Transient(error)) if attempts < max_attempts => {
+                log::debug!(
+                    "Retrying transient failure ({}), attempt {} of {}",
+                    error,
+                    attempts + 1,
+                    max_attempts
+                );
+                if let Some(policy) = policy {
+                    let delay = with_jitter(backoff_delay(policy, attempts));
+                    futures_timer::Delay::new(delay).await;
+                }
+            }
+            Err(error) => return Err(error.into_inner()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+
+    use super::*;
+
+    fn policy(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts,
+            base_delay: Duration::from_millis(1),
+        }
+    }
+
+    #[test]
+    fn test_backoff_delay() {
+        let policy = RetryPolicy {
+            max_attempts: 5,
+            base_delay: Duration::from_millis(100),
+        };
+
+        assert_eq!(backoff_delay(&policy, 1), Duration::from_millis(100));
+        assert_eq!(backoff_delay(&policy, 2), Duration::from_millis(200));
+        assert_eq!(backoff_delay(&policy, 4), Duration::from_millis(800));
+        // Does not overflow
+        assert!(backoff_delay(&policy, u32::MAX) > Duration::ZERO);
+
+        for _ in 0..100 {
+            let delay = with_jitter(Duration::from_millis(100));
+            assert!(delay > Duration::from_millis(50) && delay <= Duration::from_millis(100));
+        }
+    }
+
+    #[test]
+    fn test_retry_returns_last_error() {
+        let mut attempts = 0;
+        let result: Result<()> = block_on(retry(Some(&policy(3)), || {
+            attempts += 1;
+            let status_code = 500 + attempts;
+            async move {
+                Err(AttemptError::Transient(RadError::HttpStatus {
+                    status_code,
+                }))
+            }
+        }));
+
+        assert_eq!(attempts, 3);
+        assert_eq!(result, Err(RadError::HttpStatus { status_code: 503 }));
+    }
+
+    #[test]
+    fn test_retry_stops_on_permanent_error() {
+        let mut attempts = 0;
+        let result: Result<()> = block_on(retry(Some(&policy(3)), || {
+            attempts += 1;
+            async {
+                Err(AttemptError::Permanent(RadError::HttpStatus {
+                    status_code: 404,
+                }))
+            }
+        }));
+
+        assert_eq!(attempts, 1);
+        assert_eq!(result, Err(RadError::HttpStatus { status_code: 404 }));
+    }
+
+    #[test]
+    fn test_retry_without_policy() {
+        let mut attempts = 0;
+        let result: Result<()> = block_on(retry(None, || {
+            attempts += 1;
+            async {
+                Err(AttemptError::Transient(RadError::HttpStatus {
+                    status_code: 503,
+                }))
+            }
+        }));
+
+        assert_eq!(attempts, 1);
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,449 @@
+//! JSON Schema documents describing how `RadonTypes` and `RadonReport` look like once serialized
+//! into JSON, e.g. in the output of `witnet_toolkit` or in JSON-RPC responses.
+//!
+//! These are meant for integrators that want to validate such responses without linking to this
+//! crate.
+use serde_json::{json, Value as JsonValue};
+
+/// URI of the JSON Schema dialect used by the documents in this module.
+pub const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// JSON Schema for a single `RadonTypes` value.
+pub fn radon_types_schema() -> JsonValue {
+    json!({
+        "$schema": JSON_SCHEMA_DIALECT,
+        "title": "RadonTypes",
+        "$ref": "#/$defs/RadonTypes",
+        "$defs": definitions(),
+    })
+}
+
+/// JSON Schema for a `RadonReport<RadonTypes>`.
+pub fn radon_report_schema() -> JsonValue {
+    json!({
+        "$schema": JSON_SCHEMA_DIALECT,
+        "title": "RadonReport",
+        "$ref": "#/$defs/RadonReport",
+        "$defs": definitions(),
+    })
+}
+
+/// Every value is serialized as an object with a single key, which is the name of the type.
+fn radon_type(name: &str, value: JsonValue) -> JsonValue {
+    let mut properties = serde_json::Map::new();
+    properties.insert(String::from(name), value);
+
+    json!({
+        "type": "object",
+        "properties": properties,
+        "required": [name],
+        "additionalProperties": false,
+    })
+}
+
+fn nullable(schema: JsonValue) -> JsonValue {
+    json!({ "oneOf": [{ "type": "null" }, schema] })
+}
+
+fn definitions() -> JsonValue {
+    let radon_types = json!({ "$ref": "#/$defs/RadonTypes" });
+    let index = json!({ "type": "integer", "minimum": 0 });
+    let byte = json!({ "type": "integer", "minimum": 0, "maximum": 255 });
+    let http_request = json!({
+        "type": "object",
+        "properties": {
+            "method": { "type": "string" },
+            "url": { "type": "string" },
+            "headers": {
+                "type": "array",
+                "items": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "minItems": 2,
+                    "maxItems": 2,
+                },
+            },
+            "body": { "type": "array", "items": byte },
+        },
+        "required": ["method", "url", "headers", "body"],
+        "additionalProperties": false,
+    });
+    let time = json!({
+        "type": "object",
+        "properties": {
+            "secs_since_epoch": index,
+            "nanos_since_epoch": index,
+        },
+        "required": ["secs_since_epoch", "nanos_since_epoch"],
+        "additionalProperties": false,
+    });
+
+    json!({
+        "RadonTypes": {
+            "oneOf": [
+                radon_type("RadonArray", json!({ "type": "array", "items": radon_types })),
+                radon_type("RadonBoolean", json!({ "type": "boolean" })),
+                radon_type(
+                    "RadonBytes",
+                    json!({ "type": "array", "items": byte }),
+                ),
+                // The error message, as in the `Display` implementation of `RadError`
+                radon_type("RadonError", json!({ "type": "string" })),
+                radon_type("RadonFloat", json!({ "type": "number" })),
+                // Integers are serialized as strings because they may not fit into a JSON number
+                radon_type(
+                    "RadonInteger",
+                    json!({ "type": "string", "pattern": "^-?[0-9]+$" }),
+                ),
+                radon_type(
+                    "RadonMap",
+                    json!({ "type": "object", "additionalProperties": radon_types }),
+                ),
+                radon_type("RadonString", json!({ "type": "string" })),
+            ],
+        },
+        "Duration": {
+            "type": "object",
+            "properties": {
+                "secs": index,
+                "nanos": index,
+            },
+            "required": ["secs", "nanos"],
+            "additionalProperties": false,
+        },
+        "Stage": {
+            "oneOf": [
+                { "enum": ["Contextless"] },
+                {
+                    "type": "object",
+                    "properties": {
+                        "Aggregation": {
+                            "type": "object",
+                            "properties": {
+                                "consensus": { "type": "number" },
+                                "liars": { "type": "array", "items": { "type": "boolean" } },
+                            },
+                            "required": ["consensus", "liars"],
+                            "additionalProperties": false,
+                        },
+                    },
+                    "required": ["Aggregation"],
+                    "additionalProperties": false,
+                },
+                {
+                    "type": "object",
+                    "properties": {
+                        "Retrieval": {
+                            "type": "object",
+                            "properties": {
+                                "subscript_partial_results": {
+                                    "type": "array",
+                                    "items": {
+                                        "type": "array",
+                                        "items": { "type": "array", "items": radon_types },
+                                    },
+                                },
+                                "recovered_errors": { "type": "array", "items": radon_types },
+                                "http_request": nullable(http_request),
+                                "final_url": nullable(json!({ "type": "string" })),
+                                "divergent_final_hosts": {
+                                    "type": "array",
+                                    "items": { "type": "string" },
+                                },
+                            },
+                            "required": [
+                                "subscript_partial_results",
+                                "recovered_errors",
+                                "http_request",
+                                "final_url",
+                                "divergent_final_hosts",
+                            ],
+                            "additionalProperties": false,
+                        },
+                    },
+                    "required": ["Retrieval"],
+                    "additionalProperties": false,
+                },
+                {
+                    "type": "object",
+                    "properties": {
+                        "Tally": {
+                            "type": "object",
+                            "properties": {
+                                "consensus": { "type": "number" },
+                                "errors": { "type": "array", "items": { "type": "boolean" } },
+                                "liars": { "type": "array", "items": { "type": "boolean" } },
+                                "subscript_partial_results": {
+                                    "type": "array",
+                                    "items": radon_types,
+                                },
+                            },
+                            "required": ["consensus", "errors", "liars", "subscript_partial_results"],
+                            "additionalProperties": false,
+                        },
+                    },
+                    "required": ["Tally"],
+                    "additionalProperties": false,
+                },
+            ],
+        },
+        "ReportContext": {
+            "type": "object",
+            "properties": {
+                // Arguments are arbitrary CBOR values
+                "call_arguments": nullable(json!({ "type": "array" })),
+                "call_argument_index": nullable(index.clone()),
+                "call_index": nullable(index.clone()),
+                "call_operator": nullable(index.clone()),
+                "completion_time": nullable(time.clone()),
+                "stage": { "$ref": "#/$defs/Stage" },
+                "start_time": nullable(time),
+                "script_index": nullable(index.clone()),
+                "active_wips": nullable(json!({
+                    "type": "object",
+                    "properties": {
+                        "active_wips": { "type": "object", "additionalProperties": index },
+                        "block_epoch": index,
+                    },
+                    "required": ["active_wips", "block_epoch"],
+                    "additionalProperties": false,
+                })),
+            },
+            "required": [
+                "call_arguments",
+                "call_argument_index",
+                "call_index",
+                "call_operator",
+                "completion_time",
+                "stage",
+                "start_time",
+                "script_index",
+                "active_wips",
+            ],
+            "additionalProperties": false,
+        },
+        "RadonReport": {
+            "type": "object",
+            "properties": {
+                "context": { "$ref": "#/$defs/ReportContext" },
+                "partial_results": nullable(json!({ "type": "array", "items": radon_types })),
+                "result": radon_types,
+                "running_time": { "$ref": "#/$defs/Duration" },
+            },
+            "required": ["context", "partial_results", "result", "running_time"],
+            "additionalProperties": false,
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_cbor::Value;
+    use witnet_data_structures::{
+        chain::{tapi::all_wips_active, RADAggregate, RADFilter},
+        radon_report::{RadonReport, ReportContext, RetrievalMetadata, Stage},
+    };
+
+    use super::*;
+    use crate::{
+        error::RadError,
+        filters::RadonFilters,
+        operators::RadonOpCodes,
+        reducers::RadonReducers,
+        run_aggregation_report,
+        script::{execute_radon_script, RadonScriptExecutionSettings},
+        types::{float::RadonFloat, integer::RadonInteger, string::RadonString, RadonTypes},
+    };
+
+    /// Minimal validator for the subset of JSON Schema used in this module.
+    fn validates(schema: &JsonValue, value: &JsonValue, root: &JsonValue) -> bool {
+        if let Some(reference) = schema.get("$ref").and_then(JsonValue::as_str) {
+            let name = reference.trim_start_matches("#/$defs/");
+            if !validates(&root["$defs"][name], value, root) {
+                return false;
+            }
+        }
+        if let Some(expected) = schema.get("type").and_then(JsonValue::as_str) {
+            let matches = match expected {
+                "null" => value.is_null(),
+                "boolean" => value.is_boolean(),
+                "integer" => value.is_i64() || value.is_u64(),
+                "number" => value.is_number(),
+                "string" => value.is_string(),
+                "array" => value.is_array(),
+                "object" => value.is_object(),
+                _ => false,
+            };
+            if !matches {
+                return false;
+            }
+        }
+        if let Some(variants) = schema.get("enum").and_then(JsonValue::as_array) {
+            if !variants.contains(value) {
+                return false;
+            }
+        }
+        if let Some(one_of) = schema.get("oneOf").and_then(JsonValue::as_array) {
+            let matching = one_of
+                .iter()
+                .filter(|variant| validates(variant, value, root))
+                .count();
+            if matching != 1 {
+                return false;
+            }
+        }
+        if let (Some(pattern), Some(string)) = (
+            schema.get("pattern").and_then(JsonValue::as_str),
+            value.as_str(),
+        ) {
+            // The only pattern in use is the one for integers
+            assert_eq!(pattern, "^-?[0-9]+$");
+            if string.trim_start_matches('-').parse::<u128>().is_err() {
+                return false;
+            }
+        }
+        if let Some(number) = value.as_f64() {
+            let below_minimum = schema
+                .get("minimum")
+                .and_then(JsonValue::as_f64)
+                .map_or(false, |minimum| number < minimum);
+            let above_maximum = schema
+                .get("maximum")
+                .and_then(JsonValue::as_f64)
+                .map_or(false, |maximum| number > maximum);
+            if below_minimum || above_maximum {
+                return false;
+            }
+        }
+        if let (Some(items), Some(array)) = (schema.get("items"), value.as_array()) {
+            if !array.iter().all(|item| validates(items, item, root)) {
+                return false;
+            }
+        }
+        if let Some(object) = value.as_object() {
+            let properties = schema.get("properties").and_then(JsonValue::as_object);
+            if let Some(required) = schema.get("required").and_then(JsonValue::as_array) {
+                if !required
+                    .iter()
+                    .filter_map(JsonValue::as_str)
+                    .all(|key| object.contains_key(key))
+                {
+                    return false;
+                }
+            }
+            for (key, item) in object {
+                let item_schema = properties.and_then(|properties| properties.get(key));
+                let valid = match (item_schema, schema.get("additionalProperties")) {
+                    (Some(item_schema), _) => validates(item_schema, item, root),
+                    (None, Some(JsonValue::Bool(allowed))) => *allowed,
+                    (None, Some(additional)) => validates(additional, item, root),
+                    (None, None) => true,
+                };
+                if !valid {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+
+    fn validates_report(value: &JsonValue) -> bool {
+        let schema = radon_report_schema();
+
+        validates(&schema, value, &schema)
+    }
+
+    #[test]
+    fn test_schema_validates_retrieval_report() {
+        let input = RadonTypes::from(RadonString::from(r#"{"prices":[1,2],"name":"eth"}"#));
+        let script = vec![
+            (RadonOpCodes::StringParseJSONMap, None),
+            (
+                RadonOpCodes::MapGetArray,
+                Some(vec![Value::Text(String::from("prices"))]),
+            ),
+        ];
+        let mut context = ReportContext::from_stage(Stage::Retrieval(RetrievalMetadata::default()));
+        let report = execute_radon_script(
+            input,
+            &script,
+            &mut context,
+            RadonScriptExecutionSettings::enable_all(),
+        )
+        .unwrap();
+
+        let value = serde_json::to_value(&report).unwrap();
+        assert!(validates_report(&value), "{}", value);
+    }
+
+    #[test]
+    fn test_schema_validates_aggregation_report() {
+        let values = [1.0, 1.0, 1.0, 100.0]
+            .iter()
+            .map(|value| RadonTypes::from(RadonFloat::from(*value)))
+            .collect();
+        let aggregate = RADAggregate {
+            filters: vec![RADFilter {
+                op: RadonFilters::DeviationStandard as u32,
+                // 1.0 as a half precision float
+                args: vec![249, 60, 0],
+            }],
+            reducer: RadonReducers::AverageMean as u32,
+        };
+        let (report, _) = run_aggregation_report(
+            values,
+            aggregate,
+            RadonScriptExecutionSettings::enable_all(),
+            &all_wips_active(),
+        );
+        let report = report.unwrap();
+        assert_eq!(report.liars(), Some(&[false, false, false, true][..]));
+
+        let value = serde_json::to_value(&report).unwrap();
+        assert!(validates_report(&value), "{}", value);
+        assert_eq!(
+            value["context"]["stage"],
+            json!({ "Aggregation": { "consensus": 0.75, "liars": [false, false, false, true] } })
+        );
+
+        // The stage used to be serialized without metadata
+        let mut bare = value;
+        bare["context"]["stage"] = json!("Aggregation");
+        assert!(!validates_report(&bare), "{}", bare);
+    }
+
+    #[test]
+    fn test_schema_validates_error_report() {
+        let report: RadonReport<RadonTypes> = RadonReport::from_result(
+            Err(RadError::HttpStatus { status_code: 404 }),
+            &ReportContext::default(),
+        );
+
+        let value = serde_json::to_value(&report).unwrap();
+        assert!(validates_report(&value), "{}", value);
+    }
+
+    #[test]
+    fn test_schema_rejects_malformed_values() {
+        let schema = radon_types_schema();
+
+        let integer = serde_json::to_value(RadonTypes::from(RadonInteger::from(-3))).unwrap();
+        assert!(validates(&schema, &integer, &schema));
+
+        // Integers must be strings
+        assert!(!validates(&schema, &json!({ "RadonInteger": 3 }), &schema));
+        // Unknown types
+        assert!(!validates(
+            &schema,
+            &json!({ "RadonDate": "2021" }),
+            &schema
+        ));
+        // More than one key
+        assert!(!validates(
+            &schema,
+            &json!({ "RadonString": "a", "RadonBoolean": true }),
+            &schema
+        ));
+    }
+}
@@ -81,12 +81,36 @@ impl RadonScriptExecutionSettings {
 /// Run any RADON script on given input data, and return `RadonReport`.
 /// By enabling or disabling each of the specific flags in the settings argument, we can adjust how
 /// much execution metadata we want to track, e.g. execution time, partial results, etc.
+///
+/// Subscripts are executed through this same function, which enforces the `script_limits` of the
+/// context: going deeper than the maximum subscript depth or executing more calls than allowed
+/// makes the script fail with `RadError::ScriptTooComplex`. As this changes the result of existing
+/// requests, the limits are only enforced once WIP0029 is active.
 pub fn execute_radon_script(
     input: RadonTypes,
     script: &[RadonCall],
     context: &mut ReportContext<RadonTypes>,
     settings: RadonScriptExecutionSettings,
 ) -> Result<RadonReport<RadonTypes>, RadError> {
+    let enforce_limits = context
+        .active_wips
+        .as_ref()
+        .map(ActiveWips::wip0029)
+        .unwrap_or(true);
+
+    // The count of executed calls is shared by a script and all of its subscripts, and so is the
+    // decision of recording recovered errors
+    if context.script_depth == 0 {
+        context.executed_calls = 0;
+        context.record_recovered_errors = settings.partial_results;
+    } else if enforce_limits && context.script_depth > context.script_limits.max_subscript_depth {
+        return Ok(RadonReport::from_result(
+            Err(RadError::ScriptTooComplex),
+            context,
+        ));
+    }
+    context.script_depth += 1;
+
     // Set the execution start timestamp, if enabled by `timing` setting
     if settings.timing {
         context.start();
@@ -109,8 +133,14 @@ pub fn execute_radon_script(
                 context.call_index = Some(i);
             }
 
-            // Apply the call
-            let partial_result = operate_in_context(input, call, context);
+            // Apply the call, unless the script has already executed too many calls
+            context.executed_calls += 1;
+            let partial_result =
+                if enforce_limits && context.executed_calls > context.script_limits.max_calls {
+                    Err(RadError::ScriptTooComplex)
+                } else {
+                    operate_in_context(input, call, context)
+                };
 
             // Keep partial result, if enabled by `partial_results` setting
             if let Some(partial_results) = partial_results.as_mut() {
@@ -119,6 +149,7 @@ pub fn execute_radon_script(
 
             partial_result
         });
+    context.script_depth -= 1;
 
     // Set the completion timestamp, if enabled by `timing` settings
     if settings.timing {
@@ -152,12 +183,120 @@ pub fn unpack_radon_script(packed: &[u8]) -> Result<RadonScript, RadError> {
         Ok(_) => Err(errorify(RadError::ScriptNotArray {
             input_type: String::from("different thing"),
         })),
-        Err(error) => Err(errorify(RadError::BufferIsNotValue {
-            description: error.to_string(),
+        Err(error) => Err(errorify(RadError::ScriptDecode {
+            position: error.offset(),
+            inner: error.to_string(),
         })),
     }
 }
 
+/// Unpack a RADON script and make sure that all of its calls are available under the given set of
+/// active WIPs.
+pub fn unpack_radon_script_with_active_wips(
+    packed: &[u8],
+    active_wips: &ActiveWips,
+) -> Result<RadonScript, RadError> {
+    let script = unpack_radon_script(packed)?;
+    check_radon_script_availability(&script, active_wips)?;
+
+    Ok(script)
+}
+
+/// Make sure that every call in a RADON script, including those in subscripts, can be used under
+/// the given set of active WIPs.
+pub fn check_radon_script_availability(
+    script: &[RadonCall],
+    active_wips: &ActiveWips,
+) -> Result<(), RadError> {
+    for (op_code, args) in script {
+        check_availability(*op_code as u8, operator_activation(*op_code), active_wips)?;
+
+        // Subscripts are not unpacked until execution, so errors other than availability are
+        // left for the operator to report
+        if let (
+            RadonOpCodes::ArrayFilter
+            | RadonOpCodes::ArrayMap
+            | RadonOpCodes::ArraySort
+            | RadonOpCodes::MapUnion,
+            Some(args),
+        ) = (op_code, args)
+        {
+            if let Some(Ok(subscript)) = args.first().map(unpack_subscript) {
+                check_radon_script_availability(&subscript, active_wips)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// A WIP name along with the `ActiveWips` method telling whether it is active.
+type WipActivation = (&'static str, fn(&ActiveWips) -> bool);
+
+/// Tell which WIP, if any, introduced a RADON operator.
+fn operator_activation(op_code: RadonOpCodes) -> Option<WipActivation> {
+    match op_code {
+        RadonOpCodes::StringParseXMLMap => Some(("WIP0020", ActiveWips::wip0020)),
+        RadonOpCodes::ArrayFlatten
+        | RadonOpCodes::ArrayGroupBy
+        | RadonOpCodes::ArrayMinLength
+        | RadonOpCodes::ArrayStats
+        | RadonOpCodes::Equal
+        | RadonOpCodes::FloatClamp
+        | RadonOpCodes::FloatToString
+        | RadonOpCodes::GetPath
+        | RadonOpCodes::HashHash160
+        | RadonOpCodes::HashKeccak256
+        | RadonOpCodes::IntegerBitAnd
+        | RadonOpCodes::IntegerBitOr
+        | RadonOpCodes::IntegerClamp
+        | RadonOpCodes::IntegerShiftLeft
+        | RadonOpCodes::IntegerShiftRight
+        | RadonOpCodes::IntegerToBytes
+        | RadonOpCodes::Length
+        | RadonOpCodes::MapGetOrDefault
+        | RadonOpCodes::MapUnion
+        | RadonOpCodes::NotEqual
+        | RadonOpCodes::StringFormat
+        | RadonOpCodes::StringParseJSONOrDefault
+        | RadonOpCodes::StringParseTimestamp
+        | RadonOpCodes::StringSplit
+        | RadonOpCodes::StringUrlDecode => Some(("WIP0029", ActiveWips::wip0029)),
+        _ => None,
+    }
+}
+
+/// Tell which WIP, if any, introduced a RADON filter.
+fn filter_activation(_filter: RadonFilters) -> Option<WipActivation> {
+    None
+}
+
+/// Tell which WIP, if any, introduced a RADON reducer.
+fn reducer_activation(reducer: RadonReducers) -> Option<WipActivation> {
+    match reducer {
+        RadonReducers::AverageMedian => Some(("WIP0017", ActiveWips::wip0017)),
+        RadonReducers::AverageEma
+        | RadonReducers::AverageMeanWeighted
+        | RadonReducers::AverageGeometricMean
+        | RadonReducers::Sum => Some(("WIP0029", ActiveWips::wip0029)),
+        RadonReducers::HashConcatenate => Some(("WIP0019", ActiveWips::wip0019)),
+        _ => None,
+    }
+}
+
+fn check_availability(
+    code: u8,
+    activation: Option<WipActivation>,
+    active_wips: &ActiveWips,
+) -> Result<(), RadError> {
+    match activation {
+        Some((wip, is_active)) if !is_active(active_wips) => {
+            Err(RadError::OperatorNotActivated { code, wip })
+        }
+        _ => Ok(()),
+    }
+}
+
 pub fn unpack_radon_call(packed_call: &Value) -> Result<RadonCall, RadError> {
     match packed_call {
         Value::Array(array) => unpack_compound_call(array),
@@ -222,6 +361,8 @@ pub fn create_radon_script_from_filters_and_reducer(
             RadonFilters::try_from(u8::try_from(filter_op).map_err(|_| unknown_filter(filter_op))?)
                 .map_err(|_| unknown_filter(filter_op))?;
 
+        check_availability(rad_filter as u8, filter_activation(rad_filter), active_wips)?;
+
         // TODO: Update with more filters
         match rad_filter {
             RadonFilters::DeviationStandard | RadonFilters::Mode => {}
@@ -251,22 +392,17 @@ pub fn create_radon_script_from_filters_and_reducer(
         u8::try_from(reducer).map_err(|_| unknown_reducer(i128::from(reducer)))?,
     )
     .map_err(|_| unknown_reducer(i128::from(reducer)))?;
+    check_availability(
+        rad_reducer as u8,
+        reducer_activation(rad_reducer),
+        active_wips,
+    )?;
     match rad_reducer {
-        RadonReducers::AverageMean | RadonReducers::Mode => {}
-        RadonReducers::AverageMedian => {
-            if !active_wips.wip0017() {
-                return Err(RadError::UnsupportedReducerInAT {
-                    operator: rad_reducer as u8,
-                });
-            }
-        }
-        RadonReducers::HashConcatenate => {
-            if !active_wips.wip0019() {
-                return Err(RadError::UnsupportedReducerInAT {
-                    operator: rad_reducer as u8,
-                });
-            }
-        }
+        RadonReducers::AverageMean
+        | RadonReducers::Mode
+        | RadonReducers::AverageMedian
+        | RadonReducers::HashConcatenate
+        | RadonReducers::AverageMeanWeighted => {}
         _ => {
             return Err(RadError::UnsupportedReducerInAT {
                 operator: rad_reducer as u8,
@@ -284,6 +420,8 @@ pub fn create_radon_script_from_filters_and_reducer(
 mod tests {
     use std::collections::BTreeMap;
 
+    use witnet_data_structures::radon_report::ScriptLimits;
+
     use crate::current_active_wips;
 
     use super::*;
@@ -554,6 +692,197 @@ mod tests {
         );
     }
 
+    /// A script made of `depth` nested `ArrayMap` calls, along with an input that has enough levels
+    /// of nested arrays for it.
+    fn nested_map_script(depth: usize) -> (RadonTypes, RadonScript) {
+        use crate::types::{array::RadonArray, integer::RadonInteger};
+
+        let mut input = RadonTypes::from(RadonInteger::from(1));
+        let mut script = Value::Array(vec![Value::Integer(RadonOpCodes::Identity as i128)]);
+        for _ in 0..depth {
+            input = RadonTypes::from(RadonArray::from(vec![input]));
+            script = Value::Array(vec![Value::Array(vec![
+                Value::Integer(RadonOpCodes::ArrayMap as i128),
+                script,
+            ])]);
+        }
+
+        (input, unpack_subscript(&script).unwrap())
+    }
+
+    fn is_too_complex(report: &RadonReport<RadonTypes>) -> bool {
+        matches!(
+            &report.result,
+            RadonTypes::RadonError(error) if error.inner() == &RadError::ScriptTooComplex
+        )
+    }
+
+    #[test]
+    fn test_execute_radon_script_subscript_depth_limit() {
+        let execute = |depth: usize| {
+            let (input, script) = nested_map_script(depth);
+            let mut context = ReportContext {
+                script_limits: ScriptLimits {
+                    max_subscript_depth: 3,
+                    ..ScriptLimits::default()
+                },
+                ..ReportContext::default()
+            };
+            let report = execute_radon_script(
+                input.clone(),
+                &script,
+                &mut context,
+                RadonScriptExecutionSettings::default(),
+            )
+            .unwrap();
+            // The context can be reused once the script is done
+            assert_eq!(context.script_depth, 0);
+
+            (input, report)
+        };
+
+        let (input, report) = execute(3);
+        assert_eq!(report.result, input);
+
+        let (_, report) = execute(4);
+        assert!(is_too_complex(&report));
+
+        // The default limits also stop pathologically nested scripts
+        let (input, script) = nested_map_script(100);
+        let report = execute_radon_script(
+            input,
+            &script,
+            &mut ReportContext::default(),
+            RadonScriptExecutionSettings::default(),
+        )
+        .unwrap();
+        assert!(is_too_complex(&report));
+    }
+
+    #[test]
+    fn test_execute_radon_script_limits_require_wip0029() {
+        // Deeper than the default maximum depth, and with more calls than allowed below
+        let (input, script) = nested_map_script(20);
+        let execute = |active_wips| {
+            let mut context = ReportContext {
+                active_wips: Some(active_wips),
+                script_limits: ScriptLimits {
+                    max_calls: 10,
+                    ..ScriptLimits::default()
+                },
+                ..ReportContext::default()
+            };
+
+            execute_radon_script(
+                input.clone(),
+                &script,
+                &mut context,
+                RadonScriptExecutionSettings::default(),
+            )
+            .unwrap()
+        };
+
+        // Scripts that were fine before WIP0029 still are, even if they exceed the limits
+        let report = execute(current_active_wips());
+        assert_eq!(report.result, input);
+
+        let report = execute(crate::all_wips_active());
+        assert!(is_too_complex(&report));
+    }
+
+    #[test]
+    fn test_execute_radon_script_calls_limit() {
+        use crate::types::{array::RadonArray, integer::RadonInteger};
+
+        let input = RadonTypes::from(RadonArray::from(vec![
+            RadonTypes::from(RadonInteger::from(
+                1
+            ));
+            10
+        ]));
+        // One call in the script, plus one call in the subscript for each of the 10 items
+        let script = vec![(
+            RadonOpCodes::ArrayMap,
+            Some(vec![Value::Array(vec![Value::Integer(
+                RadonOpCodes::Identity as i128,
+            )])]),
+        )];
+        let execute = |max_calls: usize| {
+            let mut context = ReportContext {
+                script_limits: ScriptLimits {
+                    max_calls,
+                    ..ScriptLimits::default()
+                },
+                ..ReportContext::default()
+            };
+            let report = execute_radon_script(
+                input.clone(),
+                &script,
+                &mut context,
+                RadonScriptExecutionSettings::default(),
+            )
+            .unwrap();
+
+            (report, context.executed_calls)
+        };
+
+        let (report, executed_calls) = execute(11);
+        assert_eq!(report.result, input);
+        assert_eq!(executed_calls, 11);
+
+        let (report, _) = execute(10);
+        assert!(is_too_complex(&report));
+    }
+
+    #[test]
+    fn test_radon_report_diff() {
+        use crate::types::{float::RadonFloat, string::RadonString};
+        use witnet_data_structures::radon_report::ReportDiff;
+
+        let input = RadonTypes::from(RadonString::from(r#"{"price":1.5,"last":1.25}"#));
+        let execute = |key: &str| {
+            let script = vec![
+                (RadonOpCodes::StringParseJSONMap, None),
+                (
+                    RadonOpCodes::MapGetFloat,
+                    Some(vec![Value::Text(String::from(key))]),
+                ),
+                (RadonOpCodes::FloatRound, None),
+            ];
+
+            execute_radon_script(
+                input.clone(),
+                &unpack_subscript(&script).unwrap(),
+                &mut ReportContext::default(),
+                RadonScriptExecutionSettings::enable_all(),
+            )
+            .unwrap()
+        };
+
+        let price = execute("price");
+        let last = execute("last");
+        assert_eq!(price.diff(&price), ReportDiff::Equal);
+        // The reports diverge at the `MapGetFloat` call, even if later calls also differ
+        assert_eq!(
+            price.diff(&last),
+            ReportDiff::Call {
+                call_index: 1,
+                left: RadonTypes::from(RadonFloat::from(1.5)),
+                right: RadonTypes::from(RadonFloat::from(1.25)),
+            }
+        );
+
+        // Without partial results, only the final results can be compared
+        let without_partial_results = |report: &RadonReport<RadonTypes>| RadonReport {
+            partial_results: None,
+            ..report.clone()
+        };
+        assert!(matches!(
+            without_partial_results(&price).diff(&last),
+            ReportDiff::Result { .. }
+        ));
+    }
+
     #[test]
     fn test_unpack_radon_script() {
         let cbor_vec = Value::Array(vec![
@@ -587,6 +916,43 @@ mod tests {
         assert_eq!(output, expected)
     }
 
+    #[test]
+    fn test_unpack_radon_script_truncated() {
+        let cbor_vec = Value::Array(vec![
+            Value::Integer(RadonOpCodes::StringParseJSONMap as i128),
+            Value::Array(vec![
+                Value::Integer(RadonOpCodes::MapGetFloat as i128),
+                Value::Text(String::from("temp")),
+            ]),
+        ]);
+        let packed = serde_cbor::to_vec(&cbor_vec).unwrap();
+        let truncated = &packed[..packed.len() - 2];
+
+        let error = unpack_radon_script(truncated).unwrap_err();
+
+        match error {
+            RadError::ScriptDecode { position, .. } => {
+                assert!(position > 0 && position <= truncated.len() as u64)
+            }
+            other => panic!("Expected ScriptDecode error, got {:?}", other),
+        }
+        assert!(unpack_radon_script(&[]).is_err());
+    }
+
+    #[test]
+    fn test_unpack_map_get_or_default() {
+        // [[0x6B, "a", 0]]
+        let packed = [0x81, 0x83, 0x18, 0x6B, 0x61, b'a', 0x00];
+
+        assert_eq!(
+            unpack_radon_script(&packed).unwrap(),
+            vec![(
+                RadonOpCodes::MapGetOrDefault,
+                Some(vec![Value::Text(String::from("a")), Value::Integer(0)])
+            )]
+        );
+    }
+
     #[test]
     fn test_create_radon_script() {
         let expected = vec![
@@ -671,6 +1037,19 @@ mod tests {
         };
         assert_eq!(output, expected);
 
+        // Sum is only meant for scripts, as its result would depend on the number of witnesses
+        let output = create_radon_script_from_filters_and_reducer(
+            filters.as_slice(),
+            RadonReducers::Sum as u32,
+            &crate::all_wips_active(),
+        )
+        .unwrap_err();
+
+        let expected = RadError::UnsupportedReducerInAT {
+            operator: RadonReducers::Sum as u8,
+        };
+        assert_eq!(output, expected);
+
         let output = create_radon_script_from_filters_and_reducer(
             filters.as_slice(),
             99,
@@ -681,4 +1060,126 @@ mod tests {
         let expected = RadError::UnknownReducer { code: 99 };
         assert_eq!(output, expected);
     }
+
+    #[test]
+    fn test_create_radon_script_reducer_not_activated() {
+        let mut active_wips = ActiveWips::default();
+        active_wips.set_epoch(100);
+        active_wips.insert_wip("WIP0017-0018-0019", 200);
+
+        let output = create_radon_script_from_filters_and_reducer(
+            &[],
+            RadonReducers::AverageMedian as u32,
+            &active_wips,
+        )
+        .unwrap_err();
+
+        let expected = RadError::OperatorNotActivated {
+            code: RadonReducers::AverageMedian as u8,
+            wip: "WIP0017",
+        };
+        assert_eq!(output, expected);
+
+        active_wips.set_epoch(200);
+        let output = create_radon_script_from_filters_and_reducer(
+            &[],
+            RadonReducers::AverageMedian as u32,
+            &active_wips,
+        );
+        assert!(output.is_ok());
+    }
+
+    #[test]
+    fn test_create_radon_script_geometric_mean_wip0029() {
+        let reducer = RadonReducers::AverageGeometricMean as u32;
+        let output =
+            create_radon_script_from_filters_and_reducer(&[], reducer, &current_active_wips())
+                .unwrap_err();
+
+        let expected = RadError::OperatorNotActivated {
+            code: RadonReducers::AverageGeometricMean as u8,
+            wip: "WIP0029",
+        };
+        assert_eq!(output, expected);
+
+        // Even once activated, the geometric mean is not deterministic enough for aggregation and
+        // tally, and it is only available in retrieval scripts
+        let output =
+            create_radon_script_from_filters_and_reducer(&[], reducer, &crate::all_wips_active())
+                .unwrap_err();
+        let expected = RadError::UnsupportedReducerInAT {
+            operator: RadonReducers::AverageGeometricMean as u8,
+        };
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_unpack_radon_script_operator_not_activated() {
+        let cbor_vec = Value::Array(vec![
+            Value::Integer(RadonOpCodes::StringParseXMLMap as i128),
+            Value::Array(vec![
+                Value::Integer(RadonOpCodes::MapGetString as i128),
+                Value::Text(String::from("price")),
+            ]),
+        ]);
+        let packed = serde_cbor::to_vec(&cbor_vec).unwrap();
+
+        let mut active_wips = ActiveWips::default();
+        active_wips.set_epoch(100);
+        active_wips.insert_wip("WIP0020-0021", 200);
+
+        let output = unpack_radon_script_with_active_wips(&packed, &active_wips).unwrap_err();
+        let expected = RadError::OperatorNotActivated {
+            code: RadonOpCodes::StringParseXMLMap as u8,
+            wip: "WIP0020",
+        };
+        assert_eq!(output, expected);
+
+        let output = unpack_radon_script_with_active_wips(&packed, &current_active_wips());
+        assert!(output.is_ok());
+    }
+
+    #[test]
+    fn test_unpack_radon_script_equal_wip0029() {
+        let cbor_vec = Value::Array(vec![
+            Value::Integer(RadonOpCodes::StringParseJSONMap as i128),
+            Value::Array(vec![
+                Value::Integer(RadonOpCodes::MapGetString as i128),
+                Value::Text(String::from("currency")),
+            ]),
+            Value::Array(vec![
+                Value::Integer(RadonOpCodes::NotEqual as i128),
+                Value::Text(String::from("USD")),
+            ]),
+        ]);
+        let packed = serde_cbor::to_vec(&cbor_vec).unwrap();
+
+        let output =
+            unpack_radon_script_with_active_wips(&packed, &current_active_wips()).unwrap_err();
+        let expected = RadError::OperatorNotActivated {
+            code: RadonOpCodes::NotEqual as u8,
+            wip: "WIP0029",
+        };
+        assert_eq!(output, expected);
+
+        let output = unpack_radon_script_with_active_wips(&packed, &crate::all_wips_active());
+        assert!(output.is_ok());
+    }
+
+    #[test]
+    fn test_check_radon_script_availability_in_subscript() {
+        let script = vec![(
+            RadonOpCodes::ArrayMap,
+            Some(vec![Value::Array(vec![Value::Integer(
+                RadonOpCodes::StringParseXMLMap as i128,
+            )])]),
+        )];
+
+        let output = check_radon_script_availability(&script, &ActiveWips::default()).unwrap_err();
+        let expected = RadError::OperatorNotActivated {
+            code: RadonOpCodes::StringParseXMLMap as u8,
+            wip: "WIP0020",
+        };
+        assert_eq!(output, expected);
+    }
 }
@@ -8,7 +8,7 @@ use witnet_data_structures::{chain::tapi::ActiveWips, radon_report::ReportContex
 
 use crate::{
     error::RadError,
-    operators::{array as array_operators, identity, Operable, RadonOpCodes},
+    operators::{array as array_operators, get_path, identity, Operable, RadonOpCodes},
     script::RadonCall,
     types::{
         boolean::RadonBoolean, bytes::RadonBytes, float::RadonFloat, integer::RadonInteger,
@@ -137,6 +137,8 @@ impl Operable for RadonArray {
 
         match call {
             (RadonOpCodes::Identity, None) => identity(RadonTypes::from(self.clone())),
+            (RadonOpCodes::Length, None) => Ok(RadonInteger::from(self.value.len() as i128).into()),
+            (RadonOpCodes::GetPath, Some(args)) => get_path(RadonTypes::from(self.clone()), args),
             (RadonOpCodes::ArrayCount, None) => Ok(array_operators::count(self).into()),
             (RadonOpCodes::ArrayGetArray, Some(args)) => {
                 array_operators::get::<RadonArray, _>(self, args).map(RadonTypes::from)
@@ -169,6 +171,16 @@ impl Operable for RadonArray {
             (RadonOpCodes::ArrayMap, Some(args)) => array_operators::map(self, args, context),
             (RadonOpCodes::ArrayReduce, Some(args)) => array_operators::reduce(self, args, context),
             (RadonOpCodes::ArraySort, Some(args)) => array_operators::sort(self, args, context),
+            (RadonOpCodes::ArrayStats, None) => array_operators::stats(self),
+            (RadonOpCodes::ArrayMinLength, Some(args)) => {
+                array_operators::min_length(self, args).map(RadonTypes::from)
+            }
+            (RadonOpCodes::ArrayFlatten, Some(args)) => {
+                array_operators::flatten(self, args).map(RadonTypes::from)
+            }
+            (RadonOpCodes::ArrayGroupBy, Some(args)) => {
+                array_operators::group_by(self, args).map(RadonTypes::from)
+            }
             (op_code, args) => Err(RadError::UnsupportedOperator {
                 input_type: RADON_ARRAY_TYPE_NAME.to_string(),
                 operator: op_code.to_string(),
@@ -5,7 +5,7 @@ use serde_cbor::value::{from_value, Value};
 
 use crate::{
     error::RadError,
-    operators::{boolean as boolean_operators, identity, Operable, RadonOpCodes},
+    operators::{boolean as boolean_operators, equal, identity, not_equal, Operable, RadonOpCodes},
     script::RadonCall,
     types::{RadonType, RadonTypes},
 };
@@ -79,6 +79,12 @@ impl Operable for RadonBoolean {
     fn operate(&self, call: &RadonCall) -> Result<RadonTypes, RadError> {
         match call {
             (RadonOpCodes::Identity, None) => identity(RadonTypes::from(self.clone())),
+            (RadonOpCodes::Equal, Some(args)) => {
+                equal(&RadonTypes::from(self.clone()), args).map(RadonTypes::from)
+            }
+            (RadonOpCodes::NotEqual, Some(args)) => {
+                not_equal(&RadonTypes::from(self.clone()), args).map(RadonTypes::from)
+            }
             (RadonOpCodes::BooleanNegate, None) => Ok(boolean_operators::negate(self).into()),
             (RadonOpCodes::BooleanAsString, None) => boolean_operators::to_string(self.clone())
                 .map(RadonTypes::from)
@@ -1,8 +1,8 @@
 use crate::{
     error::RadError,
-    operators::{bytes as bytes_operators, identity, Operable, RadonOpCodes},
+    operators::{bytes as bytes_operators, equal, identity, not_equal, Operable, RadonOpCodes},
     script::RadonCall,
-    types::{RadonType, RadonTypes},
+    types::{integer::RadonInteger, RadonType, RadonTypes},
 };
 use serde_cbor::value::Value;
 use std::{
@@ -84,12 +84,21 @@ impl Operable for RadonBytes {
         match call {
             // Identity
             (RadonOpCodes::Identity, None) => identity(RadonTypes::from(self.clone())),
+            (RadonOpCodes::Length, None) => Ok(RadonInteger::from(self.value.len() as i128).into()),
+            (RadonOpCodes::Equal, Some(args)) => {
+                equal(&RadonTypes::from(self.clone()), args).map(RadonTypes::from)
+            }
+            (RadonOpCodes::NotEqual, Some(args)) => {
+                not_equal(&RadonTypes::from(self.clone()), args).map(RadonTypes::from)
+            }
             (RadonOpCodes::BytesAsString, None) => bytes_operators::to_string(self)
                 .map(RadonTypes::from)
                 .map_err(Into::into),
             (RadonOpCodes::BytesHash, Some(args)) => bytes_operators::hash(self, args.as_slice())
                 .map(RadonTypes::from)
                 .map_err(Into::into),
+            (RadonOpCodes::HashKeccak256, None) => Ok(bytes_operators::keccak256(self).into()),
+            (RadonOpCodes::HashHash160, None) => Ok(bytes_operators::hash160(self).into()),
             // Unsupported / unimplemented
             (op_code, args) => Err(RadError::UnsupportedOperator {
                 input_type: RADON_BYTES_TYPE_NAME.to_string(),
@@ -5,11 +5,11 @@ use std::{
 };
 
 use serde_cbor::value::Value;
-use witnet_data_structures::radon_report::ReportContext;
+use witnet_data_structures::{chain::tapi::ActiveWips, radon_report::ReportContext};
 
 use crate::{
     error::RadError,
-    operators::{float as float_operators, identity, Operable, RadonOpCodes},
+    operators::{equal, float as float_operators, identity, not_equal, Operable, RadonOpCodes},
     script::RadonCall,
     types::{string::RadonString, RadonType, RadonTypes},
 };
@@ -110,9 +110,29 @@ impl TryFrom<&str> for RadonFloat {
 
 impl Operable for RadonFloat {
     fn operate(&self, call: &RadonCall) -> Result<RadonTypes, RadError> {
+        self.operate_in_context(call, &mut ReportContext::default())
+    }
+
+    fn operate_in_context(
+        &self,
+        call: &RadonCall,
+        context: &mut ReportContext<RadonTypes>,
+    ) -> Result<RadonTypes, RadError> {
+        let wip0029 = context
+            .active_wips
+            .as_ref()
+            .map(ActiveWips::wip0029)
+            .unwrap_or(true);
+
         match call {
             // Identity
             (RadonOpCodes::Identity, None) => identity(RadonTypes::from(self.clone())),
+            (RadonOpCodes::Equal, Some(args)) => {
+                equal(&RadonTypes::from(self.clone()), args).map(RadonTypes::from)
+            }
+            (RadonOpCodes::NotEqual, Some(args)) => {
+                not_equal(&RadonTypes::from(self.clone()), args).map(RadonTypes::from)
+            }
             (RadonOpCodes::FloatAbsolute, None) => {
                 Ok(RadonTypes::from(float_operators::absolute(self)))
             }
@@ -143,9 +163,18 @@ impl Operable for RadonFloat {
                 float_operators::power(self, args.as_slice()).map(Into::into)
             }
             (RadonOpCodes::FloatRound, None) => Ok(RadonTypes::from(float_operators::round(self))),
+            (RadonOpCodes::FloatRound, Some(args)) if wip0029 => {
+                float_operators::round_with_mode(self, args).map(Into::into)
+            }
             (RadonOpCodes::FloatTruncate, None) => {
                 Ok(RadonTypes::from(float_operators::truncate(self)))
             }
+            (RadonOpCodes::FloatToString, Some(args)) => {
+                float_operators::to_fixed(self, args).map(Into::into)
+            }
+            (RadonOpCodes::FloatClamp, Some(args)) => {
+                float_operators::clamp(self, args).map(Into::into)
+            }
             // Unsupported / unimplemented
             (op_code, args) => Err(RadError::UnsupportedOperator {
                 input_type: RADON_FLOAT_TYPE_NAME.to_string(),
@@ -154,14 +183,6 @@ impl Operable for RadonFloat {
             }),
         }
     }
-
-    fn operate_in_context(
-        &self,
-        call: &RadonCall,
-        _context: &mut ReportContext<RadonTypes>,
-    ) -> Result<RadonTypes, RadError> {
-        self.operate(call)
-    }
 }
 
 impl fmt::Display for RadonFloat {
@@ -180,6 +201,32 @@ fn test_operate_unimplemented() {
     assert!(result.is_err());
 }
 
+#[test]
+fn test_operate_round_with_mode_requires_wip0029() {
+    use witnet_data_structures::chain::tapi::{all_wips_active, current_active_wips};
+
+    use crate::types::integer::RadonInteger;
+
+    let input = RadonFloat::from(2.5);
+    let call = (RadonOpCodes::FloatRound, Some(vec![Value::Integer(1)]));
+    let operate_with = |active_wips| {
+        let mut context = ReportContext {
+            active_wips: Some(active_wips),
+            ..ReportContext::default()
+        };
+        input.operate_in_context(&call, &mut context)
+    };
+
+    assert_eq!(
+        operate_with(all_wips_active()),
+        Ok(RadonTypes::from(RadonInteger::from(2)))
+    );
+    assert!(matches!(
+        operate_with(current_active_wips()),
+        Err(RadError::UnsupportedOperator { .. })
+    ));
+}
+
 #[test]
 fn test_from_vector() {
     let input: &[u8] = &[251, 64, 9, 33, 251, 84, 68, 45, 24]; // 3.141592653589793
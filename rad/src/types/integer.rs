@@ -8,7 +8,7 @@ use serde_cbor::value::Value;
 
 use crate::{
     error::RadError,
-    operators::{identity, integer as integer_operators, Operable, RadonOpCodes},
+    operators::{equal, identity, integer as integer_operators, not_equal, Operable, RadonOpCodes},
     script::RadonCall,
     types::{string::RadonString, RadonType, RadonTypes},
 };
@@ -102,6 +102,12 @@ impl Operable for RadonInteger {
         match call {
             // Identity
             (RadonOpCodes::Identity, None) => identity(RadonTypes::from(self.clone())),
+            (RadonOpCodes::Equal, Some(args)) => {
+                equal(&RadonTypes::from(self.clone()), args).map(RadonTypes::from)
+            }
+            (RadonOpCodes::NotEqual, Some(args)) => {
+                not_equal(&RadonTypes::from(self.clone()), args).map(RadonTypes::from)
+            }
             (RadonOpCodes::IntegerAbsolute, None) => integer_operators::absolute(self)
                 .map(RadonTypes::from)
                 .map_err(Into::into),
@@ -129,6 +135,24 @@ impl Operable for RadonInteger {
             (RadonOpCodes::IntegerPower, Some(args)) => {
                 integer_operators::power(self, args.as_slice()).map(Into::into)
             }
+            (RadonOpCodes::IntegerBitAnd, Some(args)) => {
+                integer_operators::bit_and(self, args.as_slice()).map(Into::into)
+            }
+            (RadonOpCodes::IntegerBitOr, Some(args)) => {
+                integer_operators::bit_or(self, args.as_slice()).map(Into::into)
+            }
+            (RadonOpCodes::IntegerShiftLeft, Some(args)) => {
+                integer_operators::shift_left(self, args.as_slice()).map(Into::into)
+            }
+            (RadonOpCodes::IntegerShiftRight, Some(args)) => {
+                integer_operators::shift_right(self, args.as_slice()).map(Into::into)
+            }
+            (RadonOpCodes::IntegerToBytes, Some(args)) => {
+                integer_operators::to_bytes(self, args.as_slice()).map(Into::into)
+            }
+            (RadonOpCodes::IntegerClamp, Some(args)) => {
+                integer_operators::clamp(self, args.as_slice()).map(Into::into)
+            }
             // Unsupported / unimplemented
             (op_code, args) => Err(RadError::UnsupportedOperator {
                 input_type: RADON_INTEGER_TYPE_NAME.to_string(),
@@ -9,7 +9,7 @@ use witnet_data_structures::{chain::tapi::ActiveWips, radon_report::ReportContex
 
 use crate::{
     error::RadError,
-    operators::{identity, map as map_operators, Operable, RadonOpCodes},
+    operators::{get_path, identity, map as map_operators, Operable, RadonOpCodes},
     script::RadonCall,
     types::{
         array::RadonArray, boolean::RadonBoolean, bytes::RadonBytes, float::RadonFloat,
@@ -132,6 +132,8 @@ impl Operable for RadonMap {
 
         match call {
             (RadonOpCodes::Identity, None) => identity(RadonTypes::from(self.clone())),
+            (RadonOpCodes::Length, None) => Ok(RadonInteger::from(self.value.len() as i128).into()),
+            (RadonOpCodes::GetPath, Some(args)) => get_path(RadonTypes::from(self.clone()), args),
             (RadonOpCodes::MapGetArray, Some(args)) => {
                 map_operators::get::<RadonArray, _>(self, args.as_slice()).map(RadonTypes::from)
             }
@@ -159,8 +161,14 @@ impl Operable for RadonMap {
             (RadonOpCodes::MapGetString, Some(args)) => {
                 map_operators::get::<RadonString, _>(self, args.as_slice()).map(RadonTypes::from)
             }
+            (RadonOpCodes::MapGetOrDefault, Some(args)) => {
+                map_operators::get_or_default(self, args)
+            }
             (RadonOpCodes::MapKeys, None) => Ok(RadonTypes::from(map_operators::keys(self))),
             (RadonOpCodes::MapValues, None) => Ok(RadonTypes::from(map_operators::values(self))),
+            (RadonOpCodes::MapUnion, Some(args)) => {
+                map_operators::union(self, args, context).map(RadonTypes::from)
+            }
             (op_code, args) => Err(RadError::UnsupportedOperator {
                 input_type: RADON_MAP_TYPE_NAME.to_string(),
                 operator: op_code.to_string(),
@@ -95,6 +95,93 @@ impl RadonTypes {
         8
     }
 
+    /// Convert into the natural JSON representation of the inner value, as opposed to the
+    /// `Serialize` implementation, which tags every value with the name of its type.
+    ///
+    /// Bytes are represented as hexadecimal strings, and integers that do not fit into 64 bits are
+    /// represented as decimal strings. Floats that cannot be represented in JSON (i.e. infinities
+    /// and NaN) become `null`. Errors are represented as an object with a single `error` key, which
+    /// contains the error code, if any, and the error message.
+    pub fn to_json_value(&self) -> JsonValue {
+        match self {
+            RadonTypes::Array(array) => JsonValue::Array(
+                array
+                    .value()
+                    .iter()
+                    .map(RadonTypes::to_json_value)
+                    .collect(),
+            ),
+            RadonTypes::Boolean(boolean) => JsonValue::Bool(boolean.value()),
+            RadonTypes::Bytes(bytes) => JsonValue::String(hex::encode(bytes.value())),
+            RadonTypes::Float(float) => serde_json::Number::from_f64(float.value())
+                .map_or(JsonValue::Null, JsonValue::Number),
+            RadonTypes::Integer(integer) => {
+                let integer = integer.value();
+                if let Ok(integer) = i64::try_from(integer) {
+                    JsonValue::from(integer)
+                } else if let Ok(integer) = u64::try_from(integer) {
+                    JsonValue::from(integer)
+                } else {
+                    JsonValue::String(integer.to_string())
+                }
+            }
+            RadonTypes::Map(map) => JsonValue::Object(
+                map.value()
+                    .iter()
+                    .map(|(key, value)| (key.clone(), value.to_json_value()))
+                    .collect(),
+            ),
+            RadonTypes::RadonError(error) => {
+                let error = error.inner();
+                serde_json::json!({
+                    "error": {
+                        "code": error.try_into_error_code().ok().map(u8::from),
+                        "message": error.to_string(),
+                    }
+                })
+            }
+            RadonTypes::String(string) => JsonValue::String(string.value()),
+        }
+    }
+
+    /// Like `RadonTypes::try_from(Value)`, but rejecting any values that could not be encoded
+    /// back in a deterministic way, instead of silently dropping or coercing them.
+    ///
+    /// Floats must be finite, map keys must be strings, and nulls and tagged values (including
+    /// bignums, i.e. integers that do not fit into an `i128`) are not allowed anywhere. Unlike the
+    /// lenient conversion, a map entry whose value cannot be converted fails the whole conversion.
+    pub fn try_from_strict(value: Value) -> Result<RadonTypes, RadError> {
+        match value {
+            Value::Array(items) => items
+                .into_iter()
+                .map(RadonTypes::try_from_strict)
+                .collect::<Result<Vec<_>, _>>()
+                .map(|items| RadonArray::from(items).into()),
+            Value::Float(float) if !float.is_finite() => Err(RadError::NonFiniteFloat {
+                value: float.to_string(),
+            }),
+            Value::Map(entries) => entries
+                .into_iter()
+                .map(|(key, value)| match key {
+                    Value::Text(key) => Ok((key, RadonTypes::try_from_strict(value)?)),
+                    key => Err(RadError::NonStringMapKey {
+                        key: format!("{:?}", key),
+                    }),
+                })
+                .collect::<Result<BTreeMap<_, _>, _>>()
+                .map(|entries| RadonMap::from(entries).into()),
+            Value::Bool(_)
+            | Value::Bytes(_)
+            | Value::Float(_)
+            | Value::Integer(_)
+            | Value::Text(_) => RadonTypes::try_from(value),
+            _ => Err(RadError::Decode {
+                from: "serde_cbor::Value",
+                to: "RadonTypes",
+            }),
+        }
+    }
+
     pub fn as_operable(&self) -> &dyn Operable {
         match self {
             RadonTypes::Array(inner) => inner,
@@ -143,6 +230,18 @@ impl TypeLike for RadonTypes {
         Vec::<u8>::try_from((*self).clone())
     }
 
+    fn encode_with_active_wips(&self, active_wips: &ActiveWips) -> Result<Vec<u8>, Self::Error> {
+        match self {
+            RadonTypes::RadonError(radon_error) => radon_error
+                .encode_tagged_bytes_with_active_wips(active_wips)
+                .map_err(|_| RadError::Encode {
+                    from: RadonTypes::radon_type_name(self),
+                    to: "Vec<u8>",
+                }),
+            _ => self.encode(),
+        }
+    }
+
     /// Eases interception of RADON errors (errors that we want to commit, reveal and tally) so
     /// they can be handled as valid `RadonTypes::RadonError` values, which are subject to
     /// commitment, revealing, tallying, etc.
@@ -551,7 +650,7 @@ pub fn serial_iter_decode<T>(
 mod tests {
     use std::collections::HashSet;
 
-    use witnet_data_structures::chain::tapi::all_wips_active;
+    use witnet_data_structures::{chain::tapi::all_wips_active, radon_error::RadonErrors};
 
     use super::*;
 
@@ -793,4 +892,120 @@ mod tests {
             .into()
         );
     }
+    #[test]
+    fn test_try_from_strict() {
+        let value = Value::Map(BTreeMap::from([
+            (
+                Value::Text(String::from("prices")),
+                Value::Array(vec![Value::Float(1.5), Value::Integer(2)]),
+            ),
+            (
+                Value::Text(String::from("symbol")),
+                Value::Text(String::from("WIT")),
+            ),
+        ]));
+
+        assert_eq!(
+            RadonTypes::try_from_strict(value.clone()),
+            RadonTypes::try_from(value)
+        );
+    }
+
+    #[test]
+    fn test_try_from_strict_nan_float() {
+        assert_eq!(
+            RadonTypes::try_from_strict(Value::Float(f64::NAN)),
+            Err(RadError::NonFiniteFloat {
+                value: String::from("NaN")
+            })
+        );
+        // Also when nested
+        assert!(matches!(
+            RadonTypes::try_from_strict(Value::Array(vec![Value::Float(f64::INFINITY)])),
+            Err(RadError::NonFiniteFloat { .. })
+        ));
+        // The lenient conversion lets them through
+        assert!(RadonTypes::try_from(Value::Float(f64::NAN)).is_ok());
+    }
+
+    #[test]
+    fn test_try_from_strict_integer_keyed_map() {
+        let value = Value::Map(BTreeMap::from([
+            (Value::Integer(1), Value::Text(String::from("one"))),
+            (Value::Text(String::from("two")), Value::Integer(2)),
+        ]));
+
+        assert_eq!(
+            RadonTypes::try_from_strict(value),
+            Err(RadError::NonStringMapKey {
+                key: String::from("Integer(1)")
+            })
+        );
+    }
+
+    #[test]
+    fn test_to_json_value_round_trip() {
+        let map = RadonTypes::from(RadonMap::from(BTreeMap::from([
+            (
+                String::from("price"),
+                RadonTypes::from(RadonFloat::from(1.5)),
+            ),
+            (
+                String::from("volume"),
+                RadonTypes::from(RadonInteger::from(7)),
+            ),
+        ])));
+        let values = vec![
+            RadonTypes::from(RadonArray::from(vec![
+                RadonTypes::from(RadonBoolean::from(true)),
+                RadonTypes::from(RadonString::from("WIT")),
+            ])),
+            RadonTypes::from(RadonBoolean::from(false)),
+            RadonTypes::from(RadonFloat::from(-0.25)),
+            RadonTypes::from(RadonInteger::from(-42)),
+            RadonTypes::from(RadonInteger::from(i128::from(u64::MAX))),
+            map,
+            RadonTypes::from(RadonString::from("hello")),
+        ];
+
+        for value in values {
+            let json = value.to_json_value();
+
+            assert_eq!(RadonTypes::try_from(json).unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn test_to_json_value_natural_representation() {
+        assert_eq!(
+            RadonTypes::from(RadonBytes::from(vec![0x01, 0xab])).to_json_value(),
+            serde_json::json!("01ab")
+        );
+        assert_eq!(
+            RadonTypes::from(RadonInteger::from(7)).to_json_value(),
+            serde_json::json!(7)
+        );
+        assert_eq!(
+            RadonTypes::from(RadonInteger::from(i128::MAX)).to_json_value(),
+            serde_json::json!(i128::MAX.to_string())
+        );
+        assert_eq!(
+            RadonTypes::from(RadonFloat::from(f64::NAN)).to_json_value(),
+            JsonValue::Null
+        );
+        assert_eq!(
+            RadonTypes::from(RadonString::from("hello")).to_json_value(),
+            serde_json::json!("hello")
+        );
+        assert_eq!(
+            RadonTypes::RadonError(RadonError::try_from(RadError::RetrieveTimeout).unwrap())
+                .to_json_value(),
+            serde_json::json!({
+                "error": {
+                    "code": u8::from(RadonErrors::RetrieveTimeout),
+                    "message": RadError::RetrieveTimeout.to_string(),
+                }
+            })
+        );
+    }
 }
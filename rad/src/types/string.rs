@@ -8,9 +8,12 @@ use witnet_data_structures::{chain::tapi::ActiveWips, radon_report::ReportContex
 
 use crate::{
     error::RadError,
-    operators::{identity, string as string_operators, Operable, RadonOpCodes},
+    operators::{
+        bytes as bytes_operators, equal, identity, not_equal, string as string_operators, Operable,
+        RadonOpCodes,
+    },
     script::RadonCall,
-    types::{RadonType, RadonTypes},
+    types::{bytes::RadonBytes, integer::RadonInteger, RadonType, RadonTypes},
 };
 
 const RADON_STRING_TYPE_NAME: &str = "RadonString";
@@ -102,9 +105,23 @@ impl Operable for RadonString {
             .as_ref()
             .map(ActiveWips::wip0024)
             .unwrap_or(true);
+        let wip0029 = context
+            .active_wips
+            .as_ref()
+            .map(ActiveWips::wip0029)
+            .unwrap_or(true);
 
         match call {
             (RadonOpCodes::Identity, None) => identity(RadonTypes::from(self.clone())),
+            (RadonOpCodes::Length, None) => {
+                Ok(RadonInteger::from(self.value.chars().count() as i128).into())
+            }
+            (RadonOpCodes::Equal, Some(args)) => {
+                equal(&RadonTypes::from(self.clone()), args).map(RadonTypes::from)
+            }
+            (RadonOpCodes::NotEqual, Some(args)) => {
+                not_equal(&RadonTypes::from(self.clone()), args).map(RadonTypes::from)
+            }
             (RadonOpCodes::StringAsFloat, args) => if wip0024 {
                 string_operators::as_float(self, args)
             } else {
@@ -119,15 +136,27 @@ impl Operable for RadonString {
             }
             .map(RadonTypes::from)
             .map_err(Into::into),
-            (RadonOpCodes::StringAsBoolean, None) => string_operators::to_bool(self)
-                .map(RadonTypes::from)
-                .map_err(Into::into),
+            (RadonOpCodes::StringAsBoolean, None) => if wip0029 {
+                string_operators::as_bool(self)
+            } else {
+                string_operators::to_bool(self)
+            }
+            .map(RadonTypes::from),
             (RadonOpCodes::StringParseJSONArray, None) => string_operators::parse_json_array(self)
                 .map(RadonTypes::from)
                 .map_err(Into::into),
+            (RadonOpCodes::StringParseJSONArray, Some(args)) if wip0029 => {
+                string_operators::parse_json_array_or_default(self, args.as_slice(), context)
+            }
             (RadonOpCodes::StringParseJSONMap, None) => string_operators::parse_json_map(self)
                 .map(RadonTypes::from)
                 .map_err(Into::into),
+            (RadonOpCodes::StringParseJSONMap, Some(args)) if wip0029 => {
+                string_operators::parse_json_map_or_default(self, args.as_slice(), context)
+            }
+            (RadonOpCodes::StringParseJSONOrDefault, Some(args)) => {
+                string_operators::parse_json_or_default(self, args.as_slice(), context)
+            }
             (RadonOpCodes::StringMatch, Some(args)) => {
                 string_operators::string_match(self, args.as_slice()).map(RadonTypes::from)
             }
@@ -143,6 +172,25 @@ impl Operable for RadonString {
             (RadonOpCodes::StringParseXMLMap, None) => string_operators::parse_xml_map(self)
                 .map(RadonTypes::from)
                 .map_err(Into::into),
+            (RadonOpCodes::StringFormat, Some(args)) => {
+                string_operators::format(self, args.as_slice()).map(RadonTypes::from)
+            }
+            (RadonOpCodes::StringSplit, Some(args)) => {
+                string_operators::split(self, args.as_slice()).map(RadonTypes::from)
+            }
+            (RadonOpCodes::StringUrlDecode, None) => {
+                string_operators::url_decode(self).map(RadonTypes::from)
+            }
+            (RadonOpCodes::StringParseTimestamp, None) => {
+                string_operators::parse_timestamp(self).map(RadonTypes::from)
+            }
+            // Strings are hashed as their UTF-8 bytes
+            (RadonOpCodes::HashKeccak256, None) => {
+                Ok(bytes_operators::keccak256(&RadonBytes::from(self.value().into_bytes())).into())
+            }
+            (RadonOpCodes::HashHash160, None) => {
+                Ok(bytes_operators::hash160(&RadonBytes::from(self.value().into_bytes())).into())
+            }
             (op_code, args) => Err(RadError::UnsupportedOperator {
                 input_type: RADON_STRING_TYPE_NAME.to_string(),
                 operator: op_code.to_string(),
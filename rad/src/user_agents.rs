@@ -86,7 +86,21 @@ pub struct UserAgent;
 impl UserAgent {
     /// Get one user agent at random
     pub fn random() -> &'static str {
-        USERAGENTS[thread_rng().gen_range(0, USERAGENTS.len())]
+        Self::from_list(USERAGENTS).unwrap()
+    }
+
+    /// Get one user agent at random out of a custom list, or `None` if the list is empty
+    pub fn from_list<'a>(list: &[&'a str]) -> Option<&'a str> {
+        if list.is_empty() {
+            None
+        } else {
+            Some(list[thread_rng().gen_range(0, list.len())])
+        }
+    }
+
+    /// Get the full list of user agents that `random` picks from
+    pub fn all() -> &'static [&'static str] {
+        USERAGENTS
     }
 }
 
@@ -95,3 +109,16 @@ fn test_user_agent_from_list() {
     let test_header = UserAgent::random();
     assert!(USERAGENTS.contains(&test_header));
 }
+
+#[test]
+fn test_user_agent_from_custom_list() {
+    let list = ["witnet-test/1.0", "witnet-test/2.0"];
+    let test_header = UserAgent::from_list(&list).unwrap();
+    assert!(list.contains(&test_header));
+
+    assert_eq!(
+        UserAgent::from_list(&["witnet-test/1.0"]),
+        Some("witnet-test/1.0")
+    );
+    assert_eq!(UserAgent::from_list(&[]), None);
+}
@@ -62,7 +62,7 @@ impl Iterator for DBIterator<'_, '_> {
     type Item = (Vec<u8>, Vec<u8>);
 
     fn next(&mut self) -> Option<Self::Item> {
-        // TODO: is this correct? Add tests
+        // Resume the scan right after the last entry visited, matching or not
         let mut skip = self.skip;
         let res = self
             .data
@@ -100,4 +100,84 @@ mod tests {
         storage.delete(b"name").unwrap();
         assert_eq!(None, storage.get(b"name").unwrap());
     }
+
+    #[test]
+    fn test_hashmap_write_batch() {
+        let storage = backend();
+        storage.put(b"old".to_vec(), b"1".to_vec()).unwrap();
+
+        let mut batch = WriteBatch::default();
+        batch.put(b"a".to_vec(), b"2".to_vec());
+        batch.put(b"b".to_vec(), b"3".to_vec());
+        batch.delete(b"old".to_vec());
+
+        // Nothing is written until the whole batch is
+        assert_eq!(None, storage.get(b"a").unwrap());
+        assert_eq!(Some(b"1".to_vec()), storage.get(b"old").unwrap());
+
+        storage.write(batch).unwrap();
+        assert_eq!(Some(b"2".to_vec()), storage.get(b"a").unwrap());
+        assert_eq!(Some(b"3".to_vec()), storage.get(b"b").unwrap());
+        assert_eq!(None, storage.get(b"old").unwrap());
+    }
+
+    #[test]
+    fn test_hashmap_write_batch_in_order() {
+        let storage = backend();
+
+        // Later operations on the same key win
+        let mut batch = WriteBatch::default();
+        batch.put(b"a".to_vec(), b"1".to_vec());
+        batch.delete(b"a".to_vec());
+        batch.put(b"b".to_vec(), b"1".to_vec());
+        batch.put(b"b".to_vec(), b"2".to_vec());
+        storage.write(batch).unwrap();
+
+        assert_eq!(None, storage.get(b"a").unwrap());
+        assert_eq!(Some(b"2".to_vec()), storage.get(b"b").unwrap());
+    }
+
+    #[test]
+    fn test_hashmap_prefix_iterator() {
+        let storage = backend();
+        for key in [
+            &b"utxo"[..],
+            b"utxo-a",
+            b"utxo-a-1",
+            b"utxo-ab",
+            b"utxo-b",
+            b"utx",
+            b"xutxo-a",
+        ] {
+            storage.put(key.to_vec(), key.to_vec()).unwrap();
+        }
+
+        let keys_with_prefix = |prefix: &[u8]| {
+            let mut keys: Vec<Vec<u8>> = storage
+                .prefix_iterator(prefix)
+                .unwrap()
+                .map(|(k, v)| {
+                    assert_eq!(k, v);
+                    k
+                })
+                .collect();
+            keys.sort();
+            keys
+        };
+
+        assert_eq!(
+            keys_with_prefix(b"utxo-a"),
+            vec![
+                b"utxo-a".to_vec(),
+                b"utxo-a-1".to_vec(),
+                b"utxo-ab".to_vec()
+            ]
+        );
+        assert_eq!(keys_with_prefix(b"utxo-a-"), vec![b"utxo-a-1".to_vec()]);
+        assert_eq!(keys_with_prefix(b"utxo").len(), 5);
+        assert_eq!(keys_with_prefix(b"utx").len(), 6);
+        assert_eq!(keys_with_prefix(b"").len(), 7);
+        assert!(keys_with_prefix(b"utxo-c").is_empty());
+        assert!(keys_with_prefix(b"utxo-a-1-").is_empty());
+    }
 }
@@ -11,6 +11,9 @@ pub type Backend = rocksdb::DB;
 /// Rocksdb Options
 pub type Options = rocksdb::Options;
 
+/// Rocksdb compression types
+pub type CompressionType = rocksdb::DBCompressionType;
+
 #[derive(Debug, Fail)]
 #[fail(display = "RocksDB error: {}", _0)]
 struct Error(#[fail(cause)] rocksdb::Error);
@@ -65,3 +68,68 @@ impl Storage for Backend {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rocksdb_zstd_compression() {
+        let path = std::env::temp_dir().join(format!(
+            "witnet_storage_test_zstd_{}_{:?}",
+            std::process::id(),
+            std::thread::current().id()
+        ));
+        let mut options = Options::default();
+        options.create_if_missing(true);
+        options.set_compression_type(CompressionType::Zstd);
+
+        {
+            let storage: Box<dyn Storage> = Box::new(Backend::open(&options, &path).unwrap());
+            let value = vec![0xAB; 4096];
+
+            storage.put(b"key".to_vec(), value.clone()).unwrap();
+            assert_eq!(Some(value), storage.get(b"key").unwrap());
+        }
+
+        Backend::destroy(&options, &path).unwrap();
+    }
+
+    #[test]
+    fn test_rocksdb_failed_write_batch_applies_nothing() {
+        let path = std::env::temp_dir().join(format!(
+            "witnet_storage_test_failed_batch_{}_{:?}",
+            std::process::id(),
+            std::thread::current().id()
+        ));
+        let mut options = Options::default();
+        options.create_if_missing(true);
+
+        {
+            let storage: Box<dyn Storage> = Box::new(Backend::open(&options, &path).unwrap());
+            storage.put(b"old".to_vec(), b"1".to_vec()).unwrap();
+        }
+
+        // A read-only database rejects the batch as a whole
+        {
+            let storage: Box<dyn Storage> =
+                Box::new(Backend::open_for_read_only(&options, &path, false).unwrap());
+            let mut batch = WriteBatch::default();
+            batch.put(b"a".to_vec(), b"2".to_vec());
+            batch.put(b"b".to_vec(), b"3".to_vec());
+            batch.delete(b"old".to_vec());
+
+            assert!(storage.write(batch).is_err());
+        }
+
+        // None of the operations in the batch were applied
+        {
+            let storage: Box<dyn Storage> = Box::new(Backend::open(&options, &path).unwrap());
+            assert_eq!(None, storage.get(b"a").unwrap());
+            assert_eq!(None, storage.get(b"b").unwrap());
+            assert_eq!(Some(b"1".to_vec()), storage.get(b"old").unwrap());
+        }
+
+        Backend::destroy(&options, &path).unwrap();
+    }
+}
@@ -29,6 +29,54 @@ pub(crate) struct TryDataRequest {
         help = "Whether to return the full execution trace, including partial results after each operator."
     )]
     pub full_trace: Option<bool>,
+    #[structopt(
+        long,
+        help = "Run the data request as if all WIPs were active, regardless of the protocol version."
+    )]
+    pub all_wips_active: bool,
+}
+
+/// Arguments for the `--verify-tally` method.
+#[derive(Debug, StructOpt)]
+pub(crate) struct VerifyTally {
+    #[structopt(long, help = "Hexadecimal serialization of the data request output.")]
+    pub hex: Option<String>,
+    #[structopt(
+        long,
+        help = "File system path to an instance to a `.sol` file containing an instance of  `WitnetRequest`"
+    )]
+    pub from_solidity: Option<String>,
+    #[structopt(
+        long,
+        use_delimiter = true,
+        help = "Comma-separated list of the revealed values, each of them as a hexadecimal CBOR byte string."
+    )]
+    pub reveals: Vec<String>,
+    #[structopt(
+        long,
+        help = "Number of commitments for the data request. Defaults to the number of reveals."
+    )]
+    pub commits: Option<usize>,
+    #[structopt(
+        long,
+        help = "Expected tally result, as a hexadecimal CBOR byte string."
+    )]
+    pub expected: Option<String>,
+    #[structopt(
+        long,
+        help = "Expected SHA-256 hash of the CBOR-encoded tally result, as a hexadecimal string."
+    )]
+    pub expected_hash: Option<String>,
+}
+
+/// Easy derivation of `DecodeDataRequest` from `VerifyTally`
+impl From<&VerifyTally> for DecodeDataRequest {
+    fn from(vt: &VerifyTally) -> Self {
+        Self {
+            hex: vt.hex.clone(),
+            from_solidity: vt.from_solidity.clone(),
+        }
+    }
 }
 
 /// Easy derivation of `DecodeDataRequest` from `TryDataRequest`
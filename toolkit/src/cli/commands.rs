@@ -13,6 +13,11 @@ pub(crate) enum SubCommand {
         about = "Tries a data request locally so as to preview what its result could be as of now."
     )]
     TryDataRequest(arguments::TryDataRequest),
+    #[structopt(
+        name = "verify-tally",
+        about = "Recomputes the tally of a data request from its reveals and checks it against an expected result."
+    )]
+    VerifyTally(arguments::VerifyTally),
 }
 
 #[derive(Debug, StructOpt)]
@@ -4,10 +4,10 @@ use std::{fs::File, io::Read, path::Path};
 
 use regex::Regex;
 
-use witnet_data_structures::chain::DataRequestOutput;
+use witnet_data_structures::chain::{tapi::all_wips_active, DataRequestOutput, Hash};
 use witnet_rad::RADRequestExecutionReport;
 
-use witnet_toolkit::errors::Error;
+use witnet_toolkit::{data_requests::ExpectedTally, errors::Error};
 
 use super::arguments;
 
@@ -43,9 +43,53 @@ pub(crate) fn try_from_args(
     args: arguments::TryDataRequest,
 ) -> Result<RADRequestExecutionReport, Error> {
     let full_trace = args.full_trace.unwrap_or(true);
+    let active_wips = args.all_wips_active.then(all_wips_active);
     let request = decode_from_args(args.into())?.data_request;
 
-    witnet_toolkit::data_requests::try_data_request(&request, full_trace)
+    witnet_toolkit::data_requests::try_data_request(&request, full_trace, active_wips)
+}
+
+/// Recompute the tally of a data request from a `VerifyTally` structure, and tell whether it matches
+/// the expected result.
+///
+/// A mismatch is reported as an error, so that the process exits with a non-zero code.
+pub(crate) fn verify_tally_from_args(args: arguments::VerifyTally) -> Result<String, Error> {
+    let request = decode_from_args((&args).into())?;
+    let reveals = args
+        .reveals
+        .iter()
+        .enumerate()
+        .map(|(index, reveal)| hex::decode(reveal).map_err(|_| Error::RevealHexNotValid(index)))
+        .collect::<Result<Vec<_>, _>>()?;
+    let expected = if let Some(expected) = &args.expected {
+        ExpectedTally::Bytes(hex::decode(expected).map_err(Error::TallyHexNotValid)?)
+    } else if let Some(expected_hash) = &args.expected_hash {
+        ExpectedTally::Hash(
+            expected_hash
+                .parse::<Hash>()
+                .map_err(|_| Error::TallyHashNotValid(expected_hash.clone()))?,
+        )
+    } else {
+        return Err(Error::TallyNoExpectation);
+    };
+
+    let verification =
+        witnet_toolkit::data_requests::verify_tally(&request, &reveals, args.commits, &expected)?;
+    let result = format!(
+        "{} [{}]",
+        hex::encode(&verification.result_bytes),
+        witnet_toolkit::data_requests::describe_result(&verification.result)
+    );
+    let hash = verification.result_hash.to_string();
+
+    if verification.passed {
+        Ok(format!(
+            "PASS: the tally result is {} (hash {}), as expected",
+            result, hash
+        ))
+    } else {
+        Err(Error::TallyMismatch { result, hash })
+    }
 }
 
 /// Extract the Protocol Buffers representation of a data request from a Solidity smart contract
@@ -18,6 +18,8 @@ pub(crate) fn process_command(command: commands::Command) -> i32 {
         // `--try-data-request`
         commands::SubCommand::TryDataRequest(args) => data_requests::try_from_args(args)
             .and_then(|report| serde_json::to_string(&report).map_err(Error::JsonSerialize)),
+        // `--verify-tally`
+        commands::SubCommand::VerifyTally(args) => data_requests::verify_tally_from_args(args),
     }
     // The output of successful commands is printed to `stdout`, and a `0` exit code is returned
     .map(|result| {
@@ -1,9 +1,20 @@
 //! Functions providing convenient utilities for working with Witnet data requests.
+use witnet_crypto::hash::calculate_sha256;
 use witnet_data_structures::{
-    chain::{DataRequestOutput, RADRequest},
+    chain::{
+        tapi::{current_active_wips, ActiveWips},
+        DataRequestOutput, Hash, RADFilter, RADRequest, RADRetrieve,
+    },
     proto::ProtobufConvert,
+    radon_report::{RadonReport, ReportContext, TypeLike},
 };
-use witnet_rad::{script::RadonScriptExecutionSettings, RADRequestExecutionReport};
+use witnet_rad::{
+    error::RadError,
+    script::RadonScriptExecutionSettings,
+    types::{serial_iter_decode, RadonTypes},
+    RADRequestExecutionReport,
+};
+use witnet_validations::validations::run_tally;
 
 use crate::errors::Error;
 
@@ -43,24 +54,397 @@ pub fn decode_rad_from_pb_bytes(pb_bytes: &[u8]) -> Result<DataRequestOutput, Er
 /// after each operator.
 ///
 /// Full trace mode can be disabled by setting `full_trace` to `false`.
+///
+/// The set of active WIPs can be pinned through `active_wips` so that the results do not depend on
+/// the global protocol state. If not given, `current_active_wips()` is used.
 pub fn try_data_request(
     request: &RADRequest,
     full_trace: bool,
+    active_wips: Option<ActiveWips>,
 ) -> Result<RADRequestExecutionReport, Error> {
     let settings = if full_trace {
         RadonScriptExecutionSettings::enable_all()
     } else {
         RadonScriptExecutionSettings::disable_all()
     };
-    let report = witnet_rad::try_data_request(request, settings, None, None, false);
+    let report =
+        witnet_rad::try_data_request(request, settings, None, None, false, None, active_wips);
 
     Ok(report)
 }
 
+/// What a locally recomputed tally result is checked against.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ExpectedTally {
+    /// The CBOR-encoded tally result, as found in the tally transaction.
+    Bytes(Vec<u8>),
+    /// The SHA-256 hash of the CBOR-encoded tally result.
+    Hash(Hash),
+}
+
+/// Outcome of recomputing the tally of a data request.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TallyVerification {
+    /// The recomputed tally result.
+    pub result: RadonTypes,
+    /// The CBOR encoding of the recomputed tally result.
+    pub result_bytes: Vec<u8>,
+    /// The SHA-256 hash of `result_bytes`.
+    pub result_hash: Hash,
+    /// Whether the recomputed tally result matches the expected one.
+    pub passed: bool,
+}
+
+/// Recompute the tally of a data request from the CBOR-encoded reveals, in the same way as nodes do
+/// when validating tally transactions, and compare it with the expected result.
+///
+/// If `commits_count` is not provided, it is assumed that every commitment was revealed.
+pub fn verify_tally(
+    request: &DataRequestOutput,
+    reveals: &[Vec<u8>],
+    commits_count: Option<usize>,
+    expected: &ExpectedTally,
+) -> Result<TallyVerification, Error> {
+    let active_wips = current_active_wips();
+    let reports = serial_iter_decode(
+        &mut reveals.iter().map(|reveal| (reveal.as_slice(), &())),
+        |_error: RadError, _slice: &[u8], _: &()| {
+            Some(RadonReport::from_result(
+                Err(RadError::MalformedReveal),
+                &ReportContext::default(),
+            ))
+        },
+        &active_wips,
+    );
+    let non_error_min = f64::from(request.min_consensus_percentage) / 100.0;
+    let commits_count = commits_count.unwrap_or(reveals.len());
+
+    let result = run_tally(
+        reports,
+        &request.data_request.tally,
+        non_error_min,
+        commits_count,
+        &active_wips,
+        false,
+    )
+    .into_inner();
+    let result_bytes = result.encode().map_err(Error::TallyEncode)?;
+    let result_hash = Hash::from(calculate_sha256(&result_bytes));
+    let passed = match expected {
+        ExpectedTally::Bytes(bytes) => bytes == &result_bytes,
+        ExpectedTally::Hash(hash) => hash == &result_hash,
+    };
+
+    Ok(TallyVerification {
+        result,
+        result_bytes,
+        result_hash,
+        passed,
+    })
+}
+
+/// Describe a tally result in a human-readable way. Errors are described by their RADON error
+/// code, which is what actually gets committed to the chain.
+pub fn describe_result(result: &RadonTypes) -> String {
+    match result {
+        RadonTypes::RadonError(error) => match error.inner().try_into_error_code() {
+            Ok(code) => format!("error 0x{:02X}: {}", u8::from(code), code.description()),
+            Err(_) => format!("error: {}", error.inner()),
+        },
+        other => other.to_string(),
+    }
+}
+
+/// Structured summary of what changed between two versions of a data request.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RequestDiff {
+    /// Whether the time lock is different.
+    pub time_lock: bool,
+    /// Changes in the retrieval sources, which are compared by their position in the request.
+    pub sources: Vec<SourceChange>,
+    /// Changes in the aggregation stage.
+    pub aggregate: StageChange,
+    /// Changes in the tally stage.
+    pub tally: StageChange,
+}
+
+impl RequestDiff {
+    /// Tell whether both requests were actually the same.
+    pub fn is_empty(&self) -> bool {
+        self == &RequestDiff::default()
+    }
+}
+
+/// A change in one of the retrieval sources of a data request.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SourceChange {
+    /// The new request has a source at this index, but the old one had not.
+    Added(usize),
+    /// The old request had a source at this index, but the new one has not.
+    Removed(usize),
+    /// The source at this index exists in both requests, but some of its fields are different.
+    Modified {
+        index: usize,
+        fields: Vec<SourceField>,
+    },
+}
+
+/// The fields of a retrieval source that can be reported as modified.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SourceField {
+    Kind,
+    Url,
+    Script,
+    Body,
+    Headers,
+}
+
+/// Changes in the aggregation or tally stage of a data request.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StageChange {
+    /// Whether the list of filters (or any of their arguments) is different.
+    pub filters: bool,
+    /// The old and new reducer codes, if they are different.
+    pub reducer: Option<(u32, u32)>,
+}
+
+/// Compare two data requests field by field and tell which sources, scripts, filters or reducers
+/// changed from `old` to `new`.
+pub fn diff_requests(old: &RADRequest, new: &RADRequest) -> RequestDiff {
+    let sources_count = old.retrieve.len().max(new.retrieve.len());
+    let sources = (0..sources_count)
+        .filter_map(
+            |index| match (old.retrieve.get(index), new.retrieve.get(index)) {
+                (None, Some(_)) => Some(SourceChange::Added(index)),
+                (Some(_), None) => Some(SourceChange::Removed(index)),
+                (Some(old), Some(new)) => {
+                    let fields = diff_sources(old, new);
+
+                    if fields.is_empty() {
+                        None
+                    } else {
+                        Some(SourceChange::Modified { index, fields })
+                    }
+                }
+                (None, None) => None,
+            },
+        )
+        .collect();
+
+    RequestDiff {
+        time_lock: old.time_lock != new.time_lock,
+        sources,
+        aggregate: diff_stages(
+            (old.aggregate.filters.as_slice(), old.aggregate.reducer),
+            (new.aggregate.filters.as_slice(), new.aggregate.reducer),
+        ),
+        tally: diff_stages(
+            (old.tally.filters.as_slice(), old.tally.reducer),
+            (new.tally.filters.as_slice(), new.tally.reducer),
+        ),
+    }
+}
+
+fn diff_sources(old: &RADRetrieve, new: &RADRetrieve) -> Vec<SourceField> {
+    let mut fields = vec![];
+    if old.kind != new.kind {
+        fields.push(SourceField::Kind);
+    }
+    if old.url != new.url {
+        fields.push(SourceField::Url);
+    }
+    if old.script != new.script {
+        fields.push(SourceField::Script);
+    }
+    if old.body != new.body {
+        fields.push(SourceField::Body);
+    }
+    if old.headers != new.headers {
+        fields.push(SourceField::Headers);
+    }
+
+    fields
+}
+
+fn diff_stages(old: (&[RADFilter], u32), new: (&[RADFilter], u32)) -> StageChange {
+    StageChange {
+        filters: old.0 != new.0,
+        reducer: if old.1 != new.1 {
+            Some((old.1, new.1))
+        } else {
+            None
+        },
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use witnet_data_structures::{
+        chain::{RADAggregate, RADTally, RADType},
+        radon_error::RadonError,
+    };
+    use witnet_rad::{reducers::RadonReducers, types::integer::RadonInteger};
+
     use super::*;
 
+    fn example_request() -> RADRequest {
+        RADRequest {
+            time_lock: 0,
+            retrieve: vec![RADRetrieve {
+                kind: RADType::HttpGet,
+                url: "https://api.binance.US/api/v3/ticker?symbol=ETHUSD".to_string(),
+                script: vec![0x84, 0x18, 0x77],
+                body: vec![],
+                headers: vec![],
+            }],
+            aggregate: RADAggregate {
+                filters: vec![],
+                reducer: RadonReducers::AverageMean as u32,
+            },
+            tally: RADTally {
+                filters: vec![],
+                reducer: RadonReducers::AverageMean as u32,
+            },
+        }
+    }
+
+    #[test]
+    fn diff_requests_identical() {
+        let request = example_request();
+
+        assert!(diff_requests(&request, &request).is_empty());
+    }
+
+    #[test]
+    fn diff_requests_added_source() {
+        let old = example_request();
+        let mut new = old.clone();
+        new.retrieve.push(RADRetrieve {
+            url: "https://api.kraken.com/0/public/Ticker?pair=ETHUSD".to_string(),
+            ..old.retrieve[0].clone()
+        });
+
+        let diff = diff_requests(&old, &new);
+
+        assert_eq!(
+            diff,
+            RequestDiff {
+                sources: vec![SourceChange::Added(1)],
+                ..RequestDiff::default()
+            }
+        );
+        // The other way around, the source is reported as removed
+        assert_eq!(
+            diff_requests(&new, &old).sources,
+            vec![SourceChange::Removed(1)]
+        );
+    }
+
+    #[test]
+    fn diff_requests_changed_reducer() {
+        let old = example_request();
+        let mut new = old.clone();
+        new.tally.reducer = RadonReducers::AverageMedian as u32;
+        new.retrieve[0].script = vec![0x84, 0x18, 0x78];
+
+        let diff = diff_requests(&old, &new);
+
+        assert_eq!(
+            diff,
+            RequestDiff {
+                sources: vec![SourceChange::Modified {
+                    index: 0,
+                    fields: vec![SourceField::Script],
+                }],
+                tally: StageChange {
+                    filters: false,
+                    reducer: Some((
+                        RadonReducers::AverageMean as u32,
+                        RadonReducers::AverageMedian as u32
+                    )),
+                },
+                ..RequestDiff::default()
+            }
+        );
+    }
+
+    fn tally_reveals(values: &[i128]) -> Vec<Vec<u8>> {
+        values
+            .iter()
+            .map(|value| {
+                RadonTypes::from(RadonInteger::from(*value))
+                    .encode()
+                    .unwrap()
+            })
+            .collect()
+    }
+
+    fn example_output() -> DataRequestOutput {
+        let mut data_request = example_request();
+        data_request.tally.reducer = RadonReducers::Mode as u32;
+
+        DataRequestOutput {
+            data_request,
+            witnesses: 3,
+            min_consensus_percentage: 51,
+            ..DataRequestOutput::default()
+        }
+    }
+
+    #[test]
+    fn test_verify_tally_matching() {
+        let reveals = tally_reveals(&[7, 7, 7]);
+        let expected_bytes = RadonTypes::from(RadonInteger::from(7)).encode().unwrap();
+
+        let verification = verify_tally(
+            &example_output(),
+            &reveals,
+            None,
+            &ExpectedTally::Bytes(expected_bytes.clone()),
+        )
+        .unwrap();
+        assert!(verification.passed);
+        assert_eq!(verification.result_bytes, expected_bytes);
+
+        // The hash of the result can be used instead
+        let verification = verify_tally(
+            &example_output(),
+            &reveals,
+            None,
+            &ExpectedTally::Hash(Hash::from(calculate_sha256(&expected_bytes))),
+        )
+        .unwrap();
+        assert!(verification.passed);
+    }
+
+    #[test]
+    fn test_verify_tally_mismatching() {
+        let reveals = tally_reveals(&[7, 7, 7]);
+        let expected_bytes = RadonTypes::from(RadonInteger::from(8)).encode().unwrap();
+
+        let verification = verify_tally(
+            &example_output(),
+            &reveals,
+            None,
+            &ExpectedTally::Bytes(expected_bytes),
+        )
+        .unwrap();
+        assert!(!verification.passed);
+        assert_eq!(verification.result, RadonTypes::from(RadonInteger::from(7)));
+    }
+
+    #[test]
+    fn test_describe_result() {
+        let timeout = RadonTypes::RadonError(RadonError::new(RadError::RetrieveTimeout));
+        assert_eq!(
+            describe_result(&timeout),
+            "error 0x31: A source could not be retrieved in time"
+        );
+
+        let integer = RadonTypes::from(RadonInteger::from(7));
+        assert_eq!(describe_result(&integer), integer.to_string());
+    }
+
     #[test]
     fn decode_dro_from_hex_string() {
         let hex = "0aab0412520801123268747470733a2f2f6170692e62696e616e63652e55532f6170692f76332f7469636b65723f73796d626f6c3d4554485553441a1a841877821864696c61737450726963658218571a000f4240185b124d0801122c68747470733a2f2f6170692e62697466696e65782e636f6d2f76312f7075627469636b65722f4554485553441a1b8418778218646a6c6173745f70726963658218571a000f4240185b12480801122d68747470733a2f2f7777772e6269747374616d702e6e65742f6170692f76322f7469636b65722f6574687573641a15841877821864646c6173748218571a000f4240185b12550801123168747470733a2f2f6170692e626974747265782e636f6d2f76332f6d61726b6574732f4554482d5553442f7469636b65721a1e8418778218646d6c6173745472616465526174658218571a000f4240185b12620801123768747470733a2f2f6170692e636f696e626173652e636f6d2f76322f65786368616e67652d72617465733f63757272656e63793d4554481a258618778218666464617461821866657261746573821864635553448218571a000f4240185b12630801123268747470733a2f2f6170692e6b72616b656e2e636f6d2f302f7075626c69632f5469636b65723f706169723d4554485553441a2b87187782186666726573756c7482186668584554485a55534482186161618216008218571a000f4240185b1a0d0a0908051205fa3fc000001003220d0a0908051205fa4020000010031080a3c347180a2080ade20428333080acc7f037";
@@ -33,6 +33,32 @@ pub enum Error {
     RegularExpression(#[cause] regex::Error),
     #[fail(display = "Error when serializing the result: {}", _0)]
     JsonSerialize(#[cause] serde_json::Error),
+    #[fail(
+        display = "The reveal at index {} is not a valid hexadecimal byte string",
+        _0
+    )]
+    RevealHexNotValid(usize),
+    #[fail(
+        display = "The expected tally result is not a valid hexadecimal byte string: {}",
+        _0
+    )]
+    TallyHexNotValid(#[cause] hex::FromHexError),
+    #[fail(
+        display = "The expected tally hash is not a valid SHA-256 hash: {}",
+        _0
+    )]
+    TallyHashNotValid(String),
+    #[fail(
+        display = "No expected tally result. Please use the --expected or --expected-hash argument"
+    )]
+    TallyNoExpectation,
+    #[fail(display = "Could not encode the tally result: {}", _0)]
+    TallyEncode(#[cause] witnet_rad::error::RadError),
+    #[fail(
+        display = "FAIL: the tally result is {} (hash {}), which does not match the expected one",
+        result, hash
+    )]
+    TallyMismatch { result: String, hash: String },
 }
 
 /// Implicit, contextless wrapping of regular expression errors.
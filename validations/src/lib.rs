@@ -16,5 +16,8 @@ pub mod witnessing;
 /// Module contaning validations specific to eligibility
 pub mod eligibility;
 
+/// Module containing utilities to simulate changes to the chain state
+pub mod simulation;
+
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,123 @@
+use witnet_data_structures::{
+    chain::{Block, ChainState, EpochConstants, Hash, Input, ValueTransferOutput},
+    error::TransactionError,
+    get_protocol_version,
+    proto::versioning::VersionedHashable,
+    utxo_pool::{Diff, UtxoDiff},
+};
+
+use crate::validations::update_utxo_diff;
+
+/// Effects that consolidating a block would have on the chain state
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ChainStateDiff {
+    /// Insertions and deletions to perform on the unspent outputs pool
+    pub utxo_diff: Diff,
+    /// Hashes of the data request transactions included in the block, which would be added to the
+    /// data request pool
+    pub new_data_requests: Vec<Hash>,
+    /// Pointers to the data requests resolved by the tally transactions included in the block,
+    /// which would be removed from the data request pool
+    pub resolved_data_requests: Vec<Hash>,
+}
+
+/// Simulate applying a block on top of `state`, without mutating it.
+///
+/// This is meant for testing and tooling: the block is assumed to be valid and its transactions
+/// are not validated, the only check performed is that every input spends an output that exists
+/// in the unspent outputs pool or that was created earlier in the same block.
+pub fn apply_block(
+    state: &ChainState,
+    block: &Block,
+    epoch_constants: EpochConstants,
+) -> Result<ChainStateDiff, failure::Error> {
+    let epoch = block.block_header.beacon.checkpoint;
+    let protocol_version = get_protocol_version(Some(epoch));
+    let is_genesis = state.chain_info.as_ref().map_or(false, |chain_info| {
+        block.is_genesis(&chain_info.consensus_constants.genesis_hash)
+    });
+    let mut utxo_diff = UtxoDiff::new(&state.unspent_outputs_pool, state.block_number());
+    let mut chain_state_diff = ChainStateDiff::default();
+
+    let mut apply_transaction = |inputs: &[Input], outputs: Vec<&ValueTransferOutput>, hash| {
+        if let Some(input) = inputs
+            .iter()
+            .find(|input| utxo_diff.get(input.output_pointer()).is_none())
+        {
+            return Err(TransactionError::OutputNotFound {
+                output: *input.output_pointer(),
+            });
+        }
+
+        update_utxo_diff(
+            &mut utxo_diff,
+            inputs,
+            outputs,
+            hash,
+            epoch,
+            epoch_constants,
+            epoch_constants.checkpoint_zero_timestamp,
+        );
+
+        Ok(())
+    };
+
+    for transaction in &block.txns.value_transfer_txns {
+        apply_transaction(
+            &transaction.body.inputs,
+            transaction.body.outputs.iter().collect(),
+            transaction.versioned_hash(protocol_version),
+        )?;
+    }
+    for transaction in &block.txns.data_request_txns {
+        let hash = transaction.versioned_hash(protocol_version);
+        apply_transaction(
+            &transaction.body.inputs,
+            transaction.body.outputs.iter().collect(),
+            hash,
+        )?;
+        chain_state_diff.new_data_requests.push(hash);
+    }
+    for transaction in &block.txns.commit_txns {
+        apply_transaction(
+            &transaction.body.collateral,
+            transaction.body.outputs.iter().collect(),
+            transaction.versioned_hash(protocol_version),
+        )?;
+    }
+    for transaction in &block.txns.tally_txns {
+        apply_transaction(
+            &[],
+            transaction.outputs.iter().collect(),
+            transaction.versioned_hash(protocol_version),
+        )?;
+        chain_state_diff
+            .resolved_data_requests
+            .push(transaction.dr_pointer);
+    }
+    for transaction in &block.txns.stake_txns {
+        apply_transaction(
+            &transaction.body.inputs,
+            transaction.body.change.iter().collect(),
+            transaction.versioned_hash(protocol_version),
+        )?;
+    }
+    for transaction in &block.txns.unstake_txns {
+        apply_transaction(
+            &[],
+            vec![&transaction.body.withdrawal],
+            transaction.versioned_hash(protocol_version),
+        )?;
+    }
+    if !is_genesis {
+        apply_transaction(
+            &[],
+            block.txns.mint.outputs.iter().collect(),
+            block.txns.mint.versioned_hash(protocol_version),
+        )?;
+    }
+
+    chain_state_diff.utxo_diff = utxo_diff.take_diff();
+
+    Ok(chain_state_diff)
+}
@@ -49,6 +49,7 @@ use crate::validations::*;
 mod compare_block_candidates;
 mod randpoe;
 mod reppoe;
+mod simulation;
 mod tally_precondition;
 mod witnessing;
 
@@ -2217,6 +2218,20 @@ fn data_request_empty_scripts() {
     );
 }
 
+#[test]
+fn data_request_graphql_malformed_query() {
+    let mut data_request = example_data_request();
+    data_request.retrieve[0].kind = RADType::GraphQl;
+    // CBOR encoding of `["{ a"]`, whose selection set is never closed
+    data_request.retrieve[0].body = vec![0x81, 0x63, b'{', b' ', b'a'];
+
+    let x = test_rad_request(data_request);
+    assert!(matches!(
+        x.unwrap_err().downcast::<RadError>().unwrap(),
+        RadError::MalformedRetrieval { .. }
+    ));
+}
+
 #[test]
 fn data_request_witnesses_0() {
     // A data request with 0 witnesses is invalid
@@ -2506,6 +2521,62 @@ fn data_request_parse_xml_before_wip_activation() {
     );
 }
 
+#[test]
+fn data_request_wip0029_operator_before_wip0020() {
+    let mut data_request = example_data_request_with_mode_filter();
+    // [StringParseJSONMap, Length]
+    data_request.retrieve[0].script = vec![0x82, 0x18, 0x77, 0x01];
+    data_request.retrieve[0].url = "http://127.0.0.1".to_string();
+    let dr_output = DataRequestOutput {
+        witness_reward: DEFAULT_WITNESS_REWARD,
+        commit_and_reveal_fee: 100,
+        witnesses: 2,
+        min_consensus_percentage: 51,
+        collateral: DEFAULT_COLLATERAL,
+        data_request,
+    };
+
+    let x = {
+        let mut signatures_to_verify = vec![];
+        let vto = ValueTransferOutput {
+            pkh: MY_PKH_1.parse().unwrap(),
+            value: DEFAULT_INPUT_VALUE,
+            time_lock: 0,
+        };
+        let utxo_set = build_utxo_set_with_mint(vec![vto], None, vec![]);
+        let block_number = 0;
+        let utxo_diff = UtxoDiff::new(&utxo_set, block_number);
+        let vti = Input::new(utxo_set.iter().next().unwrap().0);
+        let dr_tx_body = DRTransactionBody::new(vec![vti], dr_output, vec![]);
+        let drs = sign_tx(PRIV_KEY_1, &dr_tx_body, None);
+        let dr_transaction = DRTransaction::new(dr_tx_body, vec![drs]);
+
+        let mut active_wips = all_wips_active();
+        // Disable WIP0020 and WIP0029
+        active_wips.active_wips.remove("WIP0020-0021");
+        active_wips.active_wips.remove("WIP0029");
+
+        validate_dr_transaction(
+            &dr_transaction,
+            &utxo_diff,
+            Epoch::default(),
+            EpochConstants::default(),
+            &mut signatures_to_verify,
+            ONE_WIT,
+            u32::MAX,
+            REQUIRED_REWARD_COLLATERAL_RATIO,
+            &active_wips,
+            None,
+        )
+        .map(|_| ())
+    };
+
+    assert_eq!(
+        x.unwrap_err().downcast::<RadError>().unwrap(),
+        RadError::UnknownOperator { code: 0x01 },
+    );
+}
+
 #[test]
 fn data_request_parse_xml_after_wip_activation() {
     let mut data_request = example_data_request_with_mode_filter();
@@ -7090,6 +7161,118 @@ fn create_tally_validation_dr_liar() {
     }
 }
 
+fn reveals_for_tally_preview(
+    dr_pointer: Hash,
+    values: &[i128],
+) -> (Vec<RevealTransaction>, Vec<PublicKeyHash>) {
+    values
+        .iter()
+        .enumerate()
+        .map(|(i, &value)| {
+            let pkh = PublicKeyHash::from_bytes(&[u8::try_from(i + 1).unwrap(); 20]).unwrap();
+            let reveal = RadonReport::from_result(
+                Ok(RadonTypes::from(RadonInteger::from(value))),
+                &ReportContext::default(),
+            )
+            .result
+            .encode()
+            .unwrap();
+            let body = RevealTransactionBody::new(dr_pointer, reveal, pkh);
+
+            (RevealTransaction::new(body, vec![]), pkh)
+        })
+        .unzip()
+}
+
+#[test]
+fn build_tally_transaction_all_honest() {
+    let active_wips = all_wips_active();
+    let dr_output = example_data_request_output_with_mode_filter(3, DEFAULT_WITNESS_REWARD, 20);
+    let dr_pointer = Hash::SHA256([1; 32]);
+    let requester = PublicKeyHash::from_bytes(&[0xff; 20]).unwrap();
+    let (reveals, revealers) = reveals_for_tally_preview(dr_pointer, &[1, 1, 1]);
+
+    let tally_transaction = build_tally_transaction(
+        dr_pointer,
+        &dr_output,
+        requester,
+        &reveals.iter().collect::<Vec<_>>(),
+        revealers.iter().cloned().collect(),
+        ONE_WIT,
+        &active_wips,
+        false,
+        ProtocolVersion::V1_7,
+    );
+
+    assert_eq!(tally_transaction.dr_pointer, dr_pointer);
+    // Integer(1)
+    assert_eq!(tally_transaction.tally, vec![0x01]);
+    assert_eq!(
+        tally_transaction.outputs,
+        revealers
+            .iter()
+            .map(|&pkh| ValueTransferOutput {
+                pkh,
+                value: DEFAULT_WITNESS_REWARD + DEFAULT_COLLATERAL,
+                time_lock: 0,
+            })
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(tally_transaction.out_of_consensus, vec![]);
+    assert_eq!(tally_transaction.error_committers, vec![]);
+}
+
+#[test]
+fn build_tally_transaction_one_liar() {
+    let active_wips = all_wips_active();
+    let dr_output = example_data_request_output_with_mode_filter(3, DEFAULT_WITNESS_REWARD, 20);
+    let dr_pointer = Hash::SHA256([1; 32]);
+    let requester = PublicKeyHash::from_bytes(&[0xff; 20]).unwrap();
+    let (reveals, revealers) = reveals_for_tally_preview(dr_pointer, &[1, 1, 5]);
+    let liar = revealers[2];
+
+    let tally_transaction = build_tally_transaction(
+        dr_pointer,
+        &dr_output,
+        requester,
+        &reveals.iter().collect::<Vec<_>>(),
+        revealers.iter().cloned().collect(),
+        ONE_WIT,
+        &active_wips,
+        false,
+        ProtocolVersion::V1_7,
+    );
+
+    assert_eq!(tally_transaction.tally, vec![0x01]);
+    // The liar loses its collateral and its reward goes back to the requester
+    assert_eq!(
+        tally_transaction.outputs,
+        vec![
+            ValueTransferOutput {
+                pkh: revealers[0],
+                value: DEFAULT_WITNESS_REWARD + DEFAULT_COLLATERAL,
+                time_lock: 0,
+            },
+            ValueTransferOutput {
+                pkh: revealers[1],
+                value: DEFAULT_WITNESS_REWARD + DEFAULT_COLLATERAL,
+                time_lock: 0,
+            },
+            ValueTransferOutput {
+                pkh: requester,
+                value: DEFAULT_WITNESS_REWARD,
+                time_lock: 0,
+            },
+        ]
+    );
+    assert_eq!(tally_transaction.out_of_consensus, vec![liar]);
+    assert_eq!(tally_transaction.error_committers, vec![]);
+    assert_eq!(
+        calculate_liars_and_errors_count_from_tally(&tally_transaction),
+        (1, 0)
+    );
+}
+
 #[test]
 fn create_tally_validation_5_reveals_1_liar_1_error() {
     let active_wips = current_active_wips();
@@ -0,0 +1,131 @@
+use witnet_data_structures::{
+    chain::{
+        Block, BlockHeader, BlockTransactions, ChainState, EpochConstants, Hash, Input,
+        KeyedSignature, OutputPointer, ValueTransferOutput,
+    },
+    error::TransactionError,
+    transaction::{MintTransaction, Transaction, VTTransaction, VTTransactionBody},
+    utxo_pool::UnspentOutputsPool,
+};
+
+use super::{generate_unspent_outputs_pool, MY_PKH_1, MY_PKH_2, MY_PKH_3, ONE_WIT, ONE_WIT_OUTPUT};
+use crate::simulation::{apply_block, ChainStateDiff};
+
+fn chain_state_with_one_wit() -> ChainState {
+    let mut unspent_outputs_pool = UnspentOutputsPool::default();
+    unspent_outputs_pool.insert(
+        ONE_WIT_OUTPUT.parse().unwrap(),
+        ValueTransferOutput {
+            pkh: MY_PKH_1.parse().unwrap(),
+            value: ONE_WIT,
+            time_lock: 0,
+        },
+        0,
+    );
+
+    ChainState {
+        unspent_outputs_pool,
+        ..ChainState::default()
+    }
+}
+
+fn vtt_spending(output_pointer: OutputPointer) -> VTTransaction {
+    VTTransaction::new(
+        VTTransactionBody::new(
+            vec![Input::new(output_pointer)],
+            vec![ValueTransferOutput {
+                pkh: MY_PKH_2.parse().unwrap(),
+                value: ONE_WIT / 2,
+                time_lock: 0,
+            }],
+        ),
+        vec![],
+    )
+}
+
+fn block_with_vtts(value_transfer_txns: Vec<VTTransaction>) -> Block {
+    let txns = BlockTransactions {
+        mint: MintTransaction::new(
+            0,
+            vec![ValueTransferOutput {
+                pkh: MY_PKH_3.parse().unwrap(),
+                value: ONE_WIT,
+                time_lock: 0,
+            }],
+        ),
+        value_transfer_txns,
+        ..BlockTransactions::default()
+    };
+
+    Block::new(BlockHeader::default(), KeyedSignature::default(), txns)
+}
+
+#[test]
+fn apply_block_with_vtt() {
+    let state = chain_state_with_one_wit();
+    let vtt = vtt_spending(ONE_WIT_OUTPUT.parse().unwrap());
+    let block = block_with_vtts(vec![vtt.clone()]);
+
+    let diff = apply_block(&state, &block, EpochConstants::default()).unwrap();
+
+    let mut utxo_set = state.unspent_outputs_pool.clone();
+    diff.utxo_diff.apply(&mut utxo_set);
+    let expected_utxo_set = generate_unspent_outputs_pool(
+        &state.unspent_outputs_pool,
+        &[
+            Transaction::ValueTransfer(vtt),
+            Transaction::Mint(block.txns.mint.clone()),
+        ],
+        state.block_number(),
+    );
+    assert_eq!(utxo_set, expected_utxo_set);
+    assert!(diff.new_data_requests.is_empty());
+    assert!(diff.resolved_data_requests.is_empty());
+}
+
+#[test]
+fn apply_block_does_not_mutate_state() {
+    let state = chain_state_with_one_wit();
+    let block = block_with_vtts(vec![vtt_spending(ONE_WIT_OUTPUT.parse().unwrap())]);
+
+    let diff = apply_block(&state, &block, EpochConstants::default()).unwrap();
+
+    assert_eq!(state, chain_state_with_one_wit());
+    assert_ne!(diff, ChainStateDiff::default());
+}
+
+#[test]
+fn apply_block_spending_missing_output() {
+    let state = chain_state_with_one_wit();
+    let missing_output = OutputPointer {
+        transaction_id: Hash::SHA256([1; 32]),
+        output_index: 0,
+    };
+    let block = block_with_vtts(vec![vtt_spending(missing_output)]);
+
+    let error = apply_block(&state, &block, EpochConstants::default()).unwrap_err();
+    assert_eq!(
+        error.downcast::<TransactionError>().unwrap(),
+        TransactionError::OutputNotFound {
+            output: missing_output
+        }
+    );
+}
+
+#[test]
+fn apply_block_with_double_spend() {
+    let state = chain_state_with_one_wit();
+    let output_pointer = ONE_WIT_OUTPUT.parse().unwrap();
+    let block = block_with_vtts(vec![
+        vtt_spending(output_pointer),
+        vtt_spending(output_pointer),
+    ]);
+
+    let error = apply_block(&state, &block, EpochConstants::default()).unwrap_err();
+    assert_eq!(
+        error.downcast::<TransactionError>().unwrap(),
+        TransactionError::OutputNotFound {
+            output: output_pointer
+        }
+    );
+}
@@ -1,4 +1,9 @@
-use crate::witnessing::{validate_transport_address, TransportAddressError};
+use witnet_data_structures::witnessing::WitnessingConfig;
+
+use crate::witnessing::{
+    validate_transport_address, validate_witnessing_config, TransportAddressError,
+    WitnessingConfigError,
+};
 
 #[test]
 fn test_validate_transport_addresses() {
@@ -48,3 +53,32 @@ fn test_validate_transport_addresses() {
         assert_eq!(result, expected);
     }
 }
+
+#[test]
+fn test_validate_witnessing_config_user_agent() {
+    let validate = |user_agent: Option<&str>| {
+        let config = WitnessingConfig::<String> {
+            user_agent: user_agent.map(String::from),
+            ..Default::default()
+        };
+
+        validate_witnessing_config::<String, String>(&config).map(|config| config.user_agent)
+    };
+
+    assert_eq!(validate(None), Ok(None));
+    assert_eq!(
+        validate(Some("witnet-test/1.0")),
+        Ok(Some(String::from("witnet-test/1.0")))
+    );
+    for user_agent in [
+        "",
+        "  ",
+        "witnet-test/1.0\r\nX-Injected: 1",
+        "witnet-test/1.0\n",
+    ] {
+        assert_eq!(
+            validate(Some(user_agent)),
+            Err(WitnessingConfigError::UserAgent(String::from(user_agent)))
+        );
+    }
+}
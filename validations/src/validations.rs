@@ -47,8 +47,11 @@ use witnet_rad::{
         evaluate_tally_precondition_clause, radon_report_from_error,
     },
     error::RadError,
-    operators::RadonOpCodes,
-    script::{create_radon_script_from_filters_and_reducer, unpack_radon_script},
+    graphql::parse_graphql_body,
+    script::{
+        check_radon_script_availability, create_radon_script_from_filters_and_reducer,
+        unpack_radon_script, unpack_radon_script_with_active_wips,
+    },
     types::{serial_iter_decode, RadonTypes},
 };
 
@@ -381,7 +384,14 @@ pub fn validate_rad_request(
     for path in retrieval_paths {
         if active_wips.wip0020() {
             path.check_fields()?;
-            unpack_radon_script(path.script.as_slice())?;
+            unpack_radon_script_with_active_wips(path.script.as_slice(), active_wips)?;
+
+            if path.kind == RADType::GraphQl {
+                if !active_wips.wip0029() {
+                    return Err(DataRequestError::InvalidRadType.into());
+                }
+                parse_graphql_body(&path.body)?;
+            }
 
             // Regarding WIP-0019 activation:
             // Before -> Only RADType enum 0 position is valid
@@ -396,14 +406,18 @@ pub fn validate_rad_request(
             path.check_fields_before_wip0020()?;
             let rad_script = unpack_radon_script(path.script.as_slice())?;
 
-            // Scripts with new operators are invalid before TAPI activation
-            for rad_call in rad_script {
-                if rad_call.0 == RadonOpCodes::StringParseXMLMap {
-                    return Err(RadError::UnknownOperator {
-                        code: RadonOpCodes::StringParseXMLMap as i128,
-                    }
-                    .into());
-                }
+            // Scripts with operators that are not active yet are invalid, and fail as they did
+            // back when those operators did not exist. Subscripts were not decoded at the time, so
+            // only the top-level calls are checked.
+            for (op_code, _) in rad_script {
+                check_radon_script_availability(&[(op_code, None)], active_wips).map_err(
+                    |error| match error {
+                        RadError::OperatorNotActivated { code, .. } => RadError::UnknownOperator {
+                            code: i128::from(code),
+                        },
+                        error => error,
+                    },
+                )?;
             }
         }
     }
@@ -962,6 +976,53 @@ pub fn run_tally(
     report
 }
 
+/// Build the tally transaction that is expected to resolve a data request, given its reveals.
+///
+/// This runs the RADON tally script of `dr_output` over the revealed values (the consensus
+/// threshold being `min_consensus_percentage`) and then splits the rewards and collaterals
+/// according to the result: honest revealers get their reward plus their collateral back,
+/// liars and committers who did not reveal are slashed, and whatever was not paid out is
+/// returned to the data request creator (`requester`) as change.
+///
+/// `committers` must contain every identity that committed to the data request, including the
+/// ones that did not reveal. This is the same computation that miners and validators perform,
+/// so it can be used to preview the outcome of a data request before it is tallied on chain.
+#[allow(clippy::too_many_arguments)]
+pub fn build_tally_transaction(
+    dr_pointer: Hash,
+    dr_output: &DataRequestOutput,
+    requester: PublicKeyHash,
+    reveals: &[&RevealTransaction],
+    committers: HashSet<PublicKeyHash>,
+    collateral_minimum: u64,
+    active_wips: &ActiveWips,
+    too_many_witnesses: bool,
+    protocol_version: ProtocolVersion,
+) -> TallyTransaction {
+    let non_error_min = f64::from(dr_output.min_consensus_percentage) / 100.0;
+    let report = run_tally_panic_safe(
+        reveals,
+        &dr_output.data_request.tally,
+        non_error_min,
+        committers.len(),
+        active_wips,
+        too_many_witnesses,
+    );
+
+    create_tally(
+        dr_pointer,
+        dr_output,
+        requester,
+        &report,
+        reveals.iter().map(|tx| tx.body.pkh).collect(),
+        committers,
+        collateral_minimum,
+        tally_bytes_on_encode_error(),
+        active_wips,
+        protocol_version,
+    )
+}
+
 fn create_expected_tally_transaction(
     ta_tx: &TallyTransaction,
     dr_pool: &DataRequestPool,
@@ -984,33 +1045,22 @@ fn create_expected_tally_transaction(
 
     // The unwrap is safe because we know that the data request exists
     let reveal_txns = dr_pool.get_reveals(&dr_pointer, active_wips).unwrap();
-    let non_error_min = f64::from(dr_output.min_consensus_percentage) / 100.0;
     let committers = dr_state
         .info
         .commits
         .keys()
         .cloned()
         .collect::<HashSet<PublicKeyHash>>();
-    let commit_length = committers.len();
 
-    let report = run_tally_panic_safe(
-        &reveal_txns,
-        &dr_output.data_request.tally,
-        non_error_min,
-        commit_length,
-        active_wips,
-        too_many_witnesses,
-    );
-    let ta_tx = create_tally(
+    let ta_tx = build_tally_transaction(
         dr_pointer,
         dr_output,
         dr_state.pkh,
-        &report,
-        reveal_txns.into_iter().map(|tx| tx.body.pkh).collect(),
+        &reveal_txns,
         committers,
         collateral_minimum,
-        tally_bytes_on_encode_error(),
         active_wips,
+        too_many_witnesses,
         protocol_version,
     );
 
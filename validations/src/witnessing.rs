@@ -12,6 +12,7 @@ use witnet_data_structures::witnessing::WitnessingConfig;
 /// Namely, this verifies that:
 /// - Each of the addresses to use as transports are constructed correctly.
 /// - The protocols of the transports are supported.
+/// - The pinned user agent, if any, is not empty and does not contain line breaks.
 pub fn validate_witnessing_config<T, T2>(
     config: &WitnessingConfig<T>,
 ) -> Result<WitnessingConfig<T2>, WitnessingConfigError>
@@ -38,9 +39,27 @@ where
         return Err(WitnessingConfigError::Addresses(invalid));
     }
 
+    if let Some(user_agent) = &config.user_agent {
+        if user_agent.trim().is_empty() || user_agent.contains(&['\r', '\n'][..]) {
+            return Err(WitnessingConfigError::UserAgent(user_agent.clone()));
+        }
+    }
+
     Ok(WitnessingConfig {
         transports: valid,
         paranoid_threshold: config.paranoid_threshold,
+        paranoid: config.paranoid,
+        rotation: config.rotation.clone(),
+        max_download_rate: config.max_download_rate,
+        retry: config.retry,
+        require_tls: config.require_tls,
+        user_agent: config.user_agent.clone(),
+        redacted_headers: config.redacted_headers.clone(),
+        connect_timeout: config.connect_timeout,
+        read_timeout: config.read_timeout,
+        ip_version: config.ip_version,
+        resolve: config.resolve.clone(),
+        min_tls_version: config.min_tls_version,
     })
 }
 
@@ -49,6 +68,8 @@ where
 pub enum WitnessingConfigError {
     /// The error is in the addresses.
     Addresses(Vec<(String, TransportAddressError)>),
+    /// The pinned user agent is empty or contains line breaks.
+    UserAgent(String),
 }
 
 impl fmt::Display for WitnessingConfigError {
@@ -65,6 +86,10 @@ impl fmt::Display for WitnessingConfigError {
                     interpolation
                 )
             }
+            WitnessingConfigError::UserAgent(user_agent) => format!(
+                "The user agent {:?} must not be empty nor contain line breaks",
+                user_agent
+            ),
         };
 
         write!(f, "Invalid witnessing configuration. {}", submessage)
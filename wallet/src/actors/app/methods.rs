@@ -809,7 +809,7 @@ impl App {
 
         self.get_client()
             .actor
-            .do_send(jsonrpc::Subscribe(request, recipient));
+            .do_send(jsonrpc::Subscribe::new(request, recipient));
     }
 
     /// Send syncStatus request to the node every 10 seconds and send
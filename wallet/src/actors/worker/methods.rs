@@ -58,6 +58,8 @@ impl Worker {
             None,
             Some(self.params.witnessing.clone()),
             false,
+            None,
+            None,
         )
     }
 
@@ -26,7 +26,7 @@ use witnet_data_structures::{
     get_protocol_version_activation_epoch, get_protocol_version_period,
     proto::versioning::ProtocolVersion,
 };
-use witnet_net::client::tcp::JsonRpcClient;
+use witnet_net::client::tcp::{jsonrpc::Backoff, JsonRpcClient};
 use witnet_validations::witnessing::validate_witnessing_config;
 
 use crate::actors::app;
@@ -50,6 +50,12 @@ pub fn run(conf: Config) -> Result<(), Error> {
     let db_path = conf.wallet.db_path;
     let db_file_name = conf.wallet.db_file_name;
     let node_urls = conf.wallet.node_url;
+    let node_reconnect_max_backoff = Duration::from_millis(conf.wallet.node_reconnect_max_backoff);
+    let node_backoff = Backoff {
+        initial: Backoff::default().initial.min(node_reconnect_max_backoff),
+        max: node_reconnect_max_backoff,
+        jitter: Duration::from_millis(conf.wallet.node_reconnect_jitter),
+    };
     let rocksdb_opts = conf.rocksdb.to_rocksdb_options();
 
     let checkpoints_period = conf.consensus_constants.checkpoints_period;
@@ -119,15 +125,19 @@ pub fn run(conf: Config) -> Result<(), Error> {
     // Initialize actors inside system context
     system.block_on(async {
         let node_subscriptions = Arc::new(Mutex::new(Default::default()));
-        let node_client_actor =
-            JsonRpcClient::start_with_subscriptions(node_urls.clone(), node_subscriptions.clone())
-                .map_err(|_| app::Error::NodeNotConnected)?;
+        let node_client_actor = JsonRpcClient::start_with_backoff(
+            node_urls.clone(),
+            node_subscriptions.clone(),
+            node_backoff,
+        )
+        .map_err(|_| app::Error::NodeNotConnected)?;
         let node_client = Arc::new(app::NodeClient {
             actor: node_client_actor,
         });
 
         // Trigger connection validation. Due to how the JSON-RPC client works, it will keep
-        // retrying connection using a different URL each time.
+        // retrying connection using a different URL each time, waiting longer after every failed
+        // attempt up to `node_reconnect_max_backoff`.
         node_client.valid_connection().await;
 
         let params = params::Params {
@@ -82,7 +82,8 @@ impl OutputsCollection for WalletUtxos<'_> {
         };
 
         match strategy {
-            UtxoSelectionStrategy::BigFirst { from } => {
+            UtxoSelectionStrategy::BigFirst { from }
+            | UtxoSelectionStrategy::BranchAndBound { from } => {
                 sort_utxo_set(self.utxo_set, true, from.as_ref())
                     .filter_map(filter_utxos)
                     .collect()
@@ -110,6 +110,8 @@ fn test_data_request_report_json_serialization() {
         Some(&inputs),
         None,
         false,
+        None,
+        None,
     );
 
     // Number of retrieval reports should match number of sources